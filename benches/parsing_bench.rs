@@ -68,6 +68,96 @@ fn parsing_benchmark(c: &mut Criterion) {
     group.finish();
 }
 
+/// Номера, которые уже находятся в каноническом формате E.164.
+fn setup_e164_data() -> Vec<&'static str> {
+    vec![
+        "+16502530000",
+        "+442087654321",
+        "+5491187654321",
+        "+390212345678",
+        "+18003569377",
+        "+4930123456",
+    ]
+}
+
+fn e164_parsing_benchmark(c: &mut Criterion) {
+    let numbers_to_parse = setup_e164_data();
+
+    let mut group = c.benchmark_group("E164 Parsing");
+
+    // Полный парсер с неизвестным регионом
+    group.bench_function("rlibphonenumber: parse()", |b| {
+        b.iter(|| {
+            for number_str in &numbers_to_parse {
+                let _ = PHONE_NUMBER_UTIL.parse(black_box(number_str), black_box("ZZ"));
+            }
+        })
+    });
+
+    // Быстрый путь для строгого E.164
+    group.bench_function("rlibphonenumber: parse_e164()", |b| {
+        b.iter(|| {
+            for number_str in &numbers_to_parse {
+                let _ = PHONE_NUMBER_UTIL.parse_e164(black_box(number_str));
+            }
+        })
+    });
+
+    group.finish();
+}
+
+fn extension_parsing_benchmark(c: &mut Criterion) {
+    let without_extension = "+44 20 8765 4321";
+    let with_extension = "+44 20 8765 4321 ext. 1234";
+
+    // Количество выделений памяти проверяется в tests/parse_allocations.rs.
+    let mut group = c.benchmark_group("Extension Parsing");
+    group.bench_function("rlibphonenumber: parse() without extension", |b| {
+        b.iter(|| PHONE_NUMBER_UTIL.parse(black_box(without_extension), black_box("GB")))
+    });
+    group.bench_function("rlibphonenumber: parse() with extension", |b| {
+        b.iter(|| PHONE_NUMBER_UTIL.parse(black_box(with_extension), black_box("GB")))
+    });
+    group.finish();
+}
+
+/// Сравнивает `parse_many` с последовательным `parse` на большом пакете номеров.
+/// Запуск: `cargo bench --bench parsing_bench --features rayon`.
+#[cfg(feature = "rayon")]
+fn parse_many_benchmark(c: &mut Criterion) {
+    let data = setup_parsing_data();
+    let inputs: Vec<(&str, &str)> = data
+        .iter()
+        .map(|(number_str, region, _)| (*number_str, *region))
+        .cycle()
+        .take(10_000)
+        .collect();
+
+    let mut group = c.benchmark_group("Bulk Parsing");
+    group.sample_size(20);
+    group.bench_function("rlibphonenumber: parse() in a loop", |b| {
+        b.iter(|| {
+            inputs
+                .iter()
+                .map(|(number_str, region)| PHONE_NUMBER_UTIL.parse(number_str, region))
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("rlibphonenumber: parse_many()", |b| {
+        b.iter(|| PHONE_NUMBER_UTIL.parse_many(black_box(&inputs)))
+    });
+    group.finish();
+}
+
+#[cfg(not(feature = "rayon"))]
+fn parse_many_benchmark(_: &mut Criterion) {}
+
 // Макросы для регистрации и запуска бенчмарка
-criterion_group!(benches, parsing_benchmark);
+criterion_group!(
+    benches,
+    parsing_benchmark,
+    e164_parsing_benchmark,
+    extension_parsing_benchmark,
+    parse_many_benchmark
+);
 criterion_main!(benches);
\ No newline at end of file
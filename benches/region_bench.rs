@@ -0,0 +1,50 @@
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+
+use rlibphonenumber::PHONE_NUMBER_UTIL;
+
+/// Numbers of the country calling code 1, which is shared by more than twenty
+/// regions, so resolving their region goes through all of their leading digits.
+fn setup_nanpa_numbers() -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("+1 650-253-0000", "US"),
+        ("+1 416-555-0123", "CA"),
+        ("+1 242-357-1234", "BS"),
+        ("+1 876-512-3456", "JM"),
+        ("+1 787-234-5678", "PR"),
+        ("+1 268-460-1234", "AG"),
+        ("+1 809-234-5678", "DO"),
+        ("+1 800-356-9377", "US"),
+    ]
+}
+
+fn region_resolution_benchmark(c: &mut Criterion) {
+    let data = setup_nanpa_numbers();
+    let numbers: Vec<_> = data
+        .iter()
+        .map(|(number_str, _)| PHONE_NUMBER_UTIL.parse(number_str, "ZZ").unwrap())
+        .collect();
+
+    for (number, (_, region)) in numbers.iter().zip(&data) {
+        assert_eq!(*region, PHONE_NUMBER_UTIL.get_region_code_for_number(number));
+    }
+
+    let mut group = c.benchmark_group("Region Resolution (+1)");
+    group.bench_function("rlibphonenumber: get_region_code_for_number()", |b| {
+        b.iter(|| {
+            for number in &numbers {
+                PHONE_NUMBER_UTIL.get_region_code_for_number(black_box(number));
+            }
+        })
+    });
+    group.bench_function("rlibphonenumber: classify()", |b| {
+        b.iter(|| {
+            for number in &numbers {
+                PHONE_NUMBER_UTIL.classify(black_box(number));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, region_resolution_benchmark);
+criterion_main!(benches);
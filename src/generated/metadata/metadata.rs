@@ -15,7 +15,7 @@
  * limitations under the License.
  */
 
-pub const METADATA: [u8; 201829] = [
+pub static METADATA: [u8; 201829] = [
   0x0A, 0xE9, 0x01, 0x0A, 0x1D, 0x12, 0x17, 0x28, 0x3F, 0x3A, 0x5B, 0x30, 0x31,
   0x35, 0x38, 0x39, 0x5D, 0x5C, 0x64, 0x7C, 0x5B, 0x34, 0x36, 0x5D, 0x29, 0x5C,
   0x64, 0x7B, 0x34, 0x7D, 0x48, 0x05, 0x48, 0x06, 0x12, 0x18, 0x12, 0x0D, 0x36,
@@ -15,12 +15,14 @@
 
 
 mod metadata;
+mod short_metadata;
 
 // use only in test case 
 #[cfg(test)]
 mod test_metadata;
 
 pub use metadata::METADATA;
+pub use short_metadata::SHORT_METADATA;
 #[cfg(test)]
 pub use test_metadata::TEST_METADATA;
 
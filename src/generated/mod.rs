@@ -15,3 +15,4 @@
 
 pub mod proto;
 pub mod metadata;
+pub mod prefix_data;
@@ -0,0 +1,236 @@
+// Copyright (C) 2025 Kashin Vladislav (Rust adaptation author)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Files from resources/carrier as (language, country calling code, contents).
+pub static CARRIER_DATA: &[(&str, i32, &str)] = &[
+    ("ar", 965, include_str!("../../../resources/carrier/ar/965.txt")),
+    ("be", 375, include_str!("../../../resources/carrier/be/375.txt")),
+    ("en", 1, include_str!("../../../resources/carrier/en/1.txt")),
+    ("en", 20, include_str!("../../../resources/carrier/en/20.txt")),
+    ("en", 211, include_str!("../../../resources/carrier/en/211.txt")),
+    ("en", 212, include_str!("../../../resources/carrier/en/212.txt")),
+    ("en", 213, include_str!("../../../resources/carrier/en/213.txt")),
+    ("en", 216, include_str!("../../../resources/carrier/en/216.txt")),
+    ("en", 218, include_str!("../../../resources/carrier/en/218.txt")),
+    ("en", 220, include_str!("../../../resources/carrier/en/220.txt")),
+    ("en", 221, include_str!("../../../resources/carrier/en/221.txt")),
+    ("en", 222, include_str!("../../../resources/carrier/en/222.txt")),
+    ("en", 223, include_str!("../../../resources/carrier/en/223.txt")),
+    ("en", 224, include_str!("../../../resources/carrier/en/224.txt")),
+    ("en", 225, include_str!("../../../resources/carrier/en/225.txt")),
+    ("en", 226, include_str!("../../../resources/carrier/en/226.txt")),
+    ("en", 227, include_str!("../../../resources/carrier/en/227.txt")),
+    ("en", 228, include_str!("../../../resources/carrier/en/228.txt")),
+    ("en", 229, include_str!("../../../resources/carrier/en/229.txt")),
+    ("en", 230, include_str!("../../../resources/carrier/en/230.txt")),
+    ("en", 231, include_str!("../../../resources/carrier/en/231.txt")),
+    ("en", 232, include_str!("../../../resources/carrier/en/232.txt")),
+    ("en", 233, include_str!("../../../resources/carrier/en/233.txt")),
+    ("en", 234, include_str!("../../../resources/carrier/en/234.txt")),
+    ("en", 235, include_str!("../../../resources/carrier/en/235.txt")),
+    ("en", 236, include_str!("../../../resources/carrier/en/236.txt")),
+    ("en", 237, include_str!("../../../resources/carrier/en/237.txt")),
+    ("en", 238, include_str!("../../../resources/carrier/en/238.txt")),
+    ("en", 239, include_str!("../../../resources/carrier/en/239.txt")),
+    ("en", 240, include_str!("../../../resources/carrier/en/240.txt")),
+    ("en", 241, include_str!("../../../resources/carrier/en/241.txt")),
+    ("en", 242, include_str!("../../../resources/carrier/en/242.txt")),
+    ("en", 243, include_str!("../../../resources/carrier/en/243.txt")),
+    ("en", 244, include_str!("../../../resources/carrier/en/244.txt")),
+    ("en", 245, include_str!("../../../resources/carrier/en/245.txt")),
+    ("en", 246, include_str!("../../../resources/carrier/en/246.txt")),
+    ("en", 247, include_str!("../../../resources/carrier/en/247.txt")),
+    ("en", 248, include_str!("../../../resources/carrier/en/248.txt")),
+    ("en", 249, include_str!("../../../resources/carrier/en/249.txt")),
+    ("en", 250, include_str!("../../../resources/carrier/en/250.txt")),
+    ("en", 251, include_str!("../../../resources/carrier/en/251.txt")),
+    ("en", 252, include_str!("../../../resources/carrier/en/252.txt")),
+    ("en", 253, include_str!("../../../resources/carrier/en/253.txt")),
+    ("en", 254, include_str!("../../../resources/carrier/en/254.txt")),
+    ("en", 255, include_str!("../../../resources/carrier/en/255.txt")),
+    ("en", 256, include_str!("../../../resources/carrier/en/256.txt")),
+    ("en", 257, include_str!("../../../resources/carrier/en/257.txt")),
+    ("en", 258, include_str!("../../../resources/carrier/en/258.txt")),
+    ("en", 260, include_str!("../../../resources/carrier/en/260.txt")),
+    ("en", 261, include_str!("../../../resources/carrier/en/261.txt")),
+    ("en", 262, include_str!("../../../resources/carrier/en/262.txt")),
+    ("en", 263, include_str!("../../../resources/carrier/en/263.txt")),
+    ("en", 264, include_str!("../../../resources/carrier/en/264.txt")),
+    ("en", 265, include_str!("../../../resources/carrier/en/265.txt")),
+    ("en", 266, include_str!("../../../resources/carrier/en/266.txt")),
+    ("en", 267, include_str!("../../../resources/carrier/en/267.txt")),
+    ("en", 268, include_str!("../../../resources/carrier/en/268.txt")),
+    ("en", 269, include_str!("../../../resources/carrier/en/269.txt")),
+    ("en", 27, include_str!("../../../resources/carrier/en/27.txt")),
+    ("en", 290, include_str!("../../../resources/carrier/en/290.txt")),
+    ("en", 291, include_str!("../../../resources/carrier/en/291.txt")),
+    ("en", 297, include_str!("../../../resources/carrier/en/297.txt")),
+    ("en", 298, include_str!("../../../resources/carrier/en/298.txt")),
+    ("en", 299, include_str!("../../../resources/carrier/en/299.txt")),
+    ("en", 30, include_str!("../../../resources/carrier/en/30.txt")),
+    ("en", 31, include_str!("../../../resources/carrier/en/31.txt")),
+    ("en", 32, include_str!("../../../resources/carrier/en/32.txt")),
+    ("en", 33, include_str!("../../../resources/carrier/en/33.txt")),
+    ("en", 34, include_str!("../../../resources/carrier/en/34.txt")),
+    ("en", 350, include_str!("../../../resources/carrier/en/350.txt")),
+    ("en", 351, include_str!("../../../resources/carrier/en/351.txt")),
+    ("en", 352, include_str!("../../../resources/carrier/en/352.txt")),
+    ("en", 353, include_str!("../../../resources/carrier/en/353.txt")),
+    ("en", 354, include_str!("../../../resources/carrier/en/354.txt")),
+    ("en", 355, include_str!("../../../resources/carrier/en/355.txt")),
+    ("en", 356, include_str!("../../../resources/carrier/en/356.txt")),
+    ("en", 357, include_str!("../../../resources/carrier/en/357.txt")),
+    ("en", 358, include_str!("../../../resources/carrier/en/358.txt")),
+    ("en", 359, include_str!("../../../resources/carrier/en/359.txt")),
+    ("en", 36, include_str!("../../../resources/carrier/en/36.txt")),
+    ("en", 370, include_str!("../../../resources/carrier/en/370.txt")),
+    ("en", 371, include_str!("../../../resources/carrier/en/371.txt")),
+    ("en", 372, include_str!("../../../resources/carrier/en/372.txt")),
+    ("en", 373, include_str!("../../../resources/carrier/en/373.txt")),
+    ("en", 374, include_str!("../../../resources/carrier/en/374.txt")),
+    ("en", 375, include_str!("../../../resources/carrier/en/375.txt")),
+    ("en", 376, include_str!("../../../resources/carrier/en/376.txt")),
+    ("en", 377, include_str!("../../../resources/carrier/en/377.txt")),
+    ("en", 378, include_str!("../../../resources/carrier/en/378.txt")),
+    ("en", 380, include_str!("../../../resources/carrier/en/380.txt")),
+    ("en", 381, include_str!("../../../resources/carrier/en/381.txt")),
+    ("en", 382, include_str!("../../../resources/carrier/en/382.txt")),
+    ("en", 383, include_str!("../../../resources/carrier/en/383.txt")),
+    ("en", 385, include_str!("../../../resources/carrier/en/385.txt")),
+    ("en", 386, include_str!("../../../resources/carrier/en/386.txt")),
+    ("en", 387, include_str!("../../../resources/carrier/en/387.txt")),
+    ("en", 389, include_str!("../../../resources/carrier/en/389.txt")),
+    ("en", 39, include_str!("../../../resources/carrier/en/39.txt")),
+    ("en", 40, include_str!("../../../resources/carrier/en/40.txt")),
+    ("en", 41, include_str!("../../../resources/carrier/en/41.txt")),
+    ("en", 420, include_str!("../../../resources/carrier/en/420.txt")),
+    ("en", 421, include_str!("../../../resources/carrier/en/421.txt")),
+    ("en", 423, include_str!("../../../resources/carrier/en/423.txt")),
+    ("en", 43, include_str!("../../../resources/carrier/en/43.txt")),
+    ("en", 44, include_str!("../../../resources/carrier/en/44.txt")),
+    ("en", 45, include_str!("../../../resources/carrier/en/45.txt")),
+    ("en", 46, include_str!("../../../resources/carrier/en/46.txt")),
+    ("en", 47, include_str!("../../../resources/carrier/en/47.txt")),
+    ("en", 48, include_str!("../../../resources/carrier/en/48.txt")),
+    ("en", 49, include_str!("../../../resources/carrier/en/49.txt")),
+    ("en", 500, include_str!("../../../resources/carrier/en/500.txt")),
+    ("en", 501, include_str!("../../../resources/carrier/en/501.txt")),
+    ("en", 502, include_str!("../../../resources/carrier/en/502.txt")),
+    ("en", 503, include_str!("../../../resources/carrier/en/503.txt")),
+    ("en", 504, include_str!("../../../resources/carrier/en/504.txt")),
+    ("en", 505, include_str!("../../../resources/carrier/en/505.txt")),
+    ("en", 506, include_str!("../../../resources/carrier/en/506.txt")),
+    ("en", 507, include_str!("../../../resources/carrier/en/507.txt")),
+    ("en", 508, include_str!("../../../resources/carrier/en/508.txt")),
+    ("en", 509, include_str!("../../../resources/carrier/en/509.txt")),
+    ("en", 51, include_str!("../../../resources/carrier/en/51.txt")),
+    ("en", 53, include_str!("../../../resources/carrier/en/53.txt")),
+    ("en", 55, include_str!("../../../resources/carrier/en/55.txt")),
+    ("en", 56, include_str!("../../../resources/carrier/en/56.txt")),
+    ("en", 57, include_str!("../../../resources/carrier/en/57.txt")),
+    ("en", 58, include_str!("../../../resources/carrier/en/58.txt")),
+    ("en", 590, include_str!("../../../resources/carrier/en/590.txt")),
+    ("en", 591, include_str!("../../../resources/carrier/en/591.txt")),
+    ("en", 592, include_str!("../../../resources/carrier/en/592.txt")),
+    ("en", 593, include_str!("../../../resources/carrier/en/593.txt")),
+    ("en", 594, include_str!("../../../resources/carrier/en/594.txt")),
+    ("en", 595, include_str!("../../../resources/carrier/en/595.txt")),
+    ("en", 596, include_str!("../../../resources/carrier/en/596.txt")),
+    ("en", 597, include_str!("../../../resources/carrier/en/597.txt")),
+    ("en", 598, include_str!("../../../resources/carrier/en/598.txt")),
+    ("en", 599, include_str!("../../../resources/carrier/en/599.txt")),
+    ("en", 60, include_str!("../../../resources/carrier/en/60.txt")),
+    ("en", 61, include_str!("../../../resources/carrier/en/61.txt")),
+    ("en", 62, include_str!("../../../resources/carrier/en/62.txt")),
+    ("en", 63, include_str!("../../../resources/carrier/en/63.txt")),
+    ("en", 64, include_str!("../../../resources/carrier/en/64.txt")),
+    ("en", 65, include_str!("../../../resources/carrier/en/65.txt")),
+    ("en", 66, include_str!("../../../resources/carrier/en/66.txt")),
+    ("en", 670, include_str!("../../../resources/carrier/en/670.txt")),
+    ("en", 672, include_str!("../../../resources/carrier/en/672.txt")),
+    ("en", 673, include_str!("../../../resources/carrier/en/673.txt")),
+    ("en", 674, include_str!("../../../resources/carrier/en/674.txt")),
+    ("en", 675, include_str!("../../../resources/carrier/en/675.txt")),
+    ("en", 676, include_str!("../../../resources/carrier/en/676.txt")),
+    ("en", 677, include_str!("../../../resources/carrier/en/677.txt")),
+    ("en", 678, include_str!("../../../resources/carrier/en/678.txt")),
+    ("en", 679, include_str!("../../../resources/carrier/en/679.txt")),
+    ("en", 680, include_str!("../../../resources/carrier/en/680.txt")),
+    ("en", 681, include_str!("../../../resources/carrier/en/681.txt")),
+    ("en", 682, include_str!("../../../resources/carrier/en/682.txt")),
+    ("en", 683, include_str!("../../../resources/carrier/en/683.txt")),
+    ("en", 685, include_str!("../../../resources/carrier/en/685.txt")),
+    ("en", 686, include_str!("../../../resources/carrier/en/686.txt")),
+    ("en", 687, include_str!("../../../resources/carrier/en/687.txt")),
+    ("en", 688, include_str!("../../../resources/carrier/en/688.txt")),
+    ("en", 689, include_str!("../../../resources/carrier/en/689.txt")),
+    ("en", 690, include_str!("../../../resources/carrier/en/690.txt")),
+    ("en", 691, include_str!("../../../resources/carrier/en/691.txt")),
+    ("en", 692, include_str!("../../../resources/carrier/en/692.txt")),
+    ("en", 7, include_str!("../../../resources/carrier/en/7.txt")),
+    ("en", 81, include_str!("../../../resources/carrier/en/81.txt")),
+    ("en", 82, include_str!("../../../resources/carrier/en/82.txt")),
+    ("en", 84, include_str!("../../../resources/carrier/en/84.txt")),
+    ("en", 850, include_str!("../../../resources/carrier/en/850.txt")),
+    ("en", 852, include_str!("../../../resources/carrier/en/852.txt")),
+    ("en", 853, include_str!("../../../resources/carrier/en/853.txt")),
+    ("en", 855, include_str!("../../../resources/carrier/en/855.txt")),
+    ("en", 856, include_str!("../../../resources/carrier/en/856.txt")),
+    ("en", 86, include_str!("../../../resources/carrier/en/86.txt")),
+    ("en", 880, include_str!("../../../resources/carrier/en/880.txt")),
+    ("en", 881, include_str!("../../../resources/carrier/en/881.txt")),
+    ("en", 882, include_str!("../../../resources/carrier/en/882.txt")),
+    ("en", 886, include_str!("../../../resources/carrier/en/886.txt")),
+    ("en", 90, include_str!("../../../resources/carrier/en/90.txt")),
+    ("en", 91, include_str!("../../../resources/carrier/en/91.txt")),
+    ("en", 92, include_str!("../../../resources/carrier/en/92.txt")),
+    ("en", 93, include_str!("../../../resources/carrier/en/93.txt")),
+    ("en", 94, include_str!("../../../resources/carrier/en/94.txt")),
+    ("en", 95, include_str!("../../../resources/carrier/en/95.txt")),
+    ("en", 960, include_str!("../../../resources/carrier/en/960.txt")),
+    ("en", 961, include_str!("../../../resources/carrier/en/961.txt")),
+    ("en", 962, include_str!("../../../resources/carrier/en/962.txt")),
+    ("en", 963, include_str!("../../../resources/carrier/en/963.txt")),
+    ("en", 964, include_str!("../../../resources/carrier/en/964.txt")),
+    ("en", 965, include_str!("../../../resources/carrier/en/965.txt")),
+    ("en", 966, include_str!("../../../resources/carrier/en/966.txt")),
+    ("en", 967, include_str!("../../../resources/carrier/en/967.txt")),
+    ("en", 968, include_str!("../../../resources/carrier/en/968.txt")),
+    ("en", 970, include_str!("../../../resources/carrier/en/970.txt")),
+    ("en", 971, include_str!("../../../resources/carrier/en/971.txt")),
+    ("en", 972, include_str!("../../../resources/carrier/en/972.txt")),
+    ("en", 973, include_str!("../../../resources/carrier/en/973.txt")),
+    ("en", 974, include_str!("../../../resources/carrier/en/974.txt")),
+    ("en", 975, include_str!("../../../resources/carrier/en/975.txt")),
+    ("en", 976, include_str!("../../../resources/carrier/en/976.txt")),
+    ("en", 977, include_str!("../../../resources/carrier/en/977.txt")),
+    ("en", 98, include_str!("../../../resources/carrier/en/98.txt")),
+    ("en", 992, include_str!("../../../resources/carrier/en/992.txt")),
+    ("en", 993, include_str!("../../../resources/carrier/en/993.txt")),
+    ("en", 994, include_str!("../../../resources/carrier/en/994.txt")),
+    ("en", 995, include_str!("../../../resources/carrier/en/995.txt")),
+    ("en", 996, include_str!("../../../resources/carrier/en/996.txt")),
+    ("en", 998, include_str!("../../../resources/carrier/en/998.txt")),
+    ("fa", 93, include_str!("../../../resources/carrier/fa/93.txt")),
+    ("fa", 98, include_str!("../../../resources/carrier/fa/98.txt")),
+    ("ko", 82, include_str!("../../../resources/carrier/ko/82.txt")),
+    ("ru", 374, include_str!("../../../resources/carrier/ru/374.txt")),
+    ("ru", 375, include_str!("../../../resources/carrier/ru/375.txt")),
+    ("ru", 7, include_str!("../../../resources/carrier/ru/7.txt")),
+    ("uk", 380, include_str!("../../../resources/carrier/uk/380.txt")),
+    ("zh", 852, include_str!("../../../resources/carrier/zh/852.txt")),
+    ("zh", 86, include_str!("../../../resources/carrier/zh/86.txt")),
+    ("zh_Hant", 852, include_str!("../../../resources/carrier/zh_Hant/852.txt")),
+    ("zh_Hant", 86, include_str!("../../../resources/carrier/zh_Hant/86.txt")),
+];
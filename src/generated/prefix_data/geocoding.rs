@@ -0,0 +1,240 @@
+// Copyright (C) 2025 Kashin Vladislav (Rust adaptation author)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Files from resources/geocoding as (language, country calling code, contents).
+pub static GEOCODING_DATA: &[(&str, i32, &str)] = &[
+    ("ar", 966, include_str!("../../../resources/geocoding/ar/966.txt")),
+    ("be", 375, include_str!("../../../resources/geocoding/be/375.txt")),
+    ("bg", 359, include_str!("../../../resources/geocoding/bg/359.txt")),
+    ("bs", 387, include_str!("../../../resources/geocoding/bs/387.txt")),
+    ("de", 32, include_str!("../../../resources/geocoding/de/32.txt")),
+    ("de", 352, include_str!("../../../resources/geocoding/de/352.txt")),
+    ("de", 41, include_str!("../../../resources/geocoding/de/41.txt")),
+    ("de", 43, include_str!("../../../resources/geocoding/de/43.txt")),
+    ("de", 49, include_str!("../../../resources/geocoding/de/49.txt")),
+    ("el", 30, include_str!("../../../resources/geocoding/el/30.txt")),
+    ("en", 1, include_str!("../../../resources/geocoding/en/1.txt")),
+    ("en", 20, include_str!("../../../resources/geocoding/en/20.txt")),
+    ("en", 212, include_str!("../../../resources/geocoding/en/212.txt")),
+    ("en", 213, include_str!("../../../resources/geocoding/en/213.txt")),
+    ("en", 216, include_str!("../../../resources/geocoding/en/216.txt")),
+    ("en", 218, include_str!("../../../resources/geocoding/en/218.txt")),
+    ("en", 220, include_str!("../../../resources/geocoding/en/220.txt")),
+    ("en", 221, include_str!("../../../resources/geocoding/en/221.txt")),
+    ("en", 222, include_str!("../../../resources/geocoding/en/222.txt")),
+    ("en", 223, include_str!("../../../resources/geocoding/en/223.txt")),
+    ("en", 224, include_str!("../../../resources/geocoding/en/224.txt")),
+    ("en", 225, include_str!("../../../resources/geocoding/en/225.txt")),
+    ("en", 226, include_str!("../../../resources/geocoding/en/226.txt")),
+    ("en", 227, include_str!("../../../resources/geocoding/en/227.txt")),
+    ("en", 228, include_str!("../../../resources/geocoding/en/228.txt")),
+    ("en", 229, include_str!("../../../resources/geocoding/en/229.txt")),
+    ("en", 230, include_str!("../../../resources/geocoding/en/230.txt")),
+    ("en", 232, include_str!("../../../resources/geocoding/en/232.txt")),
+    ("en", 233, include_str!("../../../resources/geocoding/en/233.txt")),
+    ("en", 234, include_str!("../../../resources/geocoding/en/234.txt")),
+    ("en", 236, include_str!("../../../resources/geocoding/en/236.txt")),
+    ("en", 237, include_str!("../../../resources/geocoding/en/237.txt")),
+    ("en", 238, include_str!("../../../resources/geocoding/en/238.txt")),
+    ("en", 239, include_str!("../../../resources/geocoding/en/239.txt")),
+    ("en", 240, include_str!("../../../resources/geocoding/en/240.txt")),
+    ("en", 241, include_str!("../../../resources/geocoding/en/241.txt")),
+    ("en", 242, include_str!("../../../resources/geocoding/en/242.txt")),
+    ("en", 243, include_str!("../../../resources/geocoding/en/243.txt")),
+    ("en", 244, include_str!("../../../resources/geocoding/en/244.txt")),
+    ("en", 245, include_str!("../../../resources/geocoding/en/245.txt")),
+    ("en", 247, include_str!("../../../resources/geocoding/en/247.txt")),
+    ("en", 249, include_str!("../../../resources/geocoding/en/249.txt")),
+    ("en", 251, include_str!("../../../resources/geocoding/en/251.txt")),
+    ("en", 252, include_str!("../../../resources/geocoding/en/252.txt")),
+    ("en", 254, include_str!("../../../resources/geocoding/en/254.txt")),
+    ("en", 255, include_str!("../../../resources/geocoding/en/255.txt")),
+    ("en", 256, include_str!("../../../resources/geocoding/en/256.txt")),
+    ("en", 257, include_str!("../../../resources/geocoding/en/257.txt")),
+    ("en", 258, include_str!("../../../resources/geocoding/en/258.txt")),
+    ("en", 260, include_str!("../../../resources/geocoding/en/260.txt")),
+    ("en", 261, include_str!("../../../resources/geocoding/en/261.txt")),
+    ("en", 263, include_str!("../../../resources/geocoding/en/263.txt")),
+    ("en", 264, include_str!("../../../resources/geocoding/en/264.txt")),
+    ("en", 266, include_str!("../../../resources/geocoding/en/266.txt")),
+    ("en", 267, include_str!("../../../resources/geocoding/en/267.txt")),
+    ("en", 268, include_str!("../../../resources/geocoding/en/268.txt")),
+    ("en", 269, include_str!("../../../resources/geocoding/en/269.txt")),
+    ("en", 27, include_str!("../../../resources/geocoding/en/27.txt")),
+    ("en", 290, include_str!("../../../resources/geocoding/en/290.txt")),
+    ("en", 299, include_str!("../../../resources/geocoding/en/299.txt")),
+    ("en", 30, include_str!("../../../resources/geocoding/en/30.txt")),
+    ("en", 31, include_str!("../../../resources/geocoding/en/31.txt")),
+    ("en", 32, include_str!("../../../resources/geocoding/en/32.txt")),
+    ("en", 34, include_str!("../../../resources/geocoding/en/34.txt")),
+    ("en", 351, include_str!("../../../resources/geocoding/en/351.txt")),
+    ("en", 352, include_str!("../../../resources/geocoding/en/352.txt")),
+    ("en", 353, include_str!("../../../resources/geocoding/en/353.txt")),
+    ("en", 354, include_str!("../../../resources/geocoding/en/354.txt")),
+    ("en", 355, include_str!("../../../resources/geocoding/en/355.txt")),
+    ("en", 358, include_str!("../../../resources/geocoding/en/358.txt")),
+    ("en", 359, include_str!("../../../resources/geocoding/en/359.txt")),
+    ("en", 36, include_str!("../../../resources/geocoding/en/36.txt")),
+    ("en", 370, include_str!("../../../resources/geocoding/en/370.txt")),
+    ("en", 373, include_str!("../../../resources/geocoding/en/373.txt")),
+    ("en", 374, include_str!("../../../resources/geocoding/en/374.txt")),
+    ("en", 375, include_str!("../../../resources/geocoding/en/375.txt")),
+    ("en", 380, include_str!("../../../resources/geocoding/en/380.txt")),
+    ("en", 381, include_str!("../../../resources/geocoding/en/381.txt")),
+    ("en", 382, include_str!("../../../resources/geocoding/en/382.txt")),
+    ("en", 383, include_str!("../../../resources/geocoding/en/383.txt")),
+    ("en", 385, include_str!("../../../resources/geocoding/en/385.txt")),
+    ("en", 386, include_str!("../../../resources/geocoding/en/386.txt")),
+    ("en", 387, include_str!("../../../resources/geocoding/en/387.txt")),
+    ("en", 389, include_str!("../../../resources/geocoding/en/389.txt")),
+    ("en", 39, include_str!("../../../resources/geocoding/en/39.txt")),
+    ("en", 40, include_str!("../../../resources/geocoding/en/40.txt")),
+    ("en", 41, include_str!("../../../resources/geocoding/en/41.txt")),
+    ("en", 420, include_str!("../../../resources/geocoding/en/420.txt")),
+    ("en", 421, include_str!("../../../resources/geocoding/en/421.txt")),
+    ("en", 43, include_str!("../../../resources/geocoding/en/43.txt")),
+    ("en", 44, include_str!("../../../resources/geocoding/en/44.txt")),
+    ("en", 46, include_str!("../../../resources/geocoding/en/46.txt")),
+    ("en", 47, include_str!("../../../resources/geocoding/en/47.txt")),
+    ("en", 48, include_str!("../../../resources/geocoding/en/48.txt")),
+    ("en", 49, include_str!("../../../resources/geocoding/en/49.txt")),
+    ("en", 501, include_str!("../../../resources/geocoding/en/501.txt")),
+    ("en", 504, include_str!("../../../resources/geocoding/en/504.txt")),
+    ("en", 51, include_str!("../../../resources/geocoding/en/51.txt")),
+    ("en", 52, include_str!("../../../resources/geocoding/en/52.txt")),
+    ("en", 53, include_str!("../../../resources/geocoding/en/53.txt")),
+    ("en", 54, include_str!("../../../resources/geocoding/en/54.txt")),
+    ("en", 55, include_str!("../../../resources/geocoding/en/55.txt")),
+    ("en", 56, include_str!("../../../resources/geocoding/en/56.txt")),
+    ("en", 57, include_str!("../../../resources/geocoding/en/57.txt")),
+    ("en", 58, include_str!("../../../resources/geocoding/en/58.txt")),
+    ("en", 592, include_str!("../../../resources/geocoding/en/592.txt")),
+    ("en", 593, include_str!("../../../resources/geocoding/en/593.txt")),
+    ("en", 595, include_str!("../../../resources/geocoding/en/595.txt")),
+    ("en", 598, include_str!("../../../resources/geocoding/en/598.txt")),
+    ("en", 599, include_str!("../../../resources/geocoding/en/599.txt")),
+    ("en", 61, include_str!("../../../resources/geocoding/en/61.txt")),
+    ("en", 62, include_str!("../../../resources/geocoding/en/62.txt")),
+    ("en", 63, include_str!("../../../resources/geocoding/en/63.txt")),
+    ("en", 64, include_str!("../../../resources/geocoding/en/64.txt")),
+    ("en", 66, include_str!("../../../resources/geocoding/en/66.txt")),
+    ("en", 670, include_str!("../../../resources/geocoding/en/670.txt")),
+    ("en", 672, include_str!("../../../resources/geocoding/en/672.txt")),
+    ("en", 673, include_str!("../../../resources/geocoding/en/673.txt")),
+    ("en", 675, include_str!("../../../resources/geocoding/en/675.txt")),
+    ("en", 676, include_str!("../../../resources/geocoding/en/676.txt")),
+    ("en", 678, include_str!("../../../resources/geocoding/en/678.txt")),
+    ("en", 679, include_str!("../../../resources/geocoding/en/679.txt")),
+    ("en", 680, include_str!("../../../resources/geocoding/en/680.txt")),
+    ("en", 682, include_str!("../../../resources/geocoding/en/682.txt")),
+    ("en", 685, include_str!("../../../resources/geocoding/en/685.txt")),
+    ("en", 686, include_str!("../../../resources/geocoding/en/686.txt")),
+    ("en", 688, include_str!("../../../resources/geocoding/en/688.txt")),
+    ("en", 689, include_str!("../../../resources/geocoding/en/689.txt")),
+    ("en", 690, include_str!("../../../resources/geocoding/en/690.txt")),
+    ("en", 7, include_str!("../../../resources/geocoding/en/7.txt")),
+    ("en", 81, include_str!("../../../resources/geocoding/en/81.txt")),
+    ("en", 82, include_str!("../../../resources/geocoding/en/82.txt")),
+    ("en", 84, include_str!("../../../resources/geocoding/en/84.txt")),
+    ("en", 850, include_str!("../../../resources/geocoding/en/850.txt")),
+    ("en", 86, include_str!("../../../resources/geocoding/en/86.txt")),
+    ("en", 880, include_str!("../../../resources/geocoding/en/880.txt")),
+    ("en", 886, include_str!("../../../resources/geocoding/en/886.txt")),
+    ("en", 90, include_str!("../../../resources/geocoding/en/90.txt")),
+    ("en", 91, include_str!("../../../resources/geocoding/en/91.txt")),
+    ("en", 92, include_str!("../../../resources/geocoding/en/92.txt")),
+    ("en", 93, include_str!("../../../resources/geocoding/en/93.txt")),
+    ("en", 94, include_str!("../../../resources/geocoding/en/94.txt")),
+    ("en", 95, include_str!("../../../resources/geocoding/en/95.txt")),
+    ("en", 960, include_str!("../../../resources/geocoding/en/960.txt")),
+    ("en", 961, include_str!("../../../resources/geocoding/en/961.txt")),
+    ("en", 962, include_str!("../../../resources/geocoding/en/962.txt")),
+    ("en", 963, include_str!("../../../resources/geocoding/en/963.txt")),
+    ("en", 966, include_str!("../../../resources/geocoding/en/966.txt")),
+    ("en", 967, include_str!("../../../resources/geocoding/en/967.txt")),
+    ("en", 968, include_str!("../../../resources/geocoding/en/968.txt")),
+    ("en", 970, include_str!("../../../resources/geocoding/en/970.txt")),
+    ("en", 971, include_str!("../../../resources/geocoding/en/971.txt")),
+    ("en", 972, include_str!("../../../resources/geocoding/en/972.txt")),
+    ("en", 975, include_str!("../../../resources/geocoding/en/975.txt")),
+    ("en", 976, include_str!("../../../resources/geocoding/en/976.txt")),
+    ("en", 98, include_str!("../../../resources/geocoding/en/98.txt")),
+    ("en", 992, include_str!("../../../resources/geocoding/en/992.txt")),
+    ("en", 993, include_str!("../../../resources/geocoding/en/993.txt")),
+    ("en", 994, include_str!("../../../resources/geocoding/en/994.txt")),
+    ("en", 995, include_str!("../../../resources/geocoding/en/995.txt")),
+    ("en", 996, include_str!("../../../resources/geocoding/en/996.txt")),
+    ("es", 228, include_str!("../../../resources/geocoding/es/228.txt")),
+    ("es", 230, include_str!("../../../resources/geocoding/es/230.txt")),
+    ("es", 34, include_str!("../../../resources/geocoding/es/34.txt")),
+    ("es", 52, include_str!("../../../resources/geocoding/es/52.txt")),
+    ("es", 54, include_str!("../../../resources/geocoding/es/54.txt")),
+    ("es", 56, include_str!("../../../resources/geocoding/es/56.txt")),
+    ("es", 57, include_str!("../../../resources/geocoding/es/57.txt")),
+    ("es", 58, include_str!("../../../resources/geocoding/es/58.txt")),
+    ("fa", 93, include_str!("../../../resources/geocoding/fa/93.txt")),
+    ("fa", 98, include_str!("../../../resources/geocoding/fa/98.txt")),
+    ("fi", 358, include_str!("../../../resources/geocoding/fi/358.txt")),
+    ("fr", 212, include_str!("../../../resources/geocoding/fr/212.txt")),
+    ("fr", 222, include_str!("../../../resources/geocoding/fr/222.txt")),
+    ("fr", 225, include_str!("../../../resources/geocoding/fr/225.txt")),
+    ("fr", 228, include_str!("../../../resources/geocoding/fr/228.txt")),
+    ("fr", 229, include_str!("../../../resources/geocoding/fr/229.txt")),
+    ("fr", 230, include_str!("../../../resources/geocoding/fr/230.txt")),
+    ("fr", 242, include_str!("../../../resources/geocoding/fr/242.txt")),
+    ("fr", 243, include_str!("../../../resources/geocoding/fr/243.txt")),
+    ("fr", 269, include_str!("../../../resources/geocoding/fr/269.txt")),
+    ("fr", 290, include_str!("../../../resources/geocoding/fr/290.txt")),
+    ("fr", 32, include_str!("../../../resources/geocoding/fr/32.txt")),
+    ("fr", 352, include_str!("../../../resources/geocoding/fr/352.txt")),
+    ("fr", 41, include_str!("../../../resources/geocoding/fr/41.txt")),
+    ("hr", 387, include_str!("../../../resources/geocoding/hr/387.txt")),
+    ("hu", 36, include_str!("../../../resources/geocoding/hu/36.txt")),
+    ("hy", 374, include_str!("../../../resources/geocoding/hy/374.txt")),
+    ("id", 62, include_str!("../../../resources/geocoding/id/62.txt")),
+    ("it", 39, include_str!("../../../resources/geocoding/it/39.txt")),
+    ("it", 41, include_str!("../../../resources/geocoding/it/41.txt")),
+    ("iw", 972, include_str!("../../../resources/geocoding/iw/972.txt")),
+    ("ja", 81, include_str!("../../../resources/geocoding/ja/81.txt")),
+    ("ko", 82, include_str!("../../../resources/geocoding/ko/82.txt")),
+    ("nl", 31, include_str!("../../../resources/geocoding/nl/31.txt")),
+    ("nl", 32, include_str!("../../../resources/geocoding/nl/32.txt")),
+    ("pl", 48, include_str!("../../../resources/geocoding/pl/48.txt")),
+    ("pt", 238, include_str!("../../../resources/geocoding/pt/238.txt")),
+    ("pt", 239, include_str!("../../../resources/geocoding/pt/239.txt")),
+    ("pt", 244, include_str!("../../../resources/geocoding/pt/244.txt")),
+    ("pt", 245, include_str!("../../../resources/geocoding/pt/245.txt")),
+    ("pt", 258, include_str!("../../../resources/geocoding/pt/258.txt")),
+    ("pt", 351, include_str!("../../../resources/geocoding/pt/351.txt")),
+    ("pt", 55, include_str!("../../../resources/geocoding/pt/55.txt")),
+    ("ro", 373, include_str!("../../../resources/geocoding/ro/373.txt")),
+    ("ro", 40, include_str!("../../../resources/geocoding/ro/40.txt")),
+    ("ru", 373, include_str!("../../../resources/geocoding/ru/373.txt")),
+    ("ru", 374, include_str!("../../../resources/geocoding/ru/374.txt")),
+    ("ru", 375, include_str!("../../../resources/geocoding/ru/375.txt")),
+    ("ru", 7, include_str!("../../../resources/geocoding/ru/7.txt")),
+    ("sq", 383, include_str!("../../../resources/geocoding/sq/383.txt")),
+    ("sr", 381, include_str!("../../../resources/geocoding/sr/381.txt")),
+    ("sr", 383, include_str!("../../../resources/geocoding/sr/383.txt")),
+    ("sr", 387, include_str!("../../../resources/geocoding/sr/387.txt")),
+    ("sv", 358, include_str!("../../../resources/geocoding/sv/358.txt")),
+    ("sv", 46, include_str!("../../../resources/geocoding/sv/46.txt")),
+    ("th", 66, include_str!("../../../resources/geocoding/th/66.txt")),
+    ("tr", 90, include_str!("../../../resources/geocoding/tr/90.txt")),
+    ("uk", 380, include_str!("../../../resources/geocoding/uk/380.txt")),
+    ("vi", 84, include_str!("../../../resources/geocoding/vi/84.txt")),
+    ("zh", 86, include_str!("../../../resources/geocoding/zh/86.txt")),
+    ("zh", 886, include_str!("../../../resources/geocoding/zh/886.txt")),
+    ("zh_Hant", 886, include_str!("../../../resources/geocoding/zh_Hant/886.txt")),
+];
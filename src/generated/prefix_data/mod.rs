@@ -0,0 +1,27 @@
+// Copyright (C) 2025 Kashin Vladislav (Rust adaptation author)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "carrier")]
+mod carrier;
+#[cfg(feature = "geocoder")]
+mod geocoding;
+#[cfg(feature = "timezones")]
+mod timezones;
+
+#[cfg(feature = "carrier")]
+pub use carrier::CARRIER_DATA;
+#[cfg(feature = "geocoder")]
+pub use geocoding::GEOCODING_DATA;
+#[cfg(feature = "timezones")]
+pub use timezones::TIMEZONES_DATA;
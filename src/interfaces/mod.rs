@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::generated::proto::phonemetadata::PhoneNumberDesc;
+use crate::{generated::proto::phonemetadata::PhoneNumberDesc, regexp_cache::{CacheStats, InvalidRegexError}};
 
 /// Internal phonenumber matching API used to isolate the underlying
 /// implementation of the matcher and allow different implementations to be
@@ -23,4 +23,11 @@ pub(crate) trait MatcherApi: Send + Sync {
   /// digits) matches the national number pattern defined in the given
   /// PhoneNumberDesc message.
   fn match_national_number(&self, number: &str, number_desc: &PhoneNumberDesc, allow_prefix_match: bool) -> bool;
+
+  /// Compiles a national number pattern into the regex cache of the matcher
+  /// ahead of its first use.
+  fn compile_pattern(&self, pattern: &str) -> Result<(), InvalidRegexError>;
+
+  /// Returns the statistics of the regex cache used by the matcher.
+  fn regex_cache_stats(&self) -> CacheStats;
 }
\ No newline at end of file
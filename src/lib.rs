@@ -17,11 +17,15 @@ mod interfaces;
 /// This module is automatically generated from /resources/*.proto
 mod generated;
 mod phonenumberutil;
+mod phonenumber_builder;
 mod regexp_cache;
 mod regex_based_matcher;
 pub mod region_code;
+pub mod prelude;
 pub(crate) mod regex_util;
 pub(crate) mod string_util;
+#[cfg(feature = "wasm")]
+mod wasm;
 
 /// I decided to create this module because there are many 
 /// boilerplate places in the code that can be replaced with macros, 
@@ -32,11 +36,28 @@ mod macros;
 pub use phonenumberutil::{
     PHONE_NUMBER_UTIL,
     phonenumberutil::PhoneNumberUtil,
+    parse_options::ParseOptions,
+    util_options::PhoneNumberUtilOptions,
+    number_parts::NumberParts,
+    borrowed_number::BorrowedNumber,
+    common_formats::CommonFormats,
+    metadata_validation::MetadataProblem,
+    number_or_string::NumberOrString,
+    as_you_type::AsYouTypeFormatter,
+    phone_number_matcher::{PhoneNumberMatch, PhoneNumberMatcher},
+    short_number::ShortNumberInfo,
     errors::{*},
     enums::{*},
 };
+#[cfg(feature = "geocoder")]
+pub use phonenumberutil::geocoder::PhoneNumberOfflineGeocoder;
+#[cfg(feature = "carrier")]
+pub use phonenumberutil::carrier::PhoneNumberToCarrierMapper;
+#[cfg(feature = "timezones")]
+pub use phonenumberutil::timezones::{PhoneNumberToTimeZonesMapper, UNKNOWN_TIMEZONE};
 pub use generated::proto::phonemetadata;
 pub use generated::proto::phonenumber::PhoneNumber;
+pub use phonenumber_builder::PhoneNumberBuilder;
 pub use generated::proto::phonenumber::phone_number::CountryCodeSource;
-pub use regexp_cache::InvalidRegexError;
+pub use regexp_cache::{CacheStats, InvalidRegexError};
 mod tests;
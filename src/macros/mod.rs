@@ -33,3 +33,40 @@ macro_rules! owned_from_cow_or {
 
 pub(crate) use owned_from_cow_or;
 
+
+/// Internal logging macros. With the `logging` feature they are the macros of
+/// the `log` crate; without it they expand to nothing, so the library never
+/// logs unless asked to. The arguments are still type-checked in both cases.
+#[cfg(feature = "logging")]
+pub(crate) use log::{error, trace, warn};
+
+#[cfg(not(feature = "logging"))]
+macro_rules! silent_log {
+    ($($arg:tt)+) => {{
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    }};
+}
+
+#[cfg(not(feature = "logging"))]
+macro_rules! silent_error {
+    ($($arg:tt)+) => { $crate::macros::silent_log!($($arg)+) };
+}
+
+#[cfg(not(feature = "logging"))]
+macro_rules! silent_trace {
+    ($($arg:tt)+) => { $crate::macros::silent_log!($($arg)+) };
+}
+
+#[cfg(not(feature = "logging"))]
+macro_rules! silent_warn {
+    ($($arg:tt)+) => { $crate::macros::silent_log!($($arg)+) };
+}
+
+// The macros are defined under other names, since `warn` can't be defined
+// directly without clashing with the built-in attribute.
+#[cfg(not(feature = "logging"))]
+pub(crate) use {
+    silent_error as error, silent_log, silent_trace as trace, silent_warn as warn,
+};
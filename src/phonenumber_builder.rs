@@ -0,0 +1,73 @@
+// Copyright (C) 2025 Kashin Vladislav (Rust adaptation author)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::generated::proto::phonenumber::PhoneNumber;
+
+/// Builds a `PhoneNumber` with chained calls instead of the protobuf setters.
+///
+/// The builder only fills in the fields, nothing is validated. Use
+/// `PhoneNumberUtil::is_valid_number` on the result to check it.
+///
+/// ```
+/// use rlibphonenumber::PhoneNumberBuilder;
+///
+/// let number = PhoneNumberBuilder::new()
+///     .country_code(44)
+///     .national_number(2070313000)
+///     .extension("123")
+///     .build();
+/// assert_eq!(44, number.country_code());
+/// assert_eq!("123", number.extension());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PhoneNumberBuilder {
+    number: PhoneNumber,
+}
+
+impl PhoneNumberBuilder {
+    /// Creates a builder for a `PhoneNumber` with no fields set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the country calling code, e.g. `1` for the US.
+    pub fn country_code(mut self, country_code: i32) -> Self {
+        self.number.set_country_code(country_code);
+        self
+    }
+
+    /// Sets the national number, without any leading zeros.
+    pub fn national_number(mut self, national_number: u64) -> Self {
+        self.number.set_national_number(national_number);
+        self
+    }
+
+    /// Sets the extension, e.g. `"123"`.
+    pub fn extension(mut self, extension: impl Into<String>) -> Self {
+        self.number.set_extension(extension.into());
+        self
+    }
+
+    /// Sets whether the national significant number starts with a zero that is
+    /// not a national prefix, as in Italian fixed-line numbers.
+    pub fn italian_leading_zero(mut self, italian_leading_zero: bool) -> Self {
+        self.number.set_italian_leading_zero(italian_leading_zero);
+        self
+    }
+
+    /// Returns the built `PhoneNumber`.
+    pub fn build(self) -> PhoneNumber {
+        self.number
+    }
+}
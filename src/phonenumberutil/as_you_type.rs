@@ -0,0 +1,860 @@
+// Copyright (C) 2011 The Libphonenumber Authors
+// Copyright (C) 2025 Kashin Vladislav (Rust adaptation author)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, LazyLock};
+
+use regex::Regex;
+
+use crate::{
+    generated::proto::phonemetadata::{NumberFormat, PhoneMetadata},
+    regex_util::{RegexConsume, RegexFullMatch},
+    regexp_cache::InvalidRegexError,
+};
+
+use super::{
+    helper_constants::{PLUS_CHARS, PLUS_SIGN},
+    phonenumberutil_internal::PhoneNumberUtilInternal,
+};
+
+type RegexResult<T> = std::result::Result<T, InvalidRegexError>;
+
+/// Character used when appropriate to separate a prefix, such as a long NDD or a
+/// country calling code, from the national number.
+const SEPARATOR_BEFORE_NATIONAL_NUMBER: char = ' ';
+
+/// Stands for a digit not entered yet in a formatting template.
+const DIGIT_PLACEHOLDER: char = '\u{2008}';
+
+/// The minimum number of digits of the national number (excluding the national
+/// prefix) after which formatting is attempted.
+const MIN_LEADING_DIGITS_LENGTH: usize = 3;
+
+/// A phone number consisting only of the digit 9 that is longer than any number
+/// a formatting pattern accepts. It is used to build the formatting templates.
+const LONGEST_PHONE_NUMBER: &str = "999999999999999";
+
+/// Metadata used when the formatter is created for an unknown region. Formatting
+/// then only works for numbers entered with a "+".
+static EMPTY_METADATA: LazyLock<PhoneMetadata> = LazyLock::new(|| {
+    let mut metadata = PhoneMetadata::new();
+    metadata.set_international_prefix("NA".to_owned());
+    metadata
+});
+
+/// A national prefix formatting rule with one of these separators asks for a
+/// space between the national prefix and the national number.
+static NATIONAL_PREFIX_SEPARATORS_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new("[- ]").unwrap());
+
+/// Character classes in a formatting pattern, e.g. "[1-5]".
+static CHARACTER_CLASS_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[([^\[\]])*\]").unwrap());
+
+/// Formats a phone number on-the-fly as each digit is entered.
+///
+/// A formatter is obtained with `PhoneNumberUtil::get_as_you_type_formatter` and
+/// is fed one character at a time with `input_digit`, which returns the number
+/// formatted so far, e.g. for the US "6", "65", "650", "650 2", ..., "650 253 2222".
+/// A leading "+" or international dialing prefix switches to the rules of the
+/// country calling code that follows, and a national prefix, such as the "0" in
+/// "020 7031 3000" for GB, is kept in front of the national number.
+///
+/// As soon as a character that is neither a digit nor a leading "+" is entered,
+/// formatting stops and the input is returned as is. Call `clear` to start over
+/// with a new number.
+pub struct AsYouTypeFormatter {
+    util: Arc<PhoneNumberUtilInternal>,
+
+    current_output: String,
+    /// The template for the current formatting pattern, with every digit not
+    /// entered yet represented by `DIGIT_PLACEHOLDER`.
+    formatting_template: Vec<char>,
+    /// The pattern from the metadata the formatting template was built from.
+    current_formatting_pattern: String,
+    accrued_input: String,
+    /// The digits and the leading plus sign entered so far, normalized to ASCII.
+    accrued_input_without_formatting: String,
+    /// Set to false when the input can't be formatted, e.g. because it contains
+    /// characters other than digits or a leading "+".
+    able_to_format: bool,
+    /// Set to true when the user has entered their own formatting characters.
+    input_has_formatting: bool,
+    /// True when the number was entered with a "+", an IDD or a national prefix,
+    /// i.e. it doesn't need any more digits in front of it.
+    is_complete_number: bool,
+    is_expecting_country_code: bool,
+    /// The country calling code of the region the formatter was created for. The
+    /// metadata of its main country is used for numbers entered without one.
+    default_country_code: Option<i32>,
+    /// The country calling code whose metadata is currently used.
+    current_country_code: Option<i32>,
+    last_match_position: usize,
+    /// The byte position after the remembered character in `accrued_input`.
+    original_position: usize,
+    /// The position of the remembered character in
+    /// `accrued_input_without_formatting`.
+    position_to_remember: usize,
+    /// Everything in front of the national number, e.g. the IDD, the country
+    /// calling code and the national prefix, together with their separators.
+    prefix_before_national_number: String,
+    should_add_space_after_national_prefix: bool,
+    /// The national prefix that was extracted from the input, if any. It may
+    /// also be a carrier code, as they are not told apart.
+    extracted_national_prefix: String,
+    national_number: String,
+    /// The indices of the formats that may apply, in the international or the
+    /// national formats of the current metadata, see `uses_intl_number_format`.
+    possible_formats: Vec<usize>,
+    /// Whether `possible_formats` refers to the international formats.
+    uses_intl_number_format: bool,
+}
+
+impl AsYouTypeFormatter {
+    /// Creates a formatter that formats numbers entered without a "+" or IDD as
+    /// numbers of the given region.
+    pub(crate) fn new(util: Arc<PhoneNumberUtilInternal>, region_code: &str) -> Self {
+        let default_country_code = util.get_country_code_for_region(region_code);
+        Self {
+            util,
+            current_output: String::new(),
+            formatting_template: Vec::new(),
+            current_formatting_pattern: String::new(),
+            accrued_input: String::new(),
+            accrued_input_without_formatting: String::new(),
+            able_to_format: true,
+            input_has_formatting: false,
+            is_complete_number: false,
+            is_expecting_country_code: false,
+            default_country_code,
+            current_country_code: default_country_code,
+            last_match_position: 0,
+            original_position: 0,
+            position_to_remember: 0,
+            prefix_before_national_number: String::new(),
+            should_add_space_after_national_prefix: false,
+            extracted_national_prefix: String::new(),
+            national_number: String::new(),
+            possible_formats: Vec::new(),
+            uses_intl_number_format: false,
+        }
+    }
+
+    /// Formats a phone number on-the-fly as each digit is entered.
+    ///
+    /// # Parameters
+    ///
+    /// * `next_char`: The most recently entered character. Digits in any script
+    ///   are accepted; a "+" is only accepted as the first character.
+    ///
+    /// # Returns
+    ///
+    /// The number entered so far, formatted as well as possible. When no format
+    /// applies, the characters entered so far are returned unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn input_digit(&mut self, next_char: char) -> String {
+        self.input_digit_with_option_to_remember_position(next_char, false)
+    }
+
+    /// Same as `input_digit`, but remembers the position where `next_char` is
+    /// inserted, so that it can be retrieved later with `get_remembered_position`.
+    /// The remembered position is kept across later calls of `input_digit`, so it
+    /// can follow a cursor while the number is reformatted.
+    ///
+    /// # Parameters
+    ///
+    /// * `next_char`: The most recently entered character.
+    ///
+    /// # Returns
+    ///
+    /// The number entered so far, formatted as well as possible.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn input_digit_and_remember_position(&mut self, next_char: char) -> String {
+        self.input_digit_with_option_to_remember_position(next_char, true)
+    }
+
+    /// Returns the byte position in the current output right after the character
+    /// that was entered last with `input_digit_and_remember_position`.
+    pub fn get_remembered_position(&self) -> usize {
+        if !self.able_to_format {
+            return self.original_position;
+        }
+        let accrued_input_without_formatting = self.accrued_input_without_formatting.as_bytes();
+        let mut accrued_input_index = 0;
+        let mut current_output_index = 0;
+        for output_char in self.current_output.chars() {
+            if accrued_input_index >= self.position_to_remember {
+                break;
+            }
+            if accrued_input_without_formatting
+                .get(accrued_input_index)
+                .is_some_and(|&c| char::from(c) == output_char)
+            {
+                accrued_input_index += 1;
+            }
+            current_output_index += output_char.len_utf8();
+        }
+        current_output_index
+    }
+
+    /// Returns the national prefix extracted from the input so far, or an empty
+    /// string if none was found.
+    pub fn get_extracted_national_prefix(&self) -> &str {
+        &self.extracted_national_prefix
+    }
+
+    /// Clears the internal state of the formatter, so it can be reused for a new
+    /// number.
+    pub fn clear(&mut self) {
+        self.current_output.clear();
+        self.accrued_input.clear();
+        self.accrued_input_without_formatting.clear();
+        self.formatting_template.clear();
+        self.last_match_position = 0;
+        self.current_formatting_pattern.clear();
+        self.prefix_before_national_number.clear();
+        self.extracted_national_prefix.clear();
+        self.national_number.clear();
+        self.able_to_format = true;
+        self.input_has_formatting = false;
+        self.position_to_remember = 0;
+        self.original_position = 0;
+        self.is_complete_number = false;
+        self.is_expecting_country_code = false;
+        self.possible_formats.clear();
+        self.should_add_space_after_national_prefix = false;
+        self.current_country_code = self.default_country_code;
+    }
+
+    /// The metadata in use for the number entered so far.
+    fn current_metadata(&self) -> &PhoneMetadata {
+        metadata_for_country_code(&self.util, self.current_country_code)
+    }
+
+    /// The formats that may apply to the number entered so far. They are looked
+    /// up in `util`, a handle to `self.util`, so that they can be used while
+    /// `self` is modified.
+    fn possible_formats<'u>(&self, util: &'u PhoneNumberUtilInternal) -> Vec<&'u NumberFormat> {
+        let metadata = metadata_for_country_code(util, self.current_country_code);
+        let format_list = if self.uses_intl_number_format {
+            &metadata.intl_number_format
+        } else {
+            &metadata.number_format
+        };
+        self.possible_formats
+            .iter()
+            .map(|&index| &format_list[index])
+            .collect()
+    }
+
+    fn input_digit_with_option_to_remember_position(
+        &mut self,
+        next_char: char,
+        remember_position: bool,
+    ) -> String {
+        self.current_output = self
+            .input_digit_helper_with_option(next_char, remember_position)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.");
+        self.current_output.clone()
+    }
+
+    fn input_digit_helper_with_option(
+        &mut self,
+        next_char: char,
+        remember_position: bool,
+    ) -> RegexResult<String> {
+        self.accrued_input.push(next_char);
+        if remember_position {
+            self.original_position = self.accrued_input.len();
+        }
+        // We do formatting on-the-fly only when each character entered is either a
+        // digit, or a plus sign (accepted at the start of the number only).
+        let mut normalized_next_char = None;
+        let is_leading_plus = self.accrued_input.chars().count() == 1 && PLUS_CHARS.contains(next_char);
+        if !(is_leading_plus
+            || self
+                .util
+                .contains_only_valid_digits(next_char.encode_utf8(&mut [0; 4])))
+        {
+            self.able_to_format = false;
+            self.input_has_formatting = true;
+        } else {
+            normalized_next_char =
+                Some(self.normalize_and_accrue_digits_and_plus_sign(next_char, remember_position));
+        }
+        if !self.able_to_format {
+            // When we are unable to format because of reasons other than that
+            // formatting chars have been entered, it can be due to really long IDDs or
+            // NDDs. If that is the case, we might be able to do formatting again after
+            // extracting them.
+            if self.input_has_formatting {
+                return Ok(self.accrued_input.clone());
+            } else if self.attempt_to_extract_idd()? {
+                if self.attempt_to_extract_country_code() {
+                    return self.attempt_to_choose_pattern_with_prefix_extracted();
+                }
+            } else if self.able_to_extract_longer_ndd()? {
+                // Add an additional space to separate long NDD and national significant
+                // number for readability. We don't set
+                // should_add_space_after_national_prefix to true, since we don't want
+                // this to change later when we choose formatting templates.
+                self.prefix_before_national_number
+                    .push(SEPARATOR_BEFORE_NATIONAL_NUMBER);
+                return self.attempt_to_choose_pattern_with_prefix_extracted();
+            }
+            return Ok(self.accrued_input.clone());
+        }
+
+        // We start to attempt to format only when at least MIN_LEADING_DIGITS_LENGTH
+        // digits (the plus sign is counted as a digit as well for this purpose) have
+        // been entered.
+        match self.accrued_input_without_formatting.len() {
+            0..=2 => return Ok(self.accrued_input.clone()),
+            3 => {
+                if self.attempt_to_extract_idd()? {
+                    self.is_expecting_country_code = true;
+                } else {
+                    // No IDD or plus sign is found, might be entering in national format.
+                    self.extracted_national_prefix =
+                        self.remove_national_prefix_from_national_number()?;
+                    return self.attempt_to_choose_formatting_pattern();
+                }
+            }
+            _ => {}
+        }
+        if self.is_expecting_country_code {
+            if self.attempt_to_extract_country_code() {
+                self.is_expecting_country_code = false;
+            }
+            return Ok(fast_cat::concat_str!(
+                &self.prefix_before_national_number,
+                &self.national_number
+            ));
+        }
+        if self.possible_formats.is_empty() {
+            return self.attempt_to_choose_formatting_pattern();
+        }
+        // The formatting patterns are already chosen.
+        let Some(normalized_next_char) = normalized_next_char else {
+            return Ok(self.accrued_input.clone());
+        };
+        let temp_national_number = self.input_digit_helper(normalized_next_char);
+        // See if the accrued digits can be formatted properly already. If not, use
+        // the results from input_digit_helper, which does formatting based on the
+        // formatting pattern chosen.
+        if let Some(formatted_number) = self.attempt_to_format_accrued_digits()? {
+            return Ok(formatted_number);
+        }
+        self.narrow_down_possible_formats(&self.national_number.clone())?;
+        if self.maybe_create_new_template()? {
+            return Ok(self.input_accrued_national_number());
+        }
+        if self.able_to_format {
+            Ok(self.append_national_number(&temp_national_number))
+        } else {
+            Ok(self.accrued_input.clone())
+        }
+    }
+
+    fn attempt_to_choose_pattern_with_prefix_extracted(&mut self) -> RegexResult<String> {
+        self.able_to_format = true;
+        self.is_expecting_country_code = false;
+        self.possible_formats.clear();
+        self.last_match_position = 0;
+        self.formatting_template.clear();
+        self.current_formatting_pattern.clear();
+        self.attempt_to_choose_formatting_pattern()
+    }
+
+    /// Some national prefixes are a substring of others. If extracting the
+    /// shorter NDD doesn't result in a number we can format, we try to see if we
+    /// can extract a longer version here.
+    fn able_to_extract_longer_ndd(&mut self) -> RegexResult<bool> {
+        if !self.extracted_national_prefix.is_empty() {
+            // Put the extracted NDD back to the national number before attempting to
+            // extract a new NDD.
+            self.national_number
+                .insert_str(0, &self.extracted_national_prefix);
+            // Remove the previously extracted NDD from prefix_before_national_number.
+            // We cannot simply set it to empty string because people sometimes
+            // incorrectly enter national prefix after the country code, e.g.
+            // +44 (0)20-1234-5678.
+            if let Some(index_of_previous_ndd) = self
+                .prefix_before_national_number
+                .rfind(&self.extracted_national_prefix)
+            {
+                self.prefix_before_national_number
+                    .truncate(index_of_previous_ndd);
+            }
+        }
+        let new_national_prefix = self.remove_national_prefix_from_national_number()?;
+        Ok(self.extracted_national_prefix != new_national_prefix)
+    }
+
+    /// Checks whether the digits entered so far match one of the possible formats
+    /// exactly, and returns them formatted if so.
+    fn attempt_to_format_accrued_digits(&mut self) -> RegexResult<Option<String>> {
+        let util = Arc::clone(&self.util);
+        for number_format in self.possible_formats(&util) {
+            let pattern = self.util.regexp_cache().get_regex(number_format.pattern())?;
+            if !pattern.full_match(&self.national_number) {
+                continue;
+            }
+            self.set_should_add_space_after_national_prefix(number_format);
+            let formatted_number = pattern
+                .replace_all(&self.national_number, number_format.format())
+                .into_owned();
+            // Check that we didn't remove nor add any extra digits when we matched
+            // this formatting pattern. This usually happens after we entered the last
+            // digit during AYTF. Eg: In case of MX, we swallow mobile token (1) when
+            // formatted but AYTF should retain all the number entered and not change
+            // in order to match a format (of same leading digits and length) display
+            // in that way.
+            let full_output = self.append_national_number(&formatted_number);
+            if self.util.normalize_diallable_chars_only(&full_output)
+                == self.accrued_input_without_formatting
+            {
+                // If it's the same (i.e entered number and format is same), then it's
+                // safe to return this in formatted number as nothing is lost / added.
+                return Ok(Some(full_output));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Combines the national number with any prefix (IDD/+ and country code or
+    /// national prefix) that was collected.
+    fn append_national_number(&self, national_number: &str) -> String {
+        if self.should_add_space_after_national_prefix
+            && !self.prefix_before_national_number.is_empty()
+            && !self
+                .prefix_before_national_number
+                .ends_with(SEPARATOR_BEFORE_NATIONAL_NUMBER)
+        {
+            // We want to add a space after the national prefix if the national prefix
+            // formatting rule indicates that this would normally be done, with the
+            // exception of the case where we already appended a space because the NDD
+            // was surprisingly long.
+            let mut buf = [0; 4];
+            fast_cat::concat_str!(
+                &self.prefix_before_national_number,
+                SEPARATOR_BEFORE_NATIONAL_NUMBER.encode_utf8(&mut buf),
+                national_number
+            )
+        } else {
+            fast_cat::concat_str!(&self.prefix_before_national_number, national_number)
+        }
+    }
+
+    /// Attempts to set the formatting template and returns the formatted input.
+    fn attempt_to_choose_formatting_pattern(&mut self) -> RegexResult<String> {
+        // We start to attempt to format only when at least MIN_LEADING_DIGITS_LENGTH
+        // digits of national number (excluding national prefix) have been entered.
+        if self.national_number.len() < MIN_LEADING_DIGITS_LENGTH {
+            return Ok(self.append_national_number(&self.national_number));
+        }
+        self.get_available_formats(&self.national_number.clone())?;
+        // See if the accrued digits can be formatted properly already.
+        if let Some(formatted_number) = self.attempt_to_format_accrued_digits()? {
+            return Ok(formatted_number);
+        }
+        if self.maybe_create_new_template()? {
+            Ok(self.input_accrued_national_number())
+        } else {
+            Ok(self.accrued_input.clone())
+        }
+    }
+
+    /// Invokes input_digit_helper on each digit of the national number accrued,
+    /// and returns a formatted string in the end.
+    fn input_accrued_national_number(&mut self) -> String {
+        if self.national_number.is_empty() {
+            return self.prefix_before_national_number.clone();
+        }
+        let mut temp_national_number = String::new();
+        for digit in self.national_number.clone().chars() {
+            temp_national_number = self.input_digit_helper(digit);
+        }
+        if self.able_to_format {
+            self.append_national_number(&temp_national_number)
+        } else {
+            self.accrued_input.clone()
+        }
+    }
+
+    /// Returns true if the current country is a NANPA country and the national
+    /// number begins with the national prefix.
+    fn is_nanpa_number_with_national_prefix(&self) -> bool {
+        // For NANPA numbers beginning with 1[2-9], treat the 1 as the national
+        // prefix. The reason is that national significant numbers in NANPA always
+        // start with [2-9] after the national prefix. Numbers beginning with 1[01]
+        // can only be short/emergency numbers, which don't need the national
+        // prefix.
+        let national_number = self.national_number.as_bytes();
+        self.current_metadata().country_code() == 1
+            && national_number.first() == Some(&b'1')
+            && matches!(national_number.get(1), Some(b'2'..=b'9'))
+    }
+
+    /// Removes the national prefix from the national number and returns it.
+    fn remove_national_prefix_from_national_number(&mut self) -> RegexResult<String> {
+        let mut start_of_national_number = 0;
+        if self.is_nanpa_number_with_national_prefix() {
+            start_of_national_number = 1;
+            self.prefix_before_national_number.push('1');
+            self.prefix_before_national_number
+                .push(SEPARATOR_BEFORE_NATIONAL_NUMBER);
+            self.is_complete_number = true;
+        } else if self.current_metadata().has_national_prefix_for_parsing() {
+            let national_prefix_for_parsing = self
+                .util
+                .regexp_cache()
+                .get_regex(self.current_metadata().national_prefix_for_parsing())?;
+            // Since some national prefix patterns are entirely optional, check that a
+            // national prefix could actually be extracted.
+            if let Some(national_prefix) = national_prefix_for_parsing.find_start(&self.national_number)
+                && national_prefix.end() > 0
+            {
+                // When the national prefix is detected, we use international formatting
+                // rules instead of national ones, because national formatting rules
+                // could contain local formatting rules for numbers entered without area
+                // code.
+                start_of_national_number = national_prefix.end();
+                self.is_complete_number = true;
+                self.prefix_before_national_number
+                    .push_str(&self.national_number[..start_of_national_number]);
+            }
+        }
+        let national_prefix = self.national_number[..start_of_national_number].to_owned();
+        self.national_number.drain(..start_of_national_number);
+        Ok(national_prefix)
+    }
+
+    /// Extracts IDD and plus sign to prefix_before_national_number when they are
+    /// available, and places the remaining input into national_number.
+    fn attempt_to_extract_idd(&mut self) -> RegexResult<bool> {
+        let international_prefix = self.util.regexp_cache().get_regex(&fast_cat::concat_str!(
+            "\\",
+            PLUS_SIGN,
+            "|",
+            self.current_metadata().international_prefix()
+        ))?;
+        let Some(idd) = international_prefix.find_start(&self.accrued_input_without_formatting)
+        else {
+            return Ok(false);
+        };
+        self.is_complete_number = true;
+        let start_of_country_code = idd.end();
+        self.national_number.clear();
+        self.national_number
+            .push_str(&self.accrued_input_without_formatting[start_of_country_code..]);
+        self.prefix_before_national_number.clear();
+        self.prefix_before_national_number
+            .push_str(&self.accrued_input_without_formatting[..start_of_country_code]);
+        if !self.accrued_input_without_formatting.starts_with(PLUS_SIGN) {
+            self.prefix_before_national_number
+                .push(SEPARATOR_BEFORE_NATIONAL_NUMBER);
+        }
+        Ok(true)
+    }
+
+    /// Extracts the country calling code from the beginning of national_number to
+    /// prefix_before_national_number when they are available, and places the
+    /// remaining input into national_number.
+    fn attempt_to_extract_country_code(&mut self) -> bool {
+        if self.national_number.is_empty() {
+            return false;
+        }
+        let Some((number_without_country_code, country_code)) = self
+            .util
+            .extract_country_code(self.national_number.as_str().into())
+        else {
+            return false;
+        };
+        self.national_number = number_without_country_code.into_owned();
+        // The metadata of the main country is used for the whole calling code, and
+        // for non-geographical entities the one of the code itself.
+        self.current_country_code = Some(country_code);
+        let mut buf = itoa::Buffer::new();
+        self.prefix_before_national_number
+            .push_str(buf.format(country_code));
+        self.prefix_before_national_number
+            .push(SEPARATOR_BEFORE_NATIONAL_NUMBER);
+        // When we have successfully extracted the IDD, the previously extracted NDD
+        // should be cleared because it is no longer valid.
+        self.extracted_national_prefix.clear();
+        true
+    }
+
+    /// Accrues digits and the plus sign to accrued_input_without_formatting for
+    /// later use. If next_char contains a digit in non-ASCII format (e.g. the
+    /// full-width version of digits), it is first normalized to the ASCII version.
+    /// The return value is next_char itself, or its normalized version, if
+    /// next_char is a digit in non-ASCII format.
+    fn normalize_and_accrue_digits_and_plus_sign(
+        &mut self,
+        next_char: char,
+        remember_position: bool,
+    ) -> char {
+        let normalized_char = if PLUS_CHARS.contains(next_char) {
+            self.accrued_input_without_formatting.push_str(PLUS_SIGN);
+            '+'
+        } else {
+            let digit = self
+                .util
+                .normalize_digits_only(next_char.encode_utf8(&mut [0; 4]));
+            self.accrued_input_without_formatting.push_str(&digit);
+            self.national_number.push_str(&digit);
+            digit.chars().next().unwrap_or(next_char)
+        };
+        if remember_position {
+            self.position_to_remember = self.accrued_input_without_formatting.len();
+        }
+        normalized_char
+    }
+
+    /// Puts next_char into the formatting template and returns the template up to
+    /// and including it.
+    fn input_digit_helper(&mut self, next_char: char) -> String {
+        // Note that formatting_template is not guaranteed to have a value, it could be
+        // empty, e.g. when the next digit is entered after extracting an IDD or NDD.
+        let placeholder_position = self
+            .formatting_template
+            .iter()
+            .skip(self.last_match_position)
+            .position(|&c| c == DIGIT_PLACEHOLDER)
+            .map(|position| position + self.last_match_position);
+        if let Some(placeholder_position) = placeholder_position {
+            self.formatting_template[placeholder_position] = next_char;
+            self.last_match_position = placeholder_position;
+            self.formatting_template[..=placeholder_position]
+                .iter()
+                .collect()
+        } else {
+            if self.possible_formats.len() == 1 {
+                // More digits are entered than we could handle, and there are no other
+                // valid patterns to try.
+                self.able_to_format = false;
+            } // else, we just reset the formatting pattern.
+            self.current_formatting_pattern.clear();
+            self.accrued_input.clone()
+        }
+    }
+
+    /// Collects the formats of the current metadata which may apply to a number
+    /// starting with leading_digits.
+    fn get_available_formats(&mut self, leading_digits: &str) -> RegexResult<()> {
+        // First decide whether we should use international or national number rules.
+        let is_international_number =
+            self.is_complete_number && self.extracted_national_prefix.is_empty();
+        let util = Arc::clone(&self.util);
+        let current_metadata = metadata_for_country_code(&util, self.current_country_code);
+        self.uses_intl_number_format =
+            is_international_number && !current_metadata.intl_number_format.is_empty();
+        let format_list = if self.uses_intl_number_format {
+            &current_metadata.intl_number_format
+        } else {
+            &current_metadata.number_format
+        };
+        for (index, format) in format_list.iter().enumerate() {
+            let rule_has_first_group_only = self
+                .util
+                .formatting_rule_has_first_group_only(format.national_prefix_formatting_rule());
+            // Discard a few formats that we know are not relevant based on the presence
+            // of the national prefix.
+            if !self.extracted_national_prefix.is_empty()
+                && rule_has_first_group_only
+                && !format.national_prefix_optional_when_formatting()
+                && !format.has_domestic_carrier_code_formatting_rule()
+            {
+                // If it is a national number that had a national prefix, any rules that
+                // aren't valid with a national prefix should be excluded. A rule that has
+                // a carrier-code formatting rule is kept since the national prefix might
+                // actually be an extracted carrier code - we don't distinguish between
+                // these when extracting it in the AYTF.
+                continue;
+            } else if self.extracted_national_prefix.is_empty()
+                && !self.is_complete_number
+                && !rule_has_first_group_only
+                && !format.national_prefix_optional_when_formatting()
+            {
+                // This number was entered without a national prefix, and this formatting
+                // rule requires one, so we discard it.
+                continue;
+            }
+            if self
+                .util
+                .is_format_eligible_for_as_you_type_formatter(format.format())
+            {
+                self.possible_formats.push(index);
+            }
+        }
+        self.narrow_down_possible_formats(leading_digits)
+    }
+
+    /// Drops the possible formats whose leading digits pattern doesn't match
+    /// leading_digits.
+    fn narrow_down_possible_formats(&mut self, leading_digits: &str) -> RegexResult<()> {
+        let index_of_leading_digits_pattern =
+            leading_digits.len().saturating_sub(MIN_LEADING_DIGITS_LENGTH);
+        let util = Arc::clone(&self.util);
+        let current_metadata = metadata_for_country_code(&util, self.current_country_code);
+        let format_list = if self.uses_intl_number_format {
+            &current_metadata.intl_number_format
+        } else {
+            &current_metadata.number_format
+        };
+        let mut possible_formats = std::mem::take(&mut self.possible_formats);
+        let mut result = Ok(());
+        possible_formats.retain(|&index| {
+            let format = &format_list[index];
+            let Some(last_leading_digits_pattern) = format
+                .leading_digits_pattern
+                .get(index_of_leading_digits_pattern)
+                .or(format.leading_digits_pattern.last())
+            else {
+                // Keep everything that isn't restricted by leading digits.
+                return true;
+            };
+            match self.util.regexp_cache().get_regex(last_leading_digits_pattern) {
+                Ok(pattern) => pattern.matches_start(leading_digits),
+                Err(err) => {
+                    result = Err(err);
+                    false
+                }
+            }
+        });
+        self.possible_formats = possible_formats;
+        result
+    }
+
+    fn set_should_add_space_after_national_prefix(&mut self, format: &NumberFormat) {
+        self.should_add_space_after_national_prefix =
+            NATIONAL_PREFIX_SEPARATORS_PATTERN.is_match(format.national_prefix_formatting_rule());
+    }
+
+    /// Uses the first possible format a formatting template can be built for.
+    /// Returns true if a new template was created.
+    fn maybe_create_new_template(&mut self) -> RegexResult<bool> {
+        // When there are multiple available formats, the formatter uses the first
+        // format where a formatting template could be created.
+        let util = Arc::clone(&self.util);
+        for number_format in self.possible_formats(&util) {
+            let pattern = number_format.pattern();
+            if self.current_formatting_pattern == pattern {
+                return Ok(false);
+            }
+            if self.create_formatting_template(number_format)? {
+                self.current_formatting_pattern = pattern.to_owned();
+                self.set_should_add_space_after_national_prefix(number_format);
+                // With a new formatting template, the matched position using the old
+                // template needs to be reset.
+                self.last_match_position = 0;
+                return Ok(true);
+            }
+        }
+        self.able_to_format = false;
+        Ok(false)
+    }
+
+    fn create_formatting_template(&mut self, format: &NumberFormat) -> RegexResult<bool> {
+        let Some(formatting_template) =
+            self.get_formatting_template(format.pattern(), format.format())?
+        else {
+            self.formatting_template.clear();
+            return Ok(false);
+        };
+        self.formatting_template = formatting_template.chars().collect();
+        Ok(true)
+    }
+
+    /// Gets a formatting template which can be used to efficiently format a
+    /// partial number where digits are added one by one.
+    fn get_formatting_template(
+        &self,
+        number_pattern: &str,
+        number_format: &str,
+    ) -> RegexResult<Option<String>> {
+        // Creates a phone number consisting only of the digit 9 that matches the
+        // number_pattern by applying the pattern to the longest_phone_number string.
+        // The pattern is made less strict first, so that e.g. "[1-5]" matches "9".
+        let loose_pattern = self
+            .util
+            .regexp_cache()
+            .get_regex(&replace_pattern_digits(number_pattern))?;
+        let a_phone_number = loose_pattern
+            .find(LONGEST_PHONE_NUMBER)
+            .map_or("", |matched| matched.as_str());
+        // No formatting template can be created if the number of digits entered so
+        // far is longer than the maximum the current formatting rule can accommodate.
+        if a_phone_number.len() < self.national_number.len() {
+            return Ok(None);
+        }
+        // Formats the number according to number_format, and replaces each digit
+        // with the placeholder.
+        let template = loose_pattern
+            .replace_all(a_phone_number, number_format)
+            .replace('9', DIGIT_PLACEHOLDER.encode_utf8(&mut [0; 4]));
+        if template.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(template))
+    }
+}
+
+/// The metadata for the main country of a country calling code, or the empty
+/// metadata for unknown codes.
+fn metadata_for_country_code(
+    util: &PhoneNumberUtilInternal,
+    country_calling_code: Option<i32>,
+) -> &PhoneMetadata {
+    country_calling_code
+        .and_then(|country_calling_code| {
+            util.get_metadata_for_region_or_calling_code(
+                country_calling_code,
+                util.get_region_code_for_country_code(country_calling_code),
+            )
+        })
+        .unwrap_or(&*EMPTY_METADATA)
+}
+
+/// Replaces any standalone digit in the pattern (not any inside a {} grouping)
+/// and any character class with "\d", e.g. "(2[1-5])(\d{3})" becomes
+/// "(\d\d)(\d{3})".
+fn replace_pattern_digits(pattern: &str) -> String {
+    let pattern = CHARACTER_CLASS_PATTERN.replace_all(pattern, r"\d");
+    let mut new_pattern = String::with_capacity(pattern.len());
+    // This is needed since sometimes there is more than one digit in between the
+    // curly braces.
+    let mut is_in_braces = false;
+    for current_char in pattern.chars() {
+        if current_char.is_ascii_digit() && !is_in_braces {
+            new_pattern.push_str(r"\d");
+            continue;
+        }
+        new_pattern.push(current_char);
+        match current_char {
+            '{' => is_in_braces = true,
+            '}' => is_in_braces = false,
+            _ => {}
+        }
+    }
+    new_pattern
+}
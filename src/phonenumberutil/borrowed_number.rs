@@ -0,0 +1,77 @@
+// Copyright (C) 2025 Kashin Vladislav (Rust adaptation author)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Cow;
+
+use crate::PhoneNumber;
+
+/// A parsed phone number as returned by `PhoneNumberUtil::parse_borrowed`.
+///
+/// If the input was already a plain national significant number of the default
+/// region, the number borrows it instead of allocating; otherwise it holds the
+/// `PhoneNumber` produced by the regular parsing.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BorrowedNumber<'a> {
+    /// The input needed no transformation.
+    Borrowed {
+        /// The country calling code of the default region.
+        country_code: i32,
+        /// The national significant number, a slice of the input.
+        national_number: &'a str,
+    },
+    /// The input had to be transformed, e.g. to strip a national prefix.
+    Owned(PhoneNumber),
+}
+
+impl<'a> BorrowedNumber<'a> {
+    /// Returns the country calling code of the number.
+    pub fn country_code(&self) -> i32 {
+        match self {
+            Self::Borrowed { country_code, .. } => *country_code,
+            Self::Owned(number) => number.country_code(),
+        }
+    }
+
+    /// Returns the national number as a digit string. Like
+    /// `PhoneNumber::national_number`, it doesn't include an Italian leading zero.
+    pub fn national_number(&self) -> Cow<'a, str> {
+        match self {
+            Self::Borrowed { national_number, .. } => Cow::Borrowed(national_number),
+            Self::Owned(number) => Cow::Owned(number.national_number().to_string()),
+        }
+    }
+
+    /// Whether the number borrows the input.
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self, Self::Borrowed { .. })
+    }
+
+    /// Converts the number into a `PhoneNumber`, equal to what
+    /// `PhoneNumberUtil::parse` returns for the same input.
+    pub fn into_owned(self) -> PhoneNumber {
+        match self {
+            Self::Borrowed { country_code, national_number } => {
+                let mut number = PhoneNumber::new();
+                number.set_country_code(country_code);
+                // The borrowed form is only produced for at most MAX_LENGTH_FOR_NSN
+                // ASCII digits, which always fit into a u64.
+                number.set_national_number(
+                    national_number.parse().expect("a borrowed national number is all digits")
+                );
+                number
+            }
+            Self::Owned(number) => number,
+        }
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright (C) 2013 The Libphonenumber Authors
+// Copyright (C) 2025 Kashin Vladislav (Rust adaptation author)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, LazyLock};
+
+use crate::generated::{prefix_data::CARRIER_DATA, proto::phonenumber::PhoneNumber};
+
+use super::{
+    enums::PhoneNumberType,
+    phonenumberutil_internal::PhoneNumberUtilInternal,
+    prefix_file_reader::PrefixFileReader,
+};
+
+/// The carrier data bundled from resources/carrier.
+static CARRIER_FILE_READER: LazyLock<PrefixFileReader> =
+    LazyLock::new(|| PrefixFileReader::new(CARRIER_DATA));
+
+/// A phone prefix mapper which provides carrier information related to a phone
+/// number, such as "Vodafone" for +44 7900 123456.
+///
+/// The carrier is the one the number range was originally allocated to. In
+/// regions supporting mobile number portability the subscriber may have moved to
+/// another carrier since, which `get_safe_display_name` accounts for.
+///
+/// Names are looked up in the language of the given locale (e.g. "en", "ru"),
+/// falling back to English for languages other than Chinese, Japanese and Korean.
+///
+/// An instance is obtained with `PhoneNumberUtil::get_carrier_mapper`. The carrier
+/// data is shared by all instances and each data file is parsed on first use.
+pub struct PhoneNumberToCarrierMapper {
+    util: Arc<PhoneNumberUtilInternal>,
+}
+
+impl PhoneNumberToCarrierMapper {
+    pub(crate) fn new(util: Arc<PhoneNumberUtilInternal>) -> Self {
+        Self { util }
+    }
+
+    /// Returns a carrier name for the given phone number, in the language of the
+    /// provided locale. The carrier name is the one the number was originally
+    /// allocated to, however if the country supports mobile number portability
+    /// the number might not belong to the returned carrier anymore.
+    ///
+    /// This method assumes the validity of the number passed in has already been
+    /// checked.
+    ///
+    /// # Parameters
+    ///
+    /// * `number`: A valid phone number for which we want to get a carrier name.
+    /// * `locale`: The locale of the carrier name, e.g. "en" or "ru".
+    ///
+    /// # Returns
+    ///
+    /// A carrier name for the given phone number, or an empty string if the
+    /// carrier is unknown.
+    pub fn get_name_for_valid_number(&self, number: &PhoneNumber, locale: &str) -> String {
+        let mut phone_prefix = number.country_code().to_string();
+        phone_prefix.push_str(&self.util.get_national_significant_number(number));
+        CARRIER_FILE_READER
+            .get_description_for_number(&phone_prefix, 0, number.country_code(), locale)
+            .to_owned()
+    }
+
+    /// As `get_name_for_valid_number`, but explicitly checks the validity of the
+    /// number passed in. Carrier names are only returned for mobile, pager and
+    /// fixed-line-or-mobile numbers, as carrier data for other types isn't
+    /// meaningful.
+    ///
+    /// # Parameters
+    ///
+    /// * `number`: The phone number for which we want to get a carrier name.
+    /// * `locale`: The locale of the carrier name, e.g. "en" or "ru".
+    ///
+    /// # Returns
+    ///
+    /// A carrier name for the given phone number, or an empty string if the number
+    /// passed in is invalid or isn't a mobile number.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the underlying metadata contains an invalid regular
+    /// expression, which indicates a critical library bug.
+    pub fn get_name_for_number(&self, number: &PhoneNumber, locale: &str) -> String {
+        let number_type = self
+            .util
+            .get_number_type(number)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.");
+        if is_mobile(number_type) {
+            return self.get_name_for_valid_number(number, locale);
+        }
+        String::new()
+    }
+
+    /// Gets the name of the carrier for the given phone number only when it is
+    /// 'safe' to display to users. A carrier name is considered safe if the number
+    /// is valid and for a region that doesn't support mobile number portability.
+    ///
+    /// # Parameters
+    ///
+    /// * `number`: The phone number for which we want to get a carrier name.
+    /// * `locale`: The locale of the carrier name, e.g. "en" or "ru".
+    ///
+    /// # Returns
+    ///
+    /// A carrier name that is safe to display to users, or an empty string.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the underlying metadata contains an invalid regular
+    /// expression, which indicates a critical library bug.
+    pub fn get_safe_display_name(&self, number: &PhoneNumber, locale: &str) -> String {
+        let region_code = self
+            .util
+            .get_region_code_for_number(number)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.");
+        if self.util.is_mobile_number_portable_region(region_code) {
+            return String::new();
+        }
+        self.get_name_for_number(number, locale)
+    }
+}
+
+/// Checks if the supplied number type supports carrier lookup.
+fn is_mobile(number_type: PhoneNumberType) -> bool {
+    matches!(
+        number_type,
+        PhoneNumberType::Mobile | PhoneNumberType::FixedLineOrMobile | PhoneNumberType::Pager
+    )
+}
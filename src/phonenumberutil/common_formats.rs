@@ -0,0 +1,33 @@
+// Copyright (C) 2025 Kashin Vladislav (Rust adaptation author)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// A phone number formatted in the commonly displayed formats, as returned by
+/// `PhoneNumberUtil::format_common`.
+///
+/// For example, Google Zurich with extension 123 gives:
+/// - `e164`: `"+41446681800"`
+/// - `international`: `"+41 44 668 18 00 ext. 123"`
+/// - `national`: `"044 668 18 00 ext. 123"`
+/// - `rfc3966`: `"tel:+41-44-668-18-00;ext=123"`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CommonFormats {
+    /// The number in `PhoneNumberFormat::E164`, without the extension.
+    pub e164: String,
+    /// The number in `PhoneNumberFormat::International`.
+    pub international: String,
+    /// The number in `PhoneNumberFormat::National`.
+    pub national: String,
+    /// The number in `PhoneNumberFormat::RFC3966`.
+    pub rfc3966: String,
+}
@@ -13,6 +13,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cmp::Ordering;
+
 use strum::EnumIter;
 
 /// Defines the various standardized formats for representing phone numbers.
@@ -25,7 +27,7 @@ use strum::EnumIter;
 /// - **NATIONAL**: `044 668 1800`
 /// - **E164**: `+41446681800` (international format without formatting)
 /// - **RFC3966**: `tel:+41-44-668-1800` (hyphen-separated with a "tel:" prefix)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum PhoneNumberFormat {
     /// **E.164 format.**
     /// This is a standardized international format with no spaces or symbols,
@@ -50,7 +52,7 @@ pub enum PhoneNumberFormat {
 }
 
 /// Categorizes phone numbers based on their primary use.
-#[derive(Debug, EnumIter, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, EnumIter, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum PhoneNumberType {
     /// **Fixed-line numbers.**
     /// These are traditional landline telephone numbers tied to a specific geographic location.
@@ -97,7 +99,12 @@ pub enum PhoneNumberType {
 }
 
 /// Describes the degree of similarity between two phone numbers.
-#[derive(Debug, Clone, Copy, PartialEq, Hash)]
+///
+/// Match types are ordered from the weakest to the strongest match, so they can be
+/// compared, e.g. `MatchType::NsnMatch > MatchType::ShortNsnMatch`. `NotANumber`
+/// ranks below `NoMatch`, as it means the input wasn't even a number. It is declared
+/// last so the discriminants stay equal to `code()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MatchType {
     /// **No match.**
     /// The two numbers are entirely different.
@@ -114,6 +121,105 @@ pub enum MatchType {
     /// The two numbers are identical in every aspect, including country code, NSN, and
     /// any specified extensions.
     ExactMatch,
+    /// **Not a number.**
+    /// At least one of the compared strings could not be parsed as a phone number.
+    NotANumber,
+}
+
+impl MatchType {
+    /// Returns a stable integer code for this match type, suitable for
+    /// persisting or passing across FFI boundaries.
+    ///
+    /// The mapping is part of the public API and will not change once assigned:
+    ///
+    /// | Variant         | Code |
+    /// |-----------------|------|
+    /// | `NoMatch`       | 0    |
+    /// | `ShortNsnMatch` | 1    |
+    /// | `NsnMatch`      | 2    |
+    /// | `ExactMatch`    | 3    |
+    /// | `NotANumber`    | 4    |
+    pub fn code(&self) -> i32 {
+        match self {
+            MatchType::NoMatch => 0,
+            MatchType::ShortNsnMatch => 1,
+            MatchType::NsnMatch => 2,
+            MatchType::ExactMatch => 3,
+            MatchType::NotANumber => 4,
+        }
+    }
+
+    /// Returns the position of this match type in the order from the weakest to
+    /// the strongest match.
+    fn rank(&self) -> i32 {
+        match self {
+            MatchType::NotANumber => 0,
+            MatchType::NoMatch => 1,
+            MatchType::ShortNsnMatch => 2,
+            MatchType::NsnMatch => 3,
+            MatchType::ExactMatch => 4,
+        }
+    }
+}
+
+impl PartialOrd for MatchType {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MatchType {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank().cmp(&other.rank())
+    }
+}
+
+/// Describes how the extension of a parsed number was introduced in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExtensionLabelKind {
+    /// **RFC3966 extension.**
+    /// The extension follows an ";ext=" parameter, as in `tel:+1-650-253-0000;ext=123`.
+    Rfc3966,
+    /// **Explicit label.**
+    /// The extension is introduced by a label such as "ext", "extension" or "anexo".
+    Explicit,
+    /// **Ambiguous label.**
+    /// The extension follows a single character or a less common label, such as
+    /// "x", "#", "~" or "int".
+    Ambiguous,
+    /// **Trailing hash.**
+    /// American style extension written as separate digits ending in a hash,
+    /// as in `650-253-0000 - 123#`.
+    HashSuffix,
+    /// **Auto-dialling.**
+    /// The extension follows commas or a semicolon, which dialers use to send
+    /// the extension automatically once the call is connected.
+    AutoDialling,
+}
+
+/// Defines how strictly `PhoneNumberMatcher` checks a candidate before accepting it
+/// as a phone number. The levels are ordered from the most lenient to the strictest,
+/// and every level includes the checks of the previous ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Leniency {
+    /// **Possible numbers.**
+    /// Phone numbers accepted are possible, but not necessarily valid.
+    Possible,
+    /// **Valid numbers.**
+    /// Phone numbers accepted are valid, and a national prefix is present when the
+    /// number's formatting rules require one. Numbers written directly next to Latin
+    /// letters, such as "abc8005001234", are skipped.
+    Valid,
+    /// **Strictly grouped numbers.**
+    /// Phone numbers accepted are valid and their digits are grouped the way the
+    /// number is formatted, although groups may be written together, e.g. both
+    /// "650 253 0000" and "650 2530000" are accepted, but "65 02 53 00 00" is not.
+    StrictGrouping,
+    /// **Exactly grouped numbers.**
+    /// Phone numbers accepted are valid and grouped exactly the way the number is
+    /// formatted, e.g. "650 253 0000" and "6502530000" are accepted, but
+    /// "650 2530000" is not.
+    ExactGrouping,
 }
 
 
@@ -121,7 +227,7 @@ pub enum MatchType {
 // ValidationResultOk for using Result<Ok, Err>
 
 /// Represents the possible outcomes when checking if a phone number's length is valid.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum NumberLengthType {
     /// **The length is valid for a dialable number.**
     /// The number's length matches the expected length for a complete, dialable
@@ -131,4 +237,38 @@ pub enum NumberLengthType {
     /// The number's length is too short for a full national number but matches a pattern
     /// for a number that can be dialed within a specific local area (e.g., without the area code).
     IsPossibleLocalOnly,
-}
\ No newline at end of file
+}
+
+/// Describes the expected cost of dialing a short number, as returned by
+/// `ShortNumberInfo::get_expected_cost`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShortNumberCost {
+    /// **Toll free.**
+    /// The call is free, which is always the case for emergency numbers.
+    TollFree,
+    /// **Standard rate.**
+    /// The call is charged like a regular call.
+    StandardRate,
+    /// **Premium rate.**
+    /// The call is charged at a higher rate than a regular call.
+    PremiumRate,
+    /// **Unknown cost.**
+    /// The cost of the call is not known, e.g. because the number is not a valid
+    /// short number.
+    UnknownCost,
+}
+
+/// Describes how `PhoneNumberUtil::format_csv_safe` protects a formatted number from
+/// being reinterpreted by spreadsheet software, which otherwise drops leading zeros
+/// and `+` signs when importing CSV files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CsvSafeStyle {
+    /// **Formula quote.**
+    /// The number is written as a text formula, e.g. `="+41 44 668 1800"`. It is
+    /// displayed without changes, but is a formula when the file is edited.
+    FormulaQuote,
+    /// **Leading tab.**
+    /// The number is prefixed with a tab character, which stops spreadsheets from
+    /// treating it as a number. The tab stays part of the cell value.
+    LeadingTab,
+}
@@ -19,6 +19,12 @@ use thiserror::Error;
 
 use crate::regexp_cache::InvalidRegexError;
 
+use super::{
+    enums::PhoneNumberType,
+    helper_constants::{MAX_LENGTH_FOR_NSN, MIN_LENGTH_FOR_NSN},
+    metadata_validation::MetadataProblem,
+};
+
 /// Represents critical internal errors that indicate a bug within the library itself.
 /// These errors are not expected to be caught or handled by the user, as they
 /// signal a problem with the library's metadata or logic.
@@ -55,7 +61,8 @@ pub enum ParseError {
     /// **Invalid country code.**
     /// This error occurs if the number begins with a `+` but is followed by an
     /// invalid or unrecognized country calling code.
-    #[error("Invalid country code")]
+    #[error("Invalid country calling code: the number starts with an unknown country \
+        calling code, or has none and no supported default region was given")]
     InvalidCountryCode,
     /// **The string is not a number.**
     /// The input string contains invalid characters or does not conform to a recognizable
@@ -65,18 +72,60 @@ pub enum ParseError {
     /// **The number is too short after the International Direct Dialing (IDD) code.**
     /// After stripping a valid IDD prefix, the remaining part of the number is too
     /// short to be a valid national number.
-    #[error("Too short after idd")]
-    TooShortAfterIdd,
+    #[error("Too short after the international prefix {idd:?}: only {remaining:?} remained")]
+    TooShortAfterIdd {
+        /// The stripped international prefix, e.g. `"00"`, or `"+"` for a leading plus sign.
+        idd: String,
+        /// The normalized digits that remained after stripping the prefix.
+        remaining: String,
+    },
     /// **The National Significant Number (NSN) is too short.**
     /// The number, after stripping the country code and any carrier codes, is shorter
     /// than any possible valid number for that region.
-    #[error("Too short Nsn")]
-    TooShortNsn,
+    #[error("The national significant number is too short: its length is {length}, \
+        but it must have at least {} digits", MIN_LENGTH_FOR_NSN)]
+    TooShortNsn {
+        /// The number of digits in the national significant number.
+        length: usize,
+    },
     /// **The National Significant Number (NSN) is too long.**
     /// The number, after stripping the country code, is longer than any possible
     /// valid number for that region.
-    #[error("Too long nsn")]
-    TooLongNsn,
+    #[error("The national significant number is too long: its length is {length}, \
+        but it must have at most {} digits", MAX_LENGTH_FOR_NSN)]
+    TooLongNsn {
+        /// The number of digits in the national significant number.
+        length: usize,
+    },
+    /// **The string contains a character that is never part of a phone number.**
+    /// The number contains a control character or a Unicode noncharacter, such as
+    /// U+0096 or U+FFFE, where the phone number was expected.
+    #[error("Invalid character {codepoint:?} at position {position}")]
+    InvalidCharacter {
+        /// The byte offset of the character in the string passed to `parse`.
+        position: usize,
+        /// The offending character.
+        codepoint: char,
+    },
+    /// **The number likely belongs to another region.**
+    /// Returned by `PhoneNumberUtil::parse_with_region_sanity` when a number written
+    /// without a `+` sign or international prefix resolves to a region other than
+    /// the default region, which suggests the wrong region was selected.
+    #[error("The number seems to be from {actual_region}, not from the default region {default_region}")]
+    RegionMismatchSuspected {
+        /// The default region the number was parsed with.
+        default_region: String,
+        /// The region the parsed number belongs to.
+        actual_region: String,
+    },
+    /// **The number has an extension, but extensions were not allowed.**
+    /// Returned by `PhoneNumberUtil::parse_with_options` when extensions are disabled
+    /// with `ParseOptions::allow_extensions(false)` and an extension was found.
+    #[error("The number has an extension {extension:?}, but extensions are not allowed")]
+    ExtensionNotAllowed {
+        /// The extension found in the input.
+        extension: String,
+    },
 }
 
 /// Provides more specific details for a `ParseError::NotANumber` failure.
@@ -90,7 +139,7 @@ pub enum NotANumberError {
     #[error("Invalid phone context")]
     InvalidPhoneContext,
     /// A numeric portion of the phone number string could not be parsed into an integer.
-    #[error("{0}")]
+    #[error("Failed to parse the national number as an integer: {0}")]
     FailedToParseNumberAsInt(#[from] ParseIntError),
     /// An error occurred at the lowest level of extracting a numeric string from the input.
     #[error("{0}")]
@@ -105,7 +154,7 @@ pub enum ExtractNumberError {
     #[error("No valid start character found")]
     NoValidStartCharacter,
     /// Number did not match valid number pattern.
-    #[error("Invalid number")]
+    #[error("Number did not match the valid number pattern")]
     NotANumber,
 }
 
@@ -165,6 +214,19 @@ pub struct InvalidNumberError(#[from] pub ParseError);
 #[error("Metadata for valid region MUST not be null")]
 pub struct InvalidMetadataForValidRegionError;
 
+/// Represents failures when creating a `PhoneNumberUtil` from serialized metadata or
+/// reloading its metadata.
+#[derive(Debug, Error)]
+pub enum MetadataError {
+    /// The given bytes could not be decoded as a `PhoneMetadataCollection`.
+    #[error("Invalid metadata: {0}")]
+    InvalidMetadata(#[from] protobuf::Error),
+    /// The metadata was decoded, but one of its regular expressions does not compile.
+    /// The value is a `MetadataProblem::InvalidPattern` naming the region and field.
+    #[error("Invalid pattern in metadata: {0}")]
+    InvalidPattern(MetadataProblem),
+}
+
 /// Details why a phone number is considered invalid.
 ///
 /// This enum is returned by validation functions to provide a specific reason
@@ -173,28 +235,135 @@ pub struct InvalidMetadataForValidRegionError;
 pub enum ValidationError {
     /// **The country calling code is invalid.**
     /// The number has a country code that does not correspond to any known region.
-    #[error("The number has an invalid country calling code")]
-    InvalidCountryCode,
+    #[error("The country calling code {country_code} does not belong to any supported region")]
+    InvalidCountryCode {
+        /// The country calling code of the number.
+        country_code: i32,
+    },
     /// **The number is too short.**
     /// The number's length is shorter than the shortest possible valid number
     /// for its region.
-    #[error("The number is shorter than all valid numbers for this region")]
-    TooShort,
+    #[error("The number is shorter than all valid numbers for its region: \
+        its national significant number has length {length}")]
+    TooShort {
+        /// The number of digits in the national significant number.
+        length: usize,
+    },
     /// **The number has an invalid length.**
     /// The number's length falls between the shortest and longest possible lengths
     /// for its region but does not match any specific valid length. This can also occur
     ///  if no numbers of the requested type exist for the region.
-    #[error("\
-    The number is longer than the shortest valid numbers for this region,\
-    shorter than the longest valid numbers for this region, and does not\
-    itself have a number length that matches valid numbers for this region\
-    ")]
-    InvalidLength,
+    #[error("The length {length} of the national significant number is between \
+        the shortest and the longest valid numbers for its region, but is not valid itself")]
+    InvalidLength {
+        /// The number of digits in the national significant number.
+        length: usize,
+    },
     /// **The number is too long.**
     /// The number's length is longer than the longest possible valid number
     /// for its region.
-    #[error("The number is longer than all valid numbers for this region")]
-    TooLong,
+    #[error("The number is longer than all valid numbers for its region: \
+        its national significant number has length {length}")]
+    TooLong {
+        /// The number of digits in the national significant number.
+        length: usize,
+    },
+    /// **The number doesn't match any pattern.**
+    /// The number's length is possible for its region, but the number doesn't match
+    /// the pattern of any valid number. Only returned by
+    /// `PhoneNumberUtil::is_valid_number_with_reason`.
+    #[error("The number has a possible length, but doesn't match any valid number \
+        pattern for its region")]
+    PatternMismatch,
+}
+
+/// Explains why `PhoneNumberUtil::is_valid_and_type` rejected a phone number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Error)]
+pub enum TypeValidationError {
+    /// **The number is invalid.**
+    /// The number is not a valid number for any region, so it has no type.
+    #[error("The number is not valid")]
+    Invalid,
+    /// **The number has a different type.**
+    /// The number is valid, but its type is not the required one.
+    #[error("The number is valid, but its type is {actual:?}")]
+    WrongType {
+        /// The type of the number.
+        actual: PhoneNumberType,
+    },
+}
+
+impl ParseError {
+    /// Returns a stable integer code for the kind of this error, suitable for
+    /// persisting or passing across FFI boundaries. Details carried by a variant
+    /// (such as the wrapped `NotANumberError`) are not part of the code.
+    ///
+    /// The mapping is part of the public API and will not change once assigned:
+    ///
+    /// | Variant                   | Code |
+    /// |---------------------------|------|
+    /// | `InvalidCountryCode`      | 0    |
+    /// | `NotANumber`              | 1    |
+    /// | `TooShortAfterIdd`        | 2    |
+    /// | `TooShortNsn`             | 3    |
+    /// | `TooLongNsn`              | 4    |
+    /// | `InvalidCharacter`        | 5    |
+    /// | `RegionMismatchSuspected` | 6    |
+    /// | `ExtensionNotAllowed`     | 7    |
+    pub fn code(&self) -> i32 {
+        match self {
+            ParseError::InvalidCountryCode => 0,
+            ParseError::NotANumber(_) => 1,
+            ParseError::TooShortAfterIdd { .. } => 2,
+            ParseError::TooShortNsn { .. } => 3,
+            ParseError::TooLongNsn { .. } => 4,
+            ParseError::InvalidCharacter { .. } => 5,
+            ParseError::RegionMismatchSuspected { .. } => 6,
+            ParseError::ExtensionNotAllowed { .. } => 7,
+        }
+    }
+}
+
+impl ValidationError {
+    /// Returns a short human-readable explanation of this error. Unlike `Display`,
+    /// it leaves out the country calling code and the length, so it can be used as
+    /// e.g. a label for grouping failures.
+    pub fn description(&self) -> &'static str {
+        match self {
+            ValidationError::InvalidCountryCode { .. } =>
+                "The number has an invalid country calling code",
+            ValidationError::TooShort { .. } =>
+                "The number is shorter than all valid numbers for its region",
+            ValidationError::InvalidLength { .. } =>
+                "The number has a length that is not valid for its region",
+            ValidationError::TooLong { .. } =>
+                "The number is longer than all valid numbers for its region",
+            ValidationError::PatternMismatch =>
+                "The number doesn't match any valid number pattern for its region",
+        }
+    }
+
+    /// Returns a stable integer code for this error, suitable for persisting or
+    /// passing across FFI boundaries.
+    ///
+    /// The mapping is part of the public API and will not change once assigned:
+    ///
+    /// | Variant              | Code |
+    /// |----------------------|------|
+    /// | `InvalidCountryCode` | 0    |
+    /// | `TooShort`           | 1    |
+    /// | `InvalidLength`      | 2    |
+    /// | `TooLong`            | 3    |
+    /// | `PatternMismatch`    | 4    |
+    pub fn code(&self) -> i32 {
+        match self {
+            ValidationError::InvalidCountryCode { .. } => 0,
+            ValidationError::TooShort { .. } => 1,
+            ValidationError::InvalidLength { .. } => 2,
+            ValidationError::TooLong { .. } => 3,
+            ValidationError::PatternMismatch => 4,
+        }
+    }
 }
 
 impl From<ParseErrorInternal> for GetExampleNumberErrorInternal {
@@ -0,0 +1,219 @@
+// Copyright (C) 2011 The Libphonenumber Authors
+// Copyright (C) 2025 Kashin Vladislav (Rust adaptation author)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, LazyLock};
+
+use crate::generated::{prefix_data::GEOCODING_DATA, proto::phonenumber::PhoneNumber};
+
+use super::{
+    enums::PhoneNumberType,
+    phonenumberutil_internal::PhoneNumberUtilInternal,
+    prefix_file_reader::PrefixFileReader,
+    region_names::get_region_display_name,
+};
+
+/// The geocoding data bundled from resources/geocoding.
+static GEOCODING_FILE_READER: LazyLock<PrefixFileReader> =
+    LazyLock::new(|| PrefixFileReader::new(GEOCODING_DATA));
+
+/// An offline geocoder which provides geographical information related to a
+/// phone number, such as "Mountain View, CA" for +1 650-253-0000.
+///
+/// Descriptions are looked up in the language of the given locale (e.g. "en",
+/// "de-CH", "zh-TW"), falling back to English for languages other than Chinese,
+/// Japanese and Korean. When no finer data exists, the name of the country is
+/// returned instead; country names are currently only available in English.
+///
+/// An instance is obtained with `PhoneNumberUtil::get_geocoder`. The geocoding
+/// data is shared by all instances and each data file is parsed on first use.
+pub struct PhoneNumberOfflineGeocoder {
+    util: Arc<PhoneNumberUtilInternal>,
+}
+
+impl PhoneNumberOfflineGeocoder {
+    pub(crate) fn new(util: Arc<PhoneNumberUtilInternal>) -> Self {
+        Self { util }
+    }
+
+    /// Returns the customary display name of the country the number belongs to,
+    /// or an empty string if the number could belong to several countries sharing
+    /// its calling code or to none.
+    fn get_country_name_for_number(&self, number: &PhoneNumber) -> &'static str {
+        let Some(region_codes) = self
+            .util
+            .get_region_codes_for_country_calling_code(number.country_code())
+        else {
+            return "";
+        };
+        let region_codes: Vec<_> = region_codes.collect();
+        if let [region_code] = region_codes[..] {
+            return get_region_display_name(region_code);
+        }
+        let mut valid_regions = region_codes
+            .into_iter()
+            .filter(|region_code| self.util.is_valid_number_for_region(number, region_code));
+        match (valid_regions.next(), valid_regions.next()) {
+            (Some(region_code), None) => get_region_display_name(region_code),
+            // If the number is valid for several regions, or none of them, it's
+            // not clear which country to return.
+            _ => "",
+        }
+    }
+
+    /// Returns a text description for the given phone number, in the language of
+    /// the provided locale. The description might consist of the name of the
+    /// country where the phone number is from, or the name of the geographical
+    /// area the phone number is from if more detailed information is available.
+    ///
+    /// This method assumes the validity of the number passed in has already been
+    /// checked, and that the number is suitable for geocoding. We consider
+    /// fixed-line and mobile numbers possible candidates for geocoding.
+    ///
+    /// # Parameters
+    ///
+    /// * `number`: A valid phone number for which we want to get a text description.
+    /// * `locale`: The locale of the description, e.g. "en" or "de-CH".
+    ///
+    /// # Returns
+    ///
+    /// A text description for the given language code for the given phone number,
+    /// or an empty string if the number could come from multiple countries, or the
+    /// country code is in fact invalid.
+    pub fn get_description_for_valid_number(&self, number: &PhoneNumber, locale: &str) -> String {
+        let national_number = self.util.get_national_significant_number(number);
+        let mobile_token = self.util.get_country_mobile_token(number.country_code());
+        let area_description = match mobile_token {
+            // In some countries, e.g. Argentina, mobile numbers have a mobile token
+            // before the national destination code, this should be removed before
+            // geocoding.
+            Some(mobile_token) if national_number.starts_with(mobile_token) => {
+                let region_code = self
+                    .util
+                    .get_region_code_for_country_code(number.country_code());
+                let copied_number = self
+                    .util
+                    .parse(&national_number[mobile_token.len_utf8()..], region_code)
+                    .unwrap_or_else(|_| number.clone());
+                self.get_area_description(&copied_number, locale)
+            }
+            _ => self.get_area_description(number, locale),
+        };
+        if area_description.is_empty() {
+            self.get_country_name_for_number(number).to_owned()
+        } else {
+            area_description.to_owned()
+        }
+    }
+
+    /// As `get_description_for_valid_number`, but only describes the area of the
+    /// number if the user is in the same region as the number. Otherwise only the
+    /// country name is returned, as a caller from abroad is unlikely to need a
+    /// more precise location.
+    ///
+    /// # Parameters
+    ///
+    /// * `number`: A valid phone number for which we want to get a text description.
+    /// * `locale`: The locale of the description, e.g. "en" or "de-CH".
+    /// * `user_region`: The region code for a given user, e.g. "US".
+    ///
+    /// # Returns
+    ///
+    /// A text description for the given language code for the given phone number,
+    /// or an empty string if the number could come from multiple countries, or the
+    /// country code is in fact invalid.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the underlying metadata contains an invalid regular
+    /// expression, which indicates a critical library bug.
+    pub fn get_description_for_valid_number_for_user_region(
+        &self,
+        number: &PhoneNumber,
+        locale: &str,
+        user_region: &str,
+    ) -> String {
+        let region_code = self
+            .util
+            .get_region_code_for_number(number)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.");
+        if user_region == region_code {
+            return self.get_description_for_valid_number(number, locale);
+        }
+        get_region_display_name(region_code).to_owned()
+    }
+
+    /// As `get_description_for_valid_number`, but explicitly checks the validity
+    /// of the number passed in.
+    ///
+    /// Numbers whose type isn't geographical, such as toll-free numbers and most
+    /// mobile numbers, are described by their country name only. Numbers of
+    /// non-geographical entities, e.g. +800 numbers, have no description.
+    ///
+    /// # Parameters
+    ///
+    /// * `number`: The phone number for which we want to get a text description.
+    /// * `locale`: The locale of the description, e.g. "en" or "de-CH".
+    ///
+    /// # Returns
+    ///
+    /// A text description for the given language code for the given phone number,
+    /// or an empty string if the number is invalid or has no description.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the underlying metadata contains an invalid regular
+    /// expression, which indicates a critical library bug.
+    pub fn get_description_for_number(&self, number: &PhoneNumber, locale: &str) -> String {
+        let number_type = self
+            .util
+            .get_number_type(number)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.");
+        if number_type == PhoneNumberType::Unknown {
+            return String::new();
+        }
+        if !self
+            .util
+            .is_number_geographical_by_country_code_and_type(number_type, number.country_code())
+        {
+            return self.get_country_name_for_number(number).to_owned();
+        }
+        self.get_description_for_valid_number(number, locale)
+    }
+
+    /// Looks up the area description of the number in the geocoding data, or
+    /// returns an empty string if there is none. If the number has a geographical
+    /// area code, only entries covering the whole area code are used, as shorter
+    /// ones don't tell the areas of the country apart.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the underlying metadata contains an invalid regular
+    /// expression, which indicates a critical library bug.
+    fn get_area_description(&self, number: &PhoneNumber, locale: &str) -> &'static str {
+        let area_code_length = self
+            .util
+            .get_length_of_geographical_area_code(number)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.");
+        let mut phone_prefix = number.country_code().to_string();
+        let min_prefix_length = phone_prefix.len() + area_code_length;
+        phone_prefix.push_str(&self.util.get_national_significant_number(number));
+        GEOCODING_FILE_READER.get_description_for_number(
+            &phone_prefix,
+            min_prefix_length,
+            number.country_code(),
+            locale,
+        )
+    }
+}
@@ -19,7 +19,7 @@ use protobuf::Message;
 use strum::IntoEnumIterator;
 
 use crate::{
-    interfaces::MatcherApi, generated::metadata::METADATA, 
+    interfaces::MatcherApi, generated::metadata::{METADATA, SHORT_METADATA}, 
     generated::proto::{
         phonemetadata::{
             PhoneMetadata, PhoneMetadataCollection, PhoneNumberDesc
@@ -43,6 +43,19 @@ pub fn load_compiled_metadata() -> Result<PhoneMetadataCollection, protobuf::Err
     Ok(result)
 }
 
+/// Loads short number metadata from helper constants SHORT_METADATA array
+pub fn load_compiled_short_metadata() -> Result<PhoneMetadataCollection, protobuf::Error> {
+    let result = PhoneMetadataCollection::parse_from_bytes(&SHORT_METADATA)?;
+    Ok(result)
+}
+
+/// Checks whether the character can never be part of a phone number, that is
+/// whether it is a control character, such as U+0096, or a Unicode noncharacter,
+/// such as U+FFFE.
+pub fn is_invalid_character(c: char) -> bool {
+    c.is_control() || ('\u{FDD0}'..='\u{FDEF}').contains(&c) || (c as u32 & 0xFFFE) == 0xFFFE
+}
+
 /// Returns a pointer to the description inside the metadata of the appropriate
 /// type.
 pub fn get_number_desc_by_type(
@@ -122,6 +135,18 @@ pub fn is_national_number_suffix_of_the_other(
         || second_number_national_number.ends_with(first_number_national_number);
 }
 
+// Canonical-equivalence doesn't seem to be an option with RE2, so we allow
+// two options for representing any non-ASCII character like ó - the character
+// itself, and one in the unicode decomposed form with the combining acute
+// accent.
+
+// Here the extension is called out in a more explicit way, i.e mentioning it
+// obvious patterns like "ext.".
+const EXPLICIT_EXT_LABELS: &str = "(?:e?xt(?:ensi(?:o\u{0301}?|\u{00F3}))?n?|(?:\u{FF45})?\u{FF58}\u{FF54}(?:\u{FF4E})?|\u{0434}\u{043E}\u{0431}|anexo)";
+// One-character symbols that can be used to indicate an extension, and less
+// commonly used or more ambiguous extension labels.
+const AMBIGUOUS_EXT_LABELS: &str = "(?:[x\u{FF58}#\u{FF03}~\u{FF5E}]|int|\u{FF49}\u{FF4E}\u{FF54})";
+
 /// Helper method for constructing regular expressions for parsing. Creates an
 /// expression that captures up to max_length digits.
 pub fn extn_digits(max_length: u32) -> String {
@@ -145,11 +170,14 @@ pub fn extn_digits(max_length: u32) -> String {
 
 // Helper initialiser method to create the regular-expression pattern to match
 // extensions. Note that:
-// - There are currently six capturing groups for the extension itself. If this
-// number is changed, MaybeStripExtension needs to be updated.
+// - There are currently six capturing groups for the extension itself when
+// parsing, plus a seventh one when extra separators are given. If this number
+// is changed, MaybeStripExtension needs to be updated.
 // - The only capturing groups should be around the digits that you want to
 // capture as part of the extension, or else parsing will fail!
-pub fn create_extn_pattern(for_parsing: bool) -> String {
+// - `extra_ambiguous_separators` are treated like the ambiguous
+// single-character labels such as "x".
+pub fn create_extn_pattern(for_parsing: bool, extra_ambiguous_separators: &[char]) -> String {
     // We cap the maximum length of an extension based on the ambiguity of the
     // way the extension is prefixed. As per ITU, the officially allowed
     // length for extensions is actually 40, but we don't support this since we
@@ -160,17 +188,6 @@ pub fn create_extn_pattern(for_parsing: bool) -> String {
     let ext_limit_after_ambiguous_char = 9;
     let ext_limit_when_not_sure = 6;
 
-    // Canonical-equivalence doesn't seem to be an option with RE2, so we allow
-    // two options for representing any non-ASCII character like ó - the character
-    // itself, and one in the unicode decomposed form with the combining acute
-    // accent.
-
-    // Here the extension is called out in a more explicit way, i.e mentioning it
-    // obvious patterns like "ext.".
-    let explicit_ext_labels = "(?:e?xt(?:ensi(?:o\u{0301}?|\u{00F3}))?n?|(?:\u{FF45})?\u{FF58}\u{FF54}(?:\u{FF4E})?|\u{0434}\u{043E}\u{0431}|anexo)";
-    // One-character symbols that can be used to indicate an extension, and less
-    // commonly used or more ambiguous extension labels.
-    let ambiguous_ext_labels = "(?:[x\u{FF58}#\u{FF03}~\u{FF5E}]|int|\u{FF49}\u{FF4E}\u{FF54})";
     // When extension is not separated clearly.
     let ambiguous_separator = "[- ]+";
 
@@ -180,14 +197,14 @@ pub fn create_extn_pattern(for_parsing: bool) -> String {
     );
     let explicit_extn = fast_cat::concat_str!(
         POSSIBLE_SEPARATORS_BETWEEN_NUMBER_AND_EXT_LABEL,
-        explicit_ext_labels,
+        EXPLICIT_EXT_LABELS,
         POSSIBLE_CHARS_AFTER_EXT_LABEL,
         &extn_digits(ext_limit_after_explicit_label),
         OPTIONAL_EXT_SUFFIX
     );
     let ambiguous_extn = fast_cat::concat_str!(
         POSSIBLE_SEPARATORS_BETWEEN_NUMBER_AND_EXT_LABEL,
-        ambiguous_ext_labels,
+        AMBIGUOUS_EXT_LABELS,
         POSSIBLE_CHARS_AFTER_EXT_LABEL,
         &extn_digits(ext_limit_after_ambiguous_char),
         OPTIONAL_EXT_SUFFIX
@@ -248,15 +265,84 @@ pub fn create_extn_pattern(for_parsing: bool) -> String {
         // extensions. However, the second pattern is more liberal on number of
         // commas that acts as extension labels, so we have strict cap on number of
         // digits in such extensions.
-        return fast_cat::concat_str!(
+        let parsing_pattern = fast_cat::concat_str!(
             &extension_pattern,
             "|",
             &auto_dialling_extn,
             "|",
             &only_commas_extn
         );
+        return with_extra_separators_extn(
+            parsing_pattern,
+            extra_ambiguous_separators,
+            ext_limit_after_ambiguous_char,
+        );
     }
-    return extension_pattern;
+    return with_extra_separators_extn(
+        extension_pattern,
+        extra_ambiguous_separators,
+        ext_limit_after_ambiguous_char,
+    );
+}
+
+/// Appends the alternative for extensions after the extra ambiguous separators to
+/// `extension_pattern`, if there are any. At most `ext_limit` digits may follow them.
+fn with_extra_separators_extn(
+    extension_pattern: String,
+    extra_ambiguous_separators: &[char],
+    ext_limit: u32,
+) -> String {
+    if extra_ambiguous_separators.is_empty() {
+        return extension_pattern;
+    }
+    let extra_labels: String = extra_ambiguous_separators
+        .iter()
+        .map(|separator| regex::escape(separator.encode_utf8(&mut [0; 4])))
+        .collect();
+    let extra_separators_extn = fast_cat::concat_str!(
+        POSSIBLE_SEPARATORS_BETWEEN_NUMBER_AND_EXT_LABEL,
+        "[",
+        &extra_labels,
+        "]",
+        POSSIBLE_CHARS_AFTER_EXT_LABEL,
+        &extn_digits(ext_limit),
+        OPTIONAL_EXT_SUFFIX
+    );
+    fast_cat::concat_str!(&extension_pattern, "|", &extra_separators_extn)
+}
+
+/// Creates the pattern of an extension label that has no extension digits after
+/// it, e.g. the " ext." of "1800 123-1234 ext.". Like the extension patterns, it
+/// is meant to be matched case-insensitively at the end of the number.
+pub fn create_dangling_extn_label_pattern() -> String {
+    fast_cat::concat_str!(
+        POSSIBLE_SEPARATORS_BETWEEN_NUMBER_AND_EXT_LABEL,
+        "(?:",
+        EXPLICIT_EXT_LABELS,
+        "|",
+        AMBIGUOUS_EXT_LABELS,
+        ")",
+        POSSIBLE_CHARS_AFTER_EXT_LABEL
+    )
+}
+
+/// Returns the Levenshtein distance between two strings of ASCII digits, i.e. the
+/// minimal number of single-digit insertions, deletions and substitutions
+/// turning one into the other.
+pub fn digits_edit_distance(first: &[u8], second: &[u8]) -> usize {
+    // National significant numbers are at most 17 digits long, so a single row of
+    // the distance matrix is enough.
+    let mut row: Vec<usize> = (0..=second.len()).collect();
+    for (i, first_digit) in first.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, second_digit) in second.iter().enumerate() {
+            let substitution = diagonal + usize::from(first_digit != second_digit);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[second.len()]
 }
 
 /// Normalizes a string of characters representing a phone number by replacing
@@ -300,7 +386,7 @@ pub fn desc_has_possible_number_data(desc: &PhoneNumberDesc) -> bool {
     return desc.possible_length.len() != 1
         || desc
             .possible_length
-            .get(0)
+            .first()
             .and_then(|l| Some(*l != -1))
             .unwrap_or(false);
 }
@@ -412,7 +498,7 @@ pub fn test_number_length(
     // If the type is not suported at all (indicated by the possible lengths
     // containing -1 at this point) we return invalid length.
     if *possible_lengths.first().unwrap_or(&-1) == -1 {
-        return Err(ValidationError::InvalidLength);
+        return Err(ValidationError::InvalidLength { length: phone_number.len() });
     }
 
     let actual_length = phone_number.len() as i32;
@@ -428,15 +514,15 @@ pub fn test_number_length(
     if minimum_length == actual_length {
         return Ok(NumberLengthType::IsPossible);
     } else if minimum_length > actual_length {
-        return Err(ValidationError::TooShort);
+        return Err(ValidationError::TooShort { length: phone_number.len() });
     } else if possible_lengths[possible_lengths.len() - 1] < actual_length {
-        return Err(ValidationError::TooLong);
+        return Err(ValidationError::TooLong { length: phone_number.len() });
     }
     // We skip the first element; we've already checked it.
     return if possible_lengths[1..].contains(&actual_length) {
         Ok(NumberLengthType::IsPossible)
     } else {
-        Err(ValidationError::InvalidLength)
+        Err(ValidationError::InvalidLength { length: phone_number.len() })
     };
 }
 
@@ -470,10 +556,22 @@ pub(crate) fn copy_core_fields_only(from_number: &PhoneNumber) -> PhoneNumber {
     to_number
 }
 
+/// Sets an optional string field of a phone number to `value`, writing into the
+/// buffer of the previous value if the field was set, so it is not allocated anew.
+pub(crate) fn set_string_field(field: &mut Option<String>, value: &str) {
+    match field {
+        Some(buffer) => {
+            buffer.clear();
+            buffer.push_str(value);
+        }
+        None => *field = Some(value.to_owned()),
+    }
+}
+
 /// Determines whether the given number is a national number match for the given
 /// PhoneNumberDesc. Does not check against possible lengths!
 pub fn is_match(
-    matcher_api: &Box<dyn MatcherApi>,
+    matcher_api: &dyn MatcherApi,
     number: &str,
     number_desc: &PhoneNumberDesc,
 ) -> bool {
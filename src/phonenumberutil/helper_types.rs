@@ -14,7 +14,9 @@
 // limitations under the License.
 
 
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::HashMap};
+
+use regex::{RegexSet, SetMatches};
 
 use crate::CountryCodeSource;
 
@@ -29,3 +31,42 @@ impl<'a> PhoneNumberWithCountryCodeSource<'a> {
         Self { phone_number, country_code_source }
     }
 }
+
+/// The `leading_digits` patterns of all regions sharing a country calling
+/// code, compiled into one `RegexSet`. The patterns are regexes rather than
+/// literal prefixes, so a set is what lets a national number be checked
+/// against every region in a single pass.
+pub struct LeadingDigitsSet {
+    set: RegexSet,
+    /// Index of the pattern in `set` of each region, by region code.
+    pattern_indices: HashMap<String, usize>,
+}
+
+impl LeadingDigitsSet {
+    /// Builds the set from `(region_code, leading_digits)` pairs, anchoring each
+    /// pattern at the start of the number.
+    pub fn new<'a>(
+        leading_digits: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<Self, regex::Error> {
+        let mut pattern_indices = HashMap::new();
+        let mut patterns = Vec::new();
+        for (region_code, pattern) in leading_digits {
+            pattern_indices.insert(region_code.to_owned(), patterns.len());
+            patterns.push(format!("^(?:{})", pattern));
+        }
+        Ok(Self { set: RegexSet::new(patterns)?, pattern_indices })
+    }
+
+    /// Returns the indices of the patterns matching the start of `national_number`.
+    pub fn matches(&self, national_number: &str) -> SetMatches {
+        self.set.matches(national_number)
+    }
+
+    /// Whether the leading digits of `region_code` are among `matches`, or
+    /// `None` if the region has no pattern in this set.
+    pub fn is_region_matched(&self, matches: &SetMatches, region_code: &str) -> Option<bool> {
+        self.pattern_indices
+            .get(region_code)
+            .map(|&index| matches.matched(index))
+    }
+}
@@ -0,0 +1,212 @@
+// Copyright (C) 2025 Kashin Vladislav (Rust adaptation author)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pre-flight checks for custom metadata collections.
+//!
+//! The checks mirror the invariants enforced by the metadata schema of the
+//! original library, which the rest of the code silently relies on.
+
+use regex::Regex;
+use thiserror::Error;
+
+use crate::{
+    generated::proto::phonemetadata::{
+        NumberFormat, PhoneMetadata, PhoneMetadataCollection, PhoneNumberDesc,
+    },
+    region_code::RegionCode,
+};
+
+/// A single problem found in a `PhoneMetadataCollection`.
+///
+/// `region` is the id of the offending `PhoneMetadata` (e.g. `"US"`, or `"001"` for
+/// non-geographical entities) and `field` names the affected field, e.g. `"mobile"`
+/// or `"number_format[2].pattern"`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Error)]
+pub enum MetadataProblem {
+    /// A regular expression in the metadata does not compile.
+    #[error("{region}: pattern in {field} does not compile: {error}")]
+    InvalidPattern {
+        region: String,
+        field: String,
+        pattern: String,
+        error: String,
+    },
+    /// A number description has a national number pattern but no possible lengths
+    /// to fall back to.
+    #[error("{region}: {field} has no possible lengths")]
+    MissingPossibleLengths { region: String, field: String },
+    /// A length is listed both as possible and as local-only possible. Length
+    /// checks assume these sets never overlap.
+    #[error("{region}: length {length} of {field} is both possible and local-only")]
+    LocalOnlyLengthOverlap {
+        region: String,
+        field: String,
+        length: i32,
+    },
+    /// The id is neither a two-letter uppercase region code nor "001". Such
+    /// entries are skipped when the metadata is loaded.
+    #[error("{region}: not a two-letter region code or \"001\"")]
+    InvalidRegionCode { region: String },
+}
+
+/// Where a pattern of the metadata is used, which decides the regex cache it is
+/// compiled into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PatternKind {
+    /// The `national_number_pattern` of a number description, matched by the
+    /// `MatcherApi`.
+    NationalNumber,
+    /// Any other pattern, such as a number format or a national prefix for parsing.
+    Other,
+}
+
+/// Runs all checks against every metadata entry of the collection and returns
+/// the problems found, in collection order.
+pub(crate) fn validate_metadata_collection(
+    collection: &PhoneMetadataCollection,
+) -> Vec<MetadataProblem> {
+    validate_metadata_collection_with(collection, |_, pattern| {
+        Regex::new(pattern).map(drop).map_err(|err| err.to_string())
+    })
+}
+
+/// Same as `validate_metadata_collection`, but compiles the patterns with
+/// `compile`, e.g. to keep the compiled regexes in a cache.
+pub(crate) fn validate_metadata_collection_with(
+    collection: &PhoneMetadataCollection,
+    mut compile: impl FnMut(PatternKind, &str) -> Result<(), String>,
+) -> Vec<MetadataProblem> {
+    let mut problems = Vec::new();
+    for metadata in &collection.metadata {
+        validate_metadata(metadata, &mut compile, &mut problems);
+    }
+    problems
+}
+
+fn validate_metadata(
+    metadata: &PhoneMetadata,
+    compile: &mut impl FnMut(PatternKind, &str) -> Result<(), String>,
+    problems: &mut Vec<MetadataProblem>,
+) {
+    let region = metadata.id();
+    if RegionCode::to_static(region).is_none() {
+        problems.push(MetadataProblem::InvalidRegionCode { region: region.to_owned() });
+    }
+
+    let descs: [(&str, &PhoneNumberDesc); 17] = [
+        ("general_desc", &metadata.general_desc),
+        ("fixed_line", &metadata.fixed_line),
+        ("mobile", &metadata.mobile),
+        ("toll_free", &metadata.toll_free),
+        ("premium_rate", &metadata.premium_rate),
+        ("shared_cost", &metadata.shared_cost),
+        ("personal_number", &metadata.personal_number),
+        ("voip", &metadata.voip),
+        ("pager", &metadata.pager),
+        ("uan", &metadata.uan),
+        ("emergency", &metadata.emergency),
+        ("voicemail", &metadata.voicemail),
+        ("short_code", &metadata.short_code),
+        ("standard_rate", &metadata.standard_rate),
+        ("carrier_specific", &metadata.carrier_specific),
+        ("sms_services", &metadata.sms_services),
+        ("no_international_dialling", &metadata.no_international_dialling),
+    ];
+    for (field, desc) in descs {
+        if desc.has_national_number_pattern() {
+            check_pattern(
+                region, field, PatternKind::NationalNumber, desc.national_number_pattern(), compile, problems,
+            );
+        }
+
+        // Specific descriptions without possible lengths inherit them from the
+        // general description, so only the latter must always have them.
+        let possible_lengths = if desc.possible_length.is_empty() {
+            if field == "general_desc" && desc.has_national_number_pattern() {
+                problems.push(MetadataProblem::MissingPossibleLengths {
+                    region: region.to_owned(),
+                    field: field.to_owned(),
+                });
+            }
+            &metadata.general_desc.possible_length
+        } else {
+            &desc.possible_length
+        };
+        for &length in &desc.possible_length_local_only {
+            if possible_lengths.contains(&length) {
+                problems.push(MetadataProblem::LocalOnlyLengthOverlap {
+                    region: region.to_owned(),
+                    field: field.to_owned(),
+                    length,
+                });
+            }
+        }
+    }
+
+    let string_patterns = [
+        ("international_prefix", metadata.has_international_prefix(), metadata.international_prefix()),
+        ("national_prefix_for_parsing", metadata.has_national_prefix_for_parsing(), metadata.national_prefix_for_parsing()),
+        ("leading_digits", metadata.has_leading_digits(), metadata.leading_digits()),
+    ];
+    for (field, is_set, pattern) in string_patterns {
+        if is_set {
+            check_pattern(region, field, PatternKind::Other, pattern, compile, problems);
+        }
+    }
+
+    check_number_formats(region, "number_format", &metadata.number_format, compile, problems);
+    check_number_formats(region, "intl_number_format", &metadata.intl_number_format, compile, problems);
+}
+
+fn check_number_formats(
+    region: &str,
+    field: &str,
+    formats: &[NumberFormat],
+    compile: &mut impl FnMut(PatternKind, &str) -> Result<(), String>,
+    problems: &mut Vec<MetadataProblem>,
+) {
+    for (i, format) in formats.iter().enumerate() {
+        check_pattern(
+            region, &format!("{field}[{i}].pattern"), PatternKind::Other, format.pattern(), compile, problems,
+        );
+        for (j, leading_digits) in format.leading_digits_pattern.iter().enumerate() {
+            check_pattern(
+                region,
+                &format!("{field}[{i}].leading_digits_pattern[{j}]"),
+                PatternKind::Other,
+                leading_digits,
+                compile,
+                problems,
+            );
+        }
+    }
+}
+
+fn check_pattern(
+    region: &str,
+    field: &str,
+    kind: PatternKind,
+    pattern: &str,
+    compile: &mut impl FnMut(PatternKind, &str) -> Result<(), String>,
+    problems: &mut Vec<MetadataProblem>,
+) {
+    if let Err(error) = compile(kind, pattern) {
+        problems.push(MetadataProblem::InvalidPattern {
+            region: region.to_owned(),
+            field: field.to_owned(),
+            pattern: pattern.to_owned(),
+            error,
+        });
+    }
+}
@@ -17,9 +17,30 @@ mod helper_constants;
 mod helper_functions;
 pub mod errors;
 pub mod enums;
+pub mod parse_options;
+pub mod util_options;
+pub mod number_parts;
+pub mod borrowed_number;
+pub mod common_formats;
+pub mod metadata_validation;
+pub mod number_or_string;
+pub mod as_you_type;
+pub mod phone_number_matcher;
+pub mod short_number;
+#[cfg(feature = "geocoder")]
+pub mod geocoder;
+#[cfg(feature = "carrier")]
+pub mod carrier;
+#[cfg(feature = "timezones")]
+pub mod timezones;
+#[cfg(any(feature = "geocoder", feature = "carrier", feature = "timezones"))]
+mod prefix_file_reader;
+#[cfg(feature = "geocoder")]
+mod region_names;
+mod phone_number_impls;
 pub(super) mod phonenumberutil_internal;
 pub mod phonenumberutil;
-mod phone_number_regexps_and_mappings;
+pub(super) mod phone_number_regexps_and_mappings;
 pub(self) mod helper_types;
 
 use std::sync::LazyLock;
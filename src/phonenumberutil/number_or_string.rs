@@ -0,0 +1,46 @@
+// Copyright (C) 2025 Kashin Vladislav (Rust adaptation author)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::generated::proto::phonenumber::PhoneNumber;
+
+/// Either an already parsed `PhoneNumber` or a phone number string, as accepted by
+/// `PhoneNumberUtil::is_number_match_lenient`.
+///
+/// Values are usually created implicitly through the `From` implementations, so
+/// both `&PhoneNumber` and `&str` can be passed directly.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumberOrString<'a> {
+    /// A parsed phone number.
+    Number(&'a PhoneNumber),
+    /// A phone number string that still has to be parsed.
+    String(&'a str),
+}
+
+impl<'a> From<&'a PhoneNumber> for NumberOrString<'a> {
+    fn from(phone_number: &'a PhoneNumber) -> Self {
+        NumberOrString::Number(phone_number)
+    }
+}
+
+impl<'a> From<&'a str> for NumberOrString<'a> {
+    fn from(phone_number: &'a str) -> Self {
+        NumberOrString::String(phone_number)
+    }
+}
+
+impl<'a> From<&'a String> for NumberOrString<'a> {
+    fn from(phone_number: &'a String) -> Self {
+        NumberOrString::String(phone_number)
+    }
+}
@@ -0,0 +1,38 @@
+// Copyright (C) 2025 Kashin Vladislav (Rust adaptation author)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// A phone number split into labeled parts, as returned by `PhoneNumberUtil::format_parts`.
+///
+/// For example, Google London `+44 20 7031 3000 ext. 123` is split into:
+/// - `country_code`: `"44"`
+/// - `area_code`: `Some("20")`
+/// - `ndc`: `Some("20")`
+/// - `subscriber`: `"70313000"`
+/// - `extension`: `Some("123")`
+///
+/// The national significant number is always `ndc` (if any) followed by `subscriber`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NumberParts {
+    /// The country calling code, without the leading `+`.
+    pub country_code: String,
+    /// The geographical area code, if the number has one.
+    pub area_code: Option<String>,
+    /// The national destination code, if the number has one. Unlike the area code,
+    /// it is also present for mobile and non-geographical numbers.
+    pub ndc: Option<String>,
+    /// The rest of the national significant number after the NDC.
+    pub subscriber: String,
+    /// The extension, if the number has one.
+    pub extension: Option<String>,
+}
@@ -0,0 +1,68 @@
+// Copyright (C) 2009 The Libphonenumber Authors
+// Copyright (C) 2025 Kashin Vladislav (Rust adaptation author)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Options that tune how `PhoneNumberUtil::parse_with_options` treats its input.
+///
+/// The default options behave exactly like `PhoneNumberUtil::parse`. Options are
+/// set with chained builder-style calls:
+///
+/// ```rust
+/// use rlibphonenumber::ParseOptions;
+///
+/// let options = ParseOptions::new().strip_leading_symbols(true);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ParseOptions {
+    pub(crate) strip_leading_symbols: bool,
+    pub(crate) keep_raw_input_only: bool,
+    /// Stored negated so that the derived default allows extensions.
+    pub(crate) forbid_extensions: bool,
+}
+
+impl ParseOptions {
+    /// Creates options that behave exactly like `PhoneNumberUtil::parse`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Removes decorative symbols (like ☎, 📞 or bullets) that precede the first digit
+    /// of the number, including ones placed between a leading `+` and the digits.
+    ///
+    /// Only non-ASCII characters which are neither letters, digits, whitespace nor
+    /// punctuation accepted in phone numbers are removed, so inputs such as RFC3966
+    /// URIs are not affected. Disabled by default.
+    pub fn strip_leading_symbols(mut self, strip_leading_symbols: bool) -> Self {
+        self.strip_leading_symbols = strip_leading_symbols;
+        self
+    }
+
+    /// Stores the input string in the `raw_input` field of the parsed number.
+    ///
+    /// Unlike `PhoneNumberUtil::parse_and_keep_raw_input`, the `country_code_source`
+    /// field is left unset, so numbers parsed from differently written inputs only
+    /// differ in `raw_input`. Disabled by default.
+    pub fn keep_raw_input_only(mut self, keep_raw_input_only: bool) -> Self {
+        self.keep_raw_input_only = keep_raw_input_only;
+        self
+    }
+
+    /// Whether numbers with an extension are accepted. If not, parsing a number such as
+    /// "+1 650 253 0000 ext 5" fails with `ParseError::ExtensionNotAllowed` instead of
+    /// returning the number with the extension. Enabled by default.
+    pub fn allow_extensions(mut self, allow_extensions: bool) -> Self {
+        self.forbid_extensions = !allow_extensions;
+        self
+    }
+}
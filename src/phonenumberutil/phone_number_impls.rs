@@ -0,0 +1,38 @@
+// Copyright (C) 2025 Kashin Vladislav (Rust adaptation author)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{fmt, str::FromStr};
+
+use crate::{generated::proto::phonenumber::PhoneNumber, region_code::RegionCode};
+
+use super::{enums::PhoneNumberFormat, errors::ParseError, PHONE_NUMBER_UTIL};
+
+/// Parses a number in international format, such as "+1 650-253-0000", with
+/// `PHONE_NUMBER_UTIL`. As no default region is known, numbers that don't start
+/// with a "+" are rejected with `ParseError::InvalidCountryCode`.
+impl FromStr for PhoneNumber {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PHONE_NUMBER_UTIL.parse(s, RegionCode::get_unknown())
+    }
+}
+
+/// Formats the number in E164 format with `PHONE_NUMBER_UTIL`, e.g. "+16502530000".
+/// The extension, if any, is not included.
+impl fmt::Display for PhoneNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&PHONE_NUMBER_UTIL.format(self, PhoneNumberFormat::E164))
+    }
+}
@@ -0,0 +1,661 @@
+// Copyright (C) 2011 The Libphonenumber Authors
+// Copyright (C) 2025 Kashin Vladislav (Rust adaptation author)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, LazyLock};
+
+use regex::Regex;
+
+use crate::{
+    generated::proto::phonenumber::{phone_number::CountryCodeSource, PhoneNumber},
+    regex_util::RegexConsume,
+    regexp_cache::InvalidRegexError,
+};
+
+use super::{
+    enums::{Leniency, MatchType, PhoneNumberFormat},
+    errors::ParseErrorInternal,
+    helper_constants::{MAX_LENGTH_COUNTRY_CODE, MAX_LENGTH_FOR_NSN, PLUS_CHARS, VALID_PUNCTUATION},
+    phonenumberutil_internal::PhoneNumberUtilInternal,
+};
+
+type RegexResult<T> = std::result::Result<T, InvalidRegexError>;
+
+const OPENING_PARENS: &str = "(\\[\u{FF08}\u{FF3B}";
+const CLOSING_PARENS: &str = ")\\]\u{FF09}\u{FF3D}";
+
+/// Matches strings that look like the start of a phone number, i.e. an opening
+/// bracket or a plus sign.
+static LEAD_CLASS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(&format!("[{OPENING_PARENS}{PLUS_CHARS}]")).unwrap());
+
+/// Creates the phone number pattern used by `PhoneNumberMatcher::find`, similar to
+/// `valid_phone_number_pattern`, but with the following differences:
+/// - All captures are limited in order to place an upper bound to the text
+///   matched by the pattern.
+/// - Leading punctuation / plus signs are limited.
+/// - Consecutive occurrences of punctuation are limited.
+/// - The number of digits is limited.
+/// - No whitespace is allowed at the start or end.
+/// - No alpha digits (vanity numbers such as 1-800-SIX-FLAGS) are currently
+///   supported.
+///
+/// # Arguments
+///
+/// * `extn_patterns_for_matching` - The extension pattern of the util, which
+///   includes its extra extension separators.
+fn create_pattern(extn_patterns_for_matching: &str) -> String {
+    let lead_class = format!("[{OPENING_PARENS}{PLUS_CHARS}]");
+    let punctuation = format!("[{VALID_PUNCTUATION}]{{0,4}}");
+    let digit_block_limit = MAX_LENGTH_FOR_NSN + MAX_LENGTH_COUNTRY_CODE;
+    let digit_sequence = format!("\\p{{Nd}}{{1,{digit_block_limit}}}");
+    format!(
+        "(?i)(?:{lead_class}{punctuation}){{0,2}}{digit_sequence}\
+        (?:{punctuation}{digit_sequence}){{0,{digit_block_limit}}}\
+        (?:{extn_patterns_for_matching})?"
+    )
+}
+
+/// Matches strings where brackets are balanced and only open brackets are
+/// followed by the corresponding closing ones, with at most three bracket
+/// pairs in total.
+static MATCHING_BRACKETS: LazyLock<Regex> = LazyLock::new(|| {
+    let non_parens = format!("[^{OPENING_PARENS}{CLOSING_PARENS}]");
+    Regex::new(&format!(
+        "^(?:[{OPENING_PARENS}])?(?:{non_parens}+[{CLOSING_PARENS}])?{non_parens}+\
+        (?:[{OPENING_PARENS}]{non_parens}+[{CLOSING_PARENS}]){{0,3}}{non_parens}*$"
+    ))
+    .unwrap()
+});
+
+/// Matches publication page ranges such as "211-227 (2003)".
+static PUB_PAGES: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[0-9]{1,5}-+[0-9]{1,5}[ \t\n\x0B\f\r]{0,4}\([0-9]{1,4}").unwrap());
+
+/// Matches dates such as "08/31/95" or "31/08/1995".
+static SLASH_SEPARATED_DATES: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:(?:[0-3]?[0-9]/[01]?[0-9])|(?:[01]?[0-9]/[0-3]?[0-9]))/(?:[12][0-9])?[0-9]{2}")
+        .unwrap()
+});
+
+/// Matches timestamps such as "2012-01-02 08" that are followed by minutes.
+static TIME_STAMPS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"[12][0-9]{3}[-/]?[01][0-9][-/]?[0-3][0-9] +[0-2][0-9]$").unwrap()
+});
+static TIME_STAMPS_SUFFIX: LazyLock<Regex> = LazyLock::new(|| Regex::new(":[0-5][0-9]").unwrap());
+
+/// Patterns used to extract phone numbers from a larger candidate that failed
+/// to parse as a whole. The first group of each one is the inner candidate.
+static INNER_MATCHES: LazyLock<[Regex; 6]> = LazyLock::new(|| {
+    [
+        // Breaks on the slash - e.g. "651-234-2345/332-445-1234".
+        Regex::new("/+(.*)").unwrap(),
+        // Note that the bracket here is inside the capturing group, since we consider it part of
+        // the phone number. Will match a pattern like "(650) 223 3345 (754) 223 3321".
+        Regex::new(r"(\([^(]*)").unwrap(),
+        // Breaks on a hyphen - e.g. "12345 - 332-445-1234 is my number." We require a space on
+        // either side of the hyphen for it to be considered a separator.
+        Regex::new(r"(?:\p{Z}-|-\p{Z})\p{Z}*(.+)").unwrap(),
+        // Various types of wide hyphens. Note we have decided not to enforce a space here, since
+        // it's possible that it's supposed to be used to break two numbers without spaces, and we
+        // haven't seen many instances of it used within a number.
+        Regex::new("[\u{2012}-\u{2015}\u{FF0D}]\\p{Z}*(.+)").unwrap(),
+        // Breaks on a full stop - e.g. "12345. 332-445-1234 is my number."
+        Regex::new(r"\.+\p{Z}*([^.]+)").unwrap(),
+        // Breaks on space - e.g. "3324451234 8002341234".
+        Regex::new(r"\p{Z}+(\P{Z}+)").unwrap(),
+    ]
+});
+
+/// Characters typically used to start a second phone number, e.g. in
+/// "(530) 583-6985 x302/x2303".
+static SECOND_NUMBER_START_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[\\/] *x").unwrap());
+
+static INVALID_PUNCTUATION_SYMBOL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[%\p{Sc}]$").unwrap());
+
+/// A phone number found in text by `PhoneNumberMatcher`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhoneNumberMatch<'t> {
+    start: usize,
+    raw_string: &'t str,
+    number: PhoneNumber,
+}
+
+impl<'t> PhoneNumberMatch<'t> {
+    /// Returns the byte offset of the match in the searched text.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the byte offset right after the end of the match in the searched text.
+    pub fn end(&self) -> usize {
+        self.start + self.raw_string.len()
+    }
+
+    /// Returns the matched part of the text, e.g. "(650) 253-0000".
+    pub fn raw_string(&self) -> &'t str {
+        self.raw_string
+    }
+
+    /// Returns the phone number parsed from the match.
+    pub fn number(&self) -> &PhoneNumber {
+        &self.number
+    }
+
+    /// Consumes the match, returning the parsed phone number.
+    pub fn into_number(self) -> PhoneNumber {
+        self.number
+    }
+}
+
+/// An iterator over the phone numbers found in a text, created with
+/// `PhoneNumberUtil::find_numbers` or `PhoneNumberUtil::find_numbers_with_leniency`.
+///
+/// Candidates are found with a pattern that is lenient about punctuation, and are
+/// then parsed and checked according to the `Leniency`. Candidates that look like
+/// dates, timestamps or page ranges are skipped. When a candidate fails, parts of
+/// it split off at slashes, brackets, hyphens, full stops and spaces are tried.
+pub struct PhoneNumberMatcher<'t> {
+    util: Arc<PhoneNumberUtilInternal>,
+    /// The pattern built by `create_pattern` for the extension pattern of `util`.
+    pattern: Arc<Regex>,
+    text: &'t str,
+    preferred_region: String,
+    leniency: Leniency,
+    /// The maximum number of candidates still to be tried.
+    max_tries: u64,
+    /// The byte offset in the text at which the next search starts.
+    search_index: usize,
+}
+
+impl<'t> PhoneNumberMatcher<'t> {
+    /// Creates a matcher for the given text. Numbers written in national format are
+    /// parsed as numbers of `preferred_region`. At most `max_tries` candidates are
+    /// checked, which bounds the time spent on long texts.
+    pub(crate) fn new(
+        util: Arc<PhoneNumberUtilInternal>,
+        text: &'t str,
+        preferred_region: &str,
+        leniency: Leniency,
+        max_tries: u64,
+    ) -> Self {
+        let pattern = util
+            .regexp_cache()
+            .get_regex(&create_pattern(util.get_extn_patterns_for_matching()))
+            .expect("The matcher pattern should compile");
+        Self {
+            util,
+            pattern,
+            text,
+            preferred_region: preferred_region.to_owned(),
+            leniency,
+            max_tries,
+            search_index: 0,
+        }
+    }
+
+    /// Attempts to find the next match in the text, starting at `index`.
+    fn find(&mut self, mut index: usize) -> RegexResult<Option<PhoneNumberMatch<'t>>> {
+        while self.max_tries > 0 {
+            let Some(candidate_match) = self.pattern.find_at(self.text, index) else {
+                break;
+            };
+            let start = candidate_match.start();
+            let mut candidate = candidate_match.as_str();
+            // Check for extra numbers at the end.
+            if let Some(second_number_start) = SECOND_NUMBER_START_PATTERN.find(candidate) {
+                candidate = &candidate[..second_number_start.start()];
+            }
+            if let Some(found) = self.extract_match(candidate, start)? {
+                return Ok(Some(found));
+            }
+            index = start + candidate.len();
+            self.max_tries = self.max_tries.saturating_sub(1);
+        }
+        Ok(None)
+    }
+
+    /// Attempts to extract a match from a candidate.
+    fn extract_match(
+        &mut self,
+        candidate: &'t str,
+        offset: usize,
+    ) -> RegexResult<Option<PhoneNumberMatch<'t>>> {
+        // Skip a match that is more likely to be a date.
+        if SLASH_SEPARATED_DATES.is_match(candidate) {
+            return Ok(None);
+        }
+        // Skip potential time-stamps.
+        if TIME_STAMPS.is_match(candidate) {
+            let following_text = &self.text[offset + candidate.len()..];
+            if TIME_STAMPS_SUFFIX.matches_start(following_text) {
+                return Ok(None);
+            }
+        }
+        // Try to come up with a valid match given the entire candidate.
+        if let Some(found) = self.parse_and_verify(candidate, offset)? {
+            return Ok(Some(found));
+        }
+        // If that failed, try to find an "inner match" - there might be a phone
+        // number within this candidate.
+        self.extract_inner_match(candidate, offset)
+    }
+
+    /// Attempts to extract a match from a candidate that failed to match as a
+    /// whole, using the `INNER_MATCHES` patterns.
+    fn extract_inner_match(
+        &mut self,
+        candidate: &'t str,
+        offset: usize,
+    ) -> RegexResult<Option<PhoneNumberMatch<'t>>> {
+        for possible_inner_match in INNER_MATCHES.iter() {
+            let mut is_first_match = true;
+            for captures in possible_inner_match.captures_iter(candidate) {
+                if self.max_tries == 0 {
+                    break;
+                }
+                let (Some(whole_match), Some(inner_group)) = (captures.get(0), captures.get(1))
+                else {
+                    continue;
+                };
+                if is_first_match {
+                    // We should handle any group before this one too.
+                    let group = self
+                        .util
+                        .trim_unwanted_end_chars(&candidate[..whole_match.start()]);
+                    if let Some(found) = self.parse_and_verify(group, offset)? {
+                        return Ok(Some(found));
+                    }
+                    self.max_tries = self.max_tries.saturating_sub(1);
+                    is_first_match = false;
+                }
+                let group = self.util.trim_unwanted_end_chars(inner_group.as_str());
+                if let Some(found) = self.parse_and_verify(group, offset + inner_group.start())? {
+                    return Ok(Some(found));
+                }
+                self.max_tries = self.max_tries.saturating_sub(1);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Parses a candidate and returns it as a match if it is accepted by the
+    /// leniency of this matcher.
+    fn parse_and_verify(
+        &self,
+        candidate: &'t str,
+        offset: usize,
+    ) -> RegexResult<Option<PhoneNumberMatch<'t>>> {
+        // Check the candidate doesn't contain any formatting which would indicate
+        // that it really isn't a phone number.
+        if !MATCHING_BRACKETS.is_match(candidate) || PUB_PAGES.is_match(candidate) {
+            return Ok(None);
+        }
+
+        // If leniency is set to VALID or stricter, we also want to skip numbers that
+        // are surrounded by Latin alphabetic characters, to skip cases like
+        // abc8005001234 or 8005001234def.
+        if self.leniency >= Leniency::Valid {
+            // If the candidate is not at the start of the text, and does not start
+            // with phone-number punctuation, check the previous character.
+            if offset > 0
+                && !LEAD_CLASS.matches_start(candidate)
+                && self.text[..offset]
+                    .chars()
+                    .next_back()
+                    .is_some_and(|previous_char| {
+                        is_invalid_punctuation_symbol(previous_char) || is_latin_letter(previous_char)
+                    })
+            {
+                return Ok(None);
+            }
+            if self.text[offset + candidate.len()..]
+                .chars()
+                .next()
+                .is_some_and(|next_char| {
+                    is_invalid_punctuation_symbol(next_char) || is_latin_letter(next_char)
+                })
+            {
+                return Ok(None);
+            }
+        }
+
+        let mut number = match self
+            .util
+            .parse_and_keep_raw_input(candidate, &self.preferred_region)
+        {
+            Ok(number) => number,
+            Err(ParseErrorInternal::FailedToParse(_)) => return Ok(None),
+            Err(ParseErrorInternal::RegexError(err)) => return Err(err),
+        };
+        if !self.verify(&number, candidate)? {
+            return Ok(None);
+        }
+        // We used parse_and_keep_raw_input to create this number, but the match
+        // only carries the fields that `parse` would set.
+        number.clear_country_code_source();
+        number.clear_raw_input();
+        number.raw_input_groups.clear();
+        number.clear_preferred_domestic_carrier_code();
+        Ok(Some(PhoneNumberMatch {
+            start: offset,
+            raw_string: candidate,
+            number,
+        }))
+    }
+
+    /// Checks a parsed candidate against the leniency of this matcher.
+    fn verify(&self, number: &PhoneNumber, candidate: &str) -> RegexResult<bool> {
+        if matches!(self.leniency, Leniency::Possible) {
+            return Ok(self.util.is_possible_number(number));
+        }
+        if !self.util.is_valid_number(number)?
+            || !self.contains_only_valid_x_chars(number, candidate)
+            || !self.is_national_prefix_present_if_required(number)?
+        {
+            return Ok(false);
+        }
+        match self.leniency {
+            Leniency::Possible | Leniency::Valid => Ok(true),
+            Leniency::StrictGrouping => Ok(!contains_more_than_one_slash_in_national_number(
+                number, candidate,
+            ) && self.check_number_grouping_is_valid(
+                number,
+                candidate,
+                Self::all_number_groups_remain_grouped,
+            )?),
+            Leniency::ExactGrouping => Ok(!contains_more_than_one_slash_in_national_number(
+                number, candidate,
+            ) && self.check_number_grouping_is_valid(
+                number,
+                candidate,
+                Self::all_number_groups_are_exactly_present,
+            )?),
+        }
+    }
+
+    /// The characters 'x' and 'X' can be (1) a carrier code, in which case they
+    /// always precede the national significant number or (2) an extension sign,
+    /// in which case they always precede the extension number. We assume a
+    /// carrier code is more than 1 digit, so the first case has to have more than
+    /// 1 consecutive 'x' or 'X', whereas the second case can only have exactly 1
+    /// 'x' or 'X'. We ignore the character if it appears as the last character of
+    /// the string.
+    fn contains_only_valid_x_chars(&self, number: &PhoneNumber, candidate: &str) -> bool {
+        let bytes = candidate.as_bytes();
+        let mut index = 0;
+        while index + 1 < bytes.len() {
+            if matches!(bytes[index], b'x' | b'X') {
+                if matches!(bytes[index + 1], b'x' | b'X') {
+                    // This is the carrier code case, in which the 'X's always precede the
+                    // national significant number.
+                    index += 1;
+                    if !matches!(
+                        self.util.is_number_match_with_one_string(number, &candidate[index..]),
+                        Ok(MatchType::NsnMatch)
+                    ) {
+                        return false;
+                    }
+                } else if self.util.normalize_digits_only(&candidate[index..]) != number.extension() {
+                    // This is the extension sign case, in which the 'x' or 'X' should
+                    // always precede the extension number.
+                    return false;
+                }
+            }
+            index += 1;
+        }
+        true
+    }
+
+    /// Checks that a national prefix was written if the formatting rules of the
+    /// number require one.
+    fn is_national_prefix_present_if_required(&self, number: &PhoneNumber) -> RegexResult<bool> {
+        // First, check how we deduced the country code. If it was written in
+        // international format, then the national prefix is not required.
+        if number.country_code_source() != CountryCodeSource::FROM_DEFAULT_COUNTRY {
+            return Ok(true);
+        }
+        let phone_number_region = self
+            .util
+            .get_region_code_for_country_code(number.country_code());
+        let Some(metadata) = self.util.get_metadata_for_region(phone_number_region) else {
+            return Ok(true);
+        };
+        // Check if a national prefix should be present when formatting this number.
+        let national_number = self.util.get_national_significant_number(number);
+        let Some(format_rule) = self
+            .util
+            .choose_formatting_pattern_for_number(&metadata.number_format, &national_number)?
+        else {
+            return Ok(true);
+        };
+        // To do this, we check that a national prefix formatting rule was present and
+        // that it wasn't just the first-group symbol ($1) with punctuation.
+        let national_prefix_formatting_rule = format_rule.national_prefix_formatting_rule();
+        if national_prefix_formatting_rule.is_empty()
+            // The national prefix is optional in these cases, so we don't need to
+            // check if it was present.
+            || format_rule.national_prefix_optional_when_formatting()
+            // National prefix not needed for this number.
+            || self
+                .util
+                .formatting_rule_has_first_group_only(national_prefix_formatting_rule)
+        {
+            return Ok(true);
+        }
+        // Check if we found a national prefix and/or carrier code at the start of the
+        // raw input.
+        let raw_input = self.util.normalize_digits_only(number.raw_input());
+        let (stripped_number, _) = self
+            .util
+            .maybe_strip_national_prefix_and_carrier_code(metadata, &raw_input)?;
+        Ok(stripped_number.len() != raw_input.len())
+    }
+
+    /// Checks the grouping of the digits in the candidate with `checker`, which
+    /// is given the candidate with its digits normalized and the digit groups of
+    /// the formatted number.
+    fn check_number_grouping_is_valid(
+        &self,
+        number: &PhoneNumber,
+        candidate: &str,
+        checker: fn(&Self, &PhoneNumber, &str, &[&str]) -> bool,
+    ) -> RegexResult<bool> {
+        let normalized_candidate = dec_from_char::normalize_decimals(candidate);
+        // This will be in the format tel:+CC-DG1-DG2-DGX;ext=EXT where DG1..DGX
+        // represents groups of digits.
+        let rfc3966_format = self.util.format(number, PhoneNumberFormat::RFC3966)?;
+        // We remove the extension part from the formatted string before splitting it
+        // into different groups. The country calling code will have a '-' following it.
+        let end_index = rfc3966_format.find(';').unwrap_or(rfc3966_format.len());
+        let start_index = rfc3966_format.find('-').map_or(0, |index| index + 1);
+        let formatted_number_groups: Vec<&str> = rfc3966_format
+            .get(start_index..end_index)
+            .unwrap_or_default()
+            .split('-')
+            .collect();
+        Ok(checker(self, number, &normalized_candidate, &formatted_number_groups))
+    }
+
+    /// Checks that no group of consecutive digits of the formatted number is
+    /// split up in the candidate.
+    fn all_number_groups_remain_grouped(
+        &self,
+        number: &PhoneNumber,
+        normalized_candidate: &str,
+        formatted_number_groups: &[&str],
+    ) -> bool {
+        let mut from_index = 0;
+        if number.country_code_source() != CountryCodeSource::FROM_DEFAULT_COUNTRY {
+            // First skip the country code if the normalized candidate contained it.
+            let mut buf = itoa::Buffer::new();
+            let country_code = buf.format(number.country_code());
+            from_index = normalized_candidate
+                .find(country_code)
+                .map_or(0, |index| index + country_code.len());
+        }
+        // Check each group of consecutive digits are not broken into separate
+        // groupings in the normalized candidate.
+        for (i, formatted_number_group) in formatted_number_groups.iter().enumerate() {
+            // Fails if the rest of the normalized candidate doesn't contain the
+            // consecutive digits of the group.
+            let Some(group_index) = normalized_candidate[from_index..].find(formatted_number_group)
+            else {
+                return false;
+            };
+            from_index += group_index + formatted_number_group.len();
+            if i == 0 && from_index < normalized_candidate.len() {
+                // We are at the position right after the NDC. We get the region used for
+                // formatting information based on the country code in the phone number,
+                // rather than the number itself, as we do not need to distinguish between
+                // different countries with the same country calling code and this is
+                // faster.
+                let region = self
+                    .util
+                    .get_region_code_for_country_code(number.country_code());
+                if self.util.get_ndd_prefix_for_region(region, true).is_some()
+                    && normalized_candidate.as_bytes()[from_index].is_ascii_digit()
+                {
+                    // This means there is no formatting symbol after the NDC. In this case,
+                    // we only accept the number if there is no formatting symbol at all in
+                    // the number, except for extensions. This is only important for
+                    // countries with national prefixes.
+                    let national_significant_number =
+                        self.util.get_national_significant_number(number);
+                    return normalized_candidate[from_index - formatted_number_group.len()..]
+                        .starts_with(&national_significant_number);
+                }
+            }
+        }
+        // The check here makes sure that we haven't mistakenly already used the
+        // extension to match the last group of the subscriber number. Note the
+        // extension cannot have formatting in-between digits.
+        normalized_candidate[from_index..].contains(number.extension())
+    }
+
+    /// Checks that the groups of digits in the candidate are exactly the groups of
+    /// the formatted number.
+    fn all_number_groups_are_exactly_present(
+        &self,
+        number: &PhoneNumber,
+        _normalized_candidate: &str,
+        formatted_number_groups: &[&str],
+    ) -> bool {
+        // The candidate was parsed with `parse_and_keep_raw_input`, which recorded its
+        // groups of digits without the extension.
+        let candidate_groups = &number.raw_input_groups;
+        // Set this to the last group.
+        let mut candidate_number_group_index = candidate_groups.len() as isize - 1;
+        // First we check if the national significant number is formatted as a block.
+        // We use contains and not equals, since the national significant number may
+        // be present with a prefix such as a national number prefix, or the country
+        // code itself.
+        if candidate_groups.len() <= 1
+            || usize::try_from(candidate_number_group_index)
+                .ok()
+                .and_then(|index| candidate_groups.get(index))
+                .is_some_and(|group| {
+                    group.contains(&self.util.get_national_significant_number(number))
+                })
+        {
+            return true;
+        }
+        // Starting from the end, go through in reverse, excluding the first group,
+        // and check the candidate and number groups are the same.
+        for formatted_number_group in formatted_number_groups.iter().skip(1).rev() {
+            let Ok(index) = usize::try_from(candidate_number_group_index) else {
+                break;
+            };
+            if candidate_groups[index] != *formatted_number_group {
+                return false;
+            }
+            candidate_number_group_index -= 1;
+        }
+        // Now check the first group. There may be a national prefix at the start, so
+        // we only check that the candidate group ends with the formatted number group.
+        usize::try_from(candidate_number_group_index)
+            .ok()
+            .and_then(|index| candidate_groups.get(index))
+            .zip(formatted_number_groups.first())
+            .is_some_and(|(candidate_group, first_group)| candidate_group.ends_with(first_group))
+    }
+}
+
+impl<'t> Iterator for PhoneNumberMatcher<'t> {
+    type Item = PhoneNumberMatch<'t>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let found = self
+            .find(self.search_index)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.");
+        match found {
+            Some(found) => {
+                self.search_index = found.end();
+                Some(found)
+            }
+            None => {
+                self.search_index = self.text.len();
+                self.max_tries = 0;
+                None
+            }
+        }
+    }
+}
+
+/// Checks whether the national number part of the candidate contains more than
+/// one slash, as in dates like "08/31/95".
+fn contains_more_than_one_slash_in_national_number(number: &PhoneNumber, candidate: &str) -> bool {
+    let Some(first_slash_in_body_index) = candidate.find('/') else {
+        return false;
+    };
+    let Some(second_slash_in_body_index) = candidate[first_slash_in_body_index + 1..]
+        .find('/')
+        .map(|index| index + first_slash_in_body_index + 1)
+    else {
+        return false;
+    };
+    // If the first slash is after the country calling code, this is permitted.
+    let candidate_has_country_code = matches!(
+        number.country_code_source(),
+        CountryCodeSource::FROM_NUMBER_WITH_PLUS_SIGN
+            | CountryCodeSource::FROM_NUMBER_WITHOUT_PLUS_SIGN
+    );
+    let mut buf = itoa::Buffer::new();
+    if candidate_has_country_code
+        && dec_from_char::normalize_decimals_filtering(&candidate[..first_slash_in_body_index])
+            == buf.format(number.country_code())
+    {
+        // Any more slashes and this is illegal.
+        return candidate[second_slash_in_body_index + 1..].contains('/');
+    }
+    true
+}
+
+/// Checks whether the character is a letter or a combining mark of one of the
+/// Latin Unicode blocks.
+fn is_latin_letter(letter: char) -> bool {
+    let is_combining_mark = ('\u{0300}'..='\u{036F}').contains(&letter);
+    if !letter.is_alphabetic() && !is_combining_mark {
+        return false;
+    }
+    // Basic Latin, Latin-1 Supplement, Latin Extended-A and -B, Combining
+    // Diacritical Marks and Latin Extended Additional.
+    matches!(letter, '\u{0000}'..='\u{024F}' | '\u{0300}'..='\u{036F}' | '\u{1E00}'..='\u{1EFF}')
+}
+
+fn is_invalid_punctuation_symbol(character: char) -> bool {
+    INVALID_PUNCTUATION_SYMBOL.is_match(character.encode_utf8(&mut [0; 4]))
+}
@@ -22,10 +22,10 @@ use crate::{phonenumberutil::{helper_constants::{
     CAPTURE_UP_TO_SECOND_NUMBER_START, DIGITS, MIN_LENGTH_FOR_NSN, PLUS_CHARS, 
     PLUS_SIGN, RFC3966_VISUAL_SEPARATOR, STAR_SIGN, VALID_ALPHA, VALID_ALPHA_INCL_UPPERCASE, 
     VALID_PUNCTUATION
-}, helper_functions::create_extn_pattern}, regexp_cache::RegexCache};
+}, helper_functions::{create_dangling_extn_label_pattern, create_extn_pattern}}, regexp_cache::RegexCache};
 
 #[allow(unused)]
-pub(super) struct PhoneNumberRegExpsAndMappings {
+pub(crate) struct PhoneNumberRegExpsAndMappings {
     /// Regular expression of viable phone numbers. This is location independent.
     /// Checks we have at least three leading digits, and only valid punctuation,
     /// alpha characters and digits in the phone number. Does not include extension
@@ -148,6 +148,14 @@ pub(super) struct PhoneNumberRegExpsAndMappings {
     /// by 1 or more valid digits, for use when parsing.
     pub extn_pattern: Regex,
 
+    /// Same as `extn_pattern`, but not anchored at the end of the input, for
+    /// finding every extension-looking part of a string.
+    pub extn_candidates_pattern: Regex,
+
+    /// An extension label with no digits after it at the end of the input, such
+    /// as " ext.".
+    pub dangling_extn_label_pattern: Regex,
+
     /// We append optionally the extension pattern to the end here, as a valid
     /// phone number may have an extension prefix appended, followed by 1 or more
     /// digits.
@@ -282,8 +290,14 @@ impl PhoneNumberRegExpsAndMappings {
     }
 
     pub fn new() -> Self {
+        Self::with_extra_extension_separators(&[])
+    }
+
+    /// Same as `new`, but additionally accepts the given characters as ambiguous
+    /// extension labels when parsing and when finding numbers in text.
+    pub fn with_extra_extension_separators(extra_extension_separators: &[char]) -> Self {
         let alphanum = fast_cat::concat_str!(VALID_ALPHA_INCL_UPPERCASE, DIGITS);
-        let extn_patterns_for_parsing = create_extn_pattern(true);
+        let extn_patterns_for_parsing = create_extn_pattern(true, extra_extension_separators);
         let valid_phone_number = format!(
                 // moved 2-digits pattern to an end for match full number first
                 "[{}]*(?:[{}{}]*{}){{3,}}[{}{}{}{}]*|{}{{{}}}",
@@ -305,7 +319,7 @@ impl PhoneNumberRegExpsAndMappings {
             alphanum: alphanum,
             rfc3966_domainlabel: rfc3966_domainlabel.clone(),
             rfc3966_toplabel: rfc3966_toplabel.clone(),
-            regexp_cache: RegexCache::with_capacity(128),
+            regexp_cache: RegexCache::new(),
             diallable_char_mappings: Default::default(),
             alpha_mappings: Default::default(),
             alpha_phone_mappings: Default::default(),
@@ -322,8 +336,10 @@ impl PhoneNumberRegExpsAndMappings {
             capture_up_to_second_number_start_pattern: Regex::new(CAPTURE_UP_TO_SECOND_NUMBER_START).unwrap(),
             unwanted_end_char_pattern: Regex::new("[^\\p{N}\\p{L}#]").unwrap(),
             separator_pattern: Regex::new(&format!("[{}]+", VALID_PUNCTUATION)).unwrap(),
-            extn_patterns_for_matching: create_extn_pattern(false),
+            extn_patterns_for_matching: create_extn_pattern(false, extra_extension_separators),
             extn_pattern: Regex::new(&format!("(?i)(?:{})$", &extn_patterns_for_parsing)).unwrap(),
+            extn_candidates_pattern: Regex::new(&format!("(?i)(?:{})", &extn_patterns_for_parsing)).unwrap(),
+            dangling_extn_label_pattern: Regex::new(&format!("(?i)(?:{})$", create_dangling_extn_label_pattern())).unwrap(),
             valid_phone_number_pattern: Regex::new(&format!("(?i)^(?:{})(?:{})?$", 
                 &valid_phone_number,
                 &extn_patterns_for_parsing
@@ -359,5 +375,6 @@ mod tests {
     #[test]
     fn check_regexps_are_compiling() {
         super::PhoneNumberRegExpsAndMappings::new();
+        super::PhoneNumberRegExpsAndMappings::with_extra_extension_separators(&['/', '-', ']', '\\']);
     }
 }
\ No newline at end of file
@@ -20,17 +20,41 @@
 //! around the world. This utility is designed to handle the complexities of international
 //! phone number formats, country codes, and numbering plans.
 
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    ops::Range,
+    sync::{Arc, Mutex, OnceLock, PoisonError},
+};
+
+use arc_swap::{ArcSwap, Guard};
+use protobuf::Message;
 
 use crate::{
-    generated::proto::phonenumber::PhoneNumber, 
+    regexp_cache::CacheStats,
+    generated::proto::{phonemetadata::{NumberFormat, PhoneMetadataCollection}, phonenumber::PhoneNumber}, 
 };
 
 use super::{
-    errors::{ParseError, ValidationError, GetExampleNumberError},
-    enums::{PhoneNumberFormat, PhoneNumberType, MatchType, NumberLengthType},
+    errors::{InvalidNumberErrorInternal, MetadataError, ParseError, TypeValidationError, ValidationError, GetExampleNumberError},
+    enums::{CsvSafeStyle, ExtensionLabelKind, Leniency, PhoneNumberFormat, PhoneNumberType, MatchType, NumberLengthType},
+    parse_options::ParseOptions,
+    util_options::PhoneNumberUtilOptions,
+    number_parts::NumberParts,
+    borrowed_number::BorrowedNumber,
+    common_formats::CommonFormats,
+    number_or_string::NumberOrString,
+    as_you_type::AsYouTypeFormatter,
+    phone_number_matcher::PhoneNumberMatcher,
+    short_number::ShortNumberInfo,
+    metadata_validation::{MetadataProblem, validate_metadata_collection},
     phonenumberutil_internal::PhoneNumberUtilInternal,
 };
+#[cfg(feature = "geocoder")]
+use super::geocoder::PhoneNumberOfflineGeocoder;
+#[cfg(feature = "carrier")]
+use super::carrier::PhoneNumberToCarrierMapper;
+#[cfg(feature = "timezones")]
+use super::timezones::PhoneNumberToTimeZonesMapper;
 
 
 /// The main struct for all phone number-related operations.
@@ -39,565 +63,2500 @@ use super::{
 /// formatting, and validating phone numbers. An instance of this struct is the
 /// primary entry point for using the library's features.
 pub struct PhoneNumberUtil {
-    util_internal: PhoneNumberUtilInternal
+    /// The metadata snapshot in use. `reload_metadata` swaps in a new one, and the
+    /// replaced snapshot is freed once the last call still using it returns, unless
+    /// it is pinned.
+    util_internal: ArcSwap<PhoneNumberUtilInternal>,
+    /// Serialises `reload_metadata`, so the metadata of the latest call always wins.
+    reload_lock: Mutex<()>,
+    /// The snapshots `format_with_format_info` has handed out references into. They
+    /// are kept until the util is dropped, so the references stay valid across reloads.
+    pinned_snapshots: OnceLock<Box<PinnedSnapshot>>,
+}
+
+/// A metadata snapshot kept alive for as long as its `PhoneNumberUtil`, linked to the
+/// snapshot pinned after it.
+struct PinnedSnapshot {
+    util_internal: Arc<PhoneNumberUtilInternal>,
+    next: OnceLock<Box<PinnedSnapshot>>,
 }
 
 impl PhoneNumberUtil {
     
     /// Creates new `PhoneNumberUtil` instance
     pub fn new() -> Self {
-        Self { util_internal: 
+        Self::from_util_internal(
             PhoneNumberUtilInternal::new()
-                .expect("Metadata should be valid and all regex should compile") 
+                .expect("Metadata should be valid and all regex should compile")
+        )
+    }
+
+    fn from_util_internal(util_internal: PhoneNumberUtilInternal) -> Self {
+        Self {
+            util_internal: ArcSwap::from_pointee(util_internal),
+            reload_lock: Mutex::new(()),
+            pinned_snapshots: OnceLock::new(),
         }
     }
 
-    /// Checks if a `PhoneNumber` can be dialed internationally.
+    /// Returns the snapshot in use. Every method loads it once, so it works on
+    /// consistent metadata even if `reload_metadata` runs at the same time.
+    fn util_internal(&self) -> Guard<Arc<PhoneNumberUtilInternal>> {
+        self.util_internal.load()
+    }
+
+    /// Returns the snapshot in use, pinning it so that it lives as long as `self`.
+    /// A snapshot is pinned only once, however often it is requested.
+    fn pinned_util_internal(&self) -> &PhoneNumberUtilInternal {
+        let util_internal = self.util_internal.load_full();
+        let mut slot = &self.pinned_snapshots;
+        loop {
+            let pinned = slot.get_or_init(|| {
+                Box::new(PinnedSnapshot { util_internal: Arc::clone(&util_internal), next: OnceLock::new() })
+            });
+            if Arc::ptr_eq(&pinned.util_internal, &util_internal) {
+                return &pinned.util_internal;
+            }
+            slot = &pinned.next;
+        }
+    }
+
+    /// Creates new `PhoneNumberUtil` instance with the given options.
     ///
     /// # Parameters
     ///
-    /// * `phone_number`: A reference to the `PhoneNumber` object to be checked.
+    /// * `options`: The `PhoneNumberUtilOptions` to apply, e.g. a bound on the regex caches.
+    pub fn new_with_options(options: PhoneNumberUtilOptions) -> Self {
+        Self::from_util_internal(
+            PhoneNumberUtilInternal::new_with_options(options)
+                .expect("Metadata should be valid and all regex should compile")
+        )
+    }
+
+    /// Creates new `PhoneNumberUtil` instance that only loads the metadata of the given
+    /// regions.
     ///
-    /// # Returns
+    /// Regions sharing a country calling code with a requested region are kept as well,
+    /// since numbers of that code are resolved against all of them; e.g. "US" also keeps
+    /// the other NANPA regions such as "CA". Pass "001" to keep the non-geographical
+    /// entities. Unknown region codes are ignored. Everything else behaves as if the
+    /// other regions didn't exist: parsing a number of an excluded region fails with
+    /// `ParseError::InvalidCountryCode` and lookups for it return the usual "unknown
+    /// region" results.
     ///
-    /// `true` if the number can be dialed from another country, `false` otherwise.
+    /// This saves memory and startup time for apps that only handle a few regions. The
+    /// bundled metadata is still part of the binary. The short number metadata used by
+    /// `get_short_number_info` is not restricted and covers all regions.
     ///
-    /// # Panics
+    /// # Parameters
     ///
-    /// This method panics if the underlying metadata contains an invalid regular expression,
-    /// which indicates a critical library bug.
-    pub fn can_be_internationally_dialled(&self, phone_number: &PhoneNumber) -> bool {
-        self.util_internal
-            .can_be_internationally_dialled(phone_number)
-            // This should not never happen
-            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    /// * `region_codes`: The two-letter region codes (ISO 3166-1) to load the metadata of.
+    pub fn new_for_regions(region_codes: &[&str]) -> Self {
+        Self::from_util_internal(
+            PhoneNumberUtilInternal::new_for_regions(region_codes)
+                .expect("Metadata should be valid and all regex should compile")
+        )
     }
 
-    /// Converts all alpha characters in a phone number string to their corresponding digits.
+    /// Creates new `PhoneNumberUtil` instance from a serialized `PhoneMetadataCollection`
+    /// instead of the bundled metadata.
     ///
-    /// For example, an input of "1-800-FLOWERS" will be converted to "1-800-3569377".
+    /// This allows loading custom or trimmed metadata, e.g. a build with a single region,
+    /// without depending on the protobuf crate. All regular expressions in the metadata
+    /// are compiled up front, so a broken pattern is reported here rather than causing a
+    /// panic later. Entries whose id is neither a two-letter region code nor "001" are
+    /// skipped, see `MetadataProblem::InvalidRegionCode`.
     ///
-    
     /// # Parameters
     ///
-    /// * `number`: A string slice or `String` representing the phone number.
+    /// * `metadata_bytes`: The serialized `PhoneMetadataCollection`.
     ///
     /// # Returns
     ///
-    /// A `String` containing the phone number with all alphabetic characters converted to digits.
-    pub fn convert_alpha_characters_in_number<'a>(&self, number: impl AsRef<str>) -> String {
-        self.util_internal.convert_alpha_characters_in_number(number.as_ref())
+    /// The new `PhoneNumberUtil`, or `MetadataError::InvalidMetadata` if the bytes can't
+    /// be decoded and `MetadataError::InvalidPattern` if a pattern doesn't compile.
+    pub fn from_metadata_bytes(metadata_bytes: &[u8]) -> Result<Self, MetadataError> {
+        let empty_util_internal = PhoneNumberUtilInternal::new_for_metadata(PhoneMetadataCollection::new());
+        let metadata_collection = decode_metadata(metadata_bytes, &empty_util_internal)?;
+        Ok(Self::from_util_internal(empty_util_internal.with_metadata(metadata_collection)))
     }
 
-    /// Formats a `PhoneNumber` into a standardized format.
+    /// Creates new `PhoneNumberUtil` instance that additionally accepts the given
+    /// characters as extension separators when parsing and finding numbers in text.
     ///
-    /// # Parameters
+    /// The extra separators are treated like the built-in single-character labels such
+    /// as "x", so at most 9 extension digits may follow them. For example, with `'/'`
+    /// the input "+44 20 3456 7890 / 456" is parsed as a number with extension "456".
+    /// The default labels stay unchanged.
     ///
-    /// * `phone_number`: The `PhoneNumber` to be formatted.
-    /// * `number_format`: The `PhoneNumberFormat` to be applied (e.g., E164, INTERNATIONAL, NATIONAL).
+    /// # Parameters
     ///
-    /// # Returns
+    /// * `separators`: The extra extension separator characters.
+    pub fn with_extra_extension_separators(separators: &[char]) -> Self {
+        Self::from_util_internal(
+            PhoneNumberUtilInternal::with_extra_extension_separators(separators)
+                .expect("Metadata should be valid and all regex should compile")
+        )
+    }
+
+    /// Returns statistics of the caches holding the compiled regular expressions.
     ///
-    /// A `Cow<'a, str>` which is either a borrowed reference to a pre-formatted string or a
-    /// newly allocated `String` with the formatted number.
+    /// Regexes from the metadata are compiled on first use, so a low hit rate or a
+    /// steadily growing size on a long-running server may call for a capacity set with
+    /// `PhoneNumberUtilOptions::regex_cache_capacity`.
     ///
-    /// # Panics
+    /// # Returns
     ///
-    /// This method panics if the underlying metadata contains an invalid regular expression,
-    /// indicating a library bug.
-    pub fn format<'a>(&self, phone_number: &'a PhoneNumber, number_format: PhoneNumberFormat) -> Cow<'a, str> {
-        self.util_internal
-            .format(phone_number, number_format)
-            // This should not never happen
-            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    /// The `CacheStats` with the hits, misses and size summed over all regex caches.
+    pub fn regex_cache_stats(&self) -> CacheStats {
+        self.util_internal().regex_cache_stats()
     }
 
-    /// Formats a `PhoneNumber`, attempting to preserve original formatting and punctuation.
+    /// Replaces the loaded metadata with a serialized `PhoneMetadataCollection`.
     ///
-    /// The number is formatted in the national format of the region it is from.
+    /// This allows picking up updated metadata without restarting the process, also while
+    /// other threads keep using the util. The new metadata is decoded and its patterns are
+    /// checked first, so on error the current metadata stays in place. Then it is swapped in
+    /// atomically: calls already running finish on the old metadata, later calls use the new
+    /// one. Compiled regexes are cached by pattern, so patterns that didn't change are not
+    /// compiled again.
     ///
-    /// # Parameters
+    /// Helpers such as the `AsYouTypeFormatter` keep the metadata they were created with,
+    /// so the replaced metadata is freed once the last of them is dropped. Concurrent
+    /// reloads are applied one after another, the one called last wins.
     ///
-    /// * `phone_number`: The `PhoneNumber` to be formatted.
-    /// * `region_calling_from`: The two-letter region code (ISO 3166-1) from where the call is being made.
+    /// Only the main metadata is replaced. `ShortNumberInfo` keeps checking short numbers
+    /// against the bundled short number metadata.
     ///
-    /// # Returns
+    /// # Parameters
     ///
-    /// A `Cow<'a, str>` containing the formatted number.
+    /// * `metadata_bytes`: The serialized `PhoneMetadataCollection`.
     ///
-    /// # Panics
+    /// # Returns
     ///
-    /// This method panics if metadata is invalid, which indicates a library bug.
-    pub fn format_in_original_format<'a>(
-        &self, phone_number: &'a PhoneNumber, region_calling_from: impl AsRef<str>
-    ) -> Cow<'a, str> {
-        self.util_internal
-            .format_in_original_format(phone_number, region_calling_from.as_ref())
-            // This should not never happen
-            .expect("A valid regex and region is expected in metadata; this indicates a library bug.")
+    /// `Ok(())` if the metadata was replaced, or `MetadataError::InvalidMetadata` if the bytes
+    /// can't be decoded and `MetadataError::InvalidPattern` if a pattern doesn't compile.
+    pub fn reload_metadata(&self, metadata_bytes: &[u8]) -> Result<(), MetadataError> {
+        let current_util_internal = self.util_internal();
+        let metadata_collection = decode_metadata(metadata_bytes, &current_util_internal)?;
+        let _reload_guard = self.reload_lock.lock().unwrap_or_else(PoisonError::into_inner);
+        let util_internal = current_util_internal.with_metadata(metadata_collection);
+        self.util_internal.store(Arc::new(util_internal));
+        Ok(())
     }
 
-    /// Formats a national number with a specified carrier code.
+    /// Creates an `AsYouTypeFormatter` for formatting a phone number as it is typed.
     ///
     /// # Parameters
     ///
-    /// * `phone_number`: The `PhoneNumber` to format.
-    /// * `carrier_code`: The carrier code to prepend to the number.
+    /// * `region_code`: The region where the number is being entered. It is used for
+    ///   numbers entered without a "+" or an international dialing prefix. With an unknown
+    ///   region, only numbers starting with "+" are formatted.
     ///
     /// # Returns
     ///
-    /// A `String` containing the formatted number.
-    ///
-    /// # Panics
-    ///
-    /// Panics if metadata is invalid, indicating a library bug.
-    pub fn format_national_number_with_carrier_code<'a>(
-        &self,
-        phone_number: &'a PhoneNumber,
-        carrier_code: impl AsRef<str>,
-    ) -> String {
-        self.util_internal
-            .format_national_number_with_carrier_code(phone_number, carrier_code.as_ref())
-            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    /// A new `AsYouTypeFormatter`. It holds its own reference to the metadata loaded at the
+    /// time of the call, so it keeps formatting with that metadata after a `reload_metadata`.
+    pub fn get_as_you_type_formatter(&self, region_code: impl AsRef<str>) -> AsYouTypeFormatter {
+        AsYouTypeFormatter::new(self.util_internal.load_full(), region_code.as_ref())
     }
 
-    /// Formats a `PhoneNumber` for dialing from a mobile device.
+    /// Creates a `ShortNumberInfo` for checking short numbers, such as emergency
+    /// numbers and short codes, e.g. whether "112" is an emergency number in
+    /// Germany or what dialing a short code is expected to cost.
     ///
-    /// # Parameters
+    /// # Returns
     ///
-    /// * `phone_number`: The `PhoneNumber` to format.
-    /// * `region_calling_from`: The two-letter region code (ISO 3166-1) where the user is.
-    /// * `with_formatting`: If `true`, the number is formatted with punctuation; otherwise, only digits are returned.
+    /// A new `ShortNumberInfo` holding the metadata snapshot loaded at the time of the call.
+    /// A later `reload_metadata` is not seen by it; get a new one after reloading. The short
+    /// number metadata itself is always the bundled one, see `ShortNumberInfo`.
+    pub fn get_short_number_info(&self) -> ShortNumberInfo {
+        ShortNumberInfo::new(self.util_internal.load_full())
+    }
+
+    /// Creates a `PhoneNumberOfflineGeocoder` for describing where a number is
+    /// from, e.g. "Mountain View, CA" for +1 650-253-0000.
     ///
     /// # Returns
     ///
-    /// A `Cow<'a, str>` with the dialable number.
-    ///
-    /// # Panics
+    /// A new `PhoneNumberOfflineGeocoder` holding the metadata snapshot loaded at the time
+    /// of the call. A later `reload_metadata` is not seen by it.
     ///
-    /// Panics if formatting fails due to a library bug.
-    pub fn format_number_for_mobile_dialing<'a>(
-        &self,
-        phone_number: &'a PhoneNumber,
-        region_calling_from: impl AsRef<str>,
-        with_formatting: bool,
-    ) -> Cow<'a, str> {
-        self.util_internal
-            .format_number_for_mobile_dialing(phone_number, region_calling_from.as_ref(), with_formatting)
-            .expect("Formatting failed; this indicates a library bug.")
+    /// Available with the `geocoder` feature.
+    #[cfg(feature = "geocoder")]
+    pub fn get_geocoder(&self) -> PhoneNumberOfflineGeocoder {
+        PhoneNumberOfflineGeocoder::new(self.util_internal.load_full())
     }
-    
-    /// Formats a `PhoneNumber` for out-of-country calling.
-    ///
-    /// # Parameters
-    ///
-    /// * `phone_number`: The `PhoneNumber` to format.
-    /// * `region_calling_from`: The two-letter region code (ISO 3166-1) of the calling location.
+
+    /// Creates a `PhoneNumberToCarrierMapper` for looking up the carrier a mobile
+    /// number was allocated to, e.g. "Vodafone" for +44 7900 123456.
     ///
     /// # Returns
     ///
-    /// A `Cow<'a, str>` representing the number formatted for international dialing.
-    ///
-    /// # Panics
+    /// A new `PhoneNumberToCarrierMapper` holding the metadata snapshot loaded at the time
+    /// of the call. A later `reload_metadata` is not seen by it.
     ///
-    /// Panics on invalid metadata, indicating a library bug.
-    pub fn format_out_of_country_calling_number<'a>(
-        &self, phone_number: &'a PhoneNumber, region_calling_from: impl AsRef<str>
-    ) -> Cow<'a, str> {
-        self.util_internal
-            .format_out_of_country_calling_number(phone_number, region_calling_from.as_ref())
-            // This should not never happen
-            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    /// Available with the `carrier` feature.
+    #[cfg(feature = "carrier")]
+    pub fn get_carrier_mapper(&self) -> PhoneNumberToCarrierMapper {
+        PhoneNumberToCarrierMapper::new(self.util_internal.load_full())
     }
 
-    /// Formats a `PhoneNumber` for out-of-country calling while preserving any alphabetic characters.
-    ///
-    /// # Parameters
-    ///
-    /// * `phone_number`: The `PhoneNumber` to format.
-    /// * `region_calling_from`: The two-letter region code (ISO 3166-1) of the calling location.
+    /// Creates a `PhoneNumberToTimeZonesMapper` for looking up the time zones of a
+    /// number, e.g. "America/Los_Angeles" for +1 650-253-0000.
     ///
     /// # Returns
     ///
-    /// A `Cow<'a, str>` with the formatted number.
-    ///
-    /// # Panics
+    /// A new `PhoneNumberToTimeZonesMapper` holding the metadata snapshot loaded at the time
+    /// of the call. A later `reload_metadata` is not seen by it.
     ///
-    /// Panics on invalid metadata, indicating a library bug.
-    pub fn format_out_of_country_keeping_alpha_chars<'a>(
-        &self,
-        phone_number: &'a PhoneNumber,
-        region_calling_from: impl AsRef<str>,
-    ) -> Cow<'a, str> {
-        self.util_internal
-            .format_out_of_country_keeping_alpha_chars(phone_number, region_calling_from.as_ref())
-            .expect("Formatting failed; this indicates a library bug.")
+    /// Available with the `timezones` feature.
+    #[cfg(feature = "timezones")]
+    pub fn get_time_zones_mapper(&self) -> PhoneNumberToTimeZonesMapper {
+        PhoneNumberToTimeZonesMapper::new(self.util_internal.load_full())
     }
 
-    /// Retrieves the country calling code for a given region.
+    /// Finds the phone numbers in a text, accepting only valid numbers.
+    ///
+    /// This is `find_numbers_with_leniency` with `Leniency::Valid` and no limit on the
+    /// number of tries. Sequences that look like dates, timestamps or page ranges are
+    /// skipped, as are numbers glued to Latin letters, such as "abc8005001234".
     ///
     /// # Parameters
     ///
-    /// * `region_code`: The two-letter region code (ISO 3166-1).
+    /// * `text`: The text to search for phone numbers.
+    /// * `region`: The region to assume for numbers written in national format. With an
+    ///   unknown region, only numbers starting with "+" are found.
     ///
     /// # Returns
     ///
-    /// An `Option<i32>` containing the country code, or `None` if the region code is invalid.
-    pub fn get_country_code_for_region(&self, region_code: impl AsRef<str>) -> Option<i32> {
-        self.util_internal
-            .get_country_code_for_region(region_code.as_ref())
+    /// An iterator over the `PhoneNumberMatch`es, in the order they appear in the text.
+    pub fn find_numbers<'t>(&self, text: &'t str, region: impl AsRef<str>) -> PhoneNumberMatcher<'t> {
+        self.find_numbers_with_leniency(text, region, Leniency::Valid, u64::MAX)
     }
 
-    /// Gets a valid example `PhoneNumber` for a specific region.
+    /// Finds the phone numbers in a text, checking each candidate with the given
+    /// leniency.
+    ///
+    /// Unlike upstream, no alternate formats are known for grouping checks, so with
+    /// `Leniency::StrictGrouping` and `Leniency::ExactGrouping` only the grouping of
+    /// the standard formats is accepted.
     ///
     /// # Parameters
     ///
-    /// * `region_code`: The two-letter region code (ISO 3166-1).
+    /// * `text`: The text to search for phone numbers.
+    /// * `region`: The region to assume for numbers written in national format.
+    /// * `leniency`: How strictly candidates are checked before they are returned.
+    /// * `max_tries`: The maximum number of candidates to try. Once reached, the
+    ///   iterator stops, which bounds the time spent on long texts.
     ///
     /// # Returns
     ///
-    /// A `Result` containing a valid `PhoneNumber` on success, or a `GetExampleNumberError` on failure.
-    pub fn get_example_number(&self, region_code: impl AsRef<str>) -> Result<PhoneNumber, GetExampleNumberError> {
-        self.util_internal.get_example_number(region_code.as_ref())
-            .map_err(|err| err.into_public())
+    /// An iterator over the `PhoneNumberMatch`es, in the order they appear in the text.
+    /// It owns the metadata snapshot loaded at the time of the call, so a `reload_metadata`
+    /// while iterating doesn't change the matches found.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the underlying metadata contains an invalid regular expression,
+    /// which indicates a bug in the library.
+    pub fn find_numbers_with_leniency<'t>(
+        &self,
+        text: &'t str,
+        region: impl AsRef<str>,
+        leniency: Leniency,
+        max_tries: u64,
+    ) -> PhoneNumberMatcher<'t> {
+        PhoneNumberMatcher::new(self.util_internal.load_full(), text, region.as_ref(), leniency, max_tries)
     }
 
-    /// Gets a valid example `PhoneNumber` for a specific number type.
+    /// Checks if a `PhoneNumber` can be dialed internationally.
     ///
     /// # Parameters
     ///
-    /// * `number_type`: The desired `PhoneNumberType` (e.g., MOBILE, TOLL_FREE).
+    /// * `phone_number`: A reference to the `PhoneNumber` object to be checked.
     ///
     /// # Returns
     ///
-    /// A `Result` containing a `PhoneNumber` on success, or `GetExampleNumberError` if no example exists.
-    pub fn get_example_number_for_type(
-        &self,
-        number_type: PhoneNumberType,
-    ) -> Result<PhoneNumber, GetExampleNumberError> {
-        self.util_internal.get_example_number_for_type(number_type)
-            .map_err(|err| err.into_public())
+    /// `true` if the number can be dialed from another country, `false` otherwise.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the underlying metadata contains an invalid regular expression,
+    /// which indicates a critical library bug.
+    pub fn can_be_internationally_dialled(&self, phone_number: &PhoneNumber) -> bool {
+        self.util_internal()
+            .can_be_internationally_dialled(phone_number)
+            // This should not never happen
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
     }
 
-    /// Gets an invalid but plausible example `PhoneNumber` for a specific region.
+    /// Converts all alpha characters in a phone number string to their corresponding digits.
+    ///
+    /// For example, an input of "1-800-FLOWERS" will be converted to "1-800-3569377".
     ///
+    
     /// # Parameters
     ///
-    /// * `region_code`: The two-letter region code (ISO 3166-1).
+    /// * `number`: A string slice or `String` representing the phone number.
     ///
     /// # Returns
     ///
-    /// A `Result` containing an invalid `PhoneNumber` on success, or a `GetExampleNumberError` on failure.
-    pub fn get_invalid_example_number(&self, region_code: impl AsRef<str>) -> Result<PhoneNumber, GetExampleNumberError> {
-        self.util_internal.get_invalid_example_number(region_code.as_ref())
-            .map_err(|err| err.into_public())
+    /// A `String` containing the phone number with all alphabetic characters converted to digits.
+    pub fn convert_alpha_characters_in_number<'a>(&self, number: impl AsRef<str>) -> String {
+        self.util_internal().convert_alpha_characters_in_number(number.as_ref())
     }
 
-    /// Gets the length of the geographical area code from a `PhoneNumber`.
+    /// Builds a vanity (alpha) version of a region's toll-free example number, for
+    /// demonstrating `convert_alpha_characters_in_number`.
+    ///
+    /// The metadata has no vanity examples, so the digits following the first group
+    /// of the nationally formatted example are replaced by keypad letters, e.g.
+    /// "(800) 234-5678" becomes "(800) ADG-JMPT". Digits without letters (0 and 1)
+    /// are kept. Converting the result back yields the formatted example number.
     ///
     /// # Parameters
     ///
-    /// * `phone_number`: The `PhoneNumber` to examine.
+    /// * `region`: The two-letter region code (ISO 3166-1).
     ///
     /// # Returns
     ///
-    /// The length of the area code, or `0` if it cannot be determined.
+    /// The vanity number, or `None` if the region has no toll-free example number
+    /// or none of its digits map to letters.
     ///
     /// # Panics
     ///
     /// Panics on invalid metadata, indicating a library bug.
-    pub fn get_length_of_geographical_area_code(&self, phone_number: &PhoneNumber) -> usize {
-        self.util_internal
-            .get_length_of_geographical_area_code(phone_number)
+    pub fn synthesize_alpha_example(&self, region: impl AsRef<str>) -> Option<String> {
+        self.util_internal()
+            .synthesize_alpha_example(region.as_ref())
             .expect("A valid regex is expected in metadata; this indicates a library bug.")
     }
 
-    /// Gets the length of the national destination code from a `PhoneNumber`.
+    /// Strips all characters which can't be dialled from a phone number string.
+    ///
+    /// Only ASCII digits and the `+`, `*` and `#` symbols are kept, so that service
+    /// numbers such as "*#06#" stay intact. For example, "03*4-56&+1a#234" becomes
+    /// "03*456+1#234". Letters are removed rather than converted to digits.
     ///
     /// # Parameters
     ///
-    /// * `phone_number`: The `PhoneNumber` to examine.
+    /// * `number`: A string slice or `String` representing the phone number.
     ///
     /// # Returns
     ///
-    /// The length of the national destination code.
-    ///
-    /// # Panics
-    ///
-    /// Panics on invalid metadata, indicating a library bug.
-    pub fn get_length_of_national_destination_code(&self, phone_number: &PhoneNumber) -> usize {
-        self.util_internal
-            .get_length_of_national_destination_code(phone_number)
-            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    /// A `String` containing only the diallable characters of the number.
+    pub fn normalize_diallable_chars_only(&self, number: impl AsRef<str>) -> String {
+        self.util_internal().normalize_diallable_chars_only(number.as_ref())
     }
 
-    /// Gets the National Significant Number (NSN) from a `PhoneNumber`.
+    /// Replaces the dialing wait symbol `~` in a formatted number with a pause token.
     ///
-    /// The NSN is the part of the number that follows the country code.
+    /// Some regions use a preferred international prefix with a wait, e.g. numbers
+    /// dialled from Uzbekistan are formatted as "8~10 39 02 3661 8300", where `~` means
+    /// "wait for a dial tone". This helper turns it into "8,10 39 02 3661 8300" for dialers
+    /// that use "," as a pause, or drops the waits entirely for display when `pause` is empty.
     ///
     /// # Parameters
     ///
-    /// * `phone_number`: The `PhoneNumber` from which to extract the NSN.
+    /// * `formatted_number`: A number previously formatted by this library.
+    /// * `pause`: The token to put in place of each `~`.
     ///
     /// # Returns
     ///
-    /// A `String` containing the NSN.
-    pub fn get_national_significant_number<'a>(&self, phone_number: &'a PhoneNumber) -> String {
-        self.util_internal.get_national_significant_number(phone_number)
+    /// A `String` with all waits replaced.
+    pub fn expand_dialing_waits(&self, formatted_number: impl AsRef<str>, pause: impl AsRef<str>) -> String {
+        self.util_internal().expand_dialing_waits(formatted_number.as_ref(), pause.as_ref())
     }
 
-    /// Determines the `PhoneNumberType` of a given `PhoneNumber`.
+    /// Formats a `PhoneNumber` into a standardized format.
     ///
     /// # Parameters
     ///
-    /// * `phone_number`: The `PhoneNumber` to be categorized.
+    /// * `phone_number`: The `PhoneNumber` to be formatted.
+    /// * `number_format`: The `PhoneNumberFormat` to be applied (e.g., E164, INTERNATIONAL, NATIONAL).
     ///
     /// # Returns
     ///
-    /// The `PhoneNumberType` (e.g., MOBILE, FIXED_LINE, UNKNOWN).
+    /// A `Cow<'a, str>` which is either a borrowed reference to a pre-formatted string or a
+    /// newly allocated `String` with the formatted number.
     ///
     /// # Panics
     ///
-    /// Panics on invalid metadata, indicating a library bug.
-    pub fn get_number_type(&self, phone_number: &PhoneNumber) -> PhoneNumberType {
-        self
-            .util_internal
-            .get_number_type(phone_number)
+    /// This method panics if the underlying metadata contains an invalid regular expression,
+    /// indicating a library bug.
+    pub fn format<'a>(&self, phone_number: &'a PhoneNumber, number_format: PhoneNumberFormat) -> Cow<'a, str> {
+        self.util_internal()
+            .format(phone_number, number_format)
             // This should not never happen
-            .expect("A valid regex and region is expected in metadata; this indicates a library bug.")
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
     }
 
-    /// Gets the primary region code for a given country calling code.
+    /// Formats a `PhoneNumber` like `format` and reports where the extension is in
+    /// the result, e.g. for styling it differently.
     ///
-    /// Note: Some country codes are shared by multiple regions (e.g., +1 for USA, Canada).
-    /// This returns the main region for that code (e.g., "US" for +1).
+    /// The span covers the extension together with its label, such as " ext. 123" in
+    /// the national format or ";ext=123" in RFC3966.
     ///
     /// # Parameters
     ///
-    /// * `country_code`: The country calling code.
+    /// * `phone_number`: The `PhoneNumber` to be formatted.
+    /// * `number_format`: The `PhoneNumberFormat` to be applied.
     ///
     /// # Returns
     ///
-    /// A string slice with the corresponding two-letter region code. Returns "ZZ" for invalid codes.
-    pub fn get_region_code_for_country_code(&self, country_code: i32) -> &str {
-        self.util_internal.get_region_code_for_country_code(country_code)
+    /// The formatted number and the byte range of the extension within it, or `None`
+    /// if no extension was written. E164 never contains the extension.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the underlying metadata contains an invalid regular expression,
+    /// indicating a library bug.
+    pub fn format_with_extension_span(
+        &self,
+        phone_number: &PhoneNumber,
+        number_format: PhoneNumberFormat,
+    ) -> (String, Option<Range<usize>>) {
+        self.util_internal()
+            .format_with_extension_span(phone_number, number_format)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
     }
 
-    /// Gets the region code for a `PhoneNumber`.
+    /// Formats a `PhoneNumber` in national format, padded so that the numbers of a
+    /// region line up in a column of a monospaced table.
+    ///
+    /// One `pad` character is appended for every digit the number is shorter than the
+    /// longest possible number of its region, according to the metadata. For example,
+    /// with a maximum length of 10 digits, a 9 digit number gets one pad character. If
+    /// the number has an extension, the padding is inserted before it.
     ///
     /// # Parameters
     ///
-    /// * `phone_number`: The `PhoneNumber` to identify.
+    /// * `phone_number`: The `PhoneNumber` to be formatted.
+    /// * `pad`: The character used for padding, e.g. `' '`.
     ///
     /// # Returns
     ///
-    /// A string slice with the two-letter region code.
+    /// The padded nationally formatted number. Numbers of unknown country calling codes
+    /// are not padded.
     ///
     /// # Panics
     ///
-    /// Panics on invalid metadata, indicating a library bug.
-    pub fn get_region_code_for_number(&self, phone_number: &PhoneNumber) -> &str {
-        self
-            .util_internal
-            .get_region_code_for_number(phone_number)
-            // This should not never happen
-            .expect("A valid regex is expected in metadata; this indicates a library bug.")            
+    /// This method panics if the underlying metadata contains an invalid regular expression,
+    /// indicating a library bug.
+    pub fn format_national_padded(&self, phone_number: &PhoneNumber, pad: char) -> String {
+        self.util_internal()
+            .format_national_padded(phone_number, pad)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
     }
-    
-    /// Gets all region codes associated with a country calling code.
+
+    /// Formats a `PhoneNumber` like `format` and wraps the result so that it survives
+    /// spreadsheet import of a CSV file without losing leading zeros or `+` signs.
+    ///
+    /// The result still has to be quoted by the CSV writer like any other field, since
+    /// `CsvSafeStyle::FormulaQuote` output contains quotes and formatted numbers may
+    /// contain commas.
     ///
     /// # Parameters
     ///
-    /// * `country_code`: The country calling code.
+    /// * `phone_number`: The `PhoneNumber` to be formatted.
+    /// * `number_format`: The `PhoneNumberFormat` to be applied.
+    /// * `style`: How the formatted number is protected, see `CsvSafeStyle`.
     ///
     /// # Returns
     ///
-    /// An `Option` containing an iterator over all associated region codes, or `None` if the
+    /// The wrapped formatted number, e.g. `="044 668 1800"` or `"\t044 668 1800"`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the underlying metadata contains an invalid regular expression,
+    /// indicating a library bug.
+    pub fn format_csv_safe(
+        &self,
+        phone_number: &PhoneNumber,
+        number_format: PhoneNumberFormat,
+        style: CsvSafeStyle,
+    ) -> String {
+        let formatted = self.format(phone_number, number_format);
+        match style {
+            // Quotes inside a formula string literal are escaped by doubling them.
+            CsvSafeStyle::FormulaQuote => format!("=\"{}\"", formatted.replace('"', "\"\"")),
+            CsvSafeStyle::LeadingTab => format!("\t{}", formatted),
+        }
+    }
+
+    /// Formats a `PhoneNumber` like `format`, but returns the extension separately
+    /// instead of appending it.
+    ///
+    /// This suits systems which store the number and the extension in distinct fields.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to be formatted.
+    /// * `number_format`: The `PhoneNumberFormat` to be applied to the main number.
+    ///
+    /// # Returns
+    ///
+    /// The formatted number without the extension, and the extension digits, or `None`
+    /// if the number has no extension. The extension is returned for every format,
+    /// including E164, which never contains it.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the underlying metadata contains an invalid regular expression,
+    /// indicating a library bug.
+    pub fn format_split_extension(
+        &self,
+        phone_number: &PhoneNumber,
+        number_format: PhoneNumberFormat,
+    ) -> (String, Option<String>) {
+        self.util_internal()
+            .format_split_extension(phone_number, number_format)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Formats a `PhoneNumber` like `format` and returns the `NumberFormat` rule of the
+    /// metadata that was applied.
+    ///
+    /// This is mostly useful for debugging metadata, e.g. to find out why a number was
+    /// grouped in a particular way. The returned rule borrows the metadata in use and stays
+    /// valid after `reload_metadata`: the metadata snapshots this method was called with
+    /// are only freed when the `PhoneNumberUtil` is dropped.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to be formatted.
+    /// * `number_format`: The `PhoneNumberFormat` to be applied.
+    ///
+    /// # Returns
+    ///
+    /// The formatted number and a reference to the applied `NumberFormat` of the metadata,
+    /// or `None` if the national number was written as raw digits, e.g. for E164 or when
+    /// no rule matched.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the underlying metadata contains an invalid regular expression,
+    /// indicating a library bug.
+    pub fn format_with_format_info(
+        &self,
+        phone_number: &PhoneNumber,
+        number_format: PhoneNumberFormat,
+    ) -> (String, Option<&NumberFormat>) {
+        self
+            .pinned_util_internal()
+            .format_with_format_info(phone_number, number_format)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Formats a `PhoneNumber`, attempting to preserve original formatting and punctuation.
+    ///
+    /// The number is formatted in the national format of the region it is from.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to be formatted.
+    /// * `region_calling_from`: The two-letter region code (ISO 3166-1) from where the call is being made.
+    ///
+    /// # Returns
+    ///
+    /// A `Cow<'a, str>` containing the formatted number.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if metadata is invalid, which indicates a library bug.
+    pub fn format_in_original_format<'a>(
+        &self, phone_number: &'a PhoneNumber, region_calling_from: impl AsRef<str>
+    ) -> Cow<'a, str> {
+        self.util_internal()
+            .format_in_original_format(phone_number, region_calling_from.as_ref())
+            // This should not never happen
+            .expect("A valid regex and region is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Formats a `PhoneNumber` in national format without the parentheses around the area code.
+    ///
+    /// For example, the GB number formatted nationally as "(020) 8738 9353" becomes
+    /// "020 8738 9353". Spaces and other punctuation are kept, and numbers whose national
+    /// format doesn't use parentheses are returned unchanged.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to be formatted.
+    ///
+    /// # Returns
+    ///
+    /// A `Cow<'a, str>` containing the formatted number.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn format_national_no_parens<'a>(&self, phone_number: &'a PhoneNumber) -> Cow<'a, str> {
+        self.util_internal()
+            .format_national_no_parens(phone_number)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Formats a `PhoneNumber` with non-breaking spaces (U+00A0) between the digit groups.
+    ///
+    /// This is useful for HTML rendering, where a number shouldn't wrap in the middle.
+    /// For example, "+44 20 8765 4321" is returned with every space replaced by U+00A0.
+    /// Only the grouping spaces are replaced; the extension suffix, such as " ext. 123",
+    /// keeps its ordinary spaces.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to be formatted.
+    /// * `number_format`: The `PhoneNumberFormat` to be applied.
+    ///
+    /// # Returns
+    ///
+    /// A `Cow<'a, str>` containing the formatted number.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn format_nbsp<'a>(
+        &self,
+        phone_number: &'a PhoneNumber,
+        number_format: PhoneNumberFormat,
+    ) -> Cow<'a, str> {
+        self.util_internal()
+            .format_nbsp(phone_number, number_format)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Formats a `PhoneNumber` as E164 digits without the leading plus sign.
+    ///
+    /// For example, the US number "+1 650-253-0000" becomes "16502530000". Italian leading
+    /// zeros are kept, so "+39 02 3661 8300" becomes "390236618300". Extensions are
+    /// not included, as in E164 format.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to be formatted.
+    ///
+    /// # Returns
+    ///
+    /// A `String` with the country calling code followed by the national significant number.
+    pub fn format_e164_digits(&self, phone_number: &PhoneNumber) -> String {
+        self.util_internal().format_e164_digits(phone_number)
+    }
+
+    /// Formats a `PhoneNumber` for screen readers and text-to-speech engines.
+    ///
+    /// The country calling code is kept as a unit, while every digit of the national
+    /// significant number is separated by a space, so that it is read out digit by
+    /// digit. For example, the US number "+1 650-253-0000" becomes
+    /// "+1 6 5 0 2 5 3 0 0 0 0". An extension is appended as " ext. 1 2 3".
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to be formatted.
+    ///
+    /// # Returns
+    ///
+    /// A `String` containing the space-separated digits.
+    pub fn format_for_screen_reader(&self, phone_number: &PhoneNumber) -> String {
+        self.util_internal().format_for_screen_reader(phone_number)
+    }
+
+    /// Formats a `PhoneNumber` in the E164, international, national and RFC3966 formats
+    /// in a single call.
+    ///
+    /// The result is the same as calling `format` once for each format, but the
+    /// metadata lookup and the choice of the formatting patterns are shared, which makes
+    /// it cheaper when several formats of the same number are displayed.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to format.
+    ///
+    /// # Returns
+    ///
+    /// The `CommonFormats` of the number.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn format_common(&self, phone_number: &PhoneNumber) -> CommonFormats {
+        self.util_internal()
+            .format_common(phone_number)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Splits a `PhoneNumber` into labeled parts for structured display.
+    ///
+    /// The result combines the geographical area code, the national destination code
+    /// and the remaining subscriber number, e.g. `+44 20 7031 3000` gives country code
+    /// `"44"`, area code and NDC `"20"` and subscriber `"70313000"`. Mobile and
+    /// non-geographical numbers have an NDC but no area code.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to split.
+    ///
+    /// # Returns
+    ///
+    /// The `NumberParts` of the number.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn format_parts(&self, phone_number: &PhoneNumber) -> NumberParts {
+        self.util_internal()
+            .format_parts(phone_number)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Formats a national number with a specified carrier code.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to format.
+    /// * `carrier_code`: The carrier code to prepend to the number.
+    ///
+    /// # Returns
+    ///
+    /// A `String` containing the formatted number.
+    ///
+    /// # Panics
+    ///
+    /// Panics if metadata is invalid, indicating a library bug.
+    pub fn format_national_number_with_carrier_code<'a>(
+        &self,
+        phone_number: &'a PhoneNumber,
+        carrier_code: impl AsRef<str>,
+    ) -> String {
+        self.util_internal()
+            .format_national_number_with_carrier_code(phone_number, carrier_code.as_ref())
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Formats a number in national format, dialing a carrier preselection code in place
+    /// of the national prefix.
+    ///
+    /// This differs from `format_national_number_with_carrier_code`, which keeps the
+    /// national prefix and inserts the carrier code after it according to the domestic
+    /// carrier code formatting rule of the region. Here the national prefix itself is
+    /// replaced wherever the national prefix formatting rule puts it, e.g. an Argentine
+    /// number formatted as "01234 12-5678" gives "1234 12-5678" with preselect "", and a
+    /// British one formatted as "(020) 7031 3000" gives "(1670 20) 7031 3000" with
+    /// preselect "1670 ". Numbers whose national format doesn't include the national
+    /// prefix are formatted as with `format` in national format.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to format.
+    /// * `preselect`: The carrier preselection code to use instead of the national prefix.
+    ///
+    /// # Returns
+    ///
+    /// A `Cow<str>` containing the formatted number.
+    ///
+    /// # Panics
+    ///
+    /// Panics if metadata is invalid, indicating a library bug.
+    pub fn format_with_carrier_preselect<'a>(
+        &self,
+        phone_number: &'a PhoneNumber,
+        preselect: impl AsRef<str>,
+    ) -> Cow<'a, str> {
+        self.util_internal()
+            .format_with_carrier_preselect(phone_number, preselect.as_ref())
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Formats a `PhoneNumber` for dialing from a mobile device.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to format.
+    /// * `region_calling_from`: The two-letter region code (ISO 3166-1) where the user is.
+    /// * `with_formatting`: If `true`, the number is formatted with punctuation; otherwise, only digits are returned.
+    ///
+    /// # Returns
+    ///
+    /// A `Cow<'a, str>` with the dialable number.
+    ///
+    /// # Panics
+    ///
+    /// Panics if formatting fails due to a library bug.
+    pub fn format_number_for_mobile_dialing<'a>(
+        &self,
+        phone_number: &'a PhoneNumber,
+        region_calling_from: impl AsRef<str>,
+        with_formatting: bool,
+    ) -> Cow<'a, str> {
+        self.util_internal()
+            .format_number_for_mobile_dialing(phone_number, region_calling_from.as_ref(), with_formatting)
+            .expect("Formatting failed; this indicates a library bug.")
+    }
+    
+    /// Formats a `PhoneNumber` for out-of-country calling.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to format.
+    /// * `region_calling_from`: The two-letter region code (ISO 3166-1) of the calling location.
+    ///
+    /// # Returns
+    ///
+    /// A `Cow<'a, str>` representing the number formatted for international dialing.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn format_out_of_country_calling_number<'a>(
+        &self, phone_number: &'a PhoneNumber, region_calling_from: impl AsRef<str>
+    ) -> Cow<'a, str> {
+        self.util_internal()
+            .format_out_of_country_calling_number(phone_number, region_calling_from.as_ref())
+            // This should not never happen
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Formats a `PhoneNumber` as the exact characters to dial from the given region.
+    ///
+    /// This is `format_out_of_country_calling_number` stripped down to what a dialer needs:
+    /// the international prefix of the calling region replaces the `+`, and all spaces,
+    /// punctuation and dialing waits are removed. For example, the GB number
+    /// "+44 20 8738 9353" dialled from the US becomes "011442087389353", and from GB
+    /// "02087389353". When the calling region has no single international prefix, the
+    /// result keeps the leading `+`, e.g. "+442087389353". Extensions are not included,
+    /// as they are dialled after the call is connected.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to format.
+    /// * `region_calling_from`: The two-letter region code (ISO 3166-1) of the calling location.
+    ///
+    /// # Returns
+    ///
+    /// A `Cow<'a, str>` containing only digits and, possibly, a leading `+`.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn format_as_dialed_from<'a>(
+        &self, phone_number: &'a PhoneNumber, region_calling_from: impl AsRef<str>
+    ) -> Cow<'a, str> {
+        self.util_internal()
+            .format_as_dialed_from(phone_number, region_calling_from.as_ref())
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Formats a `PhoneNumber` for out-of-country calling and reports whether it was
+    /// treated as a domestic call.
+    ///
+    /// The formatted number is the same as `format_out_of_country_calling_number` returns.
+    /// The flag is `true` when the number shares the country calling code of the calling
+    /// region and is therefore formatted without an international prefix: in national
+    /// format (e.g. a DE number called from DE), or with the bare country calling code
+    /// for calls between NANPA regions (e.g. "1 650-253-0000" called from CA).
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to format.
+    /// * `region_calling_from`: The two-letter region code (ISO 3166-1) of the calling location.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of the formatted number and whether the call was treated as domestic.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn format_out_of_country_calling_number_reporting<'a>(
+        &self, phone_number: &'a PhoneNumber, region_calling_from: impl AsRef<str>
+    ) -> (Cow<'a, str>, bool) {
+        self.util_internal()
+            .format_out_of_country_calling_number_reporting(phone_number, region_calling_from.as_ref())
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Formats a `PhoneNumber` for out-of-country calling while preserving any alphabetic characters.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to format.
+    /// * `region_calling_from`: The two-letter region code (ISO 3166-1) of the calling location.
+    ///
+    /// # Returns
+    ///
+    /// A `Cow<'a, str>` with the formatted number.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn format_out_of_country_keeping_alpha_chars<'a>(
+        &self,
+        phone_number: &'a PhoneNumber,
+        region_calling_from: impl AsRef<str>,
+    ) -> Cow<'a, str> {
+        self.util_internal()
+            .format_out_of_country_keeping_alpha_chars(phone_number, region_calling_from.as_ref())
+            .expect("Formatting failed; this indicates a library bug.")
+    }
+
+    /// Retrieves the country calling code for a given region.
+    ///
+    /// # Parameters
+    ///
+    /// * `region_code`: The two-letter region code (ISO 3166-1).
+    ///
+    /// # Returns
+    ///
+    /// An `Option<i32>` containing the country code, or `None` if the region code is invalid.
+    pub fn get_country_code_for_region(&self, region_code: impl AsRef<str>) -> Option<i32> {
+        self.util_internal()
+            .get_country_code_for_region(region_code.as_ref())
+    }
+
+    /// Retrieves the country calling codes for many regions at once.
+    ///
+    /// # Parameters
+    ///
+    /// * `region_codes`: The two-letter region codes (ISO 3166-1).
+    ///
+    /// # Returns
+    ///
+    /// A `Vec` with the country code of each region in the order given, holding `None`
+    /// in the position of each invalid region code.
+    pub fn get_country_codes_for_regions(&self, region_codes: &[impl AsRef<str>]) -> Vec<Option<i32>> {
+        self.util_internal().get_country_codes_for_regions(region_codes)
+    }
+
+    /// Gets the number types of a region which have lengths that can only be dialled
+    /// locally, i.e. from within the same area, such as German fixed-line numbers
+    /// dialled without their area code.
+    ///
+    /// # Parameters
+    ///
+    /// * `region_code`: The two-letter region code (ISO 3166-1).
+    ///
+    /// # Returns
+    ///
+    /// The `PhoneNumberType`s supported by the region which have local-only lengths, in
+    /// their declaration order. The list is empty for unknown regions.
+    pub fn get_types_with_local_only_lengths_for_region(
+        &self,
+        region_code: impl AsRef<str>,
+    ) -> Vec<PhoneNumberType> {
+        self.util_internal()
+            .get_types_with_local_only_lengths_for_region(region_code.as_ref())
+    }
+
+    /// Gets a valid example `PhoneNumber` for a specific region.
+    ///
+    /// # Parameters
+    ///
+    /// * `region_code`: The two-letter region code (ISO 3166-1).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a valid `PhoneNumber` on success, or a `GetExampleNumberError` on failure.
+    pub fn get_example_number(&self, region_code: impl AsRef<str>) -> Result<PhoneNumber, GetExampleNumberError> {
+        self.util_internal().get_example_number(region_code.as_ref())
+            .map_err(|err| err.into_public())
+    }
+
+    /// Gets a valid example `PhoneNumber` for a specific number type.
+    ///
+    /// # Parameters
+    ///
+    /// * `number_type`: The desired `PhoneNumberType` (e.g., MOBILE, TOLL_FREE).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a `PhoneNumber` on success, or `GetExampleNumberError` if no example exists.
+    pub fn get_example_number_for_type(
+        &self,
+        number_type: PhoneNumberType,
+    ) -> Result<PhoneNumber, GetExampleNumberError> {
+        self.util_internal().get_example_number_for_type(number_type)
+            .map_err(|err| err.into_public())
+    }
+
+    /// Iterates over valid example `PhoneNumber`s of a region, one per supported number type.
+    ///
+    /// This is handy for building test fixtures that should cover every type a region
+    /// has. Types are yielded in the declaration order of `PhoneNumberType`, and types
+    /// for which the metadata has no example number are skipped.
+    ///
+    /// # Parameters
+    ///
+    /// * `region_code`: The two-letter region code (ISO 3166-1).
+    ///
+    /// # Returns
+    ///
+    /// An iterator over pairs of a `PhoneNumberType` and its example number. It is empty
+    /// if the region code is unknown.
+    pub fn example_numbers_for_region(
+        &self,
+        region_code: impl AsRef<str>,
+    ) -> impl Iterator<Item = (PhoneNumberType, PhoneNumber)> + '_ {
+        self.util_internal()
+            .example_numbers_for_region(region_code.as_ref())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Iterates over valid example `PhoneNumber`s of a non-geographical entity, one per
+    /// supported number type.
+    ///
+    /// This is the counterpart of `example_numbers_for_region` for global network calling
+    /// codes such as 800 (International Toll Free Service) or 979 (International Premium
+    /// Rate Service). Types are yielded in the declaration order of `PhoneNumberType`, and
+    /// types for which the metadata has no example number are skipped.
+    ///
+    /// # Parameters
+    ///
+    /// * `country_calling_code`: The calling code of the non-geographical entity.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over pairs of a `PhoneNumberType` and its example number, or `None` if
+    /// the calling code doesn't belong to a known non-geographical entity.
+    pub fn get_example_numbers_for_non_geo_entity(
+        &self,
+        country_calling_code: i32,
+    ) -> Option<impl Iterator<Item = (PhoneNumberType, PhoneNumber)> + '_> {
+        self.util_internal()
+            .get_example_numbers_for_non_geo_entity(country_calling_code)
+            .map(|example_numbers| example_numbers.collect::<Vec<_>>().into_iter())
+    }
+
+    /// Gets a second valid example `PhoneNumber` for a specific region and number type.
+    ///
+    /// The returned number is guaranteed to differ from the one returned by
+    /// `get_example_number_for_type_and_region_code`, while having the same length and
+    /// number type. This is handy for test fixtures that need two distinct valid numbers,
+    /// e.g. to test deduplication.
+    ///
+    /// # Parameters
+    ///
+    /// * `region_code`: The two-letter region code (ISO 3166-1).
+    /// * `number_type`: The desired `PhoneNumberType`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a `PhoneNumber` on success, or `GetExampleNumberError` if no
+    /// example exists or no second number of the same type could be found near it.
+    pub fn get_second_example_number_for_type_and_region(
+        &self,
+        region_code: impl AsRef<str>,
+        number_type: PhoneNumberType,
+    ) -> Result<PhoneNumber, GetExampleNumberError> {
+        self.util_internal()
+            .get_second_example_number_for_type_and_region(region_code.as_ref(), number_type)
+            .map_err(|err| err.into_public())
+    }
+
+    /// Gets an invalid but plausible example `PhoneNumber` for a specific region.
+    ///
+    /// # Parameters
+    ///
+    /// * `region_code`: The two-letter region code (ISO 3166-1).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing an invalid `PhoneNumber` on success, or a `GetExampleNumberError` on failure.
+    pub fn get_invalid_example_number(&self, region_code: impl AsRef<str>) -> Result<PhoneNumber, GetExampleNumberError> {
+        self.util_internal().get_invalid_example_number(region_code.as_ref())
+            .map_err(|err| err.into_public())
+    }
+
+    /// Gets the length of the geographical area code from a `PhoneNumber`.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to examine.
+    ///
+    /// # Returns
+    ///
+    /// The length of the area code, or `0` if it cannot be determined.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn get_length_of_geographical_area_code(&self, phone_number: &PhoneNumber) -> usize {
+        self.util_internal()
+            .get_length_of_geographical_area_code(phone_number)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Parses `number` and gets the length of its geographical area code.
+    ///
+    /// This is a shortcut for `parse` followed by `get_length_of_geographical_area_code`.
+    ///
+    /// # Parameters
+    ///
+    /// * `number`: The phone number string.
+    /// * `region`: The region to assume if the number is not in international format.
+    ///
+    /// # Returns
+    ///
+    /// The length of the area code (`0` if it cannot be determined), or the
+    /// `ParseError` if the string couldn't be parsed.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn get_length_of_geographical_area_code_for_string(
+        &self,
+        number: impl AsRef<str>,
+        region: impl AsRef<str>,
+    ) -> Result<usize, ParseError> {
+        let util = self.util_internal();
+        let phone_number = util
+            .parse(number.as_ref(), region.as_ref())
+            .map_err(|err| err.into_public())?;
+        Ok(util
+            .get_length_of_geographical_area_code(&phone_number)
+            .expect("A valid regex is expected in metadata; this indicates a library bug."))
+    }
+
+    /// Gets the length of the national destination code from a `PhoneNumber`.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to examine.
+    ///
+    /// # Returns
+    ///
+    /// The length of the national destination code.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn get_length_of_national_destination_code(&self, phone_number: &PhoneNumber) -> usize {
+        self.util_internal()
+            .get_length_of_national_destination_code(phone_number)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Gets the digits of the national destination code (NDC) from a `PhoneNumber`.
+    ///
+    /// This works for non-geographical entities too, e.g. the NDC of the international
+    /// toll-free number `+800 1234 5678` is `"1234"`.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to examine.
+    ///
+    /// # Returns
+    ///
+    /// A `String` with the NDC digits, or an empty string if the number has no NDC.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn get_national_destination_code(&self, phone_number: &PhoneNumber) -> String {
+        self.util_internal()
+            .get_national_destination_code(phone_number)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Gets the national digits of a `PhoneNumber` without any grouping, e.g. for a
+    /// search index.
+    ///
+    /// This is the same as `get_national_significant_number`, and is guaranteed to:
+    ///
+    /// * contain ASCII digits only, without separators or formatting;
+    /// * include the leading zeros of the number, e.g. "0236618300" for an Italian
+    ///   number with `italian_leading_zero` set;
+    /// * exclude the country calling code, the national prefix and the extension, e.g.
+    ///   "2087389353" for the British number "020 8738 9353".
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to take the digits from.
+    ///
+    /// # Returns
+    ///
+    /// A `String` containing the national digits.
+    pub fn national_digits(&self, phone_number: &PhoneNumber) -> String {
+        self.util_internal().get_national_significant_number(phone_number)
+    }
+
+    /// Gets the National Significant Number (NSN) from a `PhoneNumber`.
+    ///
+    /// The NSN is the part of the number that follows the country code. If the number has
+    /// `italian_leading_zero` set, `number_of_leading_zeros` zeros are prepended. A
+    /// non-positive count adds no zeros, and at most 17 zeros (the maximum NSN length)
+    /// are ever added.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` from which to extract the NSN.
+    ///
+    /// # Returns
+    ///
+    /// A `String` containing the NSN.
+    pub fn get_national_significant_number<'a>(&self, phone_number: &'a PhoneNumber) -> String {
+        self.util_internal().get_national_significant_number(phone_number)
+    }
+
+    /// Packs a `PhoneNumber` into a single `i128`, e.g. for use as a compact index key.
+    ///
+    /// The bit layout is:
+    ///
+    /// | Bits      | Content                              |
+    /// |-----------|--------------------------------------|
+    /// | 0..=63    | national number (`u64`)              |
+    /// | 64..=73   | country calling code (1 to 999)      |
+    /// | 74..=127  | always zero                          |
+    ///
+    /// Packed values are therefore non-negative, and ordering them sorts numbers by
+    /// country calling code first and by national number second. Only these two fields
+    /// are kept; fields like `raw_input` or `country_code_source` are dropped.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to pack.
+    ///
+    /// # Returns
+    ///
+    /// The packed value, or `None` if the number has an extension, has Italian leading
+    /// zeros set, or its country calling code is outside of 1..=999.
+    pub fn pack(&self, phone_number: &PhoneNumber) -> Option<i128> {
+        self.util_internal().pack(phone_number)
+    }
+
+    /// Checks whether a value produced by `pack` is a possible number, without restoring
+    /// a `PhoneNumber` from it.
+    ///
+    /// The result is the same as `is_possible_number(&unpack(packed))`, except that values
+    /// `pack` can't produce (e.g. negative ones) are never possible.
+    ///
+    /// # Parameters
+    ///
+    /// * `packed`: A value returned by `pack`.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the packed number has a possible length for its country calling code.
+    pub fn is_possible_packed(&self, packed: i128) -> bool {
+        self.util_internal().is_possible_packed(packed)
+    }
+
+    /// Restores a `PhoneNumber` from a value produced by `pack`.
+    ///
+    /// # Parameters
+    ///
+    /// * `packed`: A value returned by `pack`.
+    ///
+    /// # Returns
+    ///
+    /// A `PhoneNumber` with only the country calling code and the national number set.
+    pub fn unpack(&self, packed: i128) -> PhoneNumber {
+        self.util_internal().unpack(packed)
+    }
+
+    /// Determines the `PhoneNumberType` of a given `PhoneNumber`.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to be categorized.
+    ///
+    /// # Returns
+    ///
+    /// The `PhoneNumberType` (e.g., MOBILE, FIXED_LINE, UNKNOWN). Numbers whose country
+    /// calling code has no loaded metadata are `Unknown`.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn get_number_type(&self, phone_number: &PhoneNumber) -> PhoneNumberType {
+        self
+            .util_internal()
+            .get_number_type(phone_number)
+            // This should not never happen
+            .expect("A valid regex and region is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Determines the `PhoneNumberType` of a `PhoneNumber` using the metadata of an
+    /// already known region.
+    ///
+    /// Unlike `get_number_type`, the region of the number is not resolved again, which
+    /// saves work when classifying many numbers whose region is known, e.g. from
+    /// `get_region_code_for_number`. The number is checked against the given region only,
+    /// so for a region sharing its country calling code with others (like +1) the result
+    /// is `Unknown` if the number belongs to another of these regions.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to be categorized.
+    /// * `region`: The two-letter region code (ISO 3166-1) of the number, or "001" for non-geographical numbers.
+    ///
+    /// # Returns
+    ///
+    /// The `PhoneNumberType`, or `ValidationError::InvalidCountryCode` if the region is
+    /// unknown or its country calling code differs from the number's.
+    pub fn get_number_type_for_region(
+        &self,
+        phone_number: &PhoneNumber,
+        region: impl AsRef<str>,
+    ) -> Result<PhoneNumberType, ValidationError> {
+        self.util_internal().get_number_type_for_region(phone_number, region.as_ref())
+    }
+
+    /// Determines the `PhoneNumberType` of a given `PhoneNumber` and reports which
+    /// metadata descriptor decided it.
+    ///
+    /// This is mostly useful for debugging custom metadata: e.g. when the returned type is
+    /// `Mobile`, the pattern is the `national_number_pattern` of the region's `mobile`
+    /// descriptor. For `FixedLineOrMobile` it is the fixed-line pattern.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to be categorized.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of the `PhoneNumberType` and the matched national number pattern, which is
+    /// `None` if the type is `Unknown`.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn get_number_type_with_descriptor(&self, phone_number: &PhoneNumber) -> (PhoneNumberType, Option<String>) {
+        self
+            .util_internal()
+            .get_number_type_with_descriptor(phone_number)
+            .expect("A valid regex and region is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Gets the primary region code for a given country calling code.
+    ///
+    /// Note: Some country codes are shared by multiple regions (e.g., +1 for USA, Canada).
+    /// This returns the main region for that code (e.g., "US" for +1).
+    ///
+    /// # Parameters
+    ///
+    /// * `country_code`: The country calling code.
+    ///
+    /// # Returns
+    ///
+    /// A string slice with the corresponding two-letter region code. Returns "ZZ" for invalid codes.
+    pub fn get_region_code_for_country_code(&self, country_code: i32) -> &'static str {
+        self.util_internal().get_region_code_for_country_code(country_code)
+    }
+
+    /// Gets the region code for a `PhoneNumber`.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to identify.
+    ///
+    /// # Returns
+    ///
+    /// A string slice with the two-letter region code.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn get_region_code_for_number(&self, phone_number: &PhoneNumber) -> &'static str {
+        self
+            .util_internal()
+            .get_region_code_for_number(phone_number)
+            // This should not never happen
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    }
+    
+    /// Gets the region code and the `PhoneNumberType` of a `PhoneNumber` at once.
+    ///
+    /// This gives the same results as calling `get_region_code_for_number` and
+    /// `get_number_type`, but matches the number against the metadata patterns only
+    /// once, so it should be preferred when both are needed.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to classify.
+    ///
+    /// # Returns
+    ///
+    /// The two-letter region code and the `PhoneNumberType`. A number which does not
+    /// belong to any region, e.g. because its country calling code is unknown or none of
+    /// the regions sharing it matches, gets the unknown region code "ZZ" and
+    /// `PhoneNumberType::Unknown`, just like `get_region_code_for_number` returns "ZZ".
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn classify(&self, phone_number: &PhoneNumber) -> (&'static str, PhoneNumberType) {
+        self
+            .util_internal()
+            .classify(phone_number)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Gets all region codes associated with a country calling code.
+    ///
+    /// # Parameters
+    ///
+    /// * `country_code`: The country calling code.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing an iterator over all associated region codes, or `None` if the
     /// country code is invalid.
-    pub fn get_region_codes_for_country_code(&self, country_code: i32) -> Option<impl ExactSizeIterator<Item=&str>> {
-        self.util_internal.get_region_codes_for_country_calling_code(country_code)
+    pub fn get_region_codes_for_country_code(&self, country_code: i32) -> Option<impl ExactSizeIterator<Item=&'static str>> {
+        let region_codes = self.util_internal().regions_for_calling_code_slice(country_code)?.clone();
+        Some(iter_shared_region_codes(region_codes))
+    }
+
+    /// Gets all region codes associated with a country calling code in a stable order.
+    ///
+    /// The main region for the code, as returned by `get_region_code_for_country_code`,
+    /// comes first and the other regions follow in alphabetical order, e.g. "US", "AG",
+    /// "AI", ... for +1.
+    ///
+    /// # Parameters
+    ///
+    /// * `country_code`: The country calling code.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the ordered region codes, or `None` if the country code is
+    /// invalid.
+    pub fn get_ordered_regions_for_country_code(&self, country_code: i32) -> Option<Vec<&'static str>> {
+        self.util_internal().get_ordered_regions_for_country_code(country_code)
+    }
+
+    /// Gets all region codes associated with a country calling code as a slice.
+    ///
+    /// The slice is shared with the loaded metadata, so the lookup neither allocates
+    /// nor copies the region codes. It stays valid after `reload_metadata`. The main
+    /// region for the code comes first.
+    ///
+    /// # Parameters
+    ///
+    /// * `country_code`: The country calling code.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the slice of region codes, or `None` if the country code is invalid.
+    pub fn regions_for_calling_code_slice(&self, country_code: i32) -> Option<Arc<[&'static str]>> {
+        self.util_internal()
+            .regions_for_calling_code_slice(country_code)
+            .cloned()
+    }
+
+    /// Gets an iterator over all supported two-letter region codes.
+    ///
+    /// The iterator keeps the metadata it was created from, so it is not affected by a
+    /// concurrent `reload_metadata`.
+    ///
+    /// # Returns
+    ///
+    /// An `ExactSizeIterator` that yields string slices of all supported region codes.
+    pub fn get_supported_regions(&self) -> impl ExactSizeIterator<Item=&'static str> {
+        iter_shared_region_codes(self.util_internal().supported_regions().clone())
+    }
+
+    /// Checks if a number string contains alphabetic characters.
+    ///
+    /// # Parameters
+    ///
+    /// * `number`: The phone number string to check.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the string contains letters, `false` otherwise.
+    pub fn is_alpha_number(&self, number: impl AsRef<str>) -> bool {
+        self.util_internal().is_alpha_number(number.as_ref())
+    }
+
+    /// Checks if the main part of a number string, without its extension, contains
+    /// alphabetic characters.
+    ///
+    /// Unlike `is_alpha_number`, letters of an extension label are never taken into
+    /// account, even when the label is not followed by any digits (as in
+    /// `"1800 123-1234 ext."`). Use this to tell genuine vanity numbers apart from
+    /// numbers that merely carry an "ext." label.
+    ///
+    /// # Parameters
+    ///
+    /// * `number`: The phone number string to check.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the number without its extension contains letters, `false` otherwise.
+    pub fn is_alpha_number_excluding_extension(&self, number: impl AsRef<str>) -> bool {
+        self.util_internal().is_alpha_number_excluding_extension(number.as_ref())
+    }
+
+    /// Checks if a region code denotes a geographical region supported by the library.
+    ///
+    /// This is useful to filter the regions returned by `get_supported_regions` for
+    /// features that only make sense for geographical regions, such as geocoding.
+    ///
+    /// # Parameters
+    ///
+    /// * `region_code`: The two-letter region code (ISO 3166-1) to check.
+    ///
+    /// # Returns
+    ///
+    /// `true` for regions like "US" or "DE", `false` for the non-geographical entity "001",
+    /// the unknown region "ZZ" and unsupported region codes.
+    pub fn is_geo_region(&self, region_code: impl AsRef<str>) -> bool {
+        self.util_internal().is_geo_region(region_code.as_ref())
+    }
+
+    /// Checks if a region is part of the North American Numbering Plan (NANPA).
+    ///
+    /// # Parameters
+    ///
+    /// * `region_code`: The two-letter region code (ISO 3166-1) to check.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the region is a NANPA country, `false` otherwise.
+    pub fn is_nanpa_country(&self, region_code: impl AsRef<str>) -> bool {
+        self.util_internal().is_nanpa_country(region_code.as_ref())
+    }
+
+    /// Gets the region codes of all countries in the North American Numbering Plan (NANPA).
+    ///
+    /// These are exactly the regions for which `is_nanpa_country` returns `true`. The list
+    /// is sorted once when the metadata is loaded and shared with it, so calls neither
+    /// scan the supported regions nor allocate.
+    ///
+    /// # Returns
+    ///
+    /// A slice of two-letter region codes (ISO 3166-1) in alphabetical order.
+    pub fn get_nanpa_regions(&self) -> Arc<[&'static str]> {
+        self.util_internal().get_nanpa_regions().clone()
+    }
+
+    /// Checks if a `PhoneNumber` is geographical.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to check.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the number corresponds to a specific geographic area.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn is_number_geographical(&self, phone_number: &PhoneNumber) -> bool {
+        self.util_internal().is_number_geographical(phone_number)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Compares two phone numbers and returns their `MatchType`.
+    ///
+    /// # Parameters
+    ///
+    /// * `first_number`: The first `PhoneNumber` to compare.
+    /// * `second_number`: The second `PhoneNumber` to compare.
+    ///
+    /// # Returns
+    ///
+    /// The `MatchType` indicating the level of similarity (e.g., EXACT_MATCH, NSN_MATCH).
+    pub fn is_number_match(
+        &self,
+        first_number: &PhoneNumber,
+        second_number: &PhoneNumber,
+    ) -> MatchType {
+        self.util_internal()
+            .is_number_match(first_number, second_number)
+    }
+
+    /// Finds the candidate that matches a phone number best, e.g. to link an incoming call
+    /// to a stored contact.
+    ///
+    /// Every candidate is compared with `target` using `is_number_match`, and the one with
+    /// the strongest `MatchType` wins. If several candidates match equally well, the first
+    /// of them is returned.
+    ///
+    /// # Parameters
+    ///
+    /// * `target`: The `PhoneNumber` to look up.
+    /// * `candidates`: The `PhoneNumber`s to compare it with.
+    ///
+    /// # Returns
+    ///
+    /// The best matching candidate and its `MatchType`, or `None` if no candidate matches
+    /// at all (i.e. all of them are a `NoMatch`) or there are no candidates.
+    pub fn best_match<'a>(
+        &self,
+        target: &PhoneNumber,
+        candidates: impl IntoIterator<Item = &'a PhoneNumber>,
+    ) -> Option<(&'a PhoneNumber, MatchType)> {
+        let util = self.util_internal();
+        let mut best_match: Option<(&'a PhoneNumber, MatchType)> = None;
+        for candidate in candidates {
+            let match_type = util.is_number_match(target, candidate);
+            if matches!(match_type, MatchType::NoMatch | MatchType::NotANumber) {
+                continue;
+            }
+            if best_match.is_none_or(|(_, best_match_type)| match_type > best_match_type) {
+                best_match = Some((candidate, match_type));
+                // Nothing can beat an exact match.
+                if match_type == MatchType::ExactMatch {
+                    break;
+                }
+            }
+        }
+        best_match
+    }
+
+    /// Compares two phone numbers and additionally reports how many digits they differ in.
+    ///
+    /// The distance is the edit distance (insertions, deletions and substitutions of
+    /// single digits) between the national significant numbers. It is only computed when
+    /// both numbers have the same country calling code and national significant numbers
+    /// of the same length, e.g. "+1 650-253-0000" and "+1 650-253-0001" are a `NoMatch`
+    /// at distance 1.
+    ///
+    /// # Parameters
+    ///
+    /// * `first_number`: The first `PhoneNumber` to compare.
+    /// * `second_number`: The second `PhoneNumber` to compare.
+    ///
+    /// # Returns
+    ///
+    /// The `MatchType`, as returned by `is_number_match`, and the distance, or `None` if
+    /// the numbers are not comparable digit by digit.
+    pub fn is_number_match_with_distance(
+        &self,
+        first_number: &PhoneNumber,
+        second_number: &PhoneNumber,
+    ) -> (MatchType, Option<usize>) {
+        self.util_internal()
+            .is_number_match_with_distance(first_number, second_number)
+    }
+
+    /// Compares two phone numbers and additionally reports whether Italian leading zeros
+    /// decided the result.
+    ///
+    /// Numbers that only differ in their leading zeros, e.g. "+39 02 3661 8300" and a
+    /// number with the same national number but without `italian_leading_zero` set, are
+    /// a `ShortNsnMatch`, as their national significant numbers differ. The flag tells
+    /// this case apart from one number being a shorter variant of the other.
+    ///
+    /// # Parameters
+    ///
+    /// * `first_number`: The first `PhoneNumber` to compare.
+    /// * `second_number`: The second `PhoneNumber` to compare.
+    ///
+    /// # Returns
+    ///
+    /// The `MatchType`, as returned by `is_number_match`, and `true` if the numbers would
+    /// be equal but for their leading zeros.
+    pub fn is_number_match_with_leading_zero_info(
+        &self,
+        first_number: &PhoneNumber,
+        second_number: &PhoneNumber,
+    ) -> (MatchType, bool) {
+        self.util_internal()
+            .is_number_match_with_leading_zero_info(first_number, second_number)
+    }
+
+    /// Compares two phone numbers given as strings and returns their `MatchType`.
+    ///
+    /// Numbers in international format (starting with `+`) are parsed without a region.
+    /// When only one of them has a country calling code, the other is parsed as if it
+    /// came from the same region, and the best possible result is `NsnMatch`.
+    ///
+    /// ```rust
+    /// use rlibphonenumber::{MatchType, PHONE_NUMBER_UTIL};
+    ///
+    /// assert_eq!(MatchType::ExactMatch, PHONE_NUMBER_UTIL.is_number_match_with_strings("+1 650-253-0000", "+16502530000"));
+    /// assert_eq!(MatchType::NsnMatch, PHONE_NUMBER_UTIL.is_number_match_with_strings("+1 650-253-0000", "650 253 0000"));
+    /// assert_eq!(MatchType::NotANumber, PHONE_NUMBER_UTIL.is_number_match_with_strings("+1 650-253-0000", "hello"));
+    /// ```
+    ///
+    /// # Parameters
+    ///
+    /// * `first_number`: The first number to compare.
+    /// * `second_number`: The second number to compare.
+    ///
+    /// # Returns
+    ///
+    /// The `MatchType` indicating the level of similarity, or `MatchType::NotANumber` if
+    /// either string can't be parsed as a phone number.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the underlying metadata contains an invalid regular expression,
+    /// indicating a library bug.
+    pub fn is_number_match_with_strings(
+        &self,
+        first_number: impl AsRef<str>,
+        second_number: impl AsRef<str>,
+    ) -> MatchType {
+        self.is_number_match_lenient(first_number.as_ref(), second_number.as_ref())
+    }
+
+    /// Compares a phone number given as a string with a parsed `PhoneNumber` and returns
+    /// their `MatchType`.
+    ///
+    /// If the string has no country calling code, it is parsed as if it came from the
+    /// region of `phone_number`, and the best possible result is `NsnMatch`.
+    ///
+    /// # Parameters
+    ///
+    /// * `number_to_compare`: The number to compare, as a string.
+    /// * `phone_number`: The parsed number to compare with.
+    ///
+    /// # Returns
+    ///
+    /// The `MatchType` indicating the level of similarity, or `MatchType::NotANumber` if
+    /// the string can't be parsed as a phone number.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the underlying metadata contains an invalid regular expression,
+    /// indicating a library bug.
+    pub fn is_number_match_string_and_number(
+        &self,
+        number_to_compare: impl AsRef<str>,
+        phone_number: &PhoneNumber,
+    ) -> MatchType {
+        self.is_number_match_lenient(number_to_compare.as_ref(), phone_number)
+    }
+
+    /// Compares two phone numbers, each given either as a `PhoneNumber` or as a string,
+    /// and returns their `MatchType`.
+    ///
+    /// Strings are parsed without a default region, so only numbers in international
+    /// format (starting with `+`) get a country calling code. When only one side is a
+    /// string without a country calling code, it is parsed as if it came from the
+    /// region of the other number, and the best possible result is `NsnMatch`.
+    ///
+    /// ```rust
+    /// use rlibphonenumber::{MatchType, PHONE_NUMBER_UTIL};
+    ///
+    /// let number = PHONE_NUMBER_UTIL.parse("+1 650-253-0000", "US").unwrap();
+    /// assert_eq!(MatchType::ExactMatch, PHONE_NUMBER_UTIL.is_number_match_lenient(&number, "+16502530000"));
+    /// assert_eq!(MatchType::NsnMatch, PHONE_NUMBER_UTIL.is_number_match_lenient("650 253 0000", &number));
+    /// assert_eq!(MatchType::NotANumber, PHONE_NUMBER_UTIL.is_number_match_lenient(&number, "hello"));
+    /// ```
+    ///
+    /// # Parameters
+    ///
+    /// * `first_number`: The first number to compare.
+    /// * `second_number`: The second number to compare.
+    ///
+    /// # Returns
+    ///
+    /// The `MatchType` indicating the level of similarity, or `MatchType::NotANumber` if
+    /// a string can't be parsed as a phone number.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the underlying metadata contains an invalid regular expression,
+    /// indicating a library bug.
+    pub fn is_number_match_lenient<'a, 'b>(
+        &self,
+        first_number: impl Into<NumberOrString<'a>>,
+        second_number: impl Into<NumberOrString<'b>>,
+    ) -> MatchType {
+        self.util_internal()
+            .is_number_match_lenient(first_number.into(), second_number.into())
+            .or_else(|err| match err {
+                InvalidNumberErrorInternal::InvalidNumber(_) => Ok(MatchType::NotANumber),
+                err => Err(err),
+            })
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Performs a fast check to determine if a `PhoneNumber` is possibly valid.
+    ///
+    /// This method is less strict than `is_valid_number`.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to check.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the number has a valid length, `false` otherwise.
+    pub fn is_possible_number(&self, phone_number: &PhoneNumber) -> bool {
+        self.util_internal().is_possible_number(phone_number)
+    }
+
+    /// Checks if a `PhoneNumber` is possibly valid and provides a reason if not.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to check.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok(NumberLengthType)` on success or a `ValidationError` on failure.
+    pub fn is_possible_number_with_reason(&self, phone_number: &PhoneNumber) -> Result<NumberLengthType, ValidationError> {
+        self.util_internal().is_possible_number_with_reason(phone_number)
+    }
+
+    /// Performs a full validation of a `PhoneNumber`.
+    ///
+    /// This is a more comprehensive check than `is_possible_number`.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to validate.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the number is valid, `false` otherwise. Numbers whose country calling
+    /// code has no loaded metadata, e.g. when using a trimmed metadata collection,
+    /// are not valid.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn is_valid_number(&self, phone_number: &PhoneNumber) -> bool {
+        self
+            .util_internal()
+            .is_valid_number(phone_number)
+            // This should not never happen
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Validates a `PhoneNumber` like `is_valid_number`, explaining why it is not valid.
+    ///
+    /// A number with an impossible length fails with the same `ValidationError` as
+    /// `is_possible_number_with_reason` returns (`TooShort`, `TooLong`, `InvalidLength`
+    /// or `InvalidCountryCode`). If the length is possible but the number doesn't match
+    /// any valid number pattern of its region, `ValidationError::PatternMismatch` is
+    /// returned. This includes numbers with a local-only length, which can't be valid.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to validate.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` exactly when `is_valid_number` returns `true`, otherwise the reason.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn is_valid_number_with_reason(&self, phone_number: &PhoneNumber) -> Result<(), ValidationError> {
+        self
+            .util_internal()
+            .is_valid_number_with_reason(phone_number)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Computes how plausible it is that a `PhoneNumber` is a real number, e.g. to rank
+    /// several interpretations of ambiguous user input.
+    ///
+    /// The score is a heuristic and only meant for comparing numbers with each other. It
+    /// combines whether the length is possible, whether the number matches a pattern of
+    /// its region and whether its type can be determined unambiguously:
+    ///
+    /// * 1.0 for a valid number of a single type, e.g. a mobile number;
+    /// * 0.9 for a valid number which may be fixed-line or mobile;
+    /// * 0.5 for a number of a possible length which doesn't match any pattern;
+    /// * 0.25 for a number which can only be dialled locally;
+    /// * 0.0 for a number of an impossible length or with an unknown country code.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to score.
+    ///
+    /// # Returns
+    ///
+    /// A score between 0.0 and 1.0, higher meaning more plausible.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn validity_score(&self, phone_number: &PhoneNumber) -> f32 {
+        self
+            .util_internal()
+            .validity_score(phone_number)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Checks if a `PhoneNumber` is valid and of the requested `PhoneNumberType`.
+    ///
+    /// In regions where fixed-line and mobile numbers share patterns (e.g. the US), numbers
+    /// are classified as `FixedLineOrMobile`. Such a number counts as valid for both
+    /// `FixedLine` and `Mobile` requests, and a `FixedLineOrMobile` request accepts
+    /// numbers of either of these types.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to validate.
+    /// * `number_type`: The `PhoneNumberType` the number is expected to be.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the number is valid and could be of the requested type, `false` otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn is_valid_number_for_type(&self, phone_number: &PhoneNumber, number_type: PhoneNumberType) -> bool {
+        self
+            .util_internal()
+            .is_valid_number_for_type(phone_number, number_type)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Checks if a `PhoneNumber` is valid and of the `required` type, explaining why
+    /// it isn't otherwise.
+    ///
+    /// The types match the same way as in `is_valid_number_for_type`: a
+    /// `FixedLineOrMobile` number is accepted for `FixedLine` and `Mobile`
+    /// requirements, and a `FixedLineOrMobile` requirement accepts both of them.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to validate.
+    /// * `required`: The `PhoneNumberType` the number must have.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the number is valid and of the required type, otherwise
+    /// `TypeValidationError::Invalid` for invalid numbers, or
+    /// `TypeValidationError::WrongType` with the actual type of a valid number.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn is_valid_and_type(
+        &self,
+        phone_number: &PhoneNumber,
+        required: PhoneNumberType,
+    ) -> Result<(), TypeValidationError> {
+        self
+            .util_internal()
+            .is_valid_and_type(phone_number, required)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    }
+
+    /// Validates a `PhoneNumber` for a specific region.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to validate.
+    /// * `region`: The two-letter region code (ISO 3166-1) to validate against.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the number is valid for the given region, `false` otherwise.
+    pub fn is_valid_number_for_region(&self, phone_number: &PhoneNumber, region: impl AsRef<str>) -> bool {
+        self.util_internal().is_valid_number_for_region(phone_number, region.as_ref())
+    }
+
+    /// Validates a `PhoneNumber` against the metadata of the non-geographical entity
+    /// for its country calling code, such as +800 (International Toll Free Service).
+    ///
+    /// This is the same as calling `is_valid_number_for_region` with the region code
+    /// "001", which is used for non-geographical entities.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: The `PhoneNumber` to validate.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the number is a valid number of a non-geographical entity, `false`
+    /// otherwise, including for numbers of geographical regions.
+    pub fn is_valid_non_geo_number(&self, phone_number: &PhoneNumber) -> bool {
+        self.util_internal().is_valid_non_geo_number(phone_number)
     }
 
-    /// Gets an iterator over all supported two-letter region codes.
+    /// Parses a string into a `PhoneNumber` like `parse`, and reports the domestic carrier
+    /// code that was stripped from the national number.
+    ///
+    /// Some regions, such as Korea or Argentina, let the caller select a carrier by
+    /// dialing its code after the national prefix, e.g. "081" in "08122123456". Use this
+    /// to get that code without keeping the raw input in the number.
+    ///
+    /// # Parameters
+    ///
+    /// * `number_to_parse`: The phone number string.
+    /// * `default_region`: The two-letter region code (ISO 3166-1) to use if the number is not in international format.
     ///
     /// # Returns
     ///
-    /// An `ExactSizeIterator` that yields string slices of all supported region codes.
-    pub fn get_supported_regions(&self) -> impl ExactSizeIterator<Item=&str> {
-        self.util_internal.get_supported_regions()
+    /// A `Result` containing the parsed `PhoneNumber`, the same as returned by `parse`,
+    /// and the stripped carrier code, if any, on success, or a `ParseError` on failure.
+    pub fn parse_reporting_carrier(
+        &self,
+        number_to_parse: impl AsRef<str>,
+        default_region: impl AsRef<str>,
+    ) -> Result<(PhoneNumber, Option<String>), ParseError> {
+        self.util_internal()
+            .parse_reporting_carrier(number_to_parse.as_ref(), default_region.as_ref())
+            .map_err(| err | err.into_public())
     }
 
-    /// Checks if a number string contains alphabetic characters.
+    /// Parses a string into an existing `PhoneNumber`, in the output-parameter style of
+    /// the upstream library.
+    ///
+    /// The result is identical to `parse`. This is handy in loops processing many numbers,
+    /// where the same `PhoneNumber` can be reused for every input: the extension of a
+    /// number is written into the buffer of the previous one, so it is not allocated anew.
     ///
     /// # Parameters
     ///
-    /// * `number`: The phone number string to check.
+    /// * `number_to_parse`: The phone number string.
+    /// * `default_region`: The two-letter region code (ISO 3166-1) to use if the number is not in international format.
+    /// * `phone_number`: The `PhoneNumber` to fill in. Any previous content is replaced, and
+    ///   it is left empty if parsing fails.
     ///
     /// # Returns
     ///
-    /// `true` if the string contains letters, `false` otherwise.
-    pub fn is_alpha_number(&self, number: impl AsRef<str>) -> bool {
-        self.util_internal.is_alpha_number(number.as_ref())
+    /// `Ok(())` on success, or a `ParseError` on failure.
+    pub fn parse_into(
+        &self,
+        number_to_parse: impl AsRef<str>,
+        default_region: impl AsRef<str>,
+        phone_number: &mut PhoneNumber,
+    ) -> Result<(), ParseError> {
+        self.util_internal()
+            .parse_into(number_to_parse.as_ref(), default_region.as_ref(), phone_number)
+            .map_err(| err | err.into_public())
     }
 
-    /// Checks if a region is part of the North American Numbering Plan (NANPA).
+    /// Parses an RFC3966 "tel:" URI into a `PhoneNumber` and returns its ISDN subaddress.
+    ///
+    /// `parse` accepts such URIs too, but drops the `isub` parameter since `PhoneNumber`
+    /// has no field for it. To write the number back, append `";isub="` followed by the
+    /// subaddress to the RFC3966 format of the number.
     ///
     /// # Parameters
     ///
-    /// * `region_code`: The two-letter region code (ISO 3166-1) to check.
+    /// * `number_to_parse`: The URI, e.g. "tel:+1-650-253-0000;isub=1234" or
+    ///   "tel:253-0000;isub=1234;phone-context=+1-650". Numbers without a global number
+    ///   or a phone-context can't be parsed, as no default region is used.
     ///
     /// # Returns
     ///
-    /// `true` if the region is a NANPA country, `false` otherwise.
-    pub fn is_nanpa_country(&self, region_code: impl AsRef<str>) -> bool {
-        self.util_internal.is_nanpa_country(region_code.as_ref())
+    /// A `Result` containing the parsed `PhoneNumber` and the subaddress, or `None` if
+    /// there is no `isub` parameter, on success, or a `ParseError` on failure.
+    pub fn parse_rfc3966_with_isub(
+        &self,
+        number_to_parse: impl AsRef<str>,
+    ) -> Result<(PhoneNumber, Option<String>), ParseError> {
+        self.util_internal()
+            .parse_rfc3966_with_isub(number_to_parse.as_ref())
+            .map_err(| err | err.into_public())
     }
 
-    /// Checks if a `PhoneNumber` is geographical.
+    /// Parses a string into a `PhoneNumber`, keeping the raw input string.
     ///
     /// # Parameters
     ///
-    /// * `phone_number`: The `PhoneNumber` to check.
+    /// * `number_to_parse`: The phone number string.
+    /// * `default_region`: The two-letter region code (ISO 3166-1) to use if the number is not in international format.
     ///
     /// # Returns
     ///
-    /// `true` if the number corresponds to a specific geographic area.
+    /// A `Result` containing the parsed `PhoneNumber` on success, or a `ParseError` on failure.
+    pub fn parse_and_keep_raw_input(
+        &self,
+        number_to_parse: impl AsRef<str>,
+        default_region: impl AsRef<str>,
+    ) -> Result<PhoneNumber, ParseError> {
+        self.util_internal()
+            .parse_and_keep_raw_input(number_to_parse.as_ref(), default_region.as_ref())
+            .map_err(| err | err.into_public())
+    }
+
+    /// Parses a string into a `PhoneNumber`, rejecting numbers that likely belong to
+    /// another region than the selected one.
+    ///
+    /// A number written without a `+` sign or international prefix is expected to be
+    /// a domestic number of `default_region`. If it nevertheless resolves to another
+    /// region, e.g. "44 20 7031 3000" parsed with `"US"`, the wrong region was probably
+    /// selected and `ParseError::RegionMismatchSuspected` is returned. Note that this
+    /// also applies to regions sharing a country calling code, such as a Canadian number
+    /// parsed with `"US"`. Invalid numbers, which have no region, are returned as is.
+    ///
+    /// # Parameters
+    ///
+    /// * `number_to_parse`: The phone number string.
+    /// * `default_region`: The two-letter region code (ISO 3166-1) the number is expected to belong to.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the parsed `PhoneNumber` on success, or a `ParseError` on failure.
     ///
     /// # Panics
     ///
     /// Panics on invalid metadata, indicating a library bug.
-    pub fn is_number_geographical(&self, phone_number: &PhoneNumber) -> bool {
-        self.util_internal.is_number_geographical(phone_number)
-            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    pub fn parse_with_region_sanity(
+        &self,
+        number_to_parse: impl AsRef<str>,
+        default_region: impl AsRef<str>,
+    ) -> Result<PhoneNumber, ParseError> {
+        self.util_internal()
+            .parse_with_region_sanity(number_to_parse.as_ref(), default_region.as_ref())
+            .map_err(| err | err.into_public())
     }
 
-    /// Compares two phone numbers and returns their `MatchType`.
+    /// Parses a string into a `PhoneNumber` without stripping the national prefix.
+    ///
+    /// The national prefix (and a carrier code following it) stays part of the national
+    /// number, e.g. "0 20 7031 3000" in GB gives the national number `2070313000` with
+    /// the Italian leading zero set, instead of `2070313000` without it. This is mostly
+    /// useful for diagnosing metadata and round-trip testing; numbers parsed this way are
+    /// usually not valid.
     ///
     /// # Parameters
     ///
-    /// * `first_number`: The first `PhoneNumber` to compare.
-    /// * `second_number`: The second `PhoneNumber` to compare.
+    /// * `number_to_parse`: The phone number string.
+    /// * `default_region`: The two-letter region code (ISO 3166-1) to use if the number is not in international format.
     ///
     /// # Returns
     ///
-    /// The `MatchType` indicating the level of similarity (e.g., EXACT_MATCH, NSN_MATCH).
-    pub fn is_number_match(
+    /// A `Result` containing the parsed `PhoneNumber` on success, or a `ParseError` on failure.
+    pub fn parse_keep_national_prefix(
         &self,
-        first_number: &PhoneNumber,
-        second_number: &PhoneNumber,
-    ) -> MatchType {
-        self.util_internal
-            .is_number_match(first_number, second_number)
+        number_to_parse: impl AsRef<str>,
+        default_region: impl AsRef<str>,
+    ) -> Result<PhoneNumber, ParseError> {
+        self.util_internal()
+            .parse_keep_national_prefix(number_to_parse.as_ref(), default_region.as_ref())
+            .map_err(| err | err.into_public())
     }
 
-    /// Performs a fast check to determine if a `PhoneNumber` is possibly valid.
+    /// Parses a string into a `BorrowedNumber`, avoiding allocations for input that
+    /// is already a plain national number.
     ///
-    /// This method is less strict than `is_valid_number`.
+    /// If `number_to_parse` consists only of the ASCII digits of a national significant
+    /// number of `default_region` (no formatting, leading zero, national or international
+    /// prefix), the result borrows it. Otherwise the number is parsed as with `parse` and
+    /// returned as `BorrowedNumber::Owned`. In both cases `BorrowedNumber::into_owned`
+    /// gives the same `PhoneNumber` as `parse`.
     ///
     /// # Parameters
     ///
-    /// * `phone_number`: The `PhoneNumber` to check.
+    /// * `number_to_parse`: The phone number string.
+    /// * `default_region`: The two-letter region code (ISO 3166-1) to use if the number is not in international format.
     ///
     /// # Returns
     ///
-    /// `true` if the number has a valid length, `false` otherwise.
-    pub fn is_possible_number(&self, phone_number: &PhoneNumber) -> bool {
-        self.util_internal.is_possible_number(phone_number)
+    /// A `Result` containing the parsed `BorrowedNumber` on success, or a `ParseError` on failure.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the underlying metadata contains an invalid regular expression,
+    /// which indicates a critical library bug.
+    pub fn parse_borrowed<'a>(
+        &self,
+        number_to_parse: &'a str,
+        default_region: impl AsRef<str>,
+    ) -> Result<BorrowedNumber<'a>, ParseError> {
+        self.util_internal()
+            .parse_borrowed(number_to_parse, default_region.as_ref())
+            .map_err(| err | err.into_public())
     }
 
-    /// Checks if a `PhoneNumber` is possibly valid and provides a reason if not.
+    /// Parses a stream of characters into a `PhoneNumber`.
+    ///
+    /// This is useful when reading a number character by character, e.g. from a tokenizer,
+    /// as no intermediate `String` has to be built by the caller. The characters are
+    /// collected into an internal buffer that is reused between calls on the same thread.
+    /// The result is the same as of `parse` with the collected string.
     ///
     /// # Parameters
     ///
-    /// * `phone_number`: The `PhoneNumber` to check.
+    /// * `chars`: The characters of the phone number.
+    /// * `default_region`: The two-letter region code (ISO 3166-1) to use if the number is not in international format.
     ///
     /// # Returns
     ///
-    /// A `Result` which is `Ok(NumberLengthType)` on success or a `ValidationError` on failure.
-    pub fn is_possible_number_with_reason(&self, phone_number: &PhoneNumber) -> Result<NumberLengthType, ValidationError> {
-        self.util_internal.is_possible_number_with_reason(phone_number)
+    /// A `Result` containing the parsed `PhoneNumber` on success, or a `ParseError` on failure.
+    pub fn parse_chars(
+        &self,
+        chars: impl Iterator<Item = char>,
+        default_region: impl AsRef<str>,
+    ) -> Result<PhoneNumber, ParseError> {
+        self.util_internal()
+            .parse_chars(chars, default_region.as_ref())
+            .map_err(| err | err.into_public())
     }
 
-    /// Performs a full validation of a `PhoneNumber`.
+    /// Parses a string into a `PhoneNumber`.
     ///
-    /// This is a more comprehensive check than `is_possible_number`.
+    /// This is the primary method for converting a string representation of a number
+    /// into a structured `PhoneNumber` object.
     ///
     /// # Parameters
     ///
-    /// * `phone_number`: The `PhoneNumber` to validate.
+    /// * `number_to_parse`: The phone number string.
+    /// * `default_region`: The two-letter region code (ISO 3166-1) to use if the number is not in international format.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the parsed `PhoneNumber` on success, or a `ParseError` on failure.
+    pub fn parse(
+        &self,
+        number_to_parse: impl AsRef<str>,
+        default_region: impl AsRef<str>,
+    ) -> Result<PhoneNumber, ParseError> {
+        self.util_internal()
+            .parse(number_to_parse.as_ref(), default_region.as_ref())
+            .map_err(| err | err.into_public())
+    }
+
+    /// Parses a string into a `PhoneNumber` with custom `ParseOptions`.
+    ///
+    /// With default options this behaves exactly like `parse`. See `ParseOptions` for
+    /// the available opt-in behaviours, e.g. stripping decorative symbols from messy
+    /// user input like "☎ +1 650 253 0000".
+    ///
+    /// # Parameters
+    ///
+    /// * `number_to_parse`: The phone number string.
+    /// * `default_region`: The two-letter region code (ISO 3166-1) to use if the number is not in international format.
+    /// * `options`: The `ParseOptions` to apply.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the parsed `PhoneNumber` on success, or a `ParseError` on failure.
+    pub fn parse_with_options(
+        &self,
+        number_to_parse: impl AsRef<str>,
+        default_region: impl AsRef<str>,
+        options: &ParseOptions,
+    ) -> Result<PhoneNumber, ParseError> {
+        self.util_internal()
+            .parse_with_options(number_to_parse.as_ref(), default_region.as_ref(), options)
+            .map_err(| err | err.into_public())
+    }
+
+    /// Parses a string, falling back to other regions when the number isn't valid for the
+    /// preferred one.
+    ///
+    /// The input is parsed with `preferred_region` first. If that doesn't give a valid
+    /// number, each of `fallback_regions` is tried in order, and the first valid result
+    /// is returned. This helps with users whose configured region is wrong, e.g. a GB
+    /// national number entered while the region is set to "FR". Numbers in international
+    /// format parse the same for every region, so they are returned as for `parse`.
+    ///
+    /// # Parameters
+    ///
+    /// * `number_to_parse`: The phone number string to parse.
+    /// * `preferred_region`: The two-letter region code (ISO 3166-1) to try first.
+    /// * `fallback_regions`: The region codes to try next, in order.
     ///
     /// # Returns
     ///
-    /// `true` if the number is valid, `false` otherwise.
+    /// The first valid parsed `PhoneNumber`. If no region gives a valid number, the result
+    /// of parsing with `preferred_region` is returned, which may be an invalid number or
+    /// a `ParseError`.
     ///
     /// # Panics
     ///
     /// Panics on invalid metadata, indicating a library bug.
-    pub fn is_valid_number(&self, phone_number: &PhoneNumber) -> bool {
-        self
-            .util_internal
-            .is_valid_number(phone_number)
-            // This should not never happen
-            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+    pub fn parse_best_region(
+        &self,
+        number_to_parse: impl AsRef<str>,
+        preferred_region: impl AsRef<str>,
+        fallback_regions: &[impl AsRef<str>],
+    ) -> Result<PhoneNumber, ParseError> {
+        self.util_internal()
+            .parse_best_region(number_to_parse.as_ref(), preferred_region.as_ref(), fallback_regions)
+            .map_err(| err | err.into_public())
     }
 
-    /// Validates a `PhoneNumber` for a specific region.
+    /// Parses many numbers in parallel on the global `rayon` thread pool.
+    ///
+    /// Each input is a `(number, default_region)` pair, parsed exactly like `parse`
+    /// would. The results are in the same order as the inputs. Parsing is CPU bound and
+    /// the util is shared between threads without locking, except for the regex caches,
+    /// so large batches are parsed roughly as many times faster as there are cores.
+    /// For a handful of numbers the thread pool overhead outweighs the gain; use `parse`
+    /// in a loop instead. The `parse_many` benchmark in `benches/parsing_bench.rs`
+    /// compares both on a batch of 10 000 numbers.
+    ///
+    /// Available with the `rayon` feature.
     ///
     /// # Parameters
     ///
-    /// * `phone_number`: The `PhoneNumber` to validate.
-    /// * `region`: The two-letter region code (ISO 3166-1) to validate against.
+    /// * `inputs`: The phone number strings with the regions to use if they are not in international format.
     ///
     /// # Returns
     ///
-    /// `true` if the number is valid for the given region, `false` otherwise.
-    pub fn is_valid_number_for_region(&self, phone_number: &PhoneNumber, region: impl AsRef<str>) -> bool {
-        self.util_internal.is_valid_number_for_region(phone_number, region.as_ref())
+    /// The result of parsing each input, in input order.
+    #[cfg(feature = "rayon")]
+    pub fn parse_many(&self, inputs: &[(&str, &str)]) -> Vec<Result<PhoneNumber, ParseError>> {
+        use rayon::prelude::*;
+
+        let util = self.util_internal.load_full();
+        inputs
+            .par_iter()
+            .map(|(number_to_parse, default_region)| {
+                util.parse(number_to_parse, default_region).map_err(|err| err.into_public())
+            })
+            .collect()
     }
 
-    /// Parses a string into a `PhoneNumber`, keeping the raw input string.
+    /// Parses a string into a `PhoneNumber` and reports which kind of label introduced
+    /// its extension.
+    ///
+    /// For example, "650 253 0000 ext. 123" yields `ExtensionLabelKind::Explicit`, while
+    /// "650 253 0000 x123" yields `ExtensionLabelKind::Ambiguous`. The parsed number is
+    /// the same as `parse` returns.
     ///
     /// # Parameters
     ///
-    /// * `number_to_parse`: The phone number string.
+    /// * `number_to_parse`: The phone number string to parse.
     /// * `default_region`: The two-letter region code (ISO 3166-1) to use if the number is not in international format.
     ///
     /// # Returns
     ///
-    /// A `Result` containing the parsed `PhoneNumber` on success, or a `ParseError` on failure.
-    pub fn parse_and_keep_raw_input(
+    /// A `Result` containing the parsed `PhoneNumber` and the kind of extension label, or
+    /// `None` when the number has no extension. Returns a `ParseError` on failure.
+    pub fn parse_reporting_extension_label(
         &self,
         number_to_parse: impl AsRef<str>,
         default_region: impl AsRef<str>,
-    ) -> Result<PhoneNumber, ParseError> {
-        self.util_internal
-            .parse_and_keep_raw_input(number_to_parse.as_ref(), default_region.as_ref())
+    ) -> Result<(PhoneNumber, Option<ExtensionLabelKind>), ParseError> {
+        self.util_internal()
+            .parse_reporting_extension_label(number_to_parse.as_ref(), default_region.as_ref())
             .map_err(| err | err.into_public())
     }
 
-    /// Parses a string into a `PhoneNumber`.
+    /// Strips the extension from the end of a number string and reports where it began.
     ///
-    /// This is the primary method for converting a string representation of a number
-    /// into a structured `PhoneNumber` object.
+    /// For "650 253 0000 ext. 123" this returns `("650 253 0000", Some(("123", 13)))`,
+    /// with `13` being the byte index of "ext." in the input, so UI code can highlight
+    /// the extension part of the original text. Whitespace between the number and the
+    /// label is not counted as part of the extension.
     ///
     /// # Parameters
     ///
-    /// * `number_to_parse`: The phone number string.
+    /// * `number`: The number string, as entered by the user.
+    ///
+    /// # Returns
+    ///
+    /// The number without its extension, and the extension digits together with the
+    /// byte index where the extension label starts, or `None` if no extension was found.
+    pub fn maybe_strip_extension_with_span<'a>(&self, number: &'a str) -> (&'a str, Option<(String, usize)>) {
+        let (number, extension) = self.util_internal().maybe_strip_extension_with_span(number);
+        (number, extension.map(|(extension, start)| (extension.to_owned(), start)))
+    }
+
+    /// Returns the groups of digits of a number's raw input, as the user entered them.
+    ///
+    /// For a number parsed from "+1 650-253-0000 ext. 12" with `parse_and_keep_raw_input`
+    /// this returns `["1", "650", "253", "0000"]`. The extension is not included, and
+    /// digits from other scripts are converted to ASCII.
+    ///
+    /// The groups are recorded while parsing, in `PhoneNumber::raw_input_groups`, from
+    /// the same view of the input the parser used, e.g. without RFC3966 parameters.
+    /// Numbers parsed without keeping the raw input yield an empty vector.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: A `PhoneNumber` parsed with `parse_and_keep_raw_input`.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<String>` with the digit groups of the raw input.
+    pub fn raw_input_groups(&self, phone_number: &PhoneNumber) -> Vec<String> {
+        phone_number.raw_input_groups.clone()
+    }
+
+    /// Parses a string into a `PhoneNumber` and also returns every extension candidate
+    /// found in the input.
+    ///
+    /// This helps to understand inputs with several extension-looking parts. For example,
+    /// "(212)123-1234 x508/x1234" is parsed with extension "508", as the text after the
+    /// slash is considered a second number, while the candidates are `["508", "1234"]`.
+    /// The parsed number is the same as `parse` returns.
+    ///
+    /// # Parameters
+    ///
+    /// * `number_to_parse`: The phone number string to parse.
     /// * `default_region`: The two-letter region code (ISO 3166-1) to use if the number is not in international format.
     ///
     /// # Returns
     ///
-    /// A `Result` containing the parsed `PhoneNumber` on success, or a `ParseError` on failure.
-    pub fn parse(
+    /// A `Result` containing the parsed `PhoneNumber` and the digits of all extension
+    /// candidates in input order, or a `ParseError` on failure.
+    pub fn parse_reporting_all_extensions(
         &self,
         number_to_parse: impl AsRef<str>,
         default_region: impl AsRef<str>,
+    ) -> Result<(PhoneNumber, Vec<String>), ParseError> {
+        self.util_internal()
+            .parse_reporting_all_extensions(number_to_parse.as_ref(), default_region.as_ref())
+            .map_err(| err | err.into_public())
+    }
+
+    /// Parses a number in strict E.164 form, e.g. "+16502530000".
+    ///
+    /// This is a fast path for feeds that only contain canonical E.164 strings: when the
+    /// input is a `+` followed by 1 to 15 ASCII digits, the country code is extracted
+    /// directly without the normalization, extension and viability checks of `parse`.
+    /// Any other input falls back to `parse` with an unknown default region, so the
+    /// result is always identical to what `parse` returns.
+    ///
+    /// # Parameters
+    ///
+    /// * `number_to_parse`: The phone number string, ideally in E.164 form.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the parsed `PhoneNumber` on success, or a `ParseError` on failure.
+    pub fn parse_e164(&self, number_to_parse: impl AsRef<str>) -> Result<PhoneNumber, ParseError> {
+        self.util_internal()
+            .parse_e164(number_to_parse.as_ref())
+            .map_err(| err | err.into_public())
+    }
+
+    /// Parses a bare local number (e.g. "253-0000" for the US) into a `PhoneNumber`.
+    ///
+    /// This is a constrained variant of `parse` for datasets of purely local numbers:
+    /// no IDD, country calling code or national prefix stripping is attempted, and all
+    /// digits are kept as the national number. Numbers of a local-only length are then
+    /// reported as `NumberLengthType::IsPossibleLocalOnly` by `is_possible_number_with_reason`.
+    ///
+    /// # Parameters
+    ///
+    /// * `number_to_parse`: The local phone number string.
+    /// * `region`: The two-letter region code (ISO 3166-1) the number belongs to.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the parsed `PhoneNumber` on success, or a `ParseError` on failure.
+    /// `ParseError::InvalidCountryCode` is returned if the region is not supported.
+    pub fn parse_local(
+        &self,
+        number_to_parse: impl AsRef<str>,
+        region: impl AsRef<str>,
     ) -> Result<PhoneNumber, ParseError> {
-        self.util_internal
-            .parse(number_to_parse.as_ref(), default_region.as_ref())
+        self.util_internal()
+            .parse_local(number_to_parse.as_ref(), region.as_ref())
             .map_err(| err | err.into_public())
     }
 
+    /// Checks a whole `PhoneMetadataCollection` for problems before it is used.
+    ///
+    /// This is meant for pipelines that build custom or trimmed metadata. It reports
+    /// every uncompilable regular expression, number descriptions without possible
+    /// lengths to fall back to, and lengths listed both as possible and local-only
+    /// (length checks assume these never overlap).
+    ///
+    /// # Parameters
+    ///
+    /// * `collection`: The metadata collection to check.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec` with all found `MetadataProblem`s, empty if the metadata is consistent.
+    pub fn validate_metadata(collection: &PhoneMetadataCollection) -> Vec<MetadataProblem> {
+        validate_metadata_collection(collection)
+    }
+
     /// Truncates a `PhoneNumber` that is too long to a valid length.
     ///
     /// # Parameters
@@ -612,9 +2571,58 @@ impl PhoneNumberUtil {
     ///
     /// Panics on invalid metadata, indicating a library bug.
     pub fn truncate_too_long_number(&self, phone_number: &mut PhoneNumber) -> bool {
-        self.util_internal.truncate_too_long_number(phone_number)
+        self.util_internal().truncate_too_long_number(phone_number)
             // This should not never happen
             .expect("A valid regex is expected in metadata; this indicates a library bug.")
     }
+
+    /// Truncates a `PhoneNumber` that is too long to a valid length, returning the digits
+    /// that were removed.
+    ///
+    /// This works like `truncate_too_long_number`, but is handy for showing the user what
+    /// was trimmed, e.g. in a "did you mean" prompt.
+    ///
+    /// # Parameters
+    ///
+    /// * `phone_number`: A mutable reference to the `PhoneNumber` to truncate.
+    ///
+    /// # Returns
+    ///
+    /// The trailing digits removed from the national number, or `None` if nothing was
+    /// truncated, either because the number is already valid or because it couldn't be
+    /// truncated to a valid number. In the latter case the number is left unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid metadata, indicating a library bug.
+    pub fn truncate_too_long_number_returning(&self, phone_number: &mut PhoneNumber) -> Option<String> {
+        self.util_internal().truncate_too_long_number_returning(phone_number)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.")
+            .filter(|removed_digits| !removed_digits.is_empty())
+    }
+}
+
+
+/// Decodes a serialized `PhoneMetadataCollection` and compiles all of its patterns
+/// into the regex caches of `util_internal`, so that broken metadata is rejected
+/// before it is used and the compiled regexes are kept for the new snapshot.
+fn decode_metadata(
+    metadata_bytes: &[u8],
+    util_internal: &PhoneNumberUtilInternal,
+) -> Result<PhoneMetadataCollection, MetadataError> {
+    let metadata_collection = PhoneMetadataCollection::parse_from_bytes(metadata_bytes)?;
+    util_internal
+        .compile_metadata_patterns(&metadata_collection)
+        .map_err(MetadataError::InvalidPattern)?;
+    Ok(metadata_collection)
 }
 
+
+
+/// Iterates over region codes shared with a metadata snapshot. The iterator owns
+/// the list, so it doesn't borrow the snapshot.
+fn iter_shared_region_codes(
+    region_codes: Arc<[&'static str]>,
+) -> impl ExactSizeIterator<Item = &'static str> {
+    (0..region_codes.len()).map(move |index| region_codes[index])
+}
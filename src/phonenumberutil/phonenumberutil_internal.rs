@@ -15,7 +15,9 @@
 
 use std::{
     borrow::Cow,
+    cell::RefCell,
     collections::{HashMap, HashSet, VecDeque},
+    ops::Range,
     sync::Arc,
 };
 
@@ -27,28 +29,37 @@ use super::{
         RFC3966_ISDN_SUBADDRESS, RFC3966_PHONE_CONTEXT, RFC3966_PREFIX,
     }, helper_functions::{
         self, copy_core_fields_only, get_number_desc_by_type, get_supported_types_for_metadata,
-        is_national_number_suffix_of_the_other, load_compiled_metadata, normalize_helper,
-        prefix_number_with_country_calling_code, test_number_length,
+        digits_edit_distance, is_national_number_suffix_of_the_other, load_compiled_metadata, normalize_helper,
+        prefix_number_with_country_calling_code, set_string_field, test_number_length,
         test_number_length_with_unknown_type,
     },
-    helper_types::{PhoneNumberWithCountryCodeSource}, 
-    enums::{MatchType, PhoneNumberFormat, PhoneNumberType, NumberLengthType},
+    helper_types::{LeadingDigitsSet, PhoneNumberWithCountryCodeSource},
+    metadata_validation::{MetadataProblem, PatternKind, validate_metadata_collection_with},
+    parse_options::ParseOptions,
+    util_options::PhoneNumberUtilOptions,
+    number_parts::NumberParts,
+    borrowed_number::BorrowedNumber,
+    common_formats::CommonFormats,
+    number_or_string::NumberOrString,
+    enums::{ExtensionLabelKind, MatchType, PhoneNumberFormat, PhoneNumberType, NumberLengthType},
     errors::{
         ExtractNumberError, GetExampleNumberError, InternalLogicError,
         InvalidMetadataForValidRegionError, InvalidNumberErrorInternal, ParseError,
-        ValidationError, NotANumberError, ParseErrorInternal, 
+        TypeValidationError, ValidationError, NotANumberError, ParseErrorInternal, 
     },
 };
 use crate::{
     generated::proto::{
         phonemetadata::{NumberFormat, PhoneMetadata, PhoneMetadataCollection, PhoneNumberDesc},
         phonenumber::{phone_number::CountryCodeSource, PhoneNumber}
-    }, interfaces::MatcherApi, macros::owned_from_cow_or, regex_based_matcher::RegexBasedMatcher, regex_util::{RegexConsume, RegexFullMatch}, regexp_cache::InvalidRegexError, region_code::RegionCode, string_util::strip_cow_prefix, GetExampleNumberErrorInternal,
+    }, interfaces::MatcherApi, macros::owned_from_cow_or, regex_based_matcher::RegexBasedMatcher, regex_util::{RegexConsume, RegexFullMatch}, regexp_cache::{CacheStats, InvalidRegexError, RegexCache}, region_code::RegionCode, string_util::strip_cow_prefix, GetExampleNumberErrorInternal,
 };
 
 use dec_from_char::DecimalExtended;
-use log::{error, trace, warn};
-use regex::Regex;
+use crate::macros::{error, trace, warn};
+use protobuf::Message;
+use regex::{NoExpand, Regex, SetMatches};
+use strum::IntoEnumIterator;
 
 // Helper type for Result
 
@@ -64,45 +75,136 @@ pub type InternalLogicResult<T> = std::result::Result<T, InternalLogicError>;
 
 pub struct PhoneNumberUtilInternal {
     /// An API for validation checking.
-    matcher_api: Box<dyn MatcherApi>,
+    matcher_api: Arc<dyn MatcherApi>,
 
     /// Helper class holding useful regular expressions and character mappings.
-    reg_exps: PhoneNumberRegExpsAndMappings,
+    /// It doesn't depend on the metadata, so it is shared with the instances
+    /// created by `with_metadata`.
+    reg_exps: Arc<PhoneNumberRegExpsAndMappings>,
 
     /// A mapping from a country calling code to a RegionCode object which denotes
     /// NANPA share the country calling code 1 and Russia and Kazakhstan share the
     /// country calling code 7. Under this map, 1 is mapped to region code "US" and
     /// 7 is mapped to region code "RU". This is implemented as a sorted vector to
-    /// achieve better performance.
-    country_calling_code_to_region_code_map: Vec<(i32, Vec<String>)>,
+    /// achieve better performance. The region lists are shared, so they can be
+    /// handed out without copying.
+    country_calling_code_to_region_code_map: Vec<(i32, Arc<[&'static str]>)>,
 
-    nanpa_regions: HashSet<String>,
+    /// Region codes of NANPA countries.
+    nanpa_regions: HashSet<&'static str>,
+
+    /// The region codes of `nanpa_regions` in alphabetical order, for listing
+    /// them without sorting on every call.
+    sorted_nanpa_regions: Arc<[&'static str]>,
 
     /// A mapping from a region code to a PhoneMetadata for that region.
-    region_to_metadata_map: HashMap<String, PhoneMetadata>,
+    region_to_metadata_map: HashMap<&'static str, PhoneMetadata>,
+
+    /// The keys of `region_to_metadata_map` in alphabetical order.
+    supported_regions: Arc<[&'static str]>,
 
     /// A mapping from a country calling code for a non-geographical entity to the
     /// PhoneMetadata for that country calling code. Examples of the country
     /// calling codes include 800 (International Toll Free Service) and 808
     /// (International Shared Cost Service).
     country_code_to_non_geographical_metadata_map: HashMap<i32, PhoneMetadata>,
+
+    /// For country calling codes shared by several regions, the leading digits
+    /// of those regions compiled into one set, so that the region of a number
+    /// is resolved without trying each region's pattern in turn.
+    leading_digits_sets: HashMap<i32, LeadingDigitsSet>,
+
+    /// The number formats of the metadata with their national prefix formatting
+    /// rule applied, e.g. "$1 $2" with the rule "0$1" becomes "0$1 $2". Keyed by
+    /// the rule first and by the format second, so that lookups don't allocate
+    /// a combined key.
+    national_prefix_templates: HashMap<String, HashMap<String, String>>,
 }
 
 impl PhoneNumberUtilInternal {
     pub(crate) fn new_for_metadata(metadata_collection: PhoneMetadataCollection) -> Self {
+        Self::new_for_metadata_and_reg_exps(metadata_collection, PhoneNumberRegExpsAndMappings::new())
+    }
+
+    pub(crate) fn new_for_metadata_and_reg_exps(
+        metadata_collection: PhoneMetadataCollection,
+        reg_exps: PhoneNumberRegExpsAndMappings,
+    ) -> Self {
         let mut instance = Self {
-            matcher_api: Box::new(RegexBasedMatcher::new()),
-            reg_exps: PhoneNumberRegExpsAndMappings::new(),
+            matcher_api: Arc::new(RegexBasedMatcher::new()),
+            reg_exps: Arc::new(reg_exps),
             country_calling_code_to_region_code_map: Default::default(),
             nanpa_regions: Default::default(),
+            sorted_nanpa_regions: Default::default(),
             region_to_metadata_map: Default::default(),
+            supported_regions: Default::default(),
             country_code_to_non_geographical_metadata_map: Default::default(),
+            leading_digits_sets: Default::default(),
+            national_prefix_templates: Default::default(),
         };
+        instance.load_metadata_collection(metadata_collection);
+        instance
+    }
+
+    /// Creates a new instance with the given metadata that shares the regular
+    /// expressions, caches and options of this one, so patterns that didn't
+    /// change are not compiled again.
+    ///
+    /// # Arguments
+    ///
+    /// * `metadata_collection` - The metadata of the new instance.
+    pub(crate) fn with_metadata(&self, metadata_collection: PhoneMetadataCollection) -> Self {
+        let mut instance = Self {
+            matcher_api: Arc::clone(&self.matcher_api),
+            reg_exps: Arc::clone(&self.reg_exps),
+            country_calling_code_to_region_code_map: Default::default(),
+            nanpa_regions: Default::default(),
+            sorted_nanpa_regions: Default::default(),
+            region_to_metadata_map: Default::default(),
+            supported_regions: Default::default(),
+            country_code_to_non_geographical_metadata_map: Default::default(),
+            leading_digits_sets: Default::default(),
+            national_prefix_templates: Default::default(),
+        };
+        instance.load_metadata_collection(metadata_collection);
+        instance
+    }
+
+    /// Compiles every pattern of the collection into the regex caches, which are
+    /// shared with the instances created by `with_metadata`, so that loading the
+    /// collection afterwards doesn't compile them again.
+    ///
+    /// # Returns
+    ///
+    /// The `MetadataProblem::InvalidPattern` of the first pattern that doesn't compile.
+    pub(crate) fn compile_metadata_patterns(
+        &self,
+        metadata_collection: &PhoneMetadataCollection,
+    ) -> Result<(), MetadataProblem> {
+        validate_metadata_collection_with(metadata_collection, |kind, pattern| {
+            let compiled = match kind {
+                PatternKind::NationalNumber => self.matcher_api.compile_pattern(pattern),
+                PatternKind::Other => self.reg_exps.regexp_cache.get_regex(pattern).map(drop),
+            };
+            compiled.map_err(|InvalidRegexError(err)| err.to_string())
+        })
+        .into_iter()
+        .find(|problem| matches!(problem, MetadataProblem::InvalidPattern { .. }))
+        .map_or(Ok(()), Err)
+    }
 
+    /// Fills the metadata maps from the given collection. The maps are expected
+    /// to be empty.
+    fn load_metadata_collection(&mut self, metadata_collection: PhoneMetadataCollection) {
         // that share a country calling code when inserting data.
-        let mut country_calling_code_to_region_map = HashMap::<i32, VecDeque<String>>::new();
+        let mut country_calling_code_to_region_map = HashMap::<i32, VecDeque<&'static str>>::new();
         for metadata in metadata_collection.metadata {
-            let region_code = &metadata.id().to_string();
+            // Region codes are stored as `'static` strings, so that they can be
+            // returned without borrowing the metadata.
+            let Some(region_code) = RegionCode::to_static(metadata.id()) else {
+                warn!("Skipping metadata with invalid region code {:?}", metadata.id());
+                continue;
+            };
             let main_country_code = metadata.main_country_for_code();
             if RegionCode::get_unknown() == region_code {
                 continue;
@@ -110,45 +212,108 @@ impl PhoneNumberUtilInternal {
 
             let country_calling_code = metadata.country_code();
             if REGION_CODE_FOR_NON_GEO_ENTITY == region_code {
-                instance
+                self
                     .country_code_to_non_geographical_metadata_map
                     .insert(country_calling_code, metadata);
             } else {
-                instance
+                self
                     .region_to_metadata_map
-                    .insert(region_code.to_owned(), metadata);
+                    .insert(region_code, metadata);
             }
 
             let calling_code_in_map_o =
                 country_calling_code_to_region_map.get_mut(&country_calling_code);
             if let Some(calling_code_in) = calling_code_in_map_o {
                 if main_country_code {
-                    calling_code_in.push_front(region_code.to_owned());
+                    calling_code_in.push_front(region_code);
                 } else {
-                    calling_code_in.push_back(region_code.to_owned());
+                    calling_code_in.push_back(region_code);
                 }
             } else {
                 // For most country calling codes, there will be only one region code.
                 let mut list_with_region_code = VecDeque::new();
-                list_with_region_code.push_back(region_code.to_owned());
+                list_with_region_code.push_back(region_code);
                 country_calling_code_to_region_map
                     .insert(country_calling_code, list_with_region_code);
             }
             if country_calling_code == NANPA_COUNTRY_CODE {
-                instance.nanpa_regions.insert(region_code.to_owned());
+                self.nanpa_regions.insert(region_code);
             }
         }
 
-        instance.country_calling_code_to_region_code_map.extend(
+        self.country_calling_code_to_region_code_map.extend(
             country_calling_code_to_region_map
                 .into_iter()
-                .map(|(k, v)| (k, Vec::from(v))),
+                .map(|(k, v)| (k, Arc::from(Vec::from(v)))),
         );
         // Sort all the pairs in ascending order according to country calling code.
-        instance
+        self
             .country_calling_code_to_region_code_map
             .sort_by_key(|(a, _)| *a);
-        instance
+        let mut supported_regions: Vec<_> = self.region_to_metadata_map.keys().copied().collect();
+        supported_regions.sort_unstable();
+        self.supported_regions = supported_regions.into();
+        let mut sorted_nanpa_regions: Vec<_> = self.nanpa_regions.iter().copied().collect();
+        sorted_nanpa_regions.sort_unstable();
+        self.sorted_nanpa_regions = sorted_nanpa_regions.into();
+        self.build_leading_digits_sets();
+        self.build_national_prefix_templates();
+    }
+
+    /// Applies the national prefix formatting rule of every number format of the
+    /// metadata once, so that national formatting doesn't redo the substitution
+    /// on every call.
+    fn build_national_prefix_templates(&mut self) {
+        let number_formats = self
+            .region_to_metadata_map
+            .values()
+            .chain(self.country_code_to_non_geographical_metadata_map.values())
+            .flat_map(|metadata| &metadata.number_format)
+            .filter(|number_format| !number_format.national_prefix_formatting_rule().is_empty());
+        for number_format in number_formats {
+            let national_prefix_formatting_rule = number_format.national_prefix_formatting_rule();
+            let templates = self
+                .national_prefix_templates
+                .entry(national_prefix_formatting_rule.to_owned())
+                .or_default();
+            if !templates.contains_key(number_format.format()) {
+                let template = self
+                    .reg_exps
+                    .first_group_capturing_pattern
+                    .replace(number_format.format(), national_prefix_formatting_rule)
+                    .into_owned();
+                templates.insert(number_format.format().to_owned(), template);
+            }
+        }
+    }
+
+    /// Compiles the leading digits of the regions sharing each country calling
+    /// code into a `LeadingDigitsSet`. Codes whose patterns fail to compile are
+    /// left out; their regions are then matched one by one through the regex
+    /// cache, which reports the error.
+    fn build_leading_digits_sets(&mut self) {
+        for (country_calling_code, region_codes) in &self.country_calling_code_to_region_code_map {
+            if region_codes.len() < 2 {
+                continue;
+            }
+            let leading_digits = region_codes.iter().filter_map(|&region_code| {
+                self.region_to_metadata_map
+                    .get(region_code)
+                    .filter(|metadata| metadata.has_leading_digits())
+                    .map(|metadata| (region_code, metadata.leading_digits()))
+            });
+            match LeadingDigitsSet::new(leading_digits) {
+                Ok(set) => {
+                    self.leading_digits_sets.insert(*country_calling_code, set);
+                }
+                Err(err) => {
+                    warn!(
+                        "Could not compile leading digits for country calling code {}: {}",
+                        country_calling_code, err
+                    );
+                }
+            }
+        }
     }
 
     /// Creates a new instance of the phone number utility.
@@ -160,10 +325,85 @@ impl PhoneNumberUtilInternal {
         Ok(Self::new_for_metadata(metadata_collection))
     }
 
+    /// Creates a new instance with the compiled metadata of the given regions only,
+    /// plus the regions sharing their country calling codes. Unknown region codes
+    /// are ignored; `REGION_CODE_FOR_NON_GEO_ENTITY` keeps all non-geographical
+    /// entities.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_codes` - The regions to keep the metadata of.
+    pub(crate) fn new_for_regions(region_codes: &[&str]) -> Result<Self, protobuf::Error> {
+        let mut metadata_collection = load_compiled_metadata()?;
+        // Regions sharing a country calling code are resolved against each other,
+        // so all of them have to be kept for the requested ones to work.
+        let country_calling_codes: HashSet<i32> = metadata_collection
+            .metadata
+            .iter()
+            .filter(|metadata| region_codes.contains(&metadata.id()))
+            .map(|metadata| metadata.country_code())
+            .collect();
+        metadata_collection
+            .metadata
+            .retain(|metadata| country_calling_codes.contains(&metadata.country_code()));
+        Ok(Self::new_for_metadata(metadata_collection))
+    }
+
+    /// Creates a new instance with the compiled metadata that additionally treats
+    /// the given characters as extension separators when parsing and matching,
+    /// e.g. '/' for "+44 20 3456 7890 / 456".
+    pub(crate) fn with_extra_extension_separators(
+        extra_extension_separators: &[char],
+    ) -> Result<Self, protobuf::Error> {
+        let metadata_collection = load_compiled_metadata()?;
+        Ok(Self::new_for_metadata_and_reg_exps(
+            metadata_collection,
+            PhoneNumberRegExpsAndMappings::with_extra_extension_separators(extra_extension_separators),
+        ))
+    }
+
+    /// Same as `new`, but applies the given options, such as a bound on the
+    /// regex caches.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The options to create the instance with.
+    pub(crate) fn new_with_options(
+        options: PhoneNumberUtilOptions,
+    ) -> Result<Self, protobuf::Error> {
+        let metadata_collection = load_compiled_metadata()?;
+        let mut reg_exps = PhoneNumberRegExpsAndMappings::new();
+        if let Some(capacity) = options.regex_cache_capacity {
+            reg_exps.regexp_cache = RegexCache::with_capacity(capacity);
+        }
+        let mut instance = Self::new_for_metadata_and_reg_exps(metadata_collection, reg_exps);
+        if let Some(capacity) = options.regex_cache_capacity {
+            instance.matcher_api =
+                Arc::new(RegexBasedMatcher::with_regex_cache(RegexCache::with_capacity(capacity)));
+        }
+        Ok(instance)
+    }
+
+    /// Returns the combined statistics of the regex caches.
+    pub(crate) fn regex_cache_stats(&self) -> CacheStats {
+        let reg_exps_stats = self.reg_exps.regexp_cache.stats();
+        let matcher_stats = self.matcher_api.regex_cache_stats();
+        CacheStats {
+            hits: reg_exps_stats.hits + matcher_stats.hits,
+            misses: reg_exps_stats.misses + matcher_stats.misses,
+            size: reg_exps_stats.size + matcher_stats.size,
+        }
+    }
+
     /// Gets an iterator over all region codes supported by the library.
     /// These are the regions for which metadata is available.
-    pub(crate) fn get_supported_regions(&self) -> impl ExactSizeIterator<Item = &str> {
-        self.region_to_metadata_map.keys().map(|k| k.as_str())
+    pub(crate) fn get_supported_regions(&self) -> impl ExactSizeIterator<Item = &'static str> + '_ {
+        self.supported_regions.iter().copied()
+    }
+
+    /// Same as `get_supported_regions`, as the list shared by this instance.
+    pub(crate) fn supported_regions(&self) -> &Arc<[&'static str]> {
+        &self.supported_regions
     }
 
     /// Gets an iterator over all supported global network calling codes.
@@ -181,6 +421,20 @@ impl PhoneNumberUtilInternal {
             .map(|(k, _)| *k)
     }
 
+    /// Returns the region codes of all NANPA countries in alphabetical order.
+    pub(crate) fn get_nanpa_regions(&self) -> &Arc<[&'static str]> {
+        &self.sorted_nanpa_regions
+    }
+
+    /// Checks whether the region code is a real geographical region with metadata.
+    /// Returns false for the non-geographical entity "001", for the unknown
+    /// region "ZZ" and for unsupported region codes.
+    pub(crate) fn is_geo_region(&self, region_code: &str) -> bool {
+        // Non-geographical entities and the unknown region are never stored in
+        // this map.
+        self.region_to_metadata_map.contains_key(region_code)
+    }
+
     pub(crate) fn is_nanpa_country(&self, region_code: &str) -> bool {
         self.nanpa_regions.contains(region_code)
     }
@@ -203,6 +457,32 @@ impl PhoneNumberUtilInternal {
             })
     }
 
+    /// Gets the supported phone number types of a region which have lengths
+    /// that can only be dialled locally, in the declaration order of
+    /// `PhoneNumberType`. Returns an empty list for unknown regions.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_code` - The region for which to get the types.
+    pub(crate) fn get_types_with_local_only_lengths_for_region(
+        &self,
+        region_code: &str,
+    ) -> Vec<PhoneNumberType> {
+        let Some(metadata) = self.region_to_metadata_map.get(region_code) else {
+            warn!("Invalid or unknown region code provided: {}", region_code);
+            return Vec::new();
+        };
+        let supported_types = get_supported_types_for_metadata(metadata);
+        PhoneNumberType::iter()
+            .filter(|number_type| supported_types.contains(number_type))
+            .filter(|&number_type| {
+                !get_number_desc_by_type(metadata, number_type)
+                    .possible_length_local_only
+                    .is_empty()
+            })
+            .collect()
+    }
+
     /// Gets a list of all supported phone number types for a given non-geographical country calling code.
     ///
     /// # Arguments
@@ -231,6 +511,15 @@ impl PhoneNumberUtilInternal {
             .map(| metadata | metadata.country_code())
     }
 
+    /// Returns the country calling code of every given region, in the same
+    /// order. Unknown regions yield `None` in their position.
+    pub(crate) fn get_country_codes_for_regions(&self, region_codes: &[impl AsRef<str>]) -> Vec<Option<i32>> {
+        region_codes
+            .iter()
+            .map(|region_code| self.get_country_code_for_region(region_code.as_ref()))
+            .collect()
+    }
+
     pub(crate) fn get_extn_patterns_for_matching(&self) -> &str {
         return &self.reg_exps.extn_patterns_for_matching;
     }
@@ -265,8 +554,17 @@ impl PhoneNumberUtilInternal {
         }
     }
 
-    /// formatter is not implemented yet, but ..
-    #[allow(unused)]
+    /// Returns the regexp cache shared by everything built on top of this
+    /// instance, such as the as-you-type formatter.
+    pub(crate) fn regexp_cache(&self) -> &RegexCache {
+        &self.reg_exps.regexp_cache
+    }
+
+    pub(crate) fn matcher_api(&self) -> &dyn MatcherApi {
+        self.matcher_api.as_ref()
+    }
+
+    /// Checks whether a number format can be used by the as-you-type formatter.
     pub(crate) fn is_format_eligible_for_as_you_type_formatter(&self, format: &str) -> bool {
         // We require that the first
         // group is present in the output pattern to ensure no data is lost while
@@ -277,7 +575,6 @@ impl PhoneNumberUtilInternal {
             .full_match(format);
     }
 
-    #[allow(unused)]
     pub(crate) fn formatting_rule_has_first_group_only(
         &self,
         national_prefix_formatting_rule: &str,
@@ -324,6 +621,129 @@ impl PhoneNumberUtilInternal {
         phone_number: &'b PhoneNumber,
         number_format: PhoneNumberFormat,
     ) -> RegexResult<Cow<'b, str>> {
+        self.format_with_extension_length(phone_number, number_format)
+            .map(|(formatted_number, _)| formatted_number)
+    }
+
+    /// Formats a phone number and returns the byte range of the formatted
+    /// extension, including its label, within the result. The range is `None`
+    /// when no extension is written.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The phone number to be formatted.
+    /// * `number_format` - The format to be used.
+    pub(crate) fn format_with_extension_span(
+        &self,
+        phone_number: &PhoneNumber,
+        number_format: PhoneNumberFormat,
+    ) -> RegexResult<(String, Option<Range<usize>>)> {
+        let (formatted_number, extension_length) =
+            self.format_with_extension_length(phone_number, number_format)?;
+        // The extension is always the last part of the formatted number.
+        let extension_span = extension_length
+            .map(|length| formatted_number.len() - length..formatted_number.len());
+        Ok((formatted_number.into_owned(), extension_span))
+    }
+
+    /// Formats a phone number without its extension, returning the extension
+    /// separately. The extension is `None` when the number has none.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The phone number to be formatted.
+    /// * `number_format` - The format to be used.
+    pub(crate) fn format_split_extension(
+        &self,
+        phone_number: &PhoneNumber,
+        number_format: PhoneNumberFormat,
+    ) -> RegexResult<(String, Option<String>)> {
+        let (formatted_number, extension_length) =
+            self.format_with_extension_length(phone_number, number_format)?;
+        let mut formatted_number = formatted_number.into_owned();
+        // The extension is always the last part of the formatted number.
+        if let Some(extension_length) = extension_length {
+            formatted_number.truncate(formatted_number.len() - extension_length);
+        }
+        let extension = phone_number
+            .extension
+            .as_ref()
+            .filter(|extension| !extension.is_empty())
+            .cloned();
+        Ok((formatted_number, extension))
+    }
+
+    /// Formats a phone number in national format, adding one `pad` character for
+    /// every digit the number is shorter than the longest possible number of its
+    /// region. The padding goes before the extension, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The phone number to be formatted.
+    /// * `pad` - The character used for padding.
+    pub(crate) fn format_national_padded(&self, phone_number: &PhoneNumber, pad: char) -> RegexResult<String> {
+        let (formatted_number, extension_length) =
+            self.format_with_extension_length(phone_number, PhoneNumberFormat::National)?;
+        let mut formatted_number = formatted_number.into_owned();
+
+        let country_calling_code = phone_number.country_code();
+        let region_code = self.get_region_code_for_country_code(country_calling_code);
+        let Some(metadata) =
+            self.get_metadata_for_region_or_calling_code(country_calling_code, region_code)
+        else {
+            return Ok(formatted_number);
+        };
+        let Some(max_length) = metadata.general_desc.possible_length.iter().max() else {
+            return Ok(formatted_number);
+        };
+        let nsn_length = self.get_national_significant_number(phone_number).len();
+        let padding = (*max_length as usize).saturating_sub(nsn_length);
+
+        // The extension is always the last part of the formatted number.
+        let padding_position = formatted_number.len() - extension_length.unwrap_or(0);
+        let padding: String = std::iter::repeat_n(pad, padding).collect();
+        formatted_number.insert_str(padding_position, &padding);
+        Ok(formatted_number)
+    }
+
+    /// Formats a phone number and returns the rule of the metadata used to
+    /// format its national significant number. The rule is `None` when the
+    /// digits were written without grouping, e.g. for E164 or when no rule of
+    /// the region matches the number.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The phone number to be formatted.
+    /// * `number_format` - The format to be used.
+    pub(crate) fn format_with_format_info(
+        &self,
+        phone_number: &PhoneNumber,
+        number_format: PhoneNumberFormat,
+    ) -> RegexResult<(String, Option<&NumberFormat>)> {
+        let (formatted_number, _, formatting_pattern) =
+            self.format_with_details(phone_number, number_format)?;
+        Ok((formatted_number.into_owned(), formatting_pattern))
+    }
+
+    /// Formats a phone number, also returning the length of the formatted
+    /// extension at the end of the result, if one was written.
+    fn format_with_extension_length<'b>(
+        &self,
+        phone_number: &'b PhoneNumber,
+        number_format: PhoneNumberFormat,
+    ) -> RegexResult<(Cow<'b, str>, Option<usize>)> {
+        self.format_with_details(phone_number, number_format)
+            .map(|(formatted_number, extension_length, _)| (formatted_number, extension_length))
+    }
+
+    /// Formats a phone number, also returning the length of the formatted
+    /// extension at the end of the result, if one was written, and the rule of
+    /// the metadata used to format the national significant number, if any.
+    fn format_with_details<'b>(
+        &self,
+        phone_number: &'b PhoneNumber,
+        number_format: PhoneNumberFormat,
+    ) -> RegexResult<(Cow<'b, str>, Option<usize>, Option<&NumberFormat>)> {
         if phone_number.national_number() == 0 {
             let raw_input = phone_number.raw_input();
             if !raw_input.is_empty() {
@@ -332,7 +752,7 @@ impl PhoneNumberUtilInternal {
                 // leading '+' symbol (but the original number wasn't parseable anyway).
                 // TODO: Consider removing the 'if' above so that unparseable
                 // strings without raw input format to the empty string instead of "+00".
-                return Ok(Cow::Borrowed(raw_input));
+                return Ok((Cow::Borrowed(raw_input), None, None));
             }
         }
         let country_calling_code = phone_number.country_code();
@@ -347,7 +767,7 @@ impl PhoneNumberUtilInternal {
                 PhoneNumberFormat::E164,
                 &mut formatted_number,
             );
-            return Ok(Cow::Owned(formatted_number));
+            return Ok((Cow::Owned(formatted_number), None, None));
         }
         // Note here that all NANPA formatting rules are contained by US, so we use
         // rules are contained by Russia. French Indian Ocean country rules are
@@ -356,14 +776,20 @@ impl PhoneNumberUtilInternal {
         let metadata =
             self.get_metadata_for_region_or_calling_code(country_calling_code, &region_code);
 
+        let mut extension_length = None;
+        let mut formatting_pattern = None;
         if let Some(metadata) = metadata {
-            if let Cow::Owned(s) = self.format_nsn(&formatted_number, metadata, number_format)? {
+            let (formatted_nsn, applied_pattern) =
+                self.format_nsn_reporting_pattern(&formatted_number, metadata, number_format, "")?;
+            if let Cow::Owned(s) = formatted_nsn {
                 formatted_number = s;
             }
+            formatting_pattern = applied_pattern;
             if let Some(formatted_extension) =
                 Self::get_formatted_extension(phone_number, metadata, number_format)
             {
                 formatted_number.push_str(&formatted_extension);
+                extension_length = Some(formatted_extension.len());
             }
             prefix_number_with_country_calling_code(
                 country_calling_code,
@@ -371,24 +797,292 @@ impl PhoneNumberUtilInternal {
                 &mut formatted_number,
             );
         }
-        Ok(Cow::Owned(formatted_number))
+        Ok((Cow::Owned(formatted_number), extension_length, formatting_pattern))
     }
 
+    /// Formats a phone number in the E164, international, national and RFC3966
+    /// formats at once. The metadata lookup and the choice of the formatting
+    /// patterns are done once and shared by all formats.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The phone number to be formatted.
+    pub(crate) fn format_common(&self, phone_number: &PhoneNumber) -> RegexResult<CommonFormats> {
+        if phone_number.national_number() == 0 {
+            let raw_input = phone_number.raw_input();
+            if !raw_input.is_empty() {
+                // Unparseable numbers that kept their raw input just use that, as in `format`.
+                return Ok(CommonFormats {
+                    e164: raw_input.to_owned(),
+                    international: raw_input.to_owned(),
+                    national: raw_input.to_owned(),
+                    rfc3966: raw_input.to_owned(),
+                });
+            }
+        }
+        let country_calling_code = phone_number.country_code();
+        let national_significant_number = self.get_national_significant_number(phone_number);
+
+        let mut e164 = national_significant_number.clone();
+        prefix_number_with_country_calling_code(
+            country_calling_code,
+            PhoneNumberFormat::E164,
+            &mut e164,
+        );
+
+        let region_code = self.get_region_code_for_country_code(country_calling_code);
+        let Some(metadata) =
+            self.get_metadata_for_region_or_calling_code(country_calling_code, region_code)
+        else {
+            // Without metadata the national significant number is returned as is.
+            return Ok(CommonFormats {
+                e164,
+                international: national_significant_number.clone(),
+                national: national_significant_number.clone(),
+                rfc3966: national_significant_number,
+            });
+        };
+
+        let national_pattern = self.choose_formatting_pattern_for_number(
+            &metadata.number_format,
+            &national_significant_number,
+        )?;
+        // The international formats use intl_number_format when it exists, so the
+        // pattern chosen for the national format can only be reused otherwise.
+        let international_pattern = if metadata.intl_number_format.is_empty() {
+            national_pattern
+        } else {
+            self.choose_formatting_pattern_for_number(
+                &metadata.intl_number_format,
+                &national_significant_number,
+            )?
+        };
+
+        let format_with = |formatting_pattern: Option<&NumberFormat>,
+                           number_format: PhoneNumberFormat|
+         -> RegexResult<String> {
+            let mut formatted_number = match formatting_pattern {
+                Some(formatting_pattern) => self
+                    .format_nsn_using_pattern(
+                        &national_significant_number,
+                        formatting_pattern,
+                        number_format,
+                    )?
+                    .into_owned(),
+                None => national_significant_number.clone(),
+            };
+            if let Some(formatted_extension) =
+                Self::get_formatted_extension(phone_number, metadata, number_format)
+            {
+                formatted_number.push_str(&formatted_extension);
+            }
+            prefix_number_with_country_calling_code(
+                country_calling_code,
+                number_format,
+                &mut formatted_number,
+            );
+            Ok(formatted_number)
+        };
+
+        Ok(CommonFormats {
+            international: format_with(international_pattern, PhoneNumberFormat::International)?,
+            national: format_with(national_pattern, PhoneNumberFormat::National)?,
+            rfc3966: format_with(international_pattern, PhoneNumberFormat::RFC3966)?,
+            e164,
+        })
+    }
+
+    /// Formats a phone number in national format with the grouping parentheses
+    /// removed, e.g. "(020) 8738 9353" becomes "020 8738 9353". Numbers whose
+    /// national format doesn't use parentheses are returned unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The phone number to format.
+    pub(crate) fn format_national_no_parens<'b>(
+        &self,
+        phone_number: &'b PhoneNumber,
+    ) -> RegexResult<Cow<'b, str>> {
+        let formatted_number = self.format(phone_number, PhoneNumberFormat::National)?;
+        if !formatted_number.contains(['(', ')']) {
+            return Ok(formatted_number);
+        }
+        Ok(Cow::Owned(
+            formatted_number
+                .chars()
+                .filter(|c| !matches!(c, '(' | ')'))
+                .collect(),
+        ))
+    }
+
+    /// Formats a phone number in the given format with the grouping spaces
+    /// replaced by non-breaking spaces (U+00A0). Spaces inside the extension
+    /// suffix, e.g. " ext. ", are left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The phone number to format.
+    /// * `number_format` - The format to apply.
+    pub(crate) fn format_nbsp<'b>(
+        &self,
+        phone_number: &'b PhoneNumber,
+        number_format: PhoneNumberFormat,
+    ) -> RegexResult<Cow<'b, str>> {
+        let (formatted_number, extension_length) =
+            self.format_with_extension_length(phone_number, number_format)?;
+        if !formatted_number.contains(' ') {
+            return Ok(formatted_number);
+        }
+        // The extension is always appended at the very end, so the grouped part is
+        // everything before it.
+        let grouped_len = formatted_number.len() - extension_length.unwrap_or(0);
+        let (grouped, extension) = formatted_number.split_at(grouped_len);
+        Ok(Cow::Owned(fast_cat::concat_str!(
+            &grouped.replace(' ', "\u{a0}"),
+            extension
+        )))
+    }
+
+    /// Returns the country calling code followed by the national significant number,
+    /// i.e. the E164 format without the leading plus sign. Extensions are dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The phone number to format.
+    pub(crate) fn format_e164_digits(&self, phone_number: &PhoneNumber) -> String {
+        let mut buf = itoa::Buffer::new();
+        let country_calling_code = buf.format(phone_number.country_code());
+        fast_cat::concat_str!(
+            country_calling_code,
+            &self.get_national_significant_number(phone_number)
+        )
+    }
+
+    /// Formats the number for text-to-speech: the country calling code with a
+    /// leading plus, followed by every digit of the national significant number
+    /// separated by spaces. An extension is appended the same way after " ext. ".
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The phone number to format.
+    pub(crate) fn format_for_screen_reader(&self, phone_number: &PhoneNumber) -> String {
+        let national_significant_number = self.get_national_significant_number(phone_number);
+        let mut buf = itoa::Buffer::new();
+        let country_calling_code = buf.format(phone_number.country_code());
+
+        let mut formatted_number = String::with_capacity(
+            PLUS_SIGN.len() + country_calling_code.len() + national_significant_number.len() * 2,
+        );
+        formatted_number.push_str(PLUS_SIGN);
+        formatted_number.push_str(country_calling_code);
+        let push_spaced_digits = |formatted_number: &mut String, digits: &str| {
+            for digit in digits.chars() {
+                formatted_number.push(' ');
+                formatted_number.push(digit);
+            }
+        };
+        push_spaced_digits(&mut formatted_number, &national_significant_number);
+        if phone_number.has_extension() && !phone_number.extension().is_empty() {
+            formatted_number.push_str(DEFAULT_EXTN_PREFIX.trim_end());
+            push_spaced_digits(&mut formatted_number, phone_number.extension());
+        }
+        formatted_number
+    }
+
+    /// Packs the country calling code and the national number into an `i128`:
+    /// bits 64..=73 hold the country calling code, bits 0..=63 the national
+    /// number, all other bits are zero. Returns `None` for numbers with an
+    /// extension, with Italian leading zeros or with a country calling code
+    /// outside of 1..=999, as these can't be restored from the packed value.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The phone number to pack.
+    pub(crate) fn pack(&self, phone_number: &PhoneNumber) -> Option<i128> {
+        if phone_number.has_extension() || phone_number.italian_leading_zero() {
+            return None;
+        }
+        let country_calling_code = phone_number.country_code();
+        if !(1..=999).contains(&country_calling_code) {
+            return None;
+        }
+        Some((i128::from(country_calling_code) << 64) | i128::from(phone_number.national_number()))
+    }
+
+    /// Checks whether a number packed by `pack` has a possible length, without
+    /// restoring it into a `PhoneNumber`. Values that `pack` can't produce are
+    /// never possible.
+    ///
+    /// # Arguments
+    ///
+    /// * `packed` - A value returned by `pack`.
+    pub(crate) fn is_possible_packed(&self, packed: i128) -> bool {
+        if packed < 0 || packed >> 74 != 0 {
+            return false;
+        }
+        let country_code = (packed >> 64) as i32;
+        if !self.has_valid_country_calling_code(country_code) {
+            return false;
+        }
+        let region_code = self.get_region_code_for_country_code(country_code);
+        let Some(metadata) =
+            self.get_metadata_for_region_or_calling_code(country_code, region_code)
+        else {
+            return false;
+        };
+        // Packed numbers never have leading zeros, so the digits of the national
+        // number are the national significant number. Only its length matters.
+        let mut buf = itoa::Buffer::new();
+        let national_number = buf.format(packed as u64);
+        test_number_length(national_number, metadata, PhoneNumberType::Unknown).is_ok()
+    }
+
+    /// Restores a number packed by `pack`. Only the country calling code and
+    /// the national number are set.
+    ///
+    /// # Arguments
+    ///
+    /// * `packed` - A value returned by `pack`.
+    pub(crate) fn unpack(&self, packed: i128) -> PhoneNumber {
+        let mut phone_number = PhoneNumber::new();
+        // Truncation is intended: the low 64 bits are the national number, and
+        // the country calling code takes less than 32 bits above them.
+        phone_number.set_country_code((packed >> 64) as i32);
+        phone_number.set_national_number(packed as u64);
+        phone_number
+    }
+
+    /// Returns the national significant number, prefixed with the leading zeros
+    /// recorded in the number when `italian_leading_zero` is set.
+    ///
+    /// A non-positive `number_of_leading_zeros` adds no zeros, and the count is
+    /// capped at `MAX_LENGTH_FOR_NSN`, so programmatically built numbers with
+    /// stray values can't produce huge strings.
     pub(crate) fn get_national_significant_number(&self, phone_number: &PhoneNumber) -> String {
-        let zeros_start = if phone_number.italian_leading_zero() {
-            let zero_count = usize::try_from(phone_number.number_of_leading_zeros()).unwrap_or(0);
-            "0".repeat(zero_count)
+        let zero_count = if phone_number.italian_leading_zero() {
+            // Ensure the number of leading zeros is at least 0 so we don't crash in
+            // the case of malicious input.
+            usize::try_from(phone_number.number_of_leading_zeros())
+                .unwrap_or(0)
+                .min(MAX_LENGTH_FOR_NSN)
         } else {
-            "".to_string()
+            0
         };
 
         let mut buf = itoa::Buffer::new();
         let national_number = buf.format(phone_number.national_number());
 
         // If leading zero(s) have been set, we prefix this now. Note this is not a
-        // national prefix. Ensure the number of leading zeros is at least 0 so we
-        // don't crash in the case of malicious input.
-        return fast_cat::concat_str!(&zeros_start, national_number);
+        // national prefix.
+        let mut national_significant_number =
+            String::with_capacity(zero_count + national_number.len());
+        national_significant_number.extend(std::iter::repeat_n('0', zero_count));
+        national_significant_number.push_str(national_number);
+        debug_assert!(
+            national_significant_number.bytes().all(|b| b.is_ascii_digit()),
+            "National Significant Number must contain only digits, got '{national_significant_number}'"
+        );
+        national_significant_number
     }
 
     /// Returns the region code that matches the specific country calling code. In
@@ -397,7 +1091,7 @@ impl PhoneNumberUtilInternal {
     /// # Arguments
     /// 
     /// * `country_calling_code` - The country calling code.
-    pub(crate) fn get_region_code_for_country_code(&self, country_calling_code: i32) -> &str {
+    pub(crate) fn get_region_code_for_country_code(&self, country_calling_code: i32) -> &'static str {
         let region_codes = self.get_region_codes_for_country_calling_code(country_calling_code);
         return region_codes
             .and_then(|mut codes| codes.next())
@@ -414,18 +1108,42 @@ impl PhoneNumberUtilInternal {
     pub(crate) fn get_region_codes_for_country_calling_code(
         &self,
         country_calling_code: i32,
-    ) -> Option<impl ExactSizeIterator<Item = &str>> {
+    ) -> Option<impl ExactSizeIterator<Item = &'static str> + '_> {
+        self.regions_for_calling_code_slice(country_calling_code)
+            .map(|regions| regions.iter().copied())
+    }
+
+    /// Returns the region codes sharing a country calling code with the main
+    /// region first and the others sorted alphabetically. Returns `None` for
+    /// unknown codes.
+    ///
+    /// # Arguments
+    ///
+    /// * `country_calling_code` - The country calling code.
+    pub(crate) fn get_ordered_regions_for_country_code(
+        &self,
+        country_calling_code: i32,
+    ) -> Option<Vec<&'static str>> {
+        let mut region_codes: Vec<_> = self
+            .get_region_codes_for_country_calling_code(country_calling_code)?
+            .collect();
+        // The main region is always stored first.
+        if let Some((_, other_regions)) = region_codes.split_first_mut() {
+            other_regions.sort_unstable();
+        }
+        Some(region_codes)
+    }
+
+    /// Returns the region codes sharing a country calling code as the list stored
+    /// in the internal map, with the main region first. The list is shared, so it
+    /// can be handed out by cloning the `Arc`. Returns `None` for unknown codes.
+    pub(crate) fn regions_for_calling_code_slice(&self, country_calling_code: i32) -> Option<&Arc<[&'static str]>> {
         // Create a IntRegionsPair with the country_code passed in, and use it to
         // locate the pair with the same country_code in the sorted vector.
         self.country_calling_code_to_region_code_map
             .binary_search_by_key(&country_calling_code, |(code, _)| *code)
             .ok()
-            .map(|index| {
-                self.country_calling_code_to_region_code_map[index]
-                    .1
-                    .iter()
-                    .map(|v| v.as_str())
-            })
+            .map(|index| &self.country_calling_code_to_region_code_map[index].1)
     }
 
     pub(crate) fn get_metadata_for_region_or_calling_code(
@@ -457,27 +1175,51 @@ impl PhoneNumberUtilInternal {
         number_format: PhoneNumberFormat,
         carrier_code: &str,
     ) -> RegexResult<Cow<'b, str>> {
+        self.format_nsn_reporting_pattern(number, metadata, number_format, carrier_code)
+            .map(|(formatted_number, _)| formatted_number)
+    }
+
+    /// Same as `format_nsn_with_carrier`, but also returns the rule of the metadata
+    /// the number was formatted with, or `None` if its digits were left as they are.
+    fn format_nsn_reporting_pattern<'b, 'm>(
+        &self,
+        number: &'b str,
+        metadata: &'m PhoneMetadata,
+        number_format: PhoneNumberFormat,
+        carrier_code: &str,
+    ) -> RegexResult<(Cow<'b, str>, Option<&'m NumberFormat>)> {
+        let formatting_pattern =
+            self.choose_formatting_pattern_for_nsn(number, metadata, number_format)?;
+        let Some(formatting_pattern) = formatting_pattern else {
+            return Ok((Cow::Borrowed(number), None));
+        };
+        let formatted_number = self.format_nsn_using_pattern_with_carrier(
+            number,
+            formatting_pattern,
+            number_format,
+            carrier_code,
+        )?;
+        Ok((formatted_number, Some(formatting_pattern)))
+    }
+
+    /// Chooses the rule of the metadata used to format a national significant
+    /// number in the given format, if any applies.
+    fn choose_formatting_pattern_for_nsn<'m>(
+        &self,
+        national_number: &str,
+        metadata: &'m PhoneMetadata,
+        number_format: PhoneNumberFormat,
+    ) -> RegexResult<Option<&'m NumberFormat>> {
         // When the intl_number_formats exists, we use that to format national number
         // for the INTERNATIONAL format instead of using the number_formats.
-        let available_formats = if metadata.intl_number_format.len() == 0
+        let available_formats = if metadata.intl_number_format.is_empty()
             || number_format == PhoneNumberFormat::National
         {
             &metadata.number_format
         } else {
             &metadata.intl_number_format
         };
-        let formatting_pattern =
-            self.choose_formatting_pattern_for_number(available_formats, number)?;
-        if let Some(formatting_pattern) = formatting_pattern {
-            self.format_nsn_using_pattern_with_carrier(
-                number,
-                formatting_pattern,
-                number_format,
-                carrier_code,
-            )
-        } else {
-            Ok(Cow::Borrowed(number))
-        }
+        self.choose_formatting_pattern_for_number(available_formats, national_number)
     }
 
     pub(crate) fn choose_formatting_pattern_for_number<'b>(
@@ -520,6 +1262,7 @@ impl PhoneNumberUtilInternal {
         carrier_code: &str,
     ) -> RegexResult<Cow<'b, str>> {
         let mut number_format_rule = Cow::Borrowed(formatting_pattern.format());
+        let national_prefix_template: Cow<'_, str>;
         if matches!(number_format, PhoneNumberFormat::National)
             && carrier_code.len() > 0
             && formatting_pattern
@@ -556,13 +1299,8 @@ impl PhoneNumberUtilInternal {
                 // Apply the national_prefix_formatting_rule as the formatting_pattern
                 // contains only information on how the national significant number
                 // should be formatted at this point.
-                if let Cow::Owned(s) = self
-                    .reg_exps
-                    .first_group_capturing_pattern
-                    .replace(&number_format_rule, national_prefix_formatting_rule)
-                {
-                    number_format_rule = Cow::Owned(s);
-                }
+                national_prefix_template = self.national_prefix_template(formatting_pattern);
+                number_format_rule = Cow::Borrowed(&national_prefix_template);
             }
         }
 
@@ -596,6 +1334,24 @@ impl PhoneNumberUtilInternal {
         Ok(formatted_number)
     }
 
+    /// Returns the format of `formatting_pattern` with its national prefix formatting
+    /// rule applied to the first group. Formats of the metadata were prepared when
+    /// it was loaded; others, e.g. user-defined ones, are built on every call.
+    fn national_prefix_template<'a>(&'a self, formatting_pattern: &'a NumberFormat) -> Cow<'a, str> {
+        let number_format_rule = formatting_pattern.format();
+        let national_prefix_formatting_rule = formatting_pattern.national_prefix_formatting_rule();
+        if let Some(template) = self
+            .national_prefix_templates
+            .get(national_prefix_formatting_rule)
+            .and_then(|templates| templates.get(number_format_rule))
+        {
+            return Cow::Borrowed(template);
+        }
+        self.reg_exps
+            .first_group_capturing_pattern
+            .replace(number_format_rule, national_prefix_formatting_rule)
+    }
+
     /// Simple wrapper of FormatNsnUsingPatternWithCarrier for the common case of
     /// no carrier code.
     pub(crate) fn format_nsn_using_pattern<'b>(
@@ -635,6 +1391,10 @@ impl PhoneNumberUtilInternal {
 
     /// Formats a phone number using a user-defined pattern.
     ///
+    /// Besides the groups `$1..$n`, the national prefix formatting rule may use
+    /// `$NP` for the national prefix and `$FG` for the first group, and both the
+    /// format and the rule may use `$CC` for the country calling code.
+    ///
     /// # Arguments
     ///
     /// * `phone_number` - The phone number to format.
@@ -667,16 +1427,29 @@ impl PhoneNumberUtilInternal {
             // national prefix, we need to copy the rule so that subsequent replacements
             // for different numbers have the appropriate national prefix.
             let mut num_format_copy = formatting_pattern.clone();
+            let country_calling_code_str = country_calling_code.to_string();
+            if formatting_pattern.format().contains("$CC") {
+                // Replace $CC with the country calling code. This has to be done
+                // before the groups are substituted, as "$CC" would otherwise be
+                // taken for a named group.
+                num_format_copy.set_format(
+                    formatting_pattern
+                        .format()
+                        .replace("$CC", &country_calling_code_str),
+                );
+            }
 
             let national_prefix_formatting_rule =
                 formatting_pattern.national_prefix_formatting_rule();
             if !national_prefix_formatting_rule.is_empty() {
                 let national_prefix = metadata.national_prefix();
                 if !national_prefix.is_empty() {
-                    // Replace $NP with national prefix and $FG with the first group ($1).
+                    // Replace $NP with national prefix, $FG with the first group ($1)
+                    // and $CC with the country calling code.
                     let rule = national_prefix_formatting_rule
                         .replace("$NP", national_prefix)
-                        .replace("$FG", "$1");
+                        .replace("$FG", "$1")
+                        .replace("$CC", &country_calling_code_str);
                     num_format_copy.set_national_prefix_formatting_rule(rule);
                 } else {
                     // We don't want to have a rule for how to format the national prefix if
@@ -753,6 +1526,135 @@ impl PhoneNumberUtilInternal {
         Ok(formatted_number)
     }
 
+    /// Formats a number in national format with the national prefix replaced by
+    /// a carrier preselection code. Formats without a national prefix in their
+    /// national prefix formatting rule are applied unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The phone number to format.
+    /// * `preselect` - The carrier preselection code to dial instead of the national prefix.
+    pub(crate) fn format_with_carrier_preselect<'b>(
+        &self,
+        phone_number: &'b PhoneNumber,
+        preselect: &str,
+    ) -> RegexResult<Cow<'b, str>> {
+        let country_calling_code = phone_number.country_code();
+        let national_significant_number = self.get_national_significant_number(phone_number);
+        let region_code = self.get_region_code_for_country_code(country_calling_code);
+
+        let Some(metadata) =
+            self.get_metadata_for_region_or_calling_code(country_calling_code, region_code)
+        else {
+            return Ok(Cow::Owned(national_significant_number));
+        };
+        let formatting_pattern = self.choose_formatting_pattern_for_nsn(
+            &national_significant_number,
+            metadata,
+            PhoneNumberFormat::National,
+        )?;
+        // Without a matching pattern the digits are kept as they are, like in `format`.
+        let mut formatted_number = match formatting_pattern {
+            Some(formatting_pattern) => self
+                .format_nsn_with_carrier_preselect(
+                    &national_significant_number,
+                    metadata,
+                    formatting_pattern,
+                    preselect,
+                )?
+                .into_owned(),
+            None => national_significant_number,
+        };
+        if let Some(formatted_extension) =
+            Self::get_formatted_extension(phone_number, metadata, PhoneNumberFormat::National)
+        {
+            formatted_number.push_str(&formatted_extension);
+        }
+        Ok(Cow::Owned(formatted_number))
+    }
+
+    /// Formats a national significant number with the given pattern, dialing the
+    /// carrier preselect code where its national prefix formatting rule has the
+    /// national prefix.
+    ///
+    /// Like `format_nsn_using_pattern_with_carrier` does with the domestic carrier
+    /// code formatting rule, the national prefix formatting rule is turned into a
+    /// rule with `$CC` in place of the national prefix, e.g. "(0$1)" into
+    /// "($CC$1)", and `$CC` is substituted with the preselect code.
+    fn format_nsn_with_carrier_preselect<'b>(
+        &self,
+        national_significant_number: &'b str,
+        metadata: &PhoneMetadata,
+        formatting_pattern: &NumberFormat,
+        preselect: &str,
+    ) -> RegexResult<Cow<'b, str>> {
+        let Some(carrier_code_formatting_rule) = self.national_prefix_rule_with_carrier_code(
+            formatting_pattern.national_prefix_formatting_rule(),
+            metadata.national_prefix(),
+        ) else {
+            return self.format_nsn_using_pattern(
+                national_significant_number,
+                formatting_pattern,
+                PhoneNumberFormat::National,
+            );
+        };
+        // The rule is expanded twice: its "$1" becomes the first group of the format,
+        // and the result is the replacement template of the formatting pattern. Each
+        // expansion turns "$$" into "$", so the preselect is never read as a group
+        // reference.
+        let escaped_preselect = preselect.replace('$', "$$$$");
+        let preselect_formatting_rule = self
+            .reg_exps
+            .carrier_code_pattern
+            .replace(&carrier_code_formatting_rule, NoExpand(&escaped_preselect));
+        let number_format_rule = self
+            .reg_exps
+            .first_group_capturing_pattern
+            .replace(formatting_pattern.format(), preselect_formatting_rule.as_ref());
+        let pattern_to_match = self
+            .reg_exps
+            .regexp_cache
+            .get_regex(formatting_pattern.pattern())?;
+        Ok(pattern_to_match.replace_all(national_significant_number, number_format_rule.as_ref()))
+    }
+
+    /// Turns a national prefix formatting rule such as "(0$1)" into a carrier code
+    /// formatting rule by putting `$CC` in place of the national prefix, which may
+    /// come before or after the first group. Returns `None` if the rule doesn't
+    /// contain the national prefix.
+    ///
+    /// # Arguments
+    ///
+    /// * `national_prefix_formatting_rule` - The rule with the national prefix
+    ///   already substituted, as stored in the metadata.
+    /// * `national_prefix` - The national prefix of the region.
+    fn national_prefix_rule_with_carrier_code(
+        &self,
+        national_prefix_formatting_rule: &str,
+        national_prefix: &str,
+    ) -> Option<String> {
+        if national_prefix.is_empty() {
+            return None;
+        }
+        // The first group is skipped, so that a prefix like "1" doesn't match the
+        // "1" of "$1".
+        let first_group = self
+            .reg_exps
+            .first_group_capturing_pattern
+            .find(national_prefix_formatting_rule)?;
+        let prefix_start = national_prefix_formatting_rule[..first_group.start()]
+            .find(national_prefix)
+            .or_else(|| {
+                national_prefix_formatting_rule[first_group.end()..]
+                    .find(national_prefix)
+                    .map(|position| first_group.end() + position)
+            })?;
+        let mut carrier_code_formatting_rule = national_prefix_formatting_rule.to_owned();
+        carrier_code_formatting_rule
+            .replace_range(prefix_start..prefix_start + national_prefix.len(), "$CC");
+        Some(carrier_code_formatting_rule)
+    }
+
     /// Formats a national number, inserting the preferred domestic carrier code if available.
     /// Otherwise, uses the provided fallback carrier code.
     ///
@@ -854,7 +1756,7 @@ impl PhoneNumberUtilInternal {
                 let national_number = self.get_national_significant_number(&number_no_extension);
                 let format = if self.can_be_internationally_dialled(&number_no_extension)?
                     && !test_number_length_with_unknown_type(&national_number, region_metadata)
-                        .is_err_and(|e| matches!(e, ValidationError::TooShort))
+                        .is_err_and(|e| matches!(e, ValidationError::TooShort { .. }))
                 {
                     PhoneNumberFormat::International
                 } else {
@@ -922,23 +1824,77 @@ impl PhoneNumberUtilInternal {
         }
     }
 
-    /// Gets the type of a phone number (e.g., FIXED_LINE, MOBILE, TOLL_FREE).
+    /// Gets the type of a phone number (e.g., FIXED_LINE, MOBILE, TOLL_FREE).
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The phone number to get the type for.
+    pub(crate) fn get_number_type(
+        &self,
+        phone_number: &PhoneNumber,
+    ) -> RegexResult<PhoneNumberType> {
+        let region_code = self.get_region_code_for_number(phone_number)?;
+        let Some(metadata) =
+            self.get_metadata_for_region_or_calling_code(phone_number.country_code(), region_code)
+        else {
+            return Ok(PhoneNumberType::Unknown);
+        };
+        let national_significant_number = self.get_national_significant_number(phone_number);
+        Ok(self.get_number_type_helper(&national_significant_number, metadata))
+    }
+
+    /// Gets the type of a phone number using the metadata of the given region,
+    /// without resolving the region of the number first. Fails with
+    /// `ValidationError::InvalidCountryCode` if the region is unknown or has
+    /// another country calling code than the number.
     ///
     /// # Arguments
     ///
     /// * `phone_number` - The phone number to get the type for.
-    pub(crate) fn get_number_type(
+    /// * `region_code` - The region the number belongs to.
+    pub(crate) fn get_number_type_for_region(
         &self,
         phone_number: &PhoneNumber,
-    ) -> RegexResult<PhoneNumberType> {
+        region_code: &str,
+    ) -> Result<PhoneNumberType, ValidationError> {
+        let country_code = phone_number.country_code();
+        let Some(metadata) = self
+            .get_metadata_for_region_or_calling_code(country_code, region_code)
+            .filter(|metadata| {
+                REGION_CODE_FOR_NON_GEO_ENTITY == region_code
+                    || country_code == metadata.country_code()
+            })
+        else {
+            return Err(ValidationError::InvalidCountryCode { country_code });
+        };
+        let national_significant_number = self.get_national_significant_number(phone_number);
+        Ok(self.get_number_type_helper(&national_significant_number, metadata))
+    }
+
+    /// Gets the type of a phone number together with the national number pattern
+    /// of the metadata descriptor that matched it. The pattern is `None` when the
+    /// type is `Unknown`.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The phone number to check.
+    pub(crate) fn get_number_type_with_descriptor(
+        &self,
+        phone_number: &PhoneNumber,
+    ) -> RegexResult<(PhoneNumberType, Option<String>)> {
         let region_code = self.get_region_code_for_number(phone_number)?;
         let Some(metadata) =
             self.get_metadata_for_region_or_calling_code(phone_number.country_code(), region_code)
         else {
-            return Ok(PhoneNumberType::Unknown);
+            return Ok((PhoneNumberType::Unknown, None));
         };
         let national_significant_number = self.get_national_significant_number(phone_number);
-        Ok(self.get_number_type_helper(&national_significant_number, metadata))
+        let (phone_number_type, number_desc) =
+            self.get_number_type_and_desc_helper(&national_significant_number, metadata);
+        Ok((
+            phone_number_type,
+            number_desc.map(|desc| desc.national_number_pattern().to_owned()),
+        ))
     }
 
     /// Gets the region code for a given phone number.
@@ -950,7 +1906,7 @@ impl PhoneNumberUtilInternal {
     pub(crate) fn get_region_code_for_number(
         &self,
         phone_number: &PhoneNumber,
-    ) -> RegexResult<&str> {
+    ) -> RegexResult<&'static str> {
         let country_calling_code: i32 = phone_number.country_code();
 
         let default = || {
@@ -975,26 +1931,85 @@ impl PhoneNumberUtilInternal {
         Ok(region_codes.next().unwrap_or_else(default))
     }
 
+    /// Gets both the region code and the type of a phone number, matching the
+    /// number against the metadata of its candidate regions only once. A number
+    /// which does not belong to any region gets the unknown region code "ZZ" and
+    /// `PhoneNumberType::Unknown`, as with `get_region_code_for_number`.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The phone number to classify.
+    pub(crate) fn classify(
+        &self,
+        phone_number: &PhoneNumber,
+    ) -> RegexResult<(&'static str, PhoneNumberType)> {
+        let unknown = (RegionCode::get_unknown(), PhoneNumberType::Unknown);
+        let country_calling_code = phone_number.country_code();
+        let Some(mut region_codes) =
+            self.get_region_codes_for_country_calling_code(country_calling_code)
+        else {
+            return Ok(unknown);
+        };
+        let national_number = self.get_national_significant_number(phone_number);
+        if region_codes.len() > 1 {
+            let leading_digits_matches =
+                self.leading_digits_matches(country_calling_code, &national_number);
+            for code in region_codes {
+                let Some(metadata) = self.region_to_metadata_map.get(code) else {
+                    continue;
+                };
+                // Regions with leading digits are decided by them alone, so only the
+                // region they choose has its type computed.
+                if metadata.has_leading_digits() {
+                    if self.matches_leading_digits(
+                        code, metadata, &national_number, leading_digits_matches.as_ref()
+                    )? {
+                        let number_type = self.get_number_type_helper(&national_number, metadata);
+                        return Ok((code, number_type));
+                    }
+                    continue;
+                }
+                let number_type = self.get_number_type_helper(&national_number, metadata);
+                if number_type != PhoneNumberType::Unknown {
+                    return Ok((code, number_type));
+                }
+            }
+            return Ok(unknown);
+        }
+        let Some(region_code) = region_codes.next() else {
+            return Ok(unknown);
+        };
+        let number_type = self
+            .get_metadata_for_region_or_calling_code(country_calling_code, region_code)
+            .map_or(PhoneNumberType::Unknown, |metadata| {
+                self.get_number_type_helper(&national_number, metadata)
+            });
+        Ok((region_code, number_type))
+    }
+
     pub(crate) fn get_region_code_for_number_from_region_list<'b>(
         &self,
         phone_number: &PhoneNumber,
         region_codes: impl Iterator<Item=&'b str>,
     ) -> RegexResult<Option<&'b str>> {
         let national_number = self.get_national_significant_number(phone_number);
+        let leading_digits_matches =
+            self.leading_digits_matches(phone_number.country_code(), &national_number);
         for code in region_codes {
             // Metadata cannot be NULL because the region codes come from the country
-            // calling code map.
+            // calling code map. Skip the region anyway rather than giving up on the
+            // remaining ones.
             let Some(metadata) = &self
                 .region_to_metadata_map
                 .get(code) else {
-                return Ok(None);
+                continue;
             };
-            if metadata.has_leading_digits() && self
-                    .reg_exps
-                    .regexp_cache
-                    .get_regex(metadata.leading_digits())?
-                    .matches_start(&national_number) {
-                return Ok(Some(code));
+            if metadata.has_leading_digits() {
+                if self.matches_leading_digits(
+                    code, metadata, &national_number, leading_digits_matches.as_ref()
+                )? {
+                    return Ok(Some(code));
+                }
             } else if self
                 .get_number_type_helper(&national_number, metadata) != PhoneNumberType::Unknown {
                 return Ok(Some(code));
@@ -1003,48 +2018,100 @@ impl PhoneNumberUtilInternal {
         Ok(None)
     }
 
+    /// Matches `national_number` against the leading digits of all regions of
+    /// the country calling code at once. Returns `None` if the code has no
+    /// `LeadingDigitsSet`.
+    fn leading_digits_matches(
+        &self,
+        country_calling_code: i32,
+        national_number: &str,
+    ) -> Option<(&LeadingDigitsSet, SetMatches)> {
+        self.leading_digits_sets
+            .get(&country_calling_code)
+            .map(|set| (set, set.matches(national_number)))
+    }
+
+    /// Whether `national_number` starts with the leading digits of the region,
+    /// which must have some. The result of `leading_digits_matches` is used when
+    /// it covers the region; otherwise the region's pattern is matched on its own.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_code` - The region to check.
+    /// * `metadata` - The metadata of `region_code`.
+    /// * `national_number` - The national significant number.
+    /// * `leading_digits_matches` - The matches of the number's country calling code, if any.
+    fn matches_leading_digits(
+        &self,
+        region_code: &str,
+        metadata: &PhoneMetadata,
+        national_number: &str,
+        leading_digits_matches: Option<&(&LeadingDigitsSet, SetMatches)>,
+    ) -> RegexResult<bool> {
+        if let Some(matched) = leading_digits_matches
+            .and_then(|(set, matches)| set.is_region_matched(matches, region_code)) {
+            return Ok(matched);
+        }
+        Ok(self
+            .reg_exps
+            .regexp_cache
+            .get_regex(metadata.leading_digits())?
+            .matches_start(national_number))
+    }
+
     pub(crate) fn get_number_type_helper(
         &self,
         national_number: &str,
         metadata: &PhoneMetadata,
     ) -> PhoneNumberType {
+        self.get_number_type_and_desc_helper(national_number, metadata).0
+    }
+
+    /// Same as `get_number_type_helper`, but also returns the descriptor whose
+    /// pattern decided the type. For `FixedLineOrMobile` this is the fixed-line
+    /// descriptor; for `Unknown` there is no such descriptor.
+    pub(crate) fn get_number_type_and_desc_helper<'m>(
+        &self,
+        national_number: &str,
+        metadata: &'m PhoneMetadata,
+    ) -> (PhoneNumberType, Option<&'m PhoneNumberDesc>) {
         if !self.is_number_matching_desc(national_number, &metadata.general_desc) {
             trace!(
                 "Number '{national_number}' type unknown - doesn't match general national number pattern"
             );
-            return PhoneNumberType::Unknown;
+            return (PhoneNumberType::Unknown, None);
         }
         if self.is_number_matching_desc(national_number, &metadata.premium_rate) {
             trace!("Number '{national_number}' is a premium number.");
-            return PhoneNumberType::PremiumRate;
+            return (PhoneNumberType::PremiumRate, Some(&metadata.premium_rate));
         }
         if self.is_number_matching_desc(national_number, &metadata.toll_free) {
             trace!("Number '{national_number}' is a toll-free number.");
-            return PhoneNumberType::TollFree;
+            return (PhoneNumberType::TollFree, Some(&metadata.toll_free));
         }
         if self.is_number_matching_desc(national_number, &metadata.shared_cost) {
             trace!("Number '{national_number}' is a shared cost number.");
-            return PhoneNumberType::SharedCost;
+            return (PhoneNumberType::SharedCost, Some(&metadata.shared_cost));
         }
         if self.is_number_matching_desc(national_number, &metadata.voip) {
             trace!("Number '{national_number}' is a VOIP (Voice over IP) number.");
-            return PhoneNumberType::VoIP;
+            return (PhoneNumberType::VoIP, Some(&metadata.voip));
         }
         if self.is_number_matching_desc(national_number, &metadata.personal_number) {
             trace!("Number '{national_number}' is a personal number.");
-            return PhoneNumberType::PersonalNumber;
+            return (PhoneNumberType::PersonalNumber, Some(&metadata.personal_number));
         }
         if self.is_number_matching_desc(national_number, &metadata.pager) {
             trace!("Number '{national_number}' is a pager number.");
-            return PhoneNumberType::Pager;
+            return (PhoneNumberType::Pager, Some(&metadata.pager));
         }
         if self.is_number_matching_desc(national_number, &metadata.uan) {
             trace!("Number '{national_number}' is a UAN.");
-            return PhoneNumberType::UAN;
+            return (PhoneNumberType::UAN, Some(&metadata.uan));
         }
         if self.is_number_matching_desc(national_number, &metadata.voicemail) {
             trace!("Number '{national_number}' is a voicemail number.");
-            return PhoneNumberType::VoiceMail;
+            return (PhoneNumberType::VoiceMail, Some(&metadata.voicemail));
         }
 
         let is_fixed_line = self.is_number_matching_desc(national_number, &metadata.fixed_line);
@@ -1054,16 +2121,16 @@ impl PhoneNumberUtilInternal {
                     "Number '{national_number}': fixed-line and mobile patterns equal,\
                  number is fixed-line or mobile"
                 );
-                return PhoneNumberType::FixedLineOrMobile;
+                return (PhoneNumberType::FixedLineOrMobile, Some(&metadata.fixed_line));
             } else if self.is_number_matching_desc(national_number, &metadata.mobile) {
                 trace!(
                     "Number '{national_number}': Fixed-line and mobile patterns differ, but number is \
                         still fixed-line or mobile"
                 );
-                return PhoneNumberType::FixedLineOrMobile;
+                return (PhoneNumberType::FixedLineOrMobile, Some(&metadata.fixed_line));
             }
             trace!("Number '{national_number}' is a fixed line number.");
-            return PhoneNumberType::FixedLine;
+            return (PhoneNumberType::FixedLine, Some(&metadata.fixed_line));
         }
         // Otherwise, test to see if the number is mobile. Only do this if certain
         // that the patterns for mobile and fixed line aren't the same.
@@ -1071,12 +2138,12 @@ impl PhoneNumberUtilInternal {
             && self.is_number_matching_desc(national_number, &metadata.mobile)
         {
             trace!("Number '{national_number}' is a mobile number.");
-            return PhoneNumberType::Mobile;
+            return (PhoneNumberType::Mobile, Some(&metadata.mobile));
         }
         trace!(
             "Number'{national_number}' type unknown - doesn\'t match any specific number type pattern."
         );
-        return PhoneNumberType::Unknown;
+        (PhoneNumberType::Unknown, None)
     }
 
     pub(crate) fn is_number_matching_desc(
@@ -1095,7 +2162,7 @@ impl PhoneNumberUtilInternal {
             return false;
         }
         // very common name, so specify mod
-        helper_functions::is_match(&self.matcher_api, national_number, number_desc)
+        helper_functions::is_match(self.matcher_api.as_ref(), national_number, number_desc)
     }
 
     /// Checks if a number can be dialled internationally.
@@ -1120,6 +2187,12 @@ impl PhoneNumberUtilInternal {
         ));
     }
 
+    /// Normalizes a string of characters representing a phone number, keeping
+    /// only the characters which can be dialled: digits, '+', '*' and '#'.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The phone number string to normalize.
     pub(crate) fn normalize_diallable_chars_only(&self, phone_number: &str) -> String {
         normalize_helper(&self.reg_exps.diallable_char_mappings, true, phone_number)
     }
@@ -1145,27 +2218,75 @@ impl PhoneNumberUtilInternal {
         phone_number: &'a PhoneNumber,
         calling_from: &str,
     ) -> InternalLogicResult<Cow<'a, str>> {
+        self.format_out_of_country_calling_number_reporting(phone_number, calling_from)
+            .map(|(formatted_number, _)| formatted_number)
+    }
+
+    /// Returns the characters a user in `calling_from` has to dial to reach the
+    /// number: the out-of-country format with the international prefix expanded
+    /// and all formatting, waits and the extension removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The phone number to format.
+    /// * `calling_from` - The region where the call is being placed from.
+    pub(crate) fn format_as_dialed_from<'a>(
+        &self,
+        phone_number: &'a PhoneNumber,
+        calling_from: &str,
+    ) -> InternalLogicResult<Cow<'a, str>> {
+        let formatted_number = if phone_number.has_extension() {
+            // The extension is dialled only after the call is connected.
+            let mut number_without_extension = phone_number.clone();
+            number_without_extension.clear_extension();
+            Cow::Owned(
+                self.format_out_of_country_calling_number(&number_without_extension, calling_from)?
+                    .into_owned(),
+            )
+        } else {
+            self.format_out_of_country_calling_number(phone_number, calling_from)?
+        };
+        let dialed_number = self.normalize_diallable_chars_only(&formatted_number);
+        if dialed_number == formatted_number {
+            return Ok(formatted_number);
+        }
+        Ok(Cow::Owned(dialed_number))
+    }
+
+    /// Same as `format_out_of_country_calling_number`, but also reports whether
+    /// the number was treated as domestic, i.e. formatted without an international
+    /// prefix because it shares the country calling code of the calling region.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The phone number to format.
+    /// * `calling_from` - The region where the call is being placed from.
+    pub(crate) fn format_out_of_country_calling_number_reporting<'a>(
+        &self,
+        phone_number: &'a PhoneNumber,
+        calling_from: &str,
+    ) -> InternalLogicResult<(Cow<'a, str>, bool)> {
         let Some(metadata_calling_from) = self.region_to_metadata_map.get(calling_from) else {
             trace!(
                 "Trying to format number from invalid region {calling_from}\
               . International formatting applied."
             );
-            return Ok(self.format(phone_number, PhoneNumberFormat::International)?);
+            return Ok((self.format(phone_number, PhoneNumberFormat::International)?, false));
         };
         let country_code = phone_number.country_code();
         let national_significant_number = self.get_national_significant_number(phone_number);
         if !self.has_valid_country_calling_code(country_code) {
-            return Ok(Cow::Owned(national_significant_number));
+            return Ok((Cow::Owned(national_significant_number), false));
         }
         if country_code == NANPA_COUNTRY_CODE {
             if self.nanpa_regions.contains(calling_from) {
                 let mut buf = itoa::Buffer::new();
                 // prefix it with the country calling code.
-                return Ok(Cow::Owned(fast_cat::concat_str!(
+                return Ok((Cow::Owned(fast_cat::concat_str!(
                     buf.format(country_code),
                     " ",
                     &self.format(phone_number, PhoneNumberFormat::National)?,
-                )));
+                )), true));
             }
         } else if country_code == metadata_calling_from.country_code() {
             // If neither region is a NANPA region, then we check to see if the
@@ -1179,7 +2300,7 @@ impl PhoneNumberUtilInternal {
             // those cases return the version including country calling code.
             // Details here:
             // http://www.petitfute.com/voyage/225-info-pratiques-reunion
-            return Ok(self.format(phone_number, PhoneNumberFormat::National)?);
+            return Ok((self.format(phone_number, PhoneNumberFormat::National)?, true));
         }
         // Metadata cannot be NULL because we checked 'IsValidRegionCode()' above.
         let international_prefix = metadata_calling_from.international_prefix();
@@ -1222,7 +2343,7 @@ impl PhoneNumberUtilInternal {
             formatted_number.push_str(&extension);
         }
 
-        return Ok(Cow::Owned(
+        Ok((Cow::Owned(
             if !international_prefix_for_formatting.is_empty() {
                 let mut buf = itoa::Buffer::new();
                 fast_cat::concat_str!(
@@ -1240,7 +2361,7 @@ impl PhoneNumberUtilInternal {
                 );
                 formatted_number
             },
-        ));
+        ), false))
     }
 
     pub(crate) fn has_formatting_pattern_for_number(&self, phone_number: &PhoneNumber) -> RegexResult<bool> {
@@ -1415,21 +2536,445 @@ impl PhoneNumberUtilInternal {
     /// * `number_to_parse` - The number string to parse.
     /// * `default_region` - The region to assume if the number is not in international format.
     pub(crate) fn parse(&self, number_to_parse: &str, default_region: &str) -> ParseResult<PhoneNumber> {
-        self.parse_helper(number_to_parse, default_region, false, true)
+        self.parse_helper(number_to_parse, default_region, false, true, true)
+    }
+
+    /// Same as `parse`, but doesn't strip the national prefix and carrier code,
+    /// so they stay part of the national number.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_to_parse` - The number string to parse.
+    /// * `default_region` - The region to assume if the number is not in international format.
+    pub(crate) fn parse_keep_national_prefix(
+        &self,
+        number_to_parse: &str,
+        default_region: &str,
+    ) -> ParseResult<PhoneNumber> {
+        self.parse_helper(number_to_parse, default_region, false, true, false)
+    }
+
+    /// Same as `parse`, but returns a number borrowing `number_to_parse` when
+    /// it is already a national significant number of `default_region`: ASCII
+    /// digits only, with no leading zero, international or national prefix and
+    /// no country calling code. Any other input goes through `parse`.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_to_parse` - The number string to parse.
+    /// * `default_region` - The region to assume if the number is not in international format.
+    pub(crate) fn parse_borrowed<'a>(
+        &self,
+        number_to_parse: &'a str,
+        default_region: &str,
+    ) -> ParseResult<BorrowedNumber<'a>> {
+        if let Some(national_number) =
+            self.as_clean_national_number(number_to_parse, default_region)?
+        {
+            return Ok(national_number);
+        }
+        self.parse(number_to_parse, default_region).map(BorrowedNumber::Owned)
+    }
+
+    /// Returns the borrowed form of `number_to_parse` if parsing it wouldn't
+    /// change anything but its type, `None` otherwise.
+    fn as_clean_national_number<'a>(
+        &self,
+        number_to_parse: &'a str,
+        default_region: &str,
+    ) -> RegexResult<Option<BorrowedNumber<'a>>> {
+        let Some(metadata) = self.get_metadata_for_region(default_region) else {
+            return Ok(None);
+        };
+        if !(MIN_LENGTH_FOR_NSN..=MAX_LENGTH_FOR_NSN).contains(&number_to_parse.len())
+            || !number_to_parse.bytes().all(|b| b.is_ascii_digit())
+            || number_to_parse.starts_with('0')
+        {
+            return Ok(None);
+        }
+        // Any country calling code or international prefix found here would make
+        // `parse` produce a different national number.
+        let mut phone_number = PhoneNumber::new();
+        let Ok(national_number) =
+            self.maybe_extract_country_code(Some(metadata), false, number_to_parse, &mut phone_number)
+        else {
+            return Ok(None);
+        };
+        if phone_number.country_code() != 0 || national_number != number_to_parse {
+            return Ok(None);
+        }
+        let (stripped_number, _) =
+            self.maybe_strip_national_prefix_and_carrier_code(metadata, number_to_parse)?;
+        if stripped_number != number_to_parse {
+            // Whether the prefix is really stripped depends on the remaining length,
+            // so leave the decision to `parse`.
+            return Ok(None);
+        }
+        Ok(Some(BorrowedNumber::Borrowed {
+            country_code: metadata.country_code(),
+            national_number: number_to_parse,
+        }))
+    }
+
+    /// Same as `parse`, but writes the result into an existing phone number
+    /// object. The buffers of its string fields, such as the extension, are
+    /// reused. The object is left empty on error.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_to_parse` - The number string to parse.
+    /// * `default_region` - The region to assume if the number is not in international format.
+    /// * `phone_number` - The phone number object to fill in.
+    pub(crate) fn parse_into(
+        &self,
+        number_to_parse: &str,
+        default_region: &str,
+        phone_number: &mut PhoneNumber,
+    ) -> ParseResult<()> {
+        self.parse_helper_into(number_to_parse, default_region, false, true, true, phone_number)
+            .inspect_err(|_| phone_number.clear())
+    }
+
+    /// Same as `parse`, but takes the number as a stream of characters. They are
+    /// collected into a thread-local buffer that is reused between calls, so no
+    /// string is allocated per number once the buffer has grown.
+    ///
+    /// # Arguments
+    ///
+    /// * `chars` - The characters of the number to parse.
+    /// * `default_region` - The region to assume if the number is not in international format.
+    pub(crate) fn parse_chars(
+        &self,
+        chars: impl Iterator<Item = char>,
+        default_region: &str,
+    ) -> ParseResult<PhoneNumber> {
+        thread_local! {
+            static PARSE_BUFFER: RefCell<String> = const { RefCell::new(String::new()) };
+        }
+        PARSE_BUFFER.with(|buffer| {
+            // A reentrant call, e.g. from inside the iterator, gets its own buffer.
+            let Ok(mut buffer) = buffer.try_borrow_mut() else {
+                return self.parse(&chars.collect::<String>(), default_region);
+            };
+            buffer.clear();
+            buffer.extend(chars);
+            self.parse(&buffer, default_region)
+        })
+    }
+
+    /// Parses a string into a phone number object, keeping the raw input.
+    ///
+    /// # Arguments
+    /// 
+    /// * `number_to_parse` - The number string to parse.
+    /// * `default_region` - The region to assume if the number is not in international format.
+    pub(crate) fn parse_and_keep_raw_input(
+        &self,
+        number_to_parse: &str,
+        default_region: &str,
+    ) -> ParseResult<PhoneNumber> {
+        self.parse_helper(number_to_parse, default_region, true, true, true)
+    }
+
+    /// Parses a string into a phone number object like `parse`, also returning
+    /// the domestic carrier code that was stripped from the national number, if
+    /// any.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_to_parse` - The number string to parse.
+    /// * `default_region` - The region to assume if the number is not in international format.
+    pub(crate) fn parse_reporting_carrier(
+        &self,
+        number_to_parse: &str,
+        default_region: &str,
+    ) -> ParseResult<(PhoneNumber, Option<String>)> {
+        // The carrier code is only recorded when the raw input is kept, so drop
+        // the other fields that come with it to get the same number as `parse`.
+        let mut phone_number = self.parse_and_keep_raw_input(number_to_parse, default_region)?;
+        phone_number.clear_raw_input();
+        phone_number.raw_input_groups.clear();
+        phone_number.clear_country_code_source();
+        let carrier_code = phone_number.preferred_domestic_carrier_code.take();
+        Ok((phone_number, carrier_code))
+    }
+
+    /// Same as `parse`, but fails with `ParseError::RegionMismatchSuspected` if
+    /// the number was written without a plus sign or international prefix and
+    /// still belongs to a region other than `default_region`.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_to_parse` - The number string to parse.
+    /// * `default_region` - The region the number is expected to belong to.
+    pub(crate) fn parse_with_region_sanity(
+        &self,
+        number_to_parse: &str,
+        default_region: &str,
+    ) -> ParseResult<PhoneNumber> {
+        // The country code source is only recorded when the raw input is kept, so
+        // drop the other fields that come with it to get the same number as `parse`.
+        let mut phone_number = self.parse_and_keep_raw_input(number_to_parse, default_region)?;
+        let country_code_source = phone_number.country_code_source();
+        phone_number.clear_raw_input();
+        phone_number.raw_input_groups.clear();
+        phone_number.clear_country_code_source();
+        phone_number.clear_preferred_domestic_carrier_code();
+
+        if matches!(
+            country_code_source,
+            CountryCodeSource::FROM_DEFAULT_COUNTRY | CountryCodeSource::FROM_NUMBER_WITHOUT_PLUS_SIGN
+        ) {
+            // Invalid numbers don't resolve to any region, they aren't suspicious.
+            let actual_region = self.get_region_code_for_number(&phone_number)?;
+            if actual_region != RegionCode::get_unknown() && actual_region != default_region {
+                return Err(ParseError::RegionMismatchSuspected {
+                    default_region: default_region.to_owned(),
+                    actual_region: actual_region.to_owned(),
+                }.into());
+            }
+        }
+        Ok(phone_number)
+    }
+
+    /// Parses a number written as an RFC3966 "tel:" URI, also returning the
+    /// value of its ISDN subaddress ("isub") parameter, which `parse` drops.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_to_parse` - The URI to parse, e.g. "tel:+1-650-253-0000;isub=1234".
+    pub(crate) fn parse_rfc3966_with_isub(
+        &self,
+        number_to_parse: &str,
+    ) -> ParseResult<(PhoneNumber, Option<String>)> {
+        let phone_number = self.parse(number_to_parse, RegionCode::get_unknown())?;
+        let isdn_subaddress = number_to_parse
+            .find(RFC3966_ISDN_SUBADDRESS)
+            .and_then(|index_of_isdn| {
+                // The subaddress ends where the next parameter, e.g. the
+                // phone-context, starts.
+                number_to_parse[index_of_isdn + RFC3966_ISDN_SUBADDRESS.len()..]
+                    .split(';')
+                    .next()
+            })
+            .filter(|isdn_subaddress| !isdn_subaddress.is_empty())
+            .map(str::to_owned);
+        Ok((phone_number, isdn_subaddress))
+    }
+
+    /// Parses a number given in strict E.164 form ("+" followed by 1 to 15 ASCII
+    /// digits), skipping the normalization, extension and viability machinery of
+    /// the full parser. Inputs in any other shape fall back to `parse` with an
+    /// unknown default region, so the result is always the same as `parse` gives.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_to_parse` - The number string to parse.
+    pub(crate) fn parse_e164(&self, number_to_parse: &str) -> ParseResult<PhoneNumber> {
+        let digits = number_to_parse
+            .strip_prefix(PLUS_SIGN)
+            // Inputs too short to hold a country code and a national number are left
+            // for the full parser, which reports the exact error for them.
+            .filter(|digits| (MIN_LENGTH_FOR_NSN + 1..=15).contains(&digits.len()))
+            .filter(|digits| digits.bytes().all(|b| b.is_ascii_digit()));
+        let Some(digits) = digits else {
+            return self.parse(number_to_parse, RegionCode::get_unknown());
+        };
+
+        let Some((national_number, country_code)) = self.extract_country_code(Cow::Borrowed(digits))
+        else {
+            return Err(ParseError::InvalidCountryCode.into());
+        };
+        let region_code = self.get_region_code_for_country_code(country_code);
+        let country_metadata = self.get_metadata_for_region_or_calling_code(country_code, region_code);
+        let mut phone_number = PhoneNumber::new();
+        self.finish_parsing(&mut phone_number, country_code, country_metadata, national_number, false, true)?;
+        Ok(phone_number)
+    }
+
+    /// Parses a string with the preferred region and, if the result isn't a valid
+    /// number, with each fallback region in turn, returning the first valid
+    /// result. When no region gives a valid number, the result for the preferred
+    /// region is returned, be it an invalid number or an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_to_parse` - The number string to parse.
+    /// * `preferred_region` - The region to try first.
+    /// * `fallback_regions` - The regions to try next, in order.
+    pub(crate) fn parse_best_region(
+        &self,
+        number_to_parse: &str,
+        preferred_region: &str,
+        fallback_regions: &[impl AsRef<str>],
+    ) -> ParseResult<PhoneNumber> {
+        let preferred_result = self.parse(number_to_parse, preferred_region);
+        if let Ok(phone_number) = &preferred_result
+            && self.is_valid_number(phone_number)?
+        {
+            return preferred_result;
+        }
+        for region in fallback_regions {
+            if let Ok(phone_number) = self.parse(number_to_parse, region.as_ref())
+                && self.is_valid_number(&phone_number)?
+            {
+                return Ok(phone_number);
+            }
+        }
+        preferred_result
+    }
+
+    /// Parses a string into a phone number object and reports how its extension,
+    /// if any, was introduced in the input.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_to_parse` - The number string to parse.
+    /// * `default_region` - The region to assume if the number is not in international format.
+    pub(crate) fn parse_reporting_extension_label(
+        &self,
+        number_to_parse: &str,
+        default_region: &str,
+    ) -> ParseResult<(PhoneNumber, Option<ExtensionLabelKind>)> {
+        let phone_number = self.parse(number_to_parse, default_region)?;
+        if !phone_number.has_extension() {
+            return Ok((phone_number, None));
+        }
+        // Parsing succeeded, so the extension was stripped from exactly this string.
+        let national_number = self.build_national_number_for_parsing(number_to_parse)?;
+        let (_, extension) = self.maybe_strip_extension_with_label(&national_number);
+        Ok((phone_number, extension.map(|(_, label)| label)))
+    }
+
+    /// Parses a string into a phone number object and returns every
+    /// extension-looking part of the input, in input order. Only the last one
+    /// of the number itself can become its extension; the others are reported
+    /// for diagnostics.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_to_parse` - The number string to parse.
+    /// * `default_region` - The region to assume if the number is not in international format.
+    pub(crate) fn parse_reporting_all_extensions(
+        &self,
+        number_to_parse: &str,
+        default_region: &str,
+    ) -> ParseResult<(PhoneNumber, Vec<String>)> {
+        let phone_number = self.parse(number_to_parse, default_region)?;
+        let candidates = self
+            .reg_exps
+            .extn_candidates_pattern
+            .captures_iter(number_to_parse)
+            .filter_map(|captures| {
+                captures
+                    .iter()
+                    .skip(1)
+                    .flatten()
+                    .find(|m| !m.is_empty())
+                    .map(|m| m.as_str().to_owned())
+            })
+            .collect();
+        Ok((phone_number, candidates))
     }
 
-    /// Parses a string into a phone number object, keeping the raw input.
+    /// Parses a string into a phone number object, applying the given options
+    /// to the input first.
     ///
     /// # Arguments
-    /// 
+    ///
     /// * `number_to_parse` - The number string to parse.
     /// * `default_region` - The region to assume if the number is not in international format.
-    pub(crate) fn parse_and_keep_raw_input(
+    /// * `options` - Tuning of the parsing behaviour.
+    pub(crate) fn parse_with_options(
         &self,
         number_to_parse: &str,
         default_region: &str,
+        options: &ParseOptions,
     ) -> ParseResult<PhoneNumber> {
-        self.parse_helper(number_to_parse, default_region, true, true)
+        let stripped_number = if options.strip_leading_symbols {
+            self.strip_leading_symbols(number_to_parse)
+        } else {
+            Cow::Borrowed(number_to_parse)
+        };
+        let mut phone_number = self.parse_helper(&stripped_number, default_region, false, true, true)?;
+        if options.forbid_extensions && phone_number.has_extension() {
+            return Err(ParseError::ExtensionNotAllowed {
+                extension: phone_number.take_extension(),
+            }
+            .into());
+        }
+        if options.keep_raw_input_only {
+            // The raw input is the string as given, before any of the options
+            // touched it; `country_code_source` stays unset on purpose.
+            phone_number.set_raw_input(number_to_parse.to_owned());
+        }
+        Ok(phone_number)
+    }
+
+    /// Removes decorative symbols (emoji, bullets, ...) preceding the first digit
+    /// of the number. Plus signs, letters, whitespace, ASCII characters and valid
+    /// phone number punctuation are kept, so only "exotic" symbols that can never be
+    /// dialled are dropped.
+    pub(crate) fn strip_leading_symbols<'a>(&self, phone_number: &'a str) -> Cow<'a, str> {
+        let leading_part_end = phone_number
+            .char_indices()
+            .find(|(_, c)| c.is_decimal_utf8())
+            .map_or(phone_number.len(), |(i, _)| i);
+        let (leading_part, rest) = phone_number.split_at(leading_part_end);
+
+        let is_symbol = |c: char| {
+            let mut buf = [0u8; 4];
+            !c.is_ascii()
+                && !c.is_alphanumeric()
+                && !c.is_whitespace()
+                && !self.reg_exps.plus_chars_pattern.full_match(c.encode_utf8(&mut buf))
+                && !self.reg_exps.separator_pattern.full_match(c.encode_utf8(&mut buf))
+        };
+        if !leading_part.chars().any(&is_symbol) {
+            return Cow::Borrowed(phone_number);
+        }
+        let mut stripped = String::with_capacity(phone_number.len());
+        stripped.extend(leading_part.chars().filter(|c| !is_symbol(*c)));
+        stripped.push_str(rest);
+        Cow::Owned(stripped)
+    }
+
+    /// Parses a bare local number (e.g. "253-0000" in the US) for the given region.
+    ///
+    /// Unlike `parse`, no IDD, country calling code, national prefix or carrier code
+    /// stripping is attempted: all digits of the input are kept as the national
+    /// significant number, so numbers dialled with a local-only length are not
+    /// mangled and report `IsPossibleLocalOnly` when checked for possibility.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_to_parse` - The number string to parse.
+    /// * `region_code` - The region the local number belongs to. Must be a supported region.
+    pub(crate) fn parse_local(&self, number_to_parse: &str, region_code: &str) -> ParseResult<PhoneNumber> {
+        let Some(country_metadata) = self.get_metadata_for_region(region_code) else {
+            trace!("Missing or invalid region for local number.");
+            return Err(ParseError::InvalidCountryCode.into());
+        };
+        let national_number = self.build_national_number_for_parsing(number_to_parse)?;
+        self.check_viable_number_for_parsing(number_to_parse, &national_number)?;
+
+        let mut temp_number = PhoneNumber::new();
+        let (national_number, extension) = self.maybe_strip_extension(&national_number);
+        if let Some(extension) = extension {
+            temp_number.set_extension(extension.to_owned());
+        }
+
+        let normalized_national_number = self.normalize(national_number);
+        // No national prefix or carrier code is stripped, all digits are kept.
+        self.finish_parsing(
+            &mut temp_number,
+            country_metadata.country_code(),
+            Some(country_metadata),
+            Cow::Owned(normalized_national_number),
+            false,
+            false,
+        )?;
+        Ok(temp_number)
     }
 
     /// Checks if a phone number is valid.
@@ -1442,6 +2987,100 @@ impl PhoneNumberUtilInternal {
         return Ok(self.is_valid_number_for_region(phone_number, region_code));
     }
 
+    /// Same as `is_valid_number`, but explains why the number is not valid: the
+    /// length problem reported by `is_possible_number_with_reason`, or
+    /// `ValidationError::PatternMismatch` if the length is possible.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The phone number to validate.
+    pub(crate) fn is_valid_number_with_reason(
+        &self,
+        phone_number: &PhoneNumber,
+    ) -> RegexResult<Result<(), ValidationError>> {
+        if self.is_valid_number(phone_number)? {
+            return Ok(Ok(()));
+        }
+        // Local-only lengths are possible, but never valid for a full number, so
+        // such numbers end up as pattern mismatches too.
+        Ok(self
+            .is_possible_number_with_reason(phone_number)
+            .and(Err(ValidationError::PatternMismatch)))
+    }
+
+    /// Computes a heuristic confidence between 0.0 and 1.0 that the phone number
+    /// is a real number: half of it comes from the length, two fifths from the
+    /// number matching a pattern of its region and the rest from its type being
+    /// unambiguous.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The phone number to score.
+    pub(crate) fn validity_score(&self, phone_number: &PhoneNumber) -> RegexResult<f32> {
+        let mut score = match self.is_possible_number_with_reason(phone_number) {
+            Ok(NumberLengthType::IsPossible) => 0.5,
+            // Numbers which can only be dialled locally might be missing an area
+            // code, so they are less likely to be what was meant.
+            Ok(NumberLengthType::IsPossibleLocalOnly) => 0.25,
+            Err(_) => return Ok(0.0),
+        };
+        let (region_code, number_type) = self.classify(phone_number)?;
+        if self.is_valid_number_for_region(phone_number, region_code) {
+            score += 0.4;
+            if number_type != PhoneNumberType::FixedLineOrMobile {
+                score += 0.1;
+            }
+        }
+        Ok(score)
+    }
+
+    /// Checks if a phone number is valid and of the requested type.
+    ///
+    /// Regions where fixed-line and mobile numbers can't be told apart classify
+    /// their numbers as `FixedLineOrMobile`; such numbers are considered valid for
+    /// both `FixedLine` and `Mobile` requests. Conversely, a `FixedLineOrMobile`
+    /// request accepts fixed-line and mobile numbers.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The phone number to validate.
+    /// * `phone_number_type` - The type the number is expected to have.
+    pub(crate) fn is_valid_number_for_type(
+        &self,
+        phone_number: &PhoneNumber,
+        phone_number_type: PhoneNumberType,
+    ) -> RegexResult<bool> {
+        Ok(self.is_valid_and_type(phone_number, phone_number_type)?.is_ok())
+    }
+
+    /// Same as `is_valid_number_for_type`, but explains why the number was rejected.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The phone number to validate.
+    /// * `required_type` - The type the number is expected to have.
+    pub(crate) fn is_valid_and_type(
+        &self,
+        phone_number: &PhoneNumber,
+        required_type: PhoneNumberType,
+    ) -> RegexResult<Result<(), TypeValidationError>> {
+        // The type is only known (i.e. not `Unknown`) for valid numbers.
+        let actual_type = self.get_number_type(phone_number)?;
+        Ok(match (required_type, actual_type) {
+            (_, PhoneNumberType::Unknown) => Err(TypeValidationError::Invalid),
+            (
+                PhoneNumberType::FixedLine | PhoneNumberType::Mobile,
+                PhoneNumberType::FixedLineOrMobile,
+            ) => Ok(()),
+            (
+                PhoneNumberType::FixedLineOrMobile,
+                PhoneNumberType::FixedLine | PhoneNumberType::Mobile,
+            ) => Ok(()),
+            (required_type, actual_type) if required_type == actual_type => Ok(()),
+            (_, actual) => Err(TypeValidationError::WrongType { actual }),
+        })
+    }
+
     /// Checks if a phone number is valid for a specific region.
     ///
     /// # Arguments
@@ -1469,6 +3108,12 @@ impl PhoneNumberUtilInternal {
         }
     }
 
+    /// Checks whether a number is valid for the non-geographical entity of its
+    /// country calling code, such as +800 (International Toll Free Service).
+    pub(crate) fn is_valid_non_geo_number(&self, phone_number: &PhoneNumber) -> bool {
+        self.is_valid_number_for_region(phone_number, REGION_CODE_FOR_NON_GEO_ENTITY)
+    }
+
     /// Formats a phone number for out-of-country dialing, preserving any alphabetic characters.
     ///
     /// # Arguments
@@ -1677,6 +3322,16 @@ impl PhoneNumberUtilInternal {
             national_number
                 .push_str(&number_to_parse[index_of_national_number..index_of_phone_context]);
         } else {
+            // A bare "tel:" URI without a phone-context (e.g. a copied hyperlink) is
+            // parsed as if the scheme were absent, using the default region. The
+            // scheme is case-insensitive according to RFC3966.
+            let trimmed = number_to_parse.trim_start();
+            let number_to_parse = match trimmed.get(..RFC3966_PREFIX.len()) {
+                Some(scheme) if scheme.eq_ignore_ascii_case(RFC3966_PREFIX) => {
+                    &trimmed[RFC3966_PREFIX.len()..]
+                }
+                _ => number_to_parse,
+            };
             // Extract a possible number from the string passed in (this strips leading
             // characters that could not be the start of a phone number.)
             national_number.push_str(self
@@ -1786,6 +3441,11 @@ impl PhoneNumberUtilInternal {
     ///
     /// * `phone_number` - The phone number to check.
     pub(crate) fn is_possible_number(&self, phone_number: &PhoneNumber) -> bool {
+        // A missing country calling code is common in bulk data, so reject it
+        // before any metadata lookup or length check.
+        if phone_number.country_code() == 0 {
+            return false;
+        }
         self.is_possible_number_with_reason(phone_number).is_ok()
     }
 
@@ -1837,7 +3497,6 @@ impl PhoneNumberUtilInternal {
         phone_number: &PhoneNumber,
         phone_number_type: PhoneNumberType,
     ) -> ValidationResult {
-        let national_number = self.get_national_significant_number(phone_number);
         let country_code = phone_number.country_code();
         // Note: For regions that share a country calling code, like NANPA numbers, we
         // just use the rules from the default region (US in this case) since the
@@ -1848,15 +3507,16 @@ impl PhoneNumberUtilInternal {
         // with this country calling code in the metadata for the default region in
         // this case.
         if !self.has_valid_country_calling_code(country_code) {
-            return Err(ValidationError::InvalidCountryCode);
+            return Err(ValidationError::InvalidCountryCode { country_code });
         }
         let region_code = self.get_region_code_for_country_code(country_code);
         // Metadata cannot be NULL because the country calling code is valid.
         let Some(metadata) =
             self.get_metadata_for_region_or_calling_code(country_code, region_code)
         else {
-            return Err(ValidationError::InvalidCountryCode);
+            return Err(ValidationError::InvalidCountryCode { country_code });
         };
+        let national_number = self.get_national_significant_number(phone_number);
         return test_number_length(&national_number, metadata, phone_number_type);
     }
 
@@ -1869,8 +3529,22 @@ impl PhoneNumberUtilInternal {
         &self,
         phone_number: &mut PhoneNumber,
     ) -> InternalLogicResult<bool> {
+        Ok(self.truncate_too_long_number_returning(phone_number)?.is_some())
+    }
+
+    /// Same as `truncate_too_long_number`, but returns the trailing digits that
+    /// were removed: an empty string if the number was already valid, and
+    /// `None` if no valid number could be obtained by truncating it.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The number to truncate
+    pub(crate) fn truncate_too_long_number_returning(
+        &self,
+        phone_number: &mut PhoneNumber,
+    ) -> InternalLogicResult<Option<String>> {
         if self.is_valid_number(&phone_number)? {
-            return Ok(true);
+            return Ok(Some(String::new()));
         }
         let mut number_copy = phone_number.clone();
         let mut national_number = phone_number.national_number();
@@ -1879,17 +3553,21 @@ impl PhoneNumberUtilInternal {
             number_copy.set_national_number(national_number);
             if self
                 .is_possible_number_with_reason(&number_copy)
-                .is_err_and(|err| matches!(err, ValidationError::TooShort))
+                .is_err_and(|err| matches!(err, ValidationError::TooShort { .. }))
                 || national_number == 0
             {
-                return Ok(false);
+                return Ok(None);
             }
             if self.is_valid_number(&number_copy)? {
                 break;
             }
         }
+        let kept_digits_count = itoa::Buffer::new().format(national_number).len();
+        let removed_digits = itoa::Buffer::new()
+            .format(phone_number.national_number())[kept_digits_count..]
+            .to_owned();
         phone_number.set_national_number(national_number);
-        return Ok(true);
+        Ok(Some(removed_digits))
     }
 
     // Note if any new field is added to this method that should always be filled
@@ -1901,20 +3579,49 @@ impl PhoneNumberUtilInternal {
         default_region: &str,
         keep_raw_input: bool,
         check_region: bool,
+        strip_national_prefix: bool,
     ) -> ParseResult<PhoneNumber> {
+        let mut phone_number = PhoneNumber::new();
+        self.parse_helper_into(
+            number_to_parse,
+            default_region,
+            keep_raw_input,
+            check_region,
+            strip_national_prefix,
+            &mut phone_number,
+        )?;
+        Ok(phone_number)
+    }
+
+    /// Same as `parse_helper`, but writes the result into `temp_number`. Its
+    /// string fields are overwritten rather than cleared, so their buffers are
+    /// reused. On error, `temp_number` is left in an unspecified state.
+    pub(crate) fn parse_helper_into(
+        &self,
+        number_to_parse: &str,
+        default_region: &str,
+        keep_raw_input: bool,
+        check_region: bool,
+        strip_national_prefix: bool,
+        temp_number: &mut PhoneNumber,
+    ) -> ParseResult<()> {
         let national_number = self.build_national_number_for_parsing(number_to_parse)?;
-        if !self.is_viable_phone_number(&national_number) {
-            trace!("The string supplied did not seem to be a phone number '{national_number}'.");
-            return Err(ParseError::NotANumber(NotANumberError::NotMatchedValidNumberPattern).into());
-        }
+        self.check_viable_number_for_parsing(number_to_parse, &national_number)?;
 
         if check_region && !self.check_region_for_parsing(&national_number, default_region) {
             trace!("Missing or invalid default country.");
             return Err(ParseError::InvalidCountryCode.into());
         }
-        let mut temp_number = PhoneNumber::new();
+        temp_number.country_code = None;
+        temp_number.national_number = None;
+        temp_number.italian_leading_zero = None;
+        temp_number.number_of_leading_zeros = None;
+        temp_number.country_code_source = None;
+        temp_number.special_fields.clear();
         if keep_raw_input {
-            temp_number.set_raw_input(number_to_parse.to_owned());
+            set_string_field(&mut temp_number.raw_input, number_to_parse);
+        } else {
+            temp_number.raw_input = None;
         }
         // Attempt to parse extension first, since it doesn't require country-specific
         // data and we want to have the non-normalised number here.
@@ -1922,17 +3629,30 @@ impl PhoneNumberUtilInternal {
         let (national_number, extension) = self.maybe_strip_extension(&national_number);
 
         if let Some(extension) = extension {
-            temp_number.set_extension(extension.to_owned());
+            set_string_field(&mut temp_number.extension, extension);
+        } else {
+            temp_number.extension = None;
+        }
+        temp_number.raw_input_groups.clear();
+        if keep_raw_input {
+            // Record how the digits were grouped while the view of the input
+            // without RFC3966 parameters and extension is at hand.
+            temp_number.raw_input_groups.extend(
+                dec_from_char::normalize_decimals(national_number)
+                    .split(|c: char| !c.is_ascii_digit())
+                    .filter(|group| !group.is_empty())
+                    .map(str::to_owned),
+            );
         }
         let mut country_metadata = self.get_metadata_for_region(default_region);
         // Check to see if the number is given in international format so we know
         // whether this number is from the default country or not.
-        let mut normalized_national_number = self
+        let normalized_national_number = self
             .maybe_extract_country_code(
                 country_metadata,
                 keep_raw_input,
                 &national_number,
-                &mut temp_number,
+                temp_number,
             )
             .or_else(|err| {
                 if !matches!(err, ParseErrorInternal::FailedToParse(ParseError::InvalidCountryCode)) {
@@ -1946,7 +3666,7 @@ impl PhoneNumberUtilInternal {
                         country_metadata,
                         keep_raw_input,
                         normalized_national_number,
-                        &mut temp_number,
+                        temp_number,
                     )?;
                     if temp_number.country_code() == 0 {
                         return Err(ParseError::InvalidCountryCode.into());
@@ -1969,14 +3689,38 @@ impl PhoneNumberUtilInternal {
             // MaybeExtractCountryCode.
             country_code = country_metadata.country_code();
         }
+        self.finish_parsing(
+            temp_number,
+            country_code,
+            country_metadata,
+            normalized_national_number,
+            keep_raw_input,
+            strip_national_prefix,
+        )
+    }
+
+    /// Completes parsing once the country calling code is known: strips the
+    /// national prefix and carrier code where appropriate (and allowed by
+    /// `strip_national_prefix`), checks the length of the remaining national
+    /// number and fills in the number fields.
+    pub(crate) fn finish_parsing(
+        &self,
+        temp_number: &mut PhoneNumber,
+        country_code: i32,
+        country_metadata: Option<&PhoneMetadata>,
+        mut normalized_national_number: Cow<'_, str>,
+        keep_raw_input: bool,
+        strip_national_prefix: bool,
+    ) -> ParseResult<()> {
+        let mut preferred_domestic_carrier_code = None;
         if normalized_national_number.len() < MIN_LENGTH_FOR_NSN {
             trace!(
                 "The string supplied is too short to be a phone number '{}'.",
                 normalized_national_number
             );
-            return Err(ParseError::TooShortNsn.into());
+            return Err(ParseError::TooShortNsn { length: normalized_national_number.len() }.into());
         }
-        if let Some(country_metadata) = country_metadata {
+        if let Some(country_metadata) = country_metadata.filter(|_| strip_national_prefix) {
             let mut potential_national_number = normalized_national_number.clone();
 
             let (phone_number, carrier_code) = self.maybe_strip_national_prefix_and_carrier_code(
@@ -2002,14 +3746,12 @@ impl PhoneNumberUtilInternal {
                 && !validation_result.is_err_and(|err| {
                     matches!(
                         err,
-                        ValidationError::TooShort | ValidationError::InvalidLength
+                        ValidationError::TooShort { .. } | ValidationError::InvalidLength { .. }
                     )
                 })
             {
                 normalized_national_number = potential_national_number;
-                if let Some(carrier_code) = carrier_code.filter(|_| keep_raw_input) {
-                    temp_number.set_preferred_domestic_carrier_code(carrier_code.to_owned());
-                }
+                preferred_domestic_carrier_code = carrier_code.filter(|_| keep_raw_input);
             }
         }
         let normalized_national_number_length = normalized_national_number.len();
@@ -2018,16 +3760,22 @@ impl PhoneNumberUtilInternal {
                 "The string supplied is too short to be a phone number: '{}'.",
                 normalized_national_number
             );
-            return Err(ParseError::TooShortNsn.into());
+            return Err(ParseError::TooShortNsn { length: normalized_national_number.len() }.into());
         }
         if normalized_national_number_length > MAX_LENGTH_FOR_NSN {
             trace!(
                 "The string supplied is too long to be a phone number: '{}'.",
                 normalized_national_number
             );
-            return Err(ParseError::TooLongNsn.into());
+            return Err(ParseError::TooLongNsn { length: normalized_national_number.len() }.into());
         }
         temp_number.set_country_code(country_code);
+        match preferred_domestic_carrier_code {
+            Some(carrier_code) => {
+                set_string_field(&mut temp_number.preferred_domestic_carrier_code, &carrier_code)
+            }
+            None => temp_number.preferred_domestic_carrier_code = None,
+        }
 
         if let Some(zeroes_count) =
             Self::get_italian_leading_zeros_for_phone_number(&normalized_national_number) {
@@ -2043,7 +3791,7 @@ impl PhoneNumberUtilInternal {
                 NotANumberError::FailedToParseNumberAsInt(err).into()
             ).into()),
         }
-        return Ok(temp_number);
+        Ok(())
     }
 
     /// Checks to see if the string of characters could possibly be a phone number at
@@ -2053,13 +3801,49 @@ impl PhoneNumberUtilInternal {
     /// assume that leading non-number symbols have been removed, such as by the
     /// method `ExtractPossibleNumber`.
     pub(crate) fn is_viable_phone_number(&self, phone_number: &str) -> bool {
-        if phone_number.len() < MIN_LENGTH_FOR_NSN {
-            false
-        } else {
-            self.reg_exps
-                .valid_phone_number_pattern
-                .full_match(phone_number)
+        self.check_viable_phone_number(phone_number).is_ok()
+    }
+
+    /// Same as `is_viable_phone_number`, but when the number is not viable, the
+    /// error holds the byte offset and the character of the first codepoint that
+    /// can never be part of a phone number, if the number contains one.
+    pub(crate) fn check_viable_phone_number(
+        &self,
+        phone_number: &str,
+    ) -> Result<(), Option<(usize, char)>> {
+        if phone_number.len() >= MIN_LENGTH_FOR_NSN
+            && self.reg_exps.valid_phone_number_pattern.full_match(phone_number)
+        {
+            return Ok(());
         }
+        Err(phone_number
+            .char_indices()
+            .find(|(_, c)| helper_functions::is_invalid_character(*c)))
+    }
+
+    /// Checks that the national number built from `number_to_parse` is viable,
+    /// reporting the first invalid character with its position in `number_to_parse`.
+    fn check_viable_number_for_parsing(
+        &self,
+        number_to_parse: &str,
+        national_number: &str,
+    ) -> Result<(), ParseError> {
+        let Err(invalid_character) = self.check_viable_phone_number(national_number) else {
+            return Ok(());
+        };
+        trace!("The string supplied did not seem to be a phone number '{national_number}'.");
+        let Some((position, codepoint)) = invalid_character else {
+            return Err(NotANumberError::NotMatchedValidNumberPattern.into());
+        };
+        // The national number is usually a part of the input, so the position is moved
+        // to point into the input. Otherwise, as for RFC3966 numbers, the first
+        // occurrence of the character in the input is used.
+        let position = number_to_parse
+            .find(national_number)
+            .map(|offset| offset + position)
+            .or_else(|| number_to_parse.find(codepoint))
+            .unwrap_or(position);
+        Err(ParseError::InvalidCharacter { position, codepoint })
     }
 
     /// Checks to see that the region code used is valid, or if it is not valid, that
@@ -2079,26 +3863,70 @@ impl PhoneNumberUtilInternal {
     /// connected, usually indicated with extn, ext, x or similar) from the end of
     /// the number, and returns stripped number and extension. The number passed in should be non-normalized.
     pub(crate) fn maybe_strip_extension<'a>(&self, phone_number: &'a str) -> (&'a str, Option<&'a str>) {
-        let Some(captures) = self.reg_exps.extn_pattern.captures(phone_number) else {
+        let (phone_number, extension) = self.maybe_strip_extension_with_label(phone_number);
+        (phone_number, extension.map(|(extension, _)| extension))
+    }
+
+    /// Same as `maybe_strip_extension`, but also returns how the extension was
+    /// introduced in the number.
+    pub(crate) fn maybe_strip_extension_with_label<'a>(
+        &self,
+        phone_number: &'a str,
+    ) -> (&'a str, Option<(&'a str, ExtensionLabelKind)>) {
+        match self.find_extension(phone_number) {
+            Some((start, extension, label)) => (&phone_number[..start], Some((extension, label))),
+            None => (phone_number, None),
+        }
+    }
+
+    /// Same as `maybe_strip_extension`, but also returns the byte index in
+    /// `phone_number` where the extension label begins. Whitespace separating the
+    /// label from the number is not considered part of the label.
+    pub(crate) fn maybe_strip_extension_with_span<'a>(
+        &self,
+        phone_number: &'a str,
+    ) -> (&'a str, Option<(&'a str, usize)>) {
+        let Some((start, extension, _)) = self.find_extension(phone_number) else {
             return (phone_number, None);
         };
+        let phone_number_no_extn = &phone_number[..start];
+        let label = &phone_number[start..];
+        let label_start = start + label.len() - label.trim_start().len();
+        (phone_number_no_extn, Some((extension, label_start)))
+    }
+
+    /// Finds the extension at the end of the number, returning the byte index where
+    /// the matched extension (including its leading separators) starts, the
+    /// extension digits and the kind of label used.
+    fn find_extension<'a>(&self, phone_number: &'a str) -> Option<(usize, &'a str, ExtensionLabelKind)> {
+        let captures = self.reg_exps.extn_pattern.captures(phone_number)?;
 
         // first capture is always not None, this should not happen, but use this for safety.
-        let Some(full_capture) = captures.get(0) else {
-            return (phone_number, None);
-        };
+        let full_capture = captures.get(0)?;
         // Replace the extensions in the original string here.
         let phone_number_no_extn = &phone_number[..full_capture.start()];
         // If we find a potential extension, and the number preceding this is a
         // viable number, we assume it is an extension.
         if !self.is_viable_phone_number(&phone_number_no_extn) {
-            return (phone_number, None);
-        }
-        if let Some(ext) = captures.iter().skip(1).flatten().find(|m| !m.is_empty()) {
-            return (phone_number_no_extn, Some(ext.as_str()));
+            return None;
         }
-
-        (phone_number, None)
+        // The capturing groups follow the order of the alternatives in
+        // `create_extn_pattern`: groups 5 and 6 are auto-dialling forms, and the
+        // optional group 7 holds extensions after extra ambiguous separators.
+        let (group, ext) = captures
+            .iter()
+            .enumerate()
+            .skip(1)
+            .find_map(|(i, m)| m.filter(|m| !m.is_empty()).map(|m| (i, m)))?;
+        let label = match group {
+            1 => ExtensionLabelKind::Rfc3966,
+            2 => ExtensionLabelKind::Explicit,
+            3 => ExtensionLabelKind::Ambiguous,
+            4 => ExtensionLabelKind::HashSuffix,
+            5 | 6 => ExtensionLabelKind::AutoDialling,
+            _ => ExtensionLabelKind::Ambiguous,
+        };
+        Some((full_capture.start(), ext.as_str(), label))
     }
 
     /// Tries to extract a country calling code from a number. Country calling codes
@@ -2137,9 +3965,10 @@ impl PhoneNumberUtilInternal {
                 "NonMatch"
             };
 
+        let number_to_strip = national_number;
         let phone_number_with_country_code_source = self
             .maybe_strip_international_prefix_and_normalize(
-                national_number,
+                number_to_strip,
                 possible_country_idd_prefix,
             )?;
 
@@ -2158,7 +3987,24 @@ impl PhoneNumberUtilInternal {
                 long enough to be a viable phone number.",
                     national_number
                 );
-                return Err(ParseError::TooShortAfterIdd.into());
+                let idd = if matches!(
+                    phone_number_with_country_code_source.country_code_source,
+                    CountryCodeSource::FROM_NUMBER_WITH_PLUS_SIGN
+                ) {
+                    PLUS_SIGN.to_owned()
+                } else {
+                    // The IDD was stripped from the normalized number, so whatever
+                    // precedes the remaining digits there is the prefix itself.
+                    let normalized_number = self.normalize(number_to_strip);
+                    normalized_number
+                        .strip_suffix(national_number.as_ref())
+                        .unwrap_or_default()
+                        .to_owned()
+                };
+                return Err(ParseError::TooShortAfterIdd {
+                    idd,
+                    remaining: national_number.into_owned(),
+                }.into());
             }
             let Some((national_number, potential_country_code)) =
                 self.extract_country_code(national_number)
@@ -2198,18 +4044,18 @@ impl PhoneNumberUtilInternal {
                 // long before, we consider the number with the country code stripped to
                 // be a better result and keep that instead.
                 if (!helper_functions::is_match(
-                    &self.matcher_api,
+                    self.matcher_api.as_ref(),
                     &national_number,
                     general_num_desc,
                 ) && helper_functions::is_match(
-                    &self.matcher_api,
+                    self.matcher_api.as_ref(),
                     &potential_national_number,
                     general_num_desc,
                 )) || test_number_length_with_unknown_type(
                     &national_number,
                     default_region_metadata,
                 )
-                .is_err_and(|e| matches!(e, ValidationError::TooLong))
+                .is_err_and(|e| matches!(e, ValidationError::TooLong { .. }))
                 {
                     if keep_raw_input {
                         phone_number.set_country_code_source(
@@ -2314,6 +4160,75 @@ impl PhoneNumberUtilInternal {
         Err(GetExampleNumberError::CouldNotGetNumber.into())
     }
 
+    /// Returns an iterator over one example number for each type supported by
+    /// the region, in the declaration order of `PhoneNumberType`. Types without
+    /// an example number are skipped, and an unknown region yields nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_code` - The region for which to get example numbers.
+    pub(crate) fn example_numbers_for_region<'a>(
+        &'a self,
+        region_code: &str,
+    ) -> impl Iterator<Item = (PhoneNumberType, PhoneNumber)> + use<'a> {
+        let supported_types = self
+            .get_supported_types_for_region(region_code)
+            .unwrap_or_default();
+        let region_code = region_code.to_owned();
+        PhoneNumberType::iter()
+            .filter(move |number_type| supported_types.contains(number_type))
+            .filter_map(move |number_type| {
+                self.get_example_number_for_type_and_region_code(&region_code, number_type)
+                    .ok()
+                    .map(|example_number| (number_type, example_number))
+            })
+    }
+
+    /// Gets a valid example number for the given region and type that differs from
+    /// the one returned by `get_example_number_for_type_and_region_code`. It is
+    /// found by stepping the national number up and down from the canonical
+    /// example until a number of the same length and type is valid for the region.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_code` - The region for which to get an example number.
+    /// * `phone_number_type` - The type of number to get an example for.
+    pub(crate) fn get_second_example_number_for_type_and_region(
+        &self,
+        region_code: &str,
+        phone_number_type: PhoneNumberType,
+    ) -> ExampleNumberResult {
+        // Numbers close to the example almost always share its pattern, so only a
+        // small neighbourhood is searched.
+        const MAX_STEPS: u64 = 1000;
+
+        let example_number =
+            self.get_example_number_for_type_and_region_code(region_code, phone_number_type)?;
+        let example_type = self.get_number_type(&example_number)?;
+        let national_number = example_number.national_number();
+        let digits = national_number.checked_ilog10();
+
+        for step in 1..=MAX_STEPS {
+            let candidates = [
+                national_number.checked_add(step),
+                national_number.checked_sub(step),
+            ];
+            for candidate_national_number in candidates.into_iter().flatten() {
+                if candidate_national_number.checked_ilog10() != digits {
+                    continue;
+                }
+                let mut candidate = example_number.clone();
+                candidate.set_national_number(candidate_national_number);
+                if self.is_valid_number_for_region(&candidate, region_code)
+                    && self.get_number_type(&candidate)? == example_type
+                {
+                    return Ok(candidate);
+                }
+            }
+        }
+        Err(GetExampleNumberError::CouldNotGetNumber.into())
+    }
+
     /// Gets an example of a valid phone number for a given region and type.
     ///
     /// # Arguments
@@ -2419,6 +4334,46 @@ impl PhoneNumberUtilInternal {
         return Err(GetExampleNumberError::CouldNotGetNumber.into());
     }
 
+    /// Returns an iterator over one example number for each type supported by
+    /// the non-geographical entity, in the declaration order of
+    /// `PhoneNumberType`. Types without an example number are skipped. Returns
+    /// `None` if the country calling code isn't a known non-geographical one.
+    ///
+    /// # Arguments
+    ///
+    /// * `country_calling_code` - The non-geographical country calling code.
+    pub(crate) fn get_example_numbers_for_non_geo_entity(
+        &self,
+        country_calling_code: i32,
+    ) -> Option<impl Iterator<Item = (PhoneNumberType, PhoneNumber)> + '_> {
+        let supported_types = self.get_supported_types_for_non_geo_entity(country_calling_code)?;
+        let metadata = self
+            .country_code_to_non_geographical_metadata_map
+            .get(&country_calling_code)?;
+        Some(
+            PhoneNumberType::iter()
+                .filter(move |number_type| supported_types.contains(number_type))
+                .filter_map(move |number_type| {
+                    let desc = get_number_desc_by_type(metadata, number_type);
+                    if !desc.has_example_number() {
+                        return None;
+                    }
+                    let mut buf = itoa::Buffer::new();
+                    self.parse(
+                        &fast_cat::concat_str!(
+                            PLUS_SIGN,
+                            buf.format(country_calling_code),
+                            desc.example_number()
+                        ),
+                        RegionCode::get_unknown(),
+                    )
+                    .inspect_err(|err| error!("Error parsing example number ({:?})", err))
+                    .ok()
+                    .map(|example_number| (number_type, example_number))
+                }),
+        )
+    }
+
     /// Strips any international prefix (such as +, 00, 011) present in the number
     /// provided, normalizes the resulting number, and indicates if an international
     /// prefix was present.
@@ -2480,7 +4435,9 @@ impl PhoneNumberUtilInternal {
     ///     lessen the risk that such letters are typos.
     ///   For other numbers:
     ///   - Wide-ascii digits are converted to normal ASCII (European) digits.
-    ///   - Arabic-Indic numerals are converted to European numerals.
+    ///   - Digits of other scripts (any Unicode `Nd` digit, e.g. Arabic-Indic,
+    ///     Devanagari or Bengali) are converted to European numerals. This is
+    ///     done for alpha numbers too.
     ///   - Spurious alpha characters are stripped.
     /// 
     /// # Arguments
@@ -2492,7 +4449,10 @@ impl PhoneNumberUtilInternal {
             .valid_alpha_phone_pattern
             .is_match(phone_number)
         {
-            normalize_helper(&self.reg_exps.alpha_phone_mappings, true, phone_number)
+            // The mappings only know ASCII digits, so digits of other scripts are
+            // converted first to not lose them.
+            let phone_number = dec_from_char::normalize_decimals(phone_number);
+            normalize_helper(&self.reg_exps.alpha_phone_mappings, true, &phone_number)
         } else {
             self.normalize_digits_only(phone_number)
         }
@@ -2648,6 +4608,69 @@ impl PhoneNumberUtilInternal {
         Ok(captured_groups[ndc_index])
     }
 
+    /// Gets the digits of the national destination code for a given number.
+    ///
+    /// The NDC is always a prefix of the national significant number, so this
+    /// takes as many leading digits of it as `get_length_of_national_destination_code`
+    /// reports. Returns an empty string if the number has no NDC.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The phone number to check.
+    pub(crate) fn get_national_destination_code(
+        &self,
+        phone_number: &PhoneNumber,
+    ) -> InternalLogicResult<String> {
+        let ndc_length = self.get_length_of_national_destination_code(phone_number)?;
+        let mut national_significant_number = self.get_national_significant_number(phone_number);
+        national_significant_number.truncate(ndc_length.min(national_significant_number.len()));
+        Ok(national_significant_number)
+    }
+
+    /// Splits a phone number into its country code, area code, national
+    /// destination code, subscriber number and extension.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The phone number to split.
+    pub(crate) fn format_parts(&self, phone_number: &PhoneNumber) -> InternalLogicResult<NumberParts> {
+        let national_significant_number = self.get_national_significant_number(phone_number);
+        let area_code_length = self.get_length_of_geographical_area_code(phone_number)?;
+        let ndc_length = self
+            .get_length_of_national_destination_code(phone_number)?
+            .min(national_significant_number.len());
+
+        let non_empty_prefix = |length: usize| {
+            (length > 0).then(|| national_significant_number[..length].to_owned())
+        };
+        Ok(NumberParts {
+            country_code: itoa::Buffer::new().format(phone_number.country_code()).to_owned(),
+            area_code: non_empty_prefix(area_code_length.min(national_significant_number.len())),
+            ndc: non_empty_prefix(ndc_length),
+            subscriber: national_significant_number[ndc_length..].to_owned(),
+            extension: phone_number
+                .has_extension()
+                .then(|| phone_number.extension().to_owned()),
+        })
+    }
+
+    /// Returns true if the supplied region supports mobile number portability,
+    /// i.e. a mobile number may have been moved to a carrier other than the one
+    /// its prefix was assigned to.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_code` - The region for which we want to know whether it supports
+    ///   mobile number portability or not.
+    #[cfg(feature = "carrier")]
+    pub(crate) fn is_mobile_number_portable_region(&self, region_code: &str) -> bool {
+        let Some(metadata) = self.region_to_metadata_map.get(region_code) else {
+            warn!("Invalid or unknown region code provided: {}", region_code);
+            return false;
+        };
+        metadata.mobile_number_portable_region()
+    }
+
     pub(crate) fn get_country_mobile_token(&self, country_calling_code: i32) -> Option<char> {
         self.reg_exps
             .mobile_token_mappings
@@ -2660,8 +4683,7 @@ impl PhoneNumberUtilInternal {
     ///
     /// It assumes that the leading plus sign or IDD has already been removed.
     ///
-    /// Returns None if national_number doesn't start with a valid country calling code
-    /// Assumes the national_number is at least 3 characters long.
+    /// Returns None if national_number doesn't start with a valid country calling code.
     pub(crate) fn extract_country_code<'a>(
         &self,
         national_number: Cow<'a, str>,
@@ -2669,7 +4691,7 @@ impl PhoneNumberUtilInternal {
         if national_number.as_ref().is_empty() || national_number.as_ref().starts_with('0') {
             return None;
         }
-        for i in 0..=MAX_LENGTH_COUNTRY_CODE {
+        for i in 1..=MAX_LENGTH_COUNTRY_CODE.min(national_number.len()) {
             let Ok(potential_country_code) =
                 i32::from_str_radix(&national_number.as_ref()[0..i], 10)
             else {
@@ -2708,7 +4730,7 @@ impl PhoneNumberUtilInternal {
         let general_desc = &metadata.general_desc;
         // Check if the original number is viable.
         let is_viable_original_number =
-            helper_functions::is_match(&self.matcher_api, &phone_number, general_desc);
+            helper_functions::is_match(self.matcher_api.as_ref(), &phone_number, general_desc);
         // Attempt to parse the first digits as a national prefix. We make a
         // copy so that we can revert to the original string if necessary.
         let transform_rule = metadata.national_prefix_transform_rule();
@@ -2746,7 +4768,7 @@ impl PhoneNumberUtilInternal {
             let replaced_number =
                 possible_national_prefix_pattern.replace(&phone_number, transform_rule);
             if is_viable_original_number
-                && !helper_functions::is_match(&self.matcher_api, &replaced_number, general_desc)
+                && !helper_functions::is_match(self.matcher_api.as_ref(), &replaced_number, general_desc)
             {
                 return Ok((phone_number.into(), None));
             }
@@ -2761,7 +4783,7 @@ impl PhoneNumberUtilInternal {
             // transformation is necessary, and we just remove the national prefix.
             let stripped_number = &phone_number[matched.end()..];
             if is_viable_original_number
-                && !helper_functions::is_match(&self.matcher_api, stripped_number, general_desc) {
+                && !helper_functions::is_match(self.matcher_api.as_ref(), stripped_number, general_desc) {
                 return Ok((phone_number.into(), None));
             }
             let carrier_code_temp = if let Some(capture) = first_capture {
@@ -2798,6 +4820,20 @@ impl PhoneNumberUtilInternal {
         Some(zero_count)
     }
 
+    /// Replaces every dialing wait symbol ('~') in a formatted number with the given
+    /// pause token. Preferred international prefixes such as "8~10" in Uzbekistan use
+    /// '~' to mean "wait for a dial tone". An empty `pause` removes the waits.
+    ///
+    /// # Arguments
+    ///
+    /// * `formatted_number` - A number formatted by this library.
+    /// * `pause` - The token to put in place of each wait, e.g. ",".
+    pub(crate) fn expand_dialing_waits(&self, formatted_number: &str, pause: &str) -> String {
+        // '~' is ASCII, so it can never be part of a multi-byte UTF-8 sequence and
+        // plain char replacement is safe for any formatted input.
+        formatted_number.replace('~', pause)
+    }
+
     /// Converts all alpha characters in a phone number string to their respective digits on a keypad.
     ///
     /// # Arguments
@@ -2807,6 +4843,49 @@ impl PhoneNumberUtilInternal {
         normalize_helper(&self.reg_exps.alpha_phone_mappings, false, phone_number)
     }
 
+    /// Builds a vanity version of the toll-free example number of a region, e.g.
+    /// "(800) ADG-JMPT" for "(800) 234-5678". All digits after the first group of
+    /// the national format are replaced by the first letter of their keypad key;
+    /// 0 and 1 have no letters and are kept. `convert_alpha_characters_in_number`
+    /// turns the result back into the formatted example number.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_code` - The region whose toll-free example is used.
+    pub(crate) fn synthesize_alpha_example(&self, region_code: &str) -> RegexResult<Option<String>> {
+        let example_number = match self
+            .get_example_number_for_type_and_region_code(region_code, PhoneNumberType::TollFree)
+        {
+            Ok(example_number) => example_number,
+            Err(GetExampleNumberErrorInternal::RegexError(err)) => return Err(err),
+            Err(GetExampleNumberErrorInternal::FailedToGetExampleNumber(_)) => return Ok(None),
+        };
+        let formatted = self.format(&example_number, PhoneNumberFormat::National)?;
+
+        // The first group is usually the toll-free prefix ("800", "0800") which
+        // vanity numbers keep as digits.
+        let first_group_end = formatted
+            .char_indices()
+            .skip_while(|(_, c)| !c.is_ascii_digit())
+            .find(|(_, c)| !c.is_ascii_digit())
+            .map_or(formatted.len(), |(i, _)| i);
+        let (first_group, rest) = formatted.split_at(first_group_end);
+
+        let mut has_letters = false;
+        let mut alpha_example = String::with_capacity(formatted.len());
+        alpha_example.push_str(first_group);
+        for c in rest.chars() {
+            let letter = self.reg_exps.alpha_mappings
+                .iter()
+                .filter(|(_, digit)| **digit == c)
+                .map(|(letter, _)| *letter)
+                .min();
+            has_letters |= letter.is_some();
+            alpha_example.push(letter.unwrap_or(c));
+        }
+        Ok(has_letters.then_some(alpha_example))
+    }
+
     /// Checks whether two phone numbers match.
     /// Returns the type of match.
     ///
@@ -2895,18 +4974,93 @@ impl PhoneNumberUtilInternal {
                     return Err(err.into());
                 }
                 let first_number_as_proto =
-                    self.parse_helper(first_number, RegionCode::get_unknown(), false, false)?;
+                    self.parse_helper(first_number, RegionCode::get_unknown(), false, false, true)?;
                 let second_number_as_proto = self.parse_helper(
                     second_number,
                     RegionCode::get_unknown(),
                     false,
                     false,
+                    true,
                 )?;
                 return Ok(self.is_number_match(&first_number_as_proto, &second_number_as_proto));
             }
         }
     }
 
+    /// Same as `is_number_match`, but also returns the edit distance between the
+    /// national significant numbers when both numbers have the same country
+    /// calling code and national significant numbers of the same length.
+    ///
+    /// # Arguments
+    ///
+    /// * `first_number` - The first phone number to compare.
+    /// * `second_number` - The second phone number to compare.
+    pub(crate) fn is_number_match_with_distance(
+        &self,
+        first_number: &PhoneNumber,
+        second_number: &PhoneNumber,
+    ) -> (MatchType, Option<usize>) {
+        let match_type = self.is_number_match(first_number, second_number);
+        if first_number.country_code() != second_number.country_code() {
+            return (match_type, None);
+        }
+        let first_nsn = self.get_national_significant_number(first_number);
+        let second_nsn = self.get_national_significant_number(second_number);
+        if first_nsn.len() != second_nsn.len() {
+            return (match_type, None);
+        }
+        (match_type, Some(digits_edit_distance(first_nsn.as_bytes(), second_nsn.as_bytes())))
+    }
+
+    /// Same as `is_number_match`, but also reports whether the result was caused by
+    /// a difference in Italian leading zeros only, e.g. "02 3661 8300" compared to
+    /// "2 3661 8300" in Italy. Such numbers have the same national number but
+    /// different national significant numbers, and give a `ShortNsnMatch`.
+    ///
+    /// # Arguments
+    ///
+    /// * `first_number` - The first phone number to compare.
+    /// * `second_number` - The second phone number to compare.
+    pub(crate) fn is_number_match_with_leading_zero_info(
+        &self,
+        first_number: &PhoneNumber,
+        second_number: &PhoneNumber,
+    ) -> (MatchType, bool) {
+        let match_type = self.is_number_match(first_number, second_number);
+        let affected_by_leading_zeros = match_type == MatchType::ShortNsnMatch
+            && first_number.national_number() == second_number.national_number()
+            && self.get_national_significant_number(first_number)
+                != self.get_national_significant_number(second_number);
+        (match_type, affected_by_leading_zeros)
+    }
+
+    /// Checks whether two phone numbers, each given either parsed or as a string,
+    /// match. Strings are parsed the same way as in `is_number_match_with_one_string`
+    /// and `is_number_match_with_two_strings`.
+    ///
+    /// # Arguments
+    ///
+    /// * `first_number` - The first phone number to compare.
+    /// * `second_number` - The second phone number to compare.
+    pub(crate) fn is_number_match_lenient(
+        &self,
+        first_number: NumberOrString<'_>,
+        second_number: NumberOrString<'_>,
+    ) -> MatchResult {
+        match (first_number, second_number) {
+            (NumberOrString::Number(first_number), NumberOrString::Number(second_number)) => {
+                Ok(self.is_number_match(first_number, second_number))
+            }
+            (NumberOrString::Number(phone_number), NumberOrString::String(number_string))
+            | (NumberOrString::String(number_string), NumberOrString::Number(phone_number)) => {
+                self.is_number_match_with_one_string(phone_number, number_string)
+            }
+            (NumberOrString::String(first_number), NumberOrString::String(second_number)) => {
+                self.is_number_match_with_two_strings(first_number, second_number)
+            }
+        }
+    }
+
     /// Checks whether two phone numbers match.
     /// Returns the type of match.
     ///
@@ -2950,7 +5104,7 @@ impl PhoneNumberUtilInternal {
             // If the first number didn't have a valid country calling code, then we
             // parse the second number without one as well.
             let second_number_as_proto =
-                self.parse_helper(second_number, RegionCode::get_unknown(), false, false)?;
+                self.parse_helper(second_number, RegionCode::get_unknown(), false, false, true)?;
             return Ok(self.is_number_match(first_number, &second_number_as_proto));
         }
     }
@@ -2969,4 +5123,19 @@ impl PhoneNumberUtilInternal {
         let (number, _extension) = self.maybe_strip_extension(&phone_number);
         return self.reg_exps.valid_alpha_phone_pattern.full_match(number);
     }
+
+    /// Like `is_alpha_number`, but only looks at the main number: letters of the
+    /// extension label, including a trailing label with no digits after it, are ignored.
+    pub(crate) fn is_alpha_number_excluding_extension(&self, phone_number: &str) -> bool {
+        let (mut number, extension) = self.maybe_strip_extension(phone_number);
+        if extension.is_none() {
+            // A dangling label such as "ext." is not matched by the extension
+            // pattern, which requires digits after the label.
+            if let Some(label) = self.reg_exps.dangling_extn_label_pattern.find(phone_number) {
+                number = &phone_number[..label.start()];
+            }
+        }
+        self.is_viable_phone_number(number)
+            && self.reg_exps.valid_alpha_phone_pattern.full_match(number)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,192 @@
+// Copyright (C) 2011 The Libphonenumber Authors
+// Copyright (C) 2025 Kashin Vladislav (Rust adaptation author)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+#[cfg(any(feature = "geocoder", feature = "carrier"))]
+use std::sync::OnceLock;
+
+/// A map from phone number prefixes (country calling code followed by the first
+/// digits of the national significant number) to descriptions, parsed from one
+/// data file.
+pub(crate) struct PhonePrefixMap {
+    descriptions: HashMap<&'static str, &'static str>,
+    /// The distinct prefix lengths in the map, longest first.
+    possible_lengths: Vec<usize>,
+}
+
+impl PhonePrefixMap {
+    /// Parses a data file made of "prefix|description" lines. Empty lines and
+    /// lines starting with '#' are skipped.
+    pub(crate) fn parse(data: &'static str) -> Self {
+        let descriptions: HashMap<_, _> = data
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once('|'))
+            .collect();
+        let mut possible_lengths: Vec<_> = descriptions.keys().map(|prefix| prefix.len()).collect();
+        possible_lengths.sort_unstable_by(|a, b| b.cmp(a));
+        possible_lengths.dedup();
+        Self { descriptions, possible_lengths }
+    }
+
+    /// Returns the description of the longest prefix of `phone_prefix` present in
+    /// the map, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_prefix` - The country calling code followed by the national
+    ///   significant number.
+    #[cfg(feature = "timezones")]
+    pub(crate) fn lookup(&self, phone_prefix: &str) -> Option<&'static str> {
+        self.lookup_with_min_length(phone_prefix, 0)
+    }
+
+    /// Same as `lookup`, but ignores prefixes shorter than `min_length`.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_prefix` - The country calling code followed by the national
+    ///   significant number.
+    /// * `min_length` - The length of the shortest prefix to consider.
+    pub(crate) fn lookup_with_min_length(
+        &self,
+        phone_prefix: &str,
+        min_length: usize,
+    ) -> Option<&'static str> {
+        self.possible_lengths
+            .iter()
+            .filter(|&&length| (min_length..=phone_prefix.len()).contains(&length))
+            .find_map(|&length| self.descriptions.get(&phone_prefix[..length]).copied())
+    }
+}
+
+/// Gives access to a set of bundled prefix data files, one per language and
+/// country calling code. Each file is parsed on first use.
+#[cfg(any(feature = "geocoder", feature = "carrier"))]
+pub(crate) struct PrefixFileReader {
+    files: &'static [(&'static str, i32, &'static str)],
+    maps: Vec<OnceLock<PhonePrefixMap>>,
+}
+
+#[cfg(any(feature = "geocoder", feature = "carrier"))]
+impl PrefixFileReader {
+    pub(crate) fn new(files: &'static [(&'static str, i32, &'static str)]) -> Self {
+        Self {
+            files,
+            maps: files.iter().map(|_| OnceLock::new()).collect(),
+        }
+    }
+
+    /// Returns the description for the phone prefix in the language of the
+    /// locale, or in English if there is none and the language may fall back to
+    /// it. Returns an empty string if nothing is found.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_prefix` - The country calling code followed by the national
+    ///   significant number.
+    /// * `min_prefix_length` - The length of the shortest prefix to consider, e.g.
+    ///   to require a match on the whole area code.
+    /// * `country_calling_code` - The country calling code the prefix starts with.
+    /// * `locale` - A locale such as "en", "de-CH" or "zh_Hant".
+    pub(crate) fn get_description_for_number(
+        &self,
+        phone_prefix: &str,
+        min_prefix_length: usize,
+        country_calling_code: i32,
+        locale: &str,
+    ) -> &'static str {
+        let (language, script, region) = split_locale(locale);
+        let description = self
+            .find_map(country_calling_code, &language, &script, &region)
+            .and_then(|map| map.lookup_with_min_length(phone_prefix, min_prefix_length));
+        match description {
+            Some(description) if !description.is_empty() => description,
+            // When a description isn't available in the requested language, fall
+            // back to English.
+            _ if may_fall_back_to_english(&language) => self
+                .find_map(country_calling_code, "en", "", "")
+                .and_then(|map| map.lookup_with_min_length(phone_prefix, min_prefix_length))
+                .unwrap_or(""),
+            _ => "",
+        }
+    }
+
+    /// Returns the map of the file that best matches the locale, trying the
+    /// language with script and region, then with either of them, then the bare
+    /// language.
+    fn find_map(
+        &self,
+        country_calling_code: i32,
+        language: &str,
+        script: &str,
+        region: &str,
+    ) -> Option<&PhonePrefixMap> {
+        let mut candidates = Vec::with_capacity(5);
+        // Traditional Chinese data is stored under "zh_Hant" for all the regions
+        // using it.
+        if language == "zh" && matches!(region, "TW" | "HK" | "MO") {
+            candidates.push("zh_Hant".to_owned());
+        }
+        for (script, region) in [(script, region), (script, ""), ("", region)] {
+            let mut candidate = language.to_owned();
+            for part in [script, region].into_iter().filter(|part| !part.is_empty()) {
+                candidate.push('_');
+                candidate.push_str(part);
+            }
+            candidates.push(candidate);
+        }
+        candidates.push(language.to_owned());
+
+        let index = candidates.iter().find_map(|candidate| {
+            self.files.iter().position(|&(file_language, file_country_code, _)| {
+                file_country_code == country_calling_code && file_language == candidate
+            })
+        })?;
+        Some(self.maps[index].get_or_init(|| PhonePrefixMap::parse(self.files[index].2)))
+    }
+}
+
+/// Splits a locale such as "zh-Hant-TW" or "en_US" into the lowercase language,
+/// the titlecase script and the uppercase region, any of which may be empty.
+#[cfg(any(feature = "geocoder", feature = "carrier"))]
+fn split_locale(locale: &str) -> (String, String, String) {
+    let mut parts = locale.split(['-', '_']);
+    let mut language = parts.next().unwrap_or_default().to_ascii_lowercase();
+    // The data uses the legacy code for Hebrew.
+    if language == "he" {
+        language = "iw".to_owned();
+    }
+    let mut script = String::new();
+    let mut region = String::new();
+    for part in parts {
+        if part.len() == 4 && part.chars().all(|c| c.is_ascii_alphabetic()) && script.is_empty() {
+            script = part[..1].to_ascii_uppercase() + &part[1..].to_ascii_lowercase();
+        } else if (part.len() == 2 && part.chars().all(|c| c.is_ascii_alphabetic()))
+            || (part.len() == 3 && part.chars().all(|c| c.is_ascii_digit()))
+        {
+            region = part.to_ascii_uppercase();
+            break;
+        }
+    }
+    (language, script, region)
+}
+
+/// Chinese, Japanese and Korean descriptions shouldn't be replaced by English
+/// ones, as the latter are unlikely to be useful to speakers of those languages.
+#[cfg(any(feature = "geocoder", feature = "carrier"))]
+fn may_fall_back_to_english(language: &str) -> bool {
+    !matches!(language, "zh" | "ja" | "ko")
+}
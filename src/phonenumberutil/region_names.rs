@@ -0,0 +1,267 @@
+// Copyright (C) 2025 Kashin Vladislav (Rust adaptation author)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Returns the English name of the region, e.g. "United States" for "US", or an
+/// empty string for region codes that don't name a country or territory, such
+/// as "ZZ" and "001".
+pub(crate) fn get_region_display_name(region_code: &str) -> &'static str {
+    match region_code {
+        "AC" => "Ascension Island",
+        "AD" => "Andorra",
+        "AE" => "United Arab Emirates",
+        "AF" => "Afghanistan",
+        "AG" => "Antigua and Barbuda",
+        "AI" => "Anguilla",
+        "AL" => "Albania",
+        "AM" => "Armenia",
+        "AO" => "Angola",
+        "AR" => "Argentina",
+        "AS" => "American Samoa",
+        "AT" => "Austria",
+        "AU" => "Australia",
+        "AW" => "Aruba",
+        "AX" => "Åland Islands",
+        "AZ" => "Azerbaijan",
+        "BA" => "Bosnia and Herzegovina",
+        "BB" => "Barbados",
+        "BD" => "Bangladesh",
+        "BE" => "Belgium",
+        "BF" => "Burkina Faso",
+        "BG" => "Bulgaria",
+        "BH" => "Bahrain",
+        "BI" => "Burundi",
+        "BJ" => "Benin",
+        "BL" => "Saint Barthélemy",
+        "BM" => "Bermuda",
+        "BN" => "Brunei",
+        "BO" => "Bolivia",
+        "BQ" => "Caribbean Netherlands",
+        "BR" => "Brazil",
+        "BS" => "Bahamas",
+        "BT" => "Bhutan",
+        "BW" => "Botswana",
+        "BY" => "Belarus",
+        "BZ" => "Belize",
+        "CA" => "Canada",
+        "CC" => "Cocos (Keeling) Islands",
+        "CD" => "Democratic Republic of the Congo",
+        "CF" => "Central African Republic",
+        "CG" => "Republic of the Congo",
+        "CH" => "Switzerland",
+        "CI" => "Côte d'Ivoire",
+        "CK" => "Cook Islands",
+        "CL" => "Chile",
+        "CM" => "Cameroon",
+        "CN" => "China",
+        "CO" => "Colombia",
+        "CR" => "Costa Rica",
+        "CU" => "Cuba",
+        "CV" => "Cabo Verde",
+        "CW" => "Curaçao",
+        "CX" => "Christmas Island",
+        "CY" => "Cyprus",
+        "CZ" => "Czechia",
+        "DE" => "Germany",
+        "DJ" => "Djibouti",
+        "DK" => "Denmark",
+        "DM" => "Dominica",
+        "DO" => "Dominican Republic",
+        "DZ" => "Algeria",
+        "EC" => "Ecuador",
+        "EE" => "Estonia",
+        "EG" => "Egypt",
+        "EH" => "Western Sahara",
+        "ER" => "Eritrea",
+        "ES" => "Spain",
+        "ET" => "Ethiopia",
+        "FI" => "Finland",
+        "FJ" => "Fiji",
+        "FK" => "Falkland Islands",
+        "FM" => "Micronesia",
+        "FO" => "Faroe Islands",
+        "FR" => "France",
+        "GA" => "Gabon",
+        "GB" => "United Kingdom",
+        "GD" => "Grenada",
+        "GE" => "Georgia",
+        "GF" => "French Guiana",
+        "GG" => "Guernsey",
+        "GH" => "Ghana",
+        "GI" => "Gibraltar",
+        "GL" => "Greenland",
+        "GM" => "Gambia",
+        "GN" => "Guinea",
+        "GP" => "Guadeloupe",
+        "GQ" => "Equatorial Guinea",
+        "GR" => "Greece",
+        "GT" => "Guatemala",
+        "GU" => "Guam",
+        "GW" => "Guinea-Bissau",
+        "GY" => "Guyana",
+        "HK" => "Hong Kong",
+        "HN" => "Honduras",
+        "HR" => "Croatia",
+        "HT" => "Haiti",
+        "HU" => "Hungary",
+        "ID" => "Indonesia",
+        "IE" => "Ireland",
+        "IL" => "Israel",
+        "IM" => "Isle of Man",
+        "IN" => "India",
+        "IO" => "British Indian Ocean Territory",
+        "IQ" => "Iraq",
+        "IR" => "Iran",
+        "IS" => "Iceland",
+        "IT" => "Italy",
+        "JE" => "Jersey",
+        "JM" => "Jamaica",
+        "JO" => "Jordan",
+        "JP" => "Japan",
+        "KE" => "Kenya",
+        "KG" => "Kyrgyzstan",
+        "KH" => "Cambodia",
+        "KI" => "Kiribati",
+        "KM" => "Comoros",
+        "KN" => "Saint Kitts and Nevis",
+        "KP" => "North Korea",
+        "KR" => "South Korea",
+        "KW" => "Kuwait",
+        "KY" => "Cayman Islands",
+        "KZ" => "Kazakhstan",
+        "LA" => "Laos",
+        "LB" => "Lebanon",
+        "LC" => "Saint Lucia",
+        "LI" => "Liechtenstein",
+        "LK" => "Sri Lanka",
+        "LR" => "Liberia",
+        "LS" => "Lesotho",
+        "LT" => "Lithuania",
+        "LU" => "Luxembourg",
+        "LV" => "Latvia",
+        "LY" => "Libya",
+        "MA" => "Morocco",
+        "MC" => "Monaco",
+        "MD" => "Moldova",
+        "ME" => "Montenegro",
+        "MF" => "Saint Martin",
+        "MG" => "Madagascar",
+        "MH" => "Marshall Islands",
+        "MK" => "North Macedonia",
+        "ML" => "Mali",
+        "MM" => "Myanmar",
+        "MN" => "Mongolia",
+        "MO" => "Macao",
+        "MP" => "Northern Mariana Islands",
+        "MQ" => "Martinique",
+        "MR" => "Mauritania",
+        "MS" => "Montserrat",
+        "MT" => "Malta",
+        "MU" => "Mauritius",
+        "MV" => "Maldives",
+        "MW" => "Malawi",
+        "MX" => "Mexico",
+        "MY" => "Malaysia",
+        "MZ" => "Mozambique",
+        "NA" => "Namibia",
+        "NC" => "New Caledonia",
+        "NE" => "Niger",
+        "NF" => "Norfolk Island",
+        "NG" => "Nigeria",
+        "NI" => "Nicaragua",
+        "NL" => "Netherlands",
+        "NO" => "Norway",
+        "NP" => "Nepal",
+        "NR" => "Nauru",
+        "NU" => "Niue",
+        "NZ" => "New Zealand",
+        "OM" => "Oman",
+        "PA" => "Panama",
+        "PE" => "Peru",
+        "PF" => "French Polynesia",
+        "PG" => "Papua New Guinea",
+        "PH" => "Philippines",
+        "PK" => "Pakistan",
+        "PL" => "Poland",
+        "PM" => "Saint Pierre and Miquelon",
+        "PR" => "Puerto Rico",
+        "PS" => "Palestine",
+        "PT" => "Portugal",
+        "PW" => "Palau",
+        "PY" => "Paraguay",
+        "QA" => "Qatar",
+        "RE" => "Réunion",
+        "RO" => "Romania",
+        "RS" => "Serbia",
+        "RU" => "Russia",
+        "RW" => "Rwanda",
+        "SA" => "Saudi Arabia",
+        "SB" => "Solomon Islands",
+        "SC" => "Seychelles",
+        "SD" => "Sudan",
+        "SE" => "Sweden",
+        "SG" => "Singapore",
+        "SH" => "Saint Helena",
+        "SI" => "Slovenia",
+        "SJ" => "Svalbard and Jan Mayen",
+        "SK" => "Slovakia",
+        "SL" => "Sierra Leone",
+        "SM" => "San Marino",
+        "SN" => "Senegal",
+        "SO" => "Somalia",
+        "SR" => "Suriname",
+        "SS" => "South Sudan",
+        "ST" => "Sao Tome and Principe",
+        "SV" => "El Salvador",
+        "SX" => "Sint Maarten",
+        "SY" => "Syria",
+        "SZ" => "Eswatini",
+        "TA" => "Tristan da Cunha",
+        "TC" => "Turks and Caicos Islands",
+        "TD" => "Chad",
+        "TG" => "Togo",
+        "TH" => "Thailand",
+        "TJ" => "Tajikistan",
+        "TK" => "Tokelau",
+        "TL" => "Timor-Leste",
+        "TM" => "Turkmenistan",
+        "TN" => "Tunisia",
+        "TO" => "Tonga",
+        "TR" => "Türkiye",
+        "TT" => "Trinidad and Tobago",
+        "TV" => "Tuvalu",
+        "TW" => "Taiwan",
+        "TZ" => "Tanzania",
+        "UA" => "Ukraine",
+        "UG" => "Uganda",
+        "US" => "United States",
+        "UY" => "Uruguay",
+        "UZ" => "Uzbekistan",
+        "VA" => "Vatican City",
+        "VC" => "Saint Vincent and the Grenadines",
+        "VE" => "Venezuela",
+        "VG" => "British Virgin Islands",
+        "VI" => "U.S. Virgin Islands",
+        "VN" => "Vietnam",
+        "VU" => "Vanuatu",
+        "WF" => "Wallis and Futuna",
+        "WS" => "Samoa",
+        "XK" => "Kosovo",
+        "YE" => "Yemen",
+        "YT" => "Mayotte",
+        "ZA" => "South Africa",
+        "ZM" => "Zambia",
+        "ZW" => "Zimbabwe",
+        _ => "",
+    }
+}
@@ -0,0 +1,548 @@
+// Copyright (C) 2013 The Libphonenumber Authors
+// Copyright (C) 2025 Kashin Vladislav (Rust adaptation author)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{collections::HashMap, sync::{Arc, LazyLock}};
+
+use crate::generated::proto::{
+    phonemetadata::{PhoneMetadata, PhoneNumberDesc},
+    phonenumber::PhoneNumber,
+};
+
+use super::{
+    enums::{NumberLengthType, PhoneNumberType, ShortNumberCost},
+    helper_functions::{load_compiled_short_metadata, test_number_length},
+    phonenumberutil_internal::PhoneNumberUtilInternal,
+};
+
+/// In these countries, if extra digits are added to an emergency number, it no
+/// longer connects to the emergency service.
+const REGIONS_WHERE_EMERGENCY_NUMBERS_MUST_BE_EXACT: [&str; 3] = ["BR", "CL", "NI"];
+
+/// A mapping from a region code to the short number metadata for that region.
+static REGION_TO_SHORT_METADATA_MAP: LazyLock<HashMap<String, PhoneMetadata>> =
+    LazyLock::new(|| {
+        load_compiled_short_metadata()
+            .expect("Short number metadata should be valid")
+            .metadata
+            .into_iter()
+            .map(|metadata| (metadata.id().to_owned(), metadata))
+            .collect()
+    });
+
+/// Methods for getting information about short phone numbers, such as short codes
+/// and emergency numbers. Note that most commercial short numbers are not handled
+/// here, but by `PhoneNumberUtil`.
+///
+/// An instance is obtained with `PhoneNumberUtil::get_short_number_info`. The short
+/// number metadata is shared by all instances and loaded on first use. It is always
+/// the bundled metadata for all regions: unlike the metadata of the `PhoneNumberUtil`,
+/// it is neither replaced by `PhoneNumberUtil::reload_metadata` nor restricted by
+/// `PhoneNumberUtil::new_for_regions`. Numbers are still parsed and their regions
+/// resolved with the metadata of the `PhoneNumberUtil` the instance was obtained from.
+pub struct ShortNumberInfo {
+    util: Arc<PhoneNumberUtilInternal>,
+}
+
+impl ShortNumberInfo {
+    pub(crate) fn new(util: Arc<PhoneNumberUtilInternal>) -> Self {
+        Self { util }
+    }
+
+    /// Returns the short number metadata for the given region, if any.
+    fn get_metadata_for_region(&self, region_code: &str) -> Option<&'static PhoneMetadata> {
+        REGION_TO_SHORT_METADATA_MAP.get(region_code)
+    }
+
+    /// Returns the region codes that share the country calling code of the number.
+    fn get_region_codes_for_country_code(&self, country_calling_code: i32) -> Vec<&str> {
+        self.util
+            .get_region_codes_for_country_calling_code(country_calling_code)
+            .map(|region_codes| region_codes.collect())
+            .unwrap_or_default()
+    }
+
+    /// Helper method to check that the country calling code of the number matches
+    /// the region it's being dialed from.
+    fn region_dialing_from_matches_number(
+        &self,
+        number: &PhoneNumber,
+        region_dialing_from: &str,
+    ) -> bool {
+        self.get_region_codes_for_country_code(number.country_code())
+            .contains(&region_dialing_from)
+    }
+
+    /// Checks whether a short number is a possible number when dialed from the given
+    /// region. This provides a more lenient check than `is_valid_short_number_for_region`.
+    ///
+    /// # Parameters
+    ///
+    /// * `number`: The short number to check.
+    /// * `region_dialing_from`: The region from which the number is dialed.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the number is a possible short number for the region.
+    pub fn is_possible_short_number_for_region(
+        &self,
+        number: &PhoneNumber,
+        region_dialing_from: impl AsRef<str>,
+    ) -> bool {
+        let region_dialing_from = region_dialing_from.as_ref();
+        if !self.region_dialing_from_matches_number(number, region_dialing_from) {
+            return false;
+        }
+        let Some(metadata) = self.get_metadata_for_region(region_dialing_from) else {
+            return false;
+        };
+        let short_number = self.util.get_national_significant_number(number);
+        is_possible_length(&short_number, metadata)
+    }
+
+    /// Checks whether a short number is a possible number. If a country calling code
+    /// is shared by multiple regions, this returns `true` if it's possible in any of
+    /// them. This provides a more lenient check than `is_valid_short_number`.
+    ///
+    /// # Parameters
+    ///
+    /// * `number`: The short number to check.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the number is a possible short number.
+    pub fn is_possible_short_number(&self, number: &PhoneNumber) -> bool {
+        let short_number = self.util.get_national_significant_number(number);
+        self.get_region_codes_for_country_code(number.country_code())
+            .into_iter()
+            .filter_map(|region_code| self.get_metadata_for_region(region_code))
+            .any(|metadata| is_possible_length(&short_number, metadata))
+    }
+
+    /// Tests whether a short number matches a valid pattern in a region. Note that
+    /// this doesn't verify the number is actually in use, which is impossible to tell
+    /// by just looking at the number itself.
+    ///
+    /// # Parameters
+    ///
+    /// * `number`: The short number to check.
+    /// * `region_dialing_from`: The region from which the number is dialed.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the number matches a valid short number pattern of the region.
+    pub fn is_valid_short_number_for_region(
+        &self,
+        number: &PhoneNumber,
+        region_dialing_from: impl AsRef<str>,
+    ) -> bool {
+        let region_dialing_from = region_dialing_from.as_ref();
+        if !self.region_dialing_from_matches_number(number, region_dialing_from) {
+            return false;
+        }
+        let Some(metadata) = self.get_metadata_for_region(region_dialing_from) else {
+            return false;
+        };
+        let short_number = self.util.get_national_significant_number(number);
+        self.matches_possible_number_and_national_number(&short_number, &metadata.general_desc)
+            && self.matches_possible_number_and_national_number(&short_number, &metadata.short_code)
+    }
+
+    /// Tests whether a short number matches a valid pattern. If a country calling
+    /// code is shared by multiple regions, this returns `true` if it's valid in any
+    /// of them. Note that this doesn't verify the number is actually in use, which
+    /// is impossible to tell by just looking at the number itself.
+    ///
+    /// # Parameters
+    ///
+    /// * `number`: The short number to check.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the number matches a valid short number pattern.
+    pub fn is_valid_short_number(&self, number: &PhoneNumber) -> bool {
+        let region_codes = self.get_region_codes_for_country_code(number.country_code());
+        let Some(region_code) = self.get_region_code_for_short_number_from_region_list(number, &region_codes)
+        else {
+            return false;
+        };
+        if region_codes.len() > 1 {
+            // If a matching region had been found for the phone number from among two or more
+            // regions, then we have already implicitly verified its validity for that region.
+            return true;
+        }
+        self.is_valid_short_number_for_region(number, region_code)
+    }
+
+    /// Gets the expected cost category of a short number when dialed from a region
+    /// (however, nothing is implied about its validity). Emergency numbers are
+    /// always considered toll free.
+    ///
+    /// # Parameters
+    ///
+    /// * `number`: The short number for which we want to know the expected cost category.
+    /// * `region_dialing_from`: The region from which the number is dialed.
+    ///
+    /// # Returns
+    ///
+    /// The expected cost category for that region. `ShortNumberCost::UnknownCost` is
+    /// returned if the number does not match a cost category, or if the number is
+    /// not a short number of the region.
+    pub fn get_expected_cost_for_region(
+        &self,
+        number: &PhoneNumber,
+        region_dialing_from: impl AsRef<str>,
+    ) -> ShortNumberCost {
+        let region_dialing_from = region_dialing_from.as_ref();
+        if !self.region_dialing_from_matches_number(number, region_dialing_from) {
+            return ShortNumberCost::UnknownCost;
+        }
+        let Some(metadata) = self.get_metadata_for_region(region_dialing_from) else {
+            return ShortNumberCost::UnknownCost;
+        };
+        let short_number = self.util.get_national_significant_number(number);
+
+        // The possible lengths are not present for a particular sub-type if they match the general
+        // description; for this reason, we check the possible lengths against the general
+        // description first to allow an early exit if possible.
+        if !is_possible_length(&short_number, metadata) {
+            return ShortNumberCost::UnknownCost;
+        }
+
+        // The cost categories are tested in order of decreasing expense, since if for some reason
+        // the patterns overlap the most expensive matching cost category should be returned.
+        if self.matches_possible_number_and_national_number(&short_number, &metadata.premium_rate) {
+            ShortNumberCost::PremiumRate
+        } else if self.matches_possible_number_and_national_number(&short_number, &metadata.standard_rate) {
+            ShortNumberCost::StandardRate
+        } else if self.matches_possible_number_and_national_number(&short_number, &metadata.toll_free)
+            || self.is_emergency_number(&short_number, region_dialing_from)
+        {
+            // Emergency numbers are implicitly toll-free.
+            ShortNumberCost::TollFree
+        } else {
+            ShortNumberCost::UnknownCost
+        }
+    }
+
+    /// Gets the expected cost category of a short number (however, nothing is implied
+    /// about its validity). If the country calling code is unique to a region, this
+    /// behaves like `get_expected_cost_for_region`. If the country calling code is
+    /// shared by multiple regions, it returns the highest cost in the sequence
+    /// `PremiumRate`, `UnknownCost`, `StandardRate`, `TollFree`. The reason for
+    /// the position of `UnknownCost` in this order is that if a number is
+    /// `UnknownCost` in one region but `StandardRate` or `TollFree` in another, its
+    /// expected cost cannot be estimated as one of the latter since it might be a
+    /// `PremiumRate` number.
+    ///
+    /// For example, if a number is `StandardRate` in the US, but `TollFree` in
+    /// Canada, the expected cost returned by this method will be `StandardRate`,
+    /// since the NANPA countries share the same country calling code.
+    ///
+    /// # Parameters
+    ///
+    /// * `number`: The short number for which we want to know the expected cost category.
+    ///
+    /// # Returns
+    ///
+    /// The highest expected cost category of the short number in the regions it
+    /// could belong to.
+    pub fn get_expected_cost(&self, number: &PhoneNumber) -> ShortNumberCost {
+        let region_codes = self.get_region_codes_for_country_code(number.country_code());
+        if region_codes.is_empty() {
+            return ShortNumberCost::UnknownCost;
+        }
+        if region_codes.len() == 1 {
+            return self.get_expected_cost_for_region(number, region_codes[0]);
+        }
+        let mut cost = ShortNumberCost::TollFree;
+        for region_code in region_codes {
+            match self.get_expected_cost_for_region(number, region_code) {
+                ShortNumberCost::PremiumRate => return ShortNumberCost::PremiumRate,
+                ShortNumberCost::UnknownCost => cost = ShortNumberCost::UnknownCost,
+                ShortNumberCost::StandardRate => {
+                    if cost != ShortNumberCost::UnknownCost {
+                        cost = ShortNumberCost::StandardRate;
+                    }
+                }
+                ShortNumberCost::TollFree => {
+                    // Do nothing.
+                }
+            }
+        }
+        cost
+    }
+
+    /// Helper method to get the region code for a given phone number, from a list
+    /// of possible region codes. If the list contains more than one region, the
+    /// first region for which the number is valid is returned.
+    fn get_region_code_for_short_number_from_region_list<'r>(
+        &self,
+        number: &PhoneNumber,
+        region_codes: &[&'r str],
+    ) -> Option<&'r str> {
+        match region_codes {
+            [] => None,
+            [region_code] => Some(region_code),
+            _ => {
+                let national_number = self.util.get_national_significant_number(number);
+                region_codes.iter().copied().find(|region_code| {
+                    self.get_metadata_for_region(region_code).is_some_and(|metadata| {
+                        self.matches_possible_number_and_national_number(
+                            &national_number,
+                            &metadata.short_code,
+                        )
+                    })
+                })
+            }
+        }
+    }
+
+    /// Gets a valid short number for the specified region.
+    ///
+    /// # Parameters
+    ///
+    /// * `region_code`: The region for which an example short number is needed.
+    ///
+    /// # Returns
+    ///
+    /// A valid short number for the specified region, or an empty string if no
+    /// short number is known for the region.
+    pub fn get_example_short_number(&self, region_code: impl AsRef<str>) -> String {
+        self.get_metadata_for_region(region_code.as_ref())
+            .map(|metadata| metadata.short_code.example_number().to_owned())
+            .unwrap_or_default()
+    }
+
+    /// Gets a valid short number for the specified cost category.
+    ///
+    /// # Parameters
+    ///
+    /// * `region_code`: The region for which an example short number is needed.
+    /// * `cost`: The cost category of the number that is needed.
+    ///
+    /// # Returns
+    ///
+    /// A valid short number for the specified region and cost category, or an empty
+    /// string if no such number is known. No example is returned for
+    /// `ShortNumberCost::UnknownCost`.
+    pub fn get_example_short_number_for_cost(
+        &self,
+        region_code: impl AsRef<str>,
+        cost: ShortNumberCost,
+    ) -> String {
+        let Some(metadata) = self.get_metadata_for_region(region_code.as_ref()) else {
+            return String::new();
+        };
+        let desc = match cost {
+            ShortNumberCost::TollFree => &metadata.toll_free,
+            ShortNumberCost::StandardRate => &metadata.standard_rate,
+            ShortNumberCost::PremiumRate => &metadata.premium_rate,
+            // UnknownCost numbers are computed by the process of elimination from the other cost
+            // categories.
+            ShortNumberCost::UnknownCost => return String::new(),
+        };
+        desc.example_number().to_owned()
+    }
+
+    /// Returns `true` if the given number, exactly as dialed, might be used to
+    /// connect to an emergency service in the given region.
+    ///
+    /// This method accepts a string, rather than a `PhoneNumber`, because it needs to
+    /// distinguish cases such as "+1 911" and "911", where the former may not connect
+    /// to an emergency service in all cases but the latter would. This method takes
+    /// into account cases where the number might contain formatting, or might have
+    /// additional digits appended (when it is okay to do that in the specified region).
+    ///
+    /// # Parameters
+    ///
+    /// * `number`: The phone number to test.
+    /// * `region_code`: The region where the phone number is being dialed.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the number might be used to connect to an emergency service in the
+    /// given region.
+    pub fn connects_to_emergency_number(
+        &self,
+        number: impl AsRef<str>,
+        region_code: impl AsRef<str>,
+    ) -> bool {
+        self.matches_emergency_number_helper(number.as_ref(), region_code.as_ref(), true)
+    }
+
+    /// Returns `true` if the given number exactly matches an emergency service number
+    /// in the given region.
+    ///
+    /// This method takes into account cases where the number might contain
+    /// formatting, but doesn't allow additional digits to be appended. Note that
+    /// `is_emergency_number(number, region)` implies
+    /// `connects_to_emergency_number(number, region)`.
+    ///
+    /// # Parameters
+    ///
+    /// * `number`: The phone number to test.
+    /// * `region_code`: The region where the phone number is being dialed.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the number is an emergency number in the given region.
+    pub fn is_emergency_number(&self, number: impl AsRef<str>, region_code: impl AsRef<str>) -> bool {
+        self.matches_emergency_number_helper(number.as_ref(), region_code.as_ref(), false)
+    }
+
+    fn matches_emergency_number_helper(
+        &self,
+        number: &str,
+        region_code: &str,
+        allow_prefix_match: bool,
+    ) -> bool {
+        let Ok(possible_number) = self.util.extract_possible_number(number) else {
+            return false;
+        };
+        if self.util.starts_with_plus_chars_pattern(possible_number) {
+            // Returns false if the number starts with a plus sign. We don't believe dialing the
+            // country code before emergency numbers (e.g. +1911) works, but later, if that proves
+            // to work, we can add additional logic here to handle it.
+            return false;
+        }
+        let Some(metadata) = self.get_metadata_for_region(region_code) else {
+            return false;
+        };
+        let Some(emergency) = metadata.emergency.as_ref() else {
+            return false;
+        };
+        let normalized_number = self.util.normalize_digits_only(possible_number);
+        let allow_prefix_match_for_region = allow_prefix_match
+            && !REGIONS_WHERE_EMERGENCY_NUMBERS_MUST_BE_EXACT.contains(&region_code);
+        self.util.matcher_api().match_national_number(
+            &normalized_number,
+            emergency,
+            allow_prefix_match_for_region,
+        )
+    }
+
+    /// Given a valid short number, determines whether it is carrier-specific
+    /// (however, nothing is implied about its validity). Carrier-specific numbers
+    /// may connect to a different end-point, or not connect at all, depending on the
+    /// user's carrier. If it is important that the number is valid, then its
+    /// validity must first be checked using `is_valid_short_number`.
+    ///
+    /// # Parameters
+    ///
+    /// * `number`: The valid short number to check.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the short number is carrier-specific.
+    pub fn is_carrier_specific(&self, number: &PhoneNumber) -> bool {
+        let region_codes = self.get_region_codes_for_country_code(number.country_code());
+        let national_number = self.util.get_national_significant_number(number);
+        self.get_region_code_for_short_number_from_region_list(number, &region_codes)
+            .and_then(|region_code| self.get_metadata_for_region(region_code))
+            .is_some_and(|metadata| {
+                self.matches_possible_number_and_national_number(
+                    &national_number,
+                    &metadata.carrier_specific,
+                )
+            })
+    }
+
+    /// Given a valid short number, determines whether it is carrier-specific when
+    /// dialed from the given region (however, nothing is implied about its validity).
+    ///
+    /// # Parameters
+    ///
+    /// * `number`: The valid short number to check.
+    /// * `region_dialing_from`: The region from which the number is dialed.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the short number is carrier-specific in the region.
+    pub fn is_carrier_specific_for_region(
+        &self,
+        number: &PhoneNumber,
+        region_dialing_from: impl AsRef<str>,
+    ) -> bool {
+        self.matches_desc_for_region(number, region_dialing_from.as_ref(), |metadata| {
+            &metadata.carrier_specific
+        })
+    }
+
+    /// Given a valid short number, determines whether it is an SMS service (however,
+    /// nothing is implied about its validity). An SMS service is where the primary
+    /// or only intended usage is to receive and/or send text messages (SMSs).
+    ///
+    /// # Parameters
+    ///
+    /// * `number`: The valid short number to check.
+    /// * `region_dialing_from`: The region from which the number is dialed.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the short number is an SMS service in the region.
+    pub fn is_sms_service_for_region(
+        &self,
+        number: &PhoneNumber,
+        region_dialing_from: impl AsRef<str>,
+    ) -> bool {
+        self.matches_desc_for_region(number, region_dialing_from.as_ref(), |metadata| {
+            &metadata.sms_services
+        })
+    }
+
+    /// Checks whether the national number matches the description selected by
+    /// `get_desc` in the short number metadata of the region dialed from.
+    fn matches_desc_for_region(
+        &self,
+        number: &PhoneNumber,
+        region_dialing_from: &str,
+        get_desc: impl FnOnce(&PhoneMetadata) -> &PhoneNumberDesc,
+    ) -> bool {
+        if !self.region_dialing_from_matches_number(number, region_dialing_from) {
+            return false;
+        }
+        let national_number = self.util.get_national_significant_number(number);
+        self.get_metadata_for_region(region_dialing_from)
+            .is_some_and(|metadata| {
+                self.matches_possible_number_and_national_number(&national_number, get_desc(metadata))
+            })
+    }
+
+    /// Checks the length of the number against the possible lengths of the
+    /// description before matching its national number pattern.
+    fn matches_possible_number_and_national_number(
+        &self,
+        number: &str,
+        number_desc: &PhoneNumberDesc,
+    ) -> bool {
+        if !number_desc.possible_length.is_empty()
+            && !number_desc.possible_length.contains(&(number.len() as i32))
+        {
+            return false;
+        }
+        self.util
+            .matcher_api()
+            .match_national_number(number, number_desc, false)
+    }
+}
+
+/// Checks the length of the short number against the possible lengths of the
+/// general description, which are those of all short codes of the region.
+fn is_possible_length(short_number: &str, metadata: &PhoneMetadata) -> bool {
+    matches!(
+        test_number_length(short_number, metadata, PhoneNumberType::Unknown),
+        Ok(NumberLengthType::IsPossible)
+    )
+}
@@ -0,0 +1,128 @@
+// Copyright (C) 2012 The Libphonenumber Authors
+// Copyright (C) 2025 Kashin Vladislav (Rust adaptation author)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, LazyLock};
+
+use crate::generated::{prefix_data::TIMEZONES_DATA, proto::phonenumber::PhoneNumber};
+
+use super::{
+    enums::PhoneNumberType,
+    phonenumberutil_internal::PhoneNumberUtilInternal,
+    prefix_file_reader::PhonePrefixMap,
+};
+
+/// The time zone returned for numbers whose time zone can't be determined.
+pub const UNKNOWN_TIMEZONE: &str = "Etc/Unknown";
+
+/// Separates the time zones of a prefix in the data file.
+const RAW_STRING_TIMEZONES_SEPARATOR: char = '&';
+
+/// The prefix to time zones map bundled from resources/timezones.
+static PREFIX_TIMEZONES_MAP: LazyLock<PhonePrefixMap> =
+    LazyLock::new(|| PhonePrefixMap::parse(TIMEZONES_DATA));
+
+/// An offline mapper from phone numbers to the time zones they're in, as IANA
+/// time zone IDs such as "America/Los_Angeles".
+///
+/// Mobile numbers are mapped by their area code where that is meaningful; the
+/// current location of the subscriber is of course not known.
+///
+/// An instance is obtained with `PhoneNumberUtil::get_time_zones_mapper`. The
+/// time zone data is shared by all instances and parsed on first use.
+pub struct PhoneNumberToTimeZonesMapper {
+    util: Arc<PhoneNumberUtilInternal>,
+}
+
+impl PhoneNumberToTimeZonesMapper {
+    pub(crate) fn new(util: Arc<PhoneNumberUtilInternal>) -> Self {
+        Self { util }
+    }
+
+    /// Returns a list of time zones to which a phone number belongs.
+    ///
+    /// This method assumes the validity of the number passed in has already been
+    /// checked, and that the number is geo-localizable. We consider fixed-line and
+    /// mobile numbers possible candidates for geo-localization.
+    ///
+    /// # Parameters
+    ///
+    /// * `number`: A valid phone number for which we want to get the time zones.
+    ///
+    /// # Returns
+    ///
+    /// The time zones of the longest matching prefix of the number, or
+    /// `[UNKNOWN_TIMEZONE]` if there is none.
+    pub fn get_time_zones_for_geographical_number(&self, number: &PhoneNumber) -> Vec<String> {
+        let mut phone_prefix = number.country_code().to_string();
+        phone_prefix.push_str(&self.util.get_national_significant_number(number));
+        lookup_time_zones(&phone_prefix)
+    }
+
+    /// As `get_time_zones_for_geographical_number`, but explicitly checks the
+    /// validity of the number passed in.
+    ///
+    /// Numbers whose type isn't geographical, such as toll-free numbers, get the
+    /// time zones of their whole country, which for most countries is a single
+    /// zone, e.g. "Europe/Berlin" for any German number.
+    ///
+    /// # Parameters
+    ///
+    /// * `number`: The phone number for which we want to get the time zones.
+    ///
+    /// # Returns
+    ///
+    /// The time zones of the number, or `[UNKNOWN_TIMEZONE]` if the number is
+    /// invalid or its time zones are unknown.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the underlying metadata contains an invalid regular
+    /// expression, which indicates a critical library bug.
+    pub fn get_time_zones_for_number(&self, number: &PhoneNumber) -> Vec<String> {
+        let number_type = self
+            .util
+            .get_number_type(number)
+            .expect("A valid regex is expected in metadata; this indicates a library bug.");
+        if number_type == PhoneNumberType::Unknown {
+            return vec![UNKNOWN_TIMEZONE.to_owned()];
+        }
+        if !self
+            .util
+            .is_number_geographical_by_country_code_and_type(number_type, number.country_code())
+        {
+            return self.get_country_level_time_zones_for_number(number);
+        }
+        self.get_time_zones_for_geographical_number(number)
+    }
+
+    /// Returns the time zones of the country the number belongs to.
+    fn get_country_level_time_zones_for_number(&self, number: &PhoneNumber) -> Vec<String> {
+        lookup_time_zones(&number.country_code().to_string())
+    }
+}
+
+/// Returns the time zones of the longest matching prefix, or `[UNKNOWN_TIMEZONE]`
+/// if no prefix matches.
+fn lookup_time_zones(phone_prefix: &str) -> Vec<String> {
+    PREFIX_TIMEZONES_MAP
+        .lookup(phone_prefix)
+        .map(|time_zones| {
+            time_zones
+                .split(RAW_STRING_TIMEZONES_SEPARATOR)
+                .map(str::to_owned)
+                .collect()
+        })
+        .unwrap_or_else(|| vec![UNKNOWN_TIMEZONE.to_owned()])
+}
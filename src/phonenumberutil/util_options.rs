@@ -0,0 +1,47 @@
+// Copyright (C) 2025 Kashin Vladislav (Rust adaptation author)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Options used to create a `PhoneNumberUtil` with `PhoneNumberUtil::new_with_options`.
+///
+/// The default options give the same instance as `PhoneNumberUtil::new`. Options are
+/// set with chained builder-style calls:
+///
+/// ```rust
+/// use rlibphonenumber::{PhoneNumberUtil, PhoneNumberUtilOptions};
+///
+/// let options = PhoneNumberUtilOptions::new().regex_cache_capacity(512);
+/// let phone_util = PhoneNumberUtil::new_with_options(options);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PhoneNumberUtilOptions {
+    pub(crate) regex_cache_capacity: Option<usize>,
+}
+
+impl PhoneNumberUtilOptions {
+    /// Creates options that give the same instance as `PhoneNumberUtil::new`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bounds the number of compiled regular expressions kept by each of the regex
+    /// caches. When a cache is full, an arbitrary regex is dropped to make room for a
+    /// new one, so a small capacity trades speed for memory.
+    ///
+    /// The caches are unbounded by default. Note that parsing and formatting numbers
+    /// of all regions uses a few thousand distinct patterns.
+    pub fn regex_cache_capacity(mut self, capacity: usize) -> Self {
+        self.regex_cache_capacity = Some(capacity);
+        self
+    }
+}
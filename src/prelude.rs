@@ -0,0 +1,35 @@
+// Copyright (C) 2025 Kashin Vladislav (Rust adaptation author)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Re-exports the types needed for typical parsing, formatting and validation.
+//!
+//! ```rust
+//! use rlibphonenumber::prelude::*;
+//!
+//! let number: PhoneNumber = PHONE_NUMBER_UTIL.parse("+1 650-253-0000", RegionCode::get_unknown()).unwrap();
+//! assert_eq!(PhoneNumberType::FixedLineOrMobile, PHONE_NUMBER_UTIL.get_number_type(&number));
+//! assert_eq!("(650) 253-0000", PHONE_NUMBER_UTIL.format(&number, PhoneNumberFormat::National));
+//! assert!(matches!(PHONE_NUMBER_UTIL.parse("", "US"), Err(ParseError::NotANumber(_))));
+//! ```
+
+pub use crate::{
+    PHONE_NUMBER_UTIL,
+    PhoneNumber,
+    PhoneNumberUtil,
+    PhoneNumberFormat,
+    PhoneNumberType,
+    ParseError,
+    ValidationError,
+    region_code::RegionCode,
+};
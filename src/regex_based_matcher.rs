@@ -14,10 +14,10 @@
 // limitations under the License.
 
 
-use log::{error};
+use crate::macros::error;
 use super::regex_util::{RegexFullMatch, RegexConsume};
 
-use crate::{interfaces, generated::proto::phonemetadata::PhoneNumberDesc, regexp_cache::{InvalidRegexError, RegexCache}};
+use crate::{interfaces, generated::proto::phonemetadata::PhoneNumberDesc, regexp_cache::{CacheStats, InvalidRegexError, RegexCache}};
 
 pub struct RegexBasedMatcher {
     cache: RegexCache,   
@@ -25,7 +25,11 @@ pub struct RegexBasedMatcher {
 
 impl RegexBasedMatcher {
     pub fn new() -> Self {
-        Self { cache: RegexCache::with_capacity(128) }
+        Self::with_regex_cache(RegexCache::new())
+    }
+
+    pub fn with_regex_cache(cache: RegexCache) -> Self {
+        Self { cache }
     }
 
     fn match_number(
@@ -63,4 +67,12 @@ impl interfaces::MatcherApi for RegexBasedMatcher {
             false
         }
     }
+
+    fn compile_pattern(&self, pattern: &str) -> Result<(), InvalidRegexError> {
+        self.cache.get_regex(pattern).map(drop)
+    }
+
+    fn regex_cache_stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
 }
\ No newline at end of file
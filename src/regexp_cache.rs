@@ -13,35 +13,95 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::sync::Arc;
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
 
 use dashmap::DashMap;
 use thiserror::Error;
 
 #[derive(Debug, PartialEq, Error)]
 #[error("An error occurred while trying to create regex: {0}")]
-pub struct InvalidRegexError(#[from] regex::Error);
+pub struct InvalidRegexError(#[from] pub(crate) regex::Error);
+
+/// Counters describing how effective the regex caches are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CacheStats {
+    /// The number of lookups served by an already compiled regex.
+    pub hits: u64,
+    /// The number of lookups which had to compile the regex.
+    pub misses: u64,
+    /// The number of compiled regexes currently held.
+    pub size: usize,
+}
 
 pub struct RegexCache {
-    cache: DashMap<String, Arc<regex::Regex>>
+    cache: DashMap<String, Arc<regex::Regex>>,
+    /// The maximum number of regexes kept, or `None` if the cache is unbounded.
+    max_size: Option<usize>,
+    hits: AtomicU64,
+    misses: AtomicU64,
 }
 
 impl RegexCache {
-    
+
+    /// Creates a cache which keeps every regex it compiles.
+    pub fn new() -> Self {
+        Self {
+            cache: DashMap::with_capacity(128),
+            max_size: None,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Creates a cache which keeps at most `capacity` regexes. When it is full,
+    /// an arbitrary regex is dropped to make room for a newly compiled one.
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
             cache: DashMap::with_capacity(capacity),
+            max_size: Some(capacity),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
         }
     }
 
     pub fn get_regex(&self, pattern: &str) -> Result<Arc<regex::Regex>, InvalidRegexError> {
         if let Some(regex) = self.cache.get(pattern) {
-            Ok(regex.value().clone())
-        } else {
-            let entry = self.cache.entry(pattern.to_string()).or_try_insert_with(|| {
-                regex::Regex::new(pattern).map(Arc::new)
-            })?;
-            Ok(entry.value().clone())
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(regex.value().clone());
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let regex = Arc::new(regex::Regex::new(pattern)?);
+        match self.max_size {
+            Some(0) => return Ok(regex),
+            Some(max_size) if self.cache.len() >= max_size => self.evict_one(),
+            _ => {}
         }
+        Ok(self
+            .cache
+            .entry(pattern.to_string())
+            .or_insert(regex)
+            .value()
+            .clone())
     }
-}
\ No newline at end of file
+
+    /// Returns the hit and miss counters and the current size of the cache.
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            size: self.cache.len(),
+        }
+    }
+
+    fn evict_one(&self) {
+        // The key is cloned so that the read lock of its shard is released
+        // before the entry is removed.
+        let key = self.cache.iter().next().map(|entry| entry.key().clone());
+        if let Some(key) = key {
+            self.cache.remove(&key);
+        }
+    }
+}
@@ -25,4 +25,38 @@ impl RegionCode {
     pub fn zz() -> &'static str {
         return "ZZ";    
     }
-}
\ No newline at end of file
+}
+
+/// Every two-letter region code from "AA" to "ZZ", back to back.
+static TWO_LETTER_REGION_CODE_BYTES: [u8; 26 * 26 * 2] = {
+    let mut bytes = [0; 26 * 26 * 2];
+    let mut i = 0;
+    while i < 26 * 26 {
+        bytes[2 * i] = b'A' + (i / 26) as u8;
+        bytes[2 * i + 1] = b'A' + (i % 26) as u8;
+        i += 1;
+    }
+    bytes
+};
+
+static TWO_LETTER_REGION_CODES: &str = match std::str::from_utf8(&TWO_LETTER_REGION_CODE_BYTES) {
+    Ok(region_codes) => region_codes,
+    Err(_) => panic!("Region codes are ASCII"),
+};
+
+impl RegionCode {
+    /// Returns a `'static` copy of a region code as used by the metadata, that is
+    /// a two-letter uppercase code or "001" for non-geographical entities. This lets
+    /// the loaded metadata hand out region codes that outlive it. Returns `None`
+    /// for any other string.
+    pub(crate) fn to_static(region_code: &str) -> Option<&'static str> {
+        match region_code.as_bytes() {
+            &[first @ b'A'..=b'Z', second @ b'A'..=b'Z'] => {
+                let start = 2 * ((first - b'A') as usize * 26 + (second - b'A') as usize);
+                Some(&TWO_LETTER_REGION_CODES[start..start + 2])
+            }
+            b"001" => Some("001"),
+            _ => None,
+        }
+    }
+}
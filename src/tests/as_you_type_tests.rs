@@ -0,0 +1,255 @@
+use std::sync::Arc;
+
+use crate::phonenumberutil::as_you_type::AsYouTypeFormatter;
+
+use super::{get_test_metadata_phone_util, region_code::RegionCode};
+
+/// Вводит символы по одному и проверяет результат после каждого из них.
+fn assert_input(formatter: &mut AsYouTypeFormatter, expected: &[(char, &str)]) {
+    for (next_char, expected_output) in expected {
+        assert_eq!(*expected_output, formatter.input_digit(*next_char));
+    }
+}
+
+#[test]
+fn invalid_region() {
+    let phone_util = get_test_metadata_phone_util();
+    let mut formatter = AsYouTypeFormatter::new(Arc::clone(&phone_util), RegionCode::zz());
+    assert_input(&mut formatter, &[
+        ('+', "+"),
+        ('4', "+4"),
+        ('8', "+48 "),
+        ('8', "+48 8"),
+        ('8', "+48 88"),
+        ('1', "+48 88 1"),
+        ('2', "+48 88 12"),
+        ('3', "+48 88 123"),
+        ('1', "+48 88 123 1"),
+        ('2', "+48 88 123 12"),
+    ]);
+
+    // Без знака "+" формат для неизвестного региона не применяется.
+    formatter.clear();
+    assert_input(&mut formatter, &[
+        ('6', "6"),
+        ('5', "65"),
+        ('0', "650"),
+        ('2', "6502"),
+        ('5', "65025"),
+        ('3', "650253"),
+    ]);
+}
+
+#[test]
+fn invalid_plus_sign() {
+    let phone_util = get_test_metadata_phone_util();
+    let mut formatter = AsYouTypeFormatter::new(Arc::clone(&phone_util), RegionCode::get_unknown());
+    assert_input(&mut formatter, &[
+        ('+', "+"),
+        ('4', "+4"),
+        ('8', "+48 "),
+        ('8', "+48 8"),
+        ('8', "+48 88"),
+        ('1', "+48 88 1"),
+        // Знак "+" допустим только в начале номера, иначе форматирование прекращается.
+        ('+', "+48881+"),
+        ('2', "+48881+2"),
+    ]);
+}
+
+#[test]
+fn too_long_number_matching_multiple_leading_digits() {
+    // Форматы с одинаковыми ведущими цифрами, но разной длиной.
+    let phone_util = get_test_metadata_phone_util();
+    let mut formatter = AsYouTypeFormatter::new(Arc::clone(&phone_util), RegionCode::get_unknown());
+    assert_input(&mut formatter, &[
+        ('+', "+"),
+        ('8', "+8"),
+        ('1', "+81 "),
+        ('9', "+81 9"),
+        ('0', "+81 90"),
+        ('1', "+81 90 1"),
+        ('2', "+81 90 12"),
+        ('3', "+81 90 123"),
+        ('4', "+81 90 1234"),
+        ('5', "+81 90 1234 5"),
+        ('6', "+81 90 1234 56"),
+        ('7', "+81 90 1234 567"),
+        ('8', "+81 90 1234 5678"),
+        ('9', "+81 90 12 345 6789"),
+        ('0', "+81901234567890"),
+        ('1', "+819012345678901"),
+    ]);
+}
+
+#[test]
+fn us() {
+    let phone_util = get_test_metadata_phone_util();
+    let mut formatter = AsYouTypeFormatter::new(Arc::clone(&phone_util), RegionCode::us());
+    assert_input(&mut formatter, &[
+        ('6', "6"),
+        ('5', "65"),
+        ('0', "650"),
+        ('2', "650 2"),
+        ('5', "650 25"),
+        ('3', "650 253"),
+        // Так форматируется местный номер без кода региона.
+        ('2', "650 2532"),
+        ('2', "650 253 22"),
+        ('2', "650 253 222"),
+        ('2', "650 253 2222"),
+    ]);
+
+    formatter.clear();
+    assert_input(&mut formatter, &[
+        ('1', "1"),
+        ('6', "16"),
+        ('5', "1 65"),
+        ('0', "1 650"),
+        ('2', "1 650 2"),
+        ('5', "1 650 25"),
+        ('3', "1 650 253"),
+        ('2', "1 650 253 2"),
+        ('2', "1 650 253 22"),
+        ('2', "1 650 253 222"),
+        ('2', "1 650 253 2222"),
+    ]);
+
+    // Международный префикс отделяется от кода страны.
+    formatter.clear();
+    assert_input(&mut formatter, &[
+        ('0', "0"),
+        ('1', "01"),
+        ('1', "011 "),
+        ('4', "011 4"),
+        ('4', "011 44 "),
+        ('6', "011 44 6"),
+        ('1', "011 44 61"),
+        ('2', "011 44 6 12"),
+        ('3', "011 44 6 123"),
+        ('1', "011 44 6 123 1"),
+        ('2', "011 44 6 123 12"),
+        ('3', "011 44 6 123 123"),
+        ('1', "011 44 6 123 123 1"),
+        ('2', "011 44 6 123 123 12"),
+        ('3', "011 44 6 123 123 123"),
+    ]);
+
+    formatter.clear();
+    assert_input(&mut formatter, &[
+        ('+', "+"),
+        ('1', "+1"),
+        ('6', "+1 6"),
+        ('5', "+1 65"),
+        ('0', "+1 650"),
+        ('2', "+1 650 2"),
+        ('5', "+1 650 25"),
+        ('3', "+1 650 253"),
+        ('2', "+1 650 253 2"),
+        ('2', "+1 650 253 22"),
+        ('2', "+1 650 253 222"),
+    ]);
+}
+
+#[test]
+fn us_full_width_characters() {
+    let phone_util = get_test_metadata_phone_util();
+    let mut formatter = AsYouTypeFormatter::new(Arc::clone(&phone_util), RegionCode::us());
+    assert_input(&mut formatter, &[
+        ('\u{FF16}', "\u{FF16}"),
+        ('\u{FF15}', "\u{FF16}\u{FF15}"),
+        ('\u{FF10}', "650"),
+        ('\u{FF12}', "650 2"),
+        ('\u{FF15}', "650 25"),
+        ('\u{FF13}', "650 253"),
+        ('\u{FF12}', "650 2532"),
+        ('\u{FF12}', "650 253 22"),
+        ('\u{FF12}', "650 253 222"),
+        ('\u{FF12}', "650 253 2222"),
+    ]);
+}
+
+#[test]
+fn us_with_formatting_characters() {
+    let phone_util = get_test_metadata_phone_util();
+    let mut formatter = AsYouTypeFormatter::new(Arc::clone(&phone_util), RegionCode::us());
+    // После ввода символа форматирования вывод совпадает с вводом.
+    assert_input(&mut formatter, &[
+        ('6', "6"),
+        ('5', "65"),
+        ('0', "650"),
+        ('-', "650-"),
+        ('2', "650-2"),
+        ('5', "650-25"),
+    ]);
+
+    // После сброса номер снова форматируется.
+    formatter.clear();
+    assert_input(&mut formatter, &[
+        ('6', "6"),
+        ('5', "65"),
+        ('0', "650"),
+        ('2', "650 2"),
+    ]);
+}
+
+#[test]
+fn gb_fixed_line() {
+    let phone_util = get_test_metadata_phone_util();
+    let mut formatter = AsYouTypeFormatter::new(Arc::clone(&phone_util), RegionCode::gb());
+    assert_input(&mut formatter, &[
+        ('0', "0"),
+        ('2', "02"),
+        ('0', "020"),
+        ('7', "020 7"),
+        ('0', "020 70"),
+        ('3', "020 703"),
+        ('1', "020 7031"),
+        ('3', "020 7031 3"),
+        ('0', "020 7031 30"),
+        ('0', "020 7031 300"),
+        ('0', "020 7031 3000"),
+    ]);
+    assert_eq!("0", formatter.get_extracted_national_prefix());
+}
+
+#[test]
+fn de() {
+    let phone_util = get_test_metadata_phone_util();
+    let mut formatter = AsYouTypeFormatter::new(Arc::clone(&phone_util), RegionCode::de());
+    assert_input(&mut formatter, &[
+        ('0', "0"),
+        ('3', "03"),
+        ('0', "030"),
+        ('1', "030/1"),
+        ('2', "030/12"),
+        ('3', "030/123"),
+        ('4', "030/1234"),
+    ]);
+}
+
+#[test]
+fn remember_position_us() {
+    let phone_util = get_test_metadata_phone_util();
+    let mut formatter = AsYouTypeFormatter::new(Arc::clone(&phone_util), RegionCode::us());
+    assert_eq!("1", formatter.input_digit_and_remember_position('1'));
+    assert_eq!(1, formatter.get_remembered_position());
+    assert_eq!("16", formatter.input_digit('6'));
+    assert_eq!("1 65", formatter.input_digit('5'));
+    assert_eq!(1, formatter.get_remembered_position());
+    assert_eq!("1 650", formatter.input_digit_and_remember_position('0'));
+    assert_eq!(5, formatter.get_remembered_position());
+    assert_eq!("1 650 2", formatter.input_digit('2'));
+    assert_eq!("1 650 25", formatter.input_digit('5'));
+    assert_eq!(5, formatter.get_remembered_position());
+
+    // Позиция в неформатированном вводе.
+    formatter.clear();
+    assert_eq!("6", formatter.input_digit('6'));
+    assert_eq!("65", formatter.input_digit('5'));
+    assert_eq!("650", formatter.input_digit('0'));
+    assert_eq!("650-", formatter.input_digit_and_remember_position('-'));
+    assert_eq!(4, formatter.get_remembered_position());
+    assert_eq!("650-2", formatter.input_digit('2'));
+    assert_eq!(4, formatter.get_remembered_position());
+}
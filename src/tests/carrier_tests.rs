@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use crate::phonenumberutil::carrier::PhoneNumberToCarrierMapper;
+
+use super::{get_phone_util, number};
+
+#[test]
+fn get_name_for_mobile_number() {
+    let phone_util = get_phone_util();
+    let carrier_mapper = PhoneNumberToCarrierMapper::new(Arc::clone(&phone_util));
+    let gb_mobile = number(44, 7900123456);
+    assert_eq!("Vodafone", carrier_mapper.get_name_for_number(&gb_mobile, "en"));
+    // Берётся самый длинный подходящий префикс.
+    assert_eq!("O2", carrier_mapper.get_name_for_number(&number(44, 7902123456), "en"));
+
+    // Название выбирается по языку локали.
+    let ru_mobile = number(7, 9031234567);
+    assert_eq!("Beeline", carrier_mapper.get_name_for_number(&ru_mobile, "en"));
+    assert_eq!("Билайн", carrier_mapper.get_name_for_number(&ru_mobile, "ru-RU"));
+    // Данных на немецком нет, используется английское название.
+    assert_eq!("Beeline", carrier_mapper.get_name_for_number(&ru_mobile, "de"));
+}
+
+#[test]
+fn get_name_for_non_mobile_number() {
+    let phone_util = get_phone_util();
+    let carrier_mapper = PhoneNumberToCarrierMapper::new(Arc::clone(&phone_util));
+    // Для стационарных, бесплатных и недействительных номеров оператор не определяется.
+    let gb_fixed_line = number(44, 2070313000);
+    assert_eq!("", carrier_mapper.get_name_for_number(&gb_fixed_line, "en"));
+    assert_eq!("", carrier_mapper.get_name_for_number(&number(800, 12345678), "en"));
+    assert_eq!("", carrier_mapper.get_name_for_number(&number(44, 79001234), "en"));
+    assert_eq!("", carrier_mapper.get_name_for_number(&number(999, 7900123456), "en"));
+}
+
+#[test]
+fn get_name_for_valid_number() {
+    let phone_util = get_phone_util();
+    let carrier_mapper = PhoneNumberToCarrierMapper::new(Arc::clone(&phone_util));
+    // Тип номера не проверяется, поиск идёт только по префиксу.
+    assert_eq!("Vodafone", carrier_mapper.get_name_for_valid_number(&number(44, 7900123456), "en"));
+    assert_eq!("", carrier_mapper.get_name_for_valid_number(&number(44, 2070313000), "en"));
+}
+
+#[test]
+fn get_safe_display_name() {
+    let phone_util = get_phone_util();
+    let carrier_mapper = PhoneNumberToCarrierMapper::new(Arc::clone(&phone_util));
+    // В Великобритании номер можно перенести к другому оператору.
+    let gb_mobile = number(44, 7900123456);
+    assert!(phone_util.is_mobile_number_portable_region("GB"));
+    assert_eq!("", carrier_mapper.get_safe_display_name(&gb_mobile, "en"));
+
+    let ao_mobile = number(244, 923123456);
+    assert!(!phone_util.is_mobile_number_portable_region("AO"));
+    assert_eq!("UNITEL", carrier_mapper.get_safe_display_name(&ao_mobile, "en"));
+}
@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use crate::phonenumberutil::geocoder::PhoneNumberOfflineGeocoder;
+
+use super::{get_phone_util, number};
+
+#[test]
+fn get_description_for_number_with_area_data() {
+    let phone_util = get_phone_util();
+    let geocoder = PhoneNumberOfflineGeocoder::new(Arc::clone(&phone_util));
+    let us_number = number(1, 6502530000);
+    assert_eq!("Mountain View, CA", geocoder.get_description_for_number(&us_number, "en"));
+    assert_eq!("Mountain View, CA", geocoder.get_description_for_number(&us_number, "en-US"));
+    // Для 1650259 есть более точная запись, чем для префикса 1650.
+    assert_eq!("Burlingame, CA", geocoder.get_description_for_number(&number(1, 6502590000), "en"));
+
+    let de_number = number(49, 30123456);
+    assert_eq!("Berlin", geocoder.get_description_for_number(&de_number, "de"));
+
+    // Описание выбирается по языку локали.
+    let ch_number = number(41, 223456789);
+    assert_eq!("Geneva", geocoder.get_description_for_number(&ch_number, "en"));
+    assert_eq!("Genf", geocoder.get_description_for_number(&ch_number, "de"));
+    assert_eq!("Genève", geocoder.get_description_for_number(&ch_number, "fr_CH"));
+    assert_eq!("Ginevra", geocoder.get_description_for_number(&ch_number, "it"));
+}
+
+#[test]
+fn get_description_falls_back_to_english() {
+    let phone_util = get_phone_util();
+    let geocoder = PhoneNumberOfflineGeocoder::new(Arc::clone(&phone_util));
+    let us_number = number(1, 6502530000);
+    // Немецких данных для +1 нет, поэтому используется английское описание.
+    assert_eq!("Mountain View, CA", geocoder.get_description_for_number(&us_number, "de"));
+    // Для китайского, японского и корейского на английский не переходим,
+    // остаётся только название страны.
+    assert_eq!("United States", geocoder.get_description_for_number(&us_number, "ko"));
+    assert_eq!("서울", geocoder.get_description_for_number(&number(82, 22123456), "ko"));
+}
+
+#[test]
+fn get_description_for_traditional_chinese() {
+    let phone_util = get_phone_util();
+    let geocoder = PhoneNumberOfflineGeocoder::new(Arc::clone(&phone_util));
+    let tw_number = number(886, 223456789);
+    assert_eq!("台北", geocoder.get_description_for_number(&tw_number, "zh"));
+    assert_eq!("臺北", geocoder.get_description_for_number(&tw_number, "zh_Hant"));
+    assert_eq!("臺北", geocoder.get_description_for_number(&tw_number, "zh-TW"));
+    assert_eq!("臺北", geocoder.get_description_for_number(&tw_number, "zh-Hant-HK"));
+    assert_eq!("Taipei", geocoder.get_description_for_number(&tw_number, "en"));
+}
+
+#[test]
+fn get_description_falls_back_to_country_name() {
+    let phone_util = get_phone_util();
+    let geocoder = PhoneNumberOfflineGeocoder::new(Arc::clone(&phone_util));
+    // Мобильные номера Великобритании не привязаны к местности.
+    let gb_mobile = number(44, 7912345678);
+    assert_eq!("United Kingdom", geocoder.get_description_for_number(&gb_mobile, "en"));
+    let de_mobile = number(49, 15123456789);
+    assert_eq!("Germany", geocoder.get_description_for_number(&de_mobile, "de"));
+    // Бесплатный номер +1 800 действителен в нескольких странах NANPA,
+    // поэтому страну выбрать нельзя.
+    let us_toll_free = number(1, 8002530000);
+    assert_eq!("", geocoder.get_description_for_number(&us_toll_free, "en"));
+}
+
+#[test]
+fn get_description_for_number_without_description() {
+    let phone_util = get_phone_util();
+    let geocoder = PhoneNumberOfflineGeocoder::new(Arc::clone(&phone_util));
+    // Негеографический номер.
+    assert_eq!("", geocoder.get_description_for_number(&number(800, 12345678), "en"));
+    // Недействительные номера.
+    assert_eq!("", geocoder.get_description_for_number(&number(1, 123456789), "en"));
+    assert_eq!("", geocoder.get_description_for_number(&number(999, 123456789), "en"));
+}
+
+#[test]
+fn get_description_for_mobile_number_with_mobile_token() {
+    let phone_util = get_phone_util();
+    let geocoder = PhoneNumberOfflineGeocoder::new(Arc::clone(&phone_util));
+    // Мобильный токен "9" в Аргентине отбрасывается перед поиском.
+    let ar_mobile = phone_util.parse("+54 9 351 123 4567", "AR").unwrap();
+    assert_eq!("Córdoba, Córdoba", geocoder.get_description_for_valid_number(&ar_mobile, "en"));
+    let ar_fixed_line = number(54, 3511234567);
+    assert_eq!("Córdoba, Córdoba", geocoder.get_description_for_valid_number(&ar_fixed_line, "en"));
+}
+
+#[test]
+fn get_description_for_valid_number_for_user_region() {
+    let phone_util = get_phone_util();
+    let geocoder = PhoneNumberOfflineGeocoder::new(Arc::clone(&phone_util));
+    let us_number = number(1, 6502530000);
+    assert_eq!(
+        "Mountain View, CA",
+        geocoder.get_description_for_valid_number_for_user_region(&us_number, "en", "US")
+    );
+    assert_eq!(
+        "United States",
+        geocoder.get_description_for_valid_number_for_user_region(&us_number, "en", "DE")
+    );
+}
@@ -1,3 +1,50 @@
 #[cfg(test)]
 mod phonenumberutil_tests;
-pub(self) mod region_code;
\ No newline at end of file
+#[cfg(test)]
+mod as_you_type_tests;
+#[cfg(test)]
+mod phone_number_matcher_tests;
+#[cfg(test)]
+mod short_number_tests;
+#[cfg(all(test, feature = "geocoder"))]
+mod geocoder_tests;
+#[cfg(all(test, feature = "carrier"))]
+mod carrier_tests;
+#[cfg(all(test, feature = "timezones"))]
+mod timezones_tests;
+pub(self) mod region_code;
+
+#[cfg(test)]
+use std::sync::Arc;
+
+#[cfg(test)]
+use protobuf::Message;
+
+#[cfg(test)]
+use crate::{
+    generated::{metadata::TEST_METADATA, proto::phonemetadata::PhoneMetadataCollection},
+    phonenumberutil::phonenumberutil_internal::PhoneNumberUtilInternal,
+    PhoneNumber,
+};
+
+/// Утилита с тестовыми метаданными из resources/PhoneNumberMetadataForTesting.xml.
+#[cfg(test)]
+fn get_test_metadata_phone_util() -> Arc<PhoneNumberUtilInternal> {
+    let metadata = PhoneMetadataCollection::parse_from_bytes(&TEST_METADATA)
+        .expect("Metadata should be valid");
+    Arc::new(PhoneNumberUtilInternal::new_for_metadata(metadata))
+}
+
+/// Утилита с настоящими метаданными, которые нужны для проверки по данным из resources.
+#[cfg(test)]
+fn get_phone_util() -> Arc<PhoneNumberUtilInternal> {
+    Arc::new(PhoneNumberUtilInternal::new().expect("Metadata should be valid"))
+}
+
+#[cfg(test)]
+fn number(country_code: i32, national_number: u64) -> PhoneNumber {
+    let mut number = PhoneNumber::new();
+    number.set_country_code(country_code);
+    number.set_national_number(national_number);
+    number
+}
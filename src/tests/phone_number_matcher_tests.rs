@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use protobuf::Message;
+
+use crate::{
+    generated::{metadata::TEST_METADATA, proto::phonemetadata::PhoneMetadataCollection},
+    phonenumberutil::{
+        enums::Leniency,
+        phone_number_matcher::{PhoneNumberMatch, PhoneNumberMatcher},
+        phone_number_regexps_and_mappings::PhoneNumberRegExpsAndMappings,
+        phonenumberutil_internal::PhoneNumberUtilInternal,
+    },
+};
+
+use super::{get_test_metadata_phone_util, number, region_code::RegionCode};
+
+fn find_all<'t>(
+    phone_util: &Arc<PhoneNumberUtilInternal>,
+    text: &'t str,
+    region: &str,
+    leniency: Leniency,
+) -> Vec<PhoneNumberMatch<'t>> {
+    PhoneNumberMatcher::new(Arc::clone(phone_util), text, region, leniency, u64::MAX).collect()
+}
+
+#[test]
+fn find_national_number() {
+    let phone_util = get_test_metadata_phone_util();
+    let text = "Call 033316005 now";
+    let matches = find_all(&phone_util, text, RegionCode::nz(), Leniency::Valid);
+    assert_eq!(1, matches.len());
+    assert_eq!("033316005", matches[0].raw_string());
+    assert_eq!(5, matches[0].start());
+    assert_eq!(14, matches[0].end());
+    assert_eq!(&number(64, 33316005), matches[0].number());
+
+    // Номер с кодом страны находится независимо от региона.
+    let matches = find_all(&phone_util, "+64 3 331 6005", RegionCode::us(), Leniency::Valid);
+    assert_eq!(1, matches.len());
+    assert_eq!(&number(64, 33316005), matches[0].number());
+}
+
+#[test]
+fn find_with_extension() {
+    let phone_util = get_test_metadata_phone_util();
+    let text = "Our office: 650-253-0000 ext. 1234, open daily";
+    let matches = find_all(&phone_util, text, RegionCode::us(), Leniency::Valid);
+    assert_eq!(1, matches.len());
+    assert_eq!("650-253-0000 ext. 1234", matches[0].raw_string());
+    let mut expected = number(1, 6502530000);
+    expected.set_extension("1234".to_owned());
+    assert_eq!(expected, matches[0].clone().into_number());
+}
+
+#[test]
+fn find_multiple_numbers() {
+    let phone_util = get_test_metadata_phone_util();
+    let text = "Call +1 650-253-0000 or (650) 253-0001 for details";
+    let matches = find_all(&phone_util, text, RegionCode::us(), Leniency::Valid);
+    assert_eq!(2, matches.len());
+    assert_eq!("+1 650-253-0000", matches[0].raw_string());
+    assert_eq!(&number(1, 6502530000), matches[0].number());
+    assert_eq!("(650) 253-0001", matches[1].raw_string());
+    assert_eq!(&number(1, 6502530001), matches[1].number());
+    assert_eq!(&text[matches[1].start()..matches[1].end()], matches[1].raw_string());
+
+    // Номера, разделённые косой чертой, находятся по отдельности.
+    let text = "651-234-2345/650-253-0000";
+    let matches = find_all(&phone_util, text, RegionCode::us(), Leniency::Valid);
+    assert_eq!(2, matches.len());
+    assert_eq!("651-234-2345", matches[0].raw_string());
+    assert_eq!("650-253-0000", matches[1].raw_string());
+    assert_eq!(13, matches[1].start());
+}
+
+#[test]
+fn skips_non_numbers() {
+    let phone_util = get_test_metadata_phone_util();
+    // Даты и отметки времени не являются номерами.
+    for text in ["As of 08/31/95", "As of 31/08/1995", "2012-01-02 08:00", "211-227 (2003)"] {
+        assert!(
+            find_all(&phone_util, text, RegionCode::us(), Leniency::Possible).is_empty(),
+            "{text}"
+        );
+    }
+    // Номер, слитый с латинскими буквами, не принимается начиная с VALID.
+    for text in ["abc8005001234", "8005001234def", "650-253-0000%"] {
+        assert!(find_all(&phone_util, text, RegionCode::us(), Leniency::Valid).is_empty(), "{text}");
+    }
+    assert_eq!(
+        1,
+        find_all(&phone_util, "abc8005001234", RegionCode::us(), Leniency::Possible).len()
+    );
+    // Буквы других алфавитов допустимы.
+    assert_eq!(
+        1,
+        find_all(&phone_util, "Тел.650-253-0000", RegionCode::us(), Leniency::Valid).len()
+    );
+}
+
+#[test]
+fn leniency_levels() {
+    let phone_util = get_test_metadata_phone_util();
+    // Возможный, но недействительный номер.
+    let text = "Call 253-0000 now";
+    assert_eq!(1, find_all(&phone_util, text, RegionCode::us(), Leniency::Possible).len());
+    assert!(find_all(&phone_util, text, RegionCode::us(), Leniency::Valid).is_empty());
+
+    // Группы цифр разбиты иначе, чем в формате номера.
+    let text = "Call 65 02 53 00 00 now";
+    assert_eq!(1, find_all(&phone_util, text, RegionCode::us(), Leniency::Valid).len());
+    assert!(find_all(&phone_util, text, RegionCode::us(), Leniency::StrictGrouping).is_empty());
+    assert!(find_all(&phone_util, text, RegionCode::us(), Leniency::ExactGrouping).is_empty());
+
+    // Группы объединены, но не разбиты.
+    let text = "Call 650 2530000 now";
+    assert_eq!(1, find_all(&phone_util, text, RegionCode::us(), Leniency::StrictGrouping).len());
+    assert!(find_all(&phone_util, text, RegionCode::us(), Leniency::ExactGrouping).is_empty());
+
+    let text = "Call 650 253 0000 now";
+    assert_eq!(1, find_all(&phone_util, text, RegionCode::us(), Leniency::ExactGrouping).len());
+}
+
+#[test]
+fn max_tries() {
+    let phone_util = get_test_metadata_phone_util();
+    let text = "1 2 3 4 5 6 7 8 9 10 11 12 13 14 +1 650-253-0000";
+    assert_eq!(1, find_all(&phone_util, text, RegionCode::us(), Leniency::Valid).len());
+    let matcher = PhoneNumberMatcher::new(Arc::clone(&phone_util), text, RegionCode::us(), Leniency::Valid, 3);
+    assert_eq!(0, matcher.count());
+}
+
+#[test]
+fn no_matches_in_empty_text() {
+    let phone_util = get_test_metadata_phone_util();
+    assert!(find_all(&phone_util, "", RegionCode::us(), Leniency::Possible).is_empty());
+    assert!(find_all(&phone_util, "no numbers here", RegionCode::us(), Leniency::Possible).is_empty());
+}
+
+#[test]
+fn find_with_extra_extension_separators() {
+    let metadata = PhoneMetadataCollection::parse_from_bytes(&TEST_METADATA).unwrap();
+    let phone_util = Arc::new(PhoneNumberUtilInternal::new_for_metadata_and_reg_exps(
+        metadata,
+        PhoneNumberRegExpsAndMappings::with_extra_extension_separators(&['|']),
+    ));
+    let text = "Call 650-253-0000 | 1234 now";
+    let matches = find_all(&phone_util, text, RegionCode::us(), Leniency::Valid);
+    assert_eq!(1, matches.len());
+    assert_eq!("650-253-0000 | 1234", matches[0].raw_string());
+    let mut expected = number(1, 6502530000);
+    expected.set_extension("1234".to_owned());
+    assert_eq!(&expected, matches[0].number());
+
+    // Без дополнительных разделителей добавочный номер не попадает в совпадение.
+    let default_util = get_test_metadata_phone_util();
+    let matches = find_all(&default_util, text, RegionCode::us(), Leniency::Valid);
+    assert_eq!(1, matches.len());
+    assert_eq!("650-253-0000", matches[0].raw_string());
+}
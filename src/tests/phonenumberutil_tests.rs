@@ -3,12 +3,14 @@ use protobuf::{Message, MessageField};
 use crate::{
     phonenumberutil::{
         enums::{
-            PhoneNumberFormat, PhoneNumberType, NumberLengthType,
+            CsvSafeStyle, ExtensionLabelKind, MatchType, PhoneNumberFormat, PhoneNumberType, NumberLengthType,
         },
         errors::{
-            ParseError, ValidationError
-        }
+            GetExampleNumberError, MetadataError, ParseError, TypeValidationError, ValidationError
+        },
+        phonenumberutil::PhoneNumberUtil,
     },
+    PHONE_NUMBER_UTIL,
     generated::proto::{
         phonemetadata::{
             NumberFormat, PhoneMetadata, PhoneMetadataCollection, PhoneNumberDesc
@@ -17,6 +19,15 @@ use crate::{
         phonenumber::PhoneNumber,
     },
     phonenumberutil::phonenumberutil_internal::PhoneNumberUtilInternal,
+    phonenumberutil::phone_number_regexps_and_mappings::PhoneNumberRegExpsAndMappings,
+    phonenumberutil::parse_options::ParseOptions,
+    phonenumberutil::util_options::PhoneNumberUtilOptions,
+    regexp_cache::{CacheStats, RegexCache},
+    phonenumberutil::number_parts::NumberParts,
+    phonenumberutil::common_formats::CommonFormats,
+    phonenumber_builder::PhoneNumberBuilder,
+    phonenumberutil::number_or_string::NumberOrString,
+    phonenumberutil::metadata_validation::{MetadataProblem, validate_metadata_collection},
 };
 
 use super::region_code::RegionCode;
@@ -50,16 +61,33 @@ fn interchange_invalid_codepoints() {
     }
 
     let invalid_inputs = vec![
-        "+44\u{96}2087654321",     // Invalid sequence
-        "+44\u{0096}2087654321", // U+0096
-        "+44\u{fffe}2087654321", // U+FFFE
+        ("+44\u{96}2087654321", '\u{96}'),     // Invalid sequence
+        ("+44\u{0096}2087654321", '\u{0096}'), // U+0096
+        ("+44\u{fffe}2087654321", '\u{fffe}'), // U+FFFE
     ];
-    for input in invalid_inputs {
+    for (input, codepoint) in invalid_inputs {
         assert!(!phone_util.is_viable_phone_number(input));
-        assert!(
-            phone_util.parse(input, RegionCode::gb()).is_err_and(| err | matches!(err.into_public(), ParseError::NotANumber(_)))
+        assert_eq!(
+            ParseError::InvalidCharacter { position: 3, codepoint },
+            phone_util.parse(input, RegionCode::gb()).unwrap_err().into_public()
         );
     }
+
+    // Позиция указывает на символ в исходной строке, а не в извлечённом номере.
+    assert_eq!(
+        ParseError::InvalidCharacter { position: 14, codepoint: '\u{fdd0}' },
+        phone_util
+            .parse("Number: +44 20\u{fdd0}87654321", RegionCode::gb())
+            .unwrap_err()
+            .into_public()
+    );
+    // Недопустимые символы до начала номера отбрасываются, как и прежде.
+    phone_util.parse("\u{0096}+44 2087654321", RegionCode::gb()).unwrap();
+    // Строка без недопустимых символов по-прежнему не является номером.
+    assert!(matches!(
+        phone_util.parse("+44 abc", RegionCode::gb()).unwrap_err().into_public(),
+        ParseError::NotANumber(_)
+    ));
 }
 
 #[test]
@@ -71,6 +99,57 @@ fn get_supported_regions() {
     )
 }
 
+#[test]
+fn is_geo_region() {
+    let phone_util = get_phone_util();
+    assert!(phone_util.is_geo_region(RegionCode::us()));
+    assert!(phone_util.is_geo_region(RegionCode::de()));
+    assert!(!phone_util.is_geo_region(RegionCode::un001()));
+    assert!(!phone_util.is_geo_region(RegionCode::zz()));
+    // Регион без метаданных.
+    assert!(!phone_util.is_geo_region(RegionCode::cs()));
+    assert!(phone_util.get_supported_regions().all(|region| phone_util.is_geo_region(region)));
+}
+
+#[test]
+fn get_nanpa_regions() {
+    let phone_util = get_phone_util();
+    let nanpa_regions = phone_util.get_nanpa_regions();
+    assert!(nanpa_regions.contains(&RegionCode::us()));
+    assert!(nanpa_regions.contains(&RegionCode::bs()));
+    assert!(!nanpa_regions.contains(&RegionCode::de()));
+    // Регионы отсортированы и совпадают с регионами кода страны 1.
+    assert!(nanpa_regions.is_sorted());
+    let mut regions_for_code = phone_util
+        .get_region_codes_for_country_calling_code(1)
+        .unwrap()
+        .collect::<Vec<_>>();
+    regions_for_code.sort();
+    assert_eq!(regions_for_code, nanpa_regions.to_vec());
+    assert!(nanpa_regions.iter().all(|region| phone_util.is_nanpa_country(region)));
+    // Список вычисляется один раз при загрузке метаданных.
+    assert!(std::sync::Arc::ptr_eq(nanpa_regions, phone_util.get_nanpa_regions()));
+}
+
+#[test]
+fn get_country_codes_for_regions() {
+    let phone_util = get_phone_util();
+    assert_eq!(
+        vec![Some(1), Some(49), None, Some(44), None],
+        phone_util.get_country_codes_for_regions(&[
+            RegionCode::us(),
+            RegionCode::de(),
+            RegionCode::zz(),
+            RegionCode::gb(),
+            RegionCode::un001(),
+        ])
+    );
+    // Порядок сохраняется и для владеющих строк.
+    let regions = vec!["NZ".to_owned(), "IT".to_owned()];
+    assert_eq!(vec![Some(64), Some(39)], phone_util.get_country_codes_for_regions(&regions));
+    assert!(phone_util.get_country_codes_for_regions(&[] as &[&str]).is_empty());
+}
+
 #[test]
 fn get_supported_global_network_calling_codes() {
     let phone_util = get_phone_util();
@@ -173,6 +252,47 @@ fn get_region_codes_for_country_calling_code() {
     );
 }
 
+#[test]
+fn regions_for_calling_code_slice() {
+    let phone_util = get_phone_util();
+
+    // Срез совпадает с итератором, основной регион идёт первым.
+    for code in [1, 44, 49, 800] {
+        let slice = phone_util.regions_for_calling_code_slice(code).expect("Codes should exist");
+        let from_iter = phone_util
+            .get_region_codes_for_country_calling_code(code)
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(from_iter, slice.to_vec());
+        assert_eq!(phone_util.get_region_code_for_country_code(code), slice[0]);
+    }
+    assert!(phone_util.regions_for_calling_code_slice(2).is_none());
+
+    // Публичный метод отдаёт срез из метаданных без копирования.
+    let public_util = PhoneNumberUtil::from_metadata_bytes(&TEST_METADATA).unwrap();
+    let slice = public_util.regions_for_calling_code_slice(1).unwrap();
+    assert!(std::sync::Arc::ptr_eq(&slice, &public_util.regions_for_calling_code_slice(1).unwrap()));
+    assert_eq!(RegionCode::us(), slice[0]);
+}
+
+#[test]
+fn get_ordered_regions_for_country_code() {
+    let phone_util = get_phone_util();
+    // Основной регион идёт первым, остальные — по алфавиту.
+    let regions = phone_util.get_ordered_regions_for_country_code(1).unwrap();
+    assert_eq!(RegionCode::us(), regions[0]);
+    assert!(regions[1..].is_sorted());
+    assert!(regions.contains(&RegionCode::bs()));
+    assert_eq!(
+        phone_util.get_region_codes_for_country_calling_code(1).unwrap().len(),
+        regions.len()
+    );
+
+    assert_eq!(Some(vec![RegionCode::gb(), "GG"]), phone_util.get_ordered_regions_for_country_code(44));
+    assert_eq!(Some(vec![RegionCode::un001()]), phone_util.get_ordered_regions_for_country_code(800));
+    assert_eq!(None, phone_util.get_ordered_regions_for_country_code(2));
+}
+
 #[test]
 fn get_instance_load_us_metadata() {
     let phone_util = get_phone_util();
@@ -263,6 +383,27 @@ fn get_national_significant_number() {
     assert_eq!("12345678", national_significant_number);
 }
 
+#[test]
+fn national_digits() {
+    let phone_util = PhoneNumberUtil::from_metadata_bytes(&TEST_METADATA).unwrap();
+    let mut number = PhoneNumber::new();
+    number.set_country_code(44);
+    number.set_national_number(2087389353);
+    number.set_extension("123".to_owned());
+    // Без национального префикса и добавочного номера.
+    assert_eq!("(020) 8738 9353 ext. 123", phone_util.format(&number, PhoneNumberFormat::National));
+    assert_eq!("2087389353", phone_util.national_digits(&number));
+
+    number.clear();
+    number.set_country_code(39);
+    number.set_national_number(236618300);
+    number.set_italian_leading_zero(true);
+    assert_eq!("0236618300", phone_util.national_digits(&number));
+    number.set_number_of_leading_zeros(2);
+    assert_eq!("00236618300", phone_util.national_digits(&number));
+    assert_eq!(phone_util.get_national_significant_number(&number), phone_util.national_digits(&number));
+}
+
 #[test]
 fn get_national_significant_number_many_leading_zeros() {
     let phone_util = get_phone_util();
@@ -277,6 +418,116 @@ fn get_national_significant_number_many_leading_zeros() {
     number.set_number_of_leading_zeros(-3);
     let national_significant_number = phone_util.get_national_significant_number(&number);
     assert_eq!("650", national_significant_number);
+
+    number.set_number_of_leading_zeros(0);
+    assert_eq!("650", phone_util.get_national_significant_number(&number));
+
+    number.set_number_of_leading_zeros(i32::MIN);
+    assert_eq!("650", phone_util.get_national_significant_number(&number));
+
+    // Без флага italian_leading_zero количество нулей игнорируется.
+    number.set_italian_leading_zero(false);
+    number.set_number_of_leading_zeros(2);
+    assert_eq!("650", phone_util.get_national_significant_number(&number));
+}
+
+#[test]
+fn get_national_significant_number_is_digits_only() {
+    let phone_util = get_phone_util();
+    // В отладочной сборке get_national_significant_number сам проверяет, что результат
+    // состоит только из цифр, поэтому проверяются и крайние значения полей.
+    for national_number in [0, 1, 6502530000, u64::MAX] {
+        for number_of_leading_zeros in [i32::MIN, -1, 0, 1, 17, i32::MAX] {
+            let mut number = PhoneNumber::new();
+            number.set_country_code(39);
+            number.set_national_number(national_number);
+            number.set_italian_leading_zero(true);
+            number.set_number_of_leading_zeros(number_of_leading_zeros);
+            let national_significant_number = phone_util.get_national_significant_number(&number);
+            assert!(!national_significant_number.is_empty());
+            assert!(national_significant_number.bytes().all(|b| b.is_ascii_digit()));
+        }
+    }
+}
+
+#[test]
+fn get_national_significant_number_large_leading_zero_count() {
+    let phone_util = get_phone_util();
+    let mut number = PhoneNumber::new();
+    number.set_country_code(39);
+    number.set_national_number(650);
+    number.set_italian_leading_zero(true);
+
+    number.set_number_of_leading_zeros(17);
+    assert_eq!(format!("{}650", "0".repeat(17)), phone_util.get_national_significant_number(&number));
+
+    // Слишком большое количество нулей ограничивается максимальной длиной NSN.
+    number.set_number_of_leading_zeros(i32::MAX);
+    assert_eq!(format!("{}650", "0".repeat(17)), phone_util.get_national_significant_number(&number));
+}
+
+#[test]
+fn pack_and_unpack() {
+    let phone_util = get_phone_util();
+    let mut us_number = PhoneNumber::new();
+    us_number.set_country_code(1);
+    us_number.set_national_number(6502530000);
+    let packed = phone_util.pack(&us_number).unwrap();
+    assert_eq!((1_i128 << 64) | 6502530000, packed);
+    assert_eq!(us_number, phone_util.unpack(packed));
+
+    let mut max_number = PhoneNumber::new();
+    max_number.set_country_code(999);
+    max_number.set_national_number(u64::MAX);
+    let max_packed = phone_util.pack(&max_number).unwrap();
+    assert!(max_packed > 0);
+    assert_eq!(max_number, phone_util.unpack(max_packed));
+
+    // Упакованные значения сортируются по коду страны, затем по номеру.
+    let mut gb_number = PhoneNumber::new();
+    gb_number.set_country_code(44);
+    gb_number.set_national_number(2070313000);
+    assert!(packed < phone_util.pack(&gb_number).unwrap());
+    us_number.set_national_number(6502530001);
+    assert!(packed < phone_util.pack(&us_number).unwrap());
+
+    // Добавочные номера, ведущие нули и неверные коды стран не упаковываются.
+    let mut with_extension = gb_number.clone();
+    with_extension.set_extension("1234".to_owned());
+    assert_eq!(None, phone_util.pack(&with_extension));
+    let mut it_number = PhoneNumber::new();
+    it_number.set_country_code(39);
+    it_number.set_national_number(236618300);
+    it_number.set_italian_leading_zero(true);
+    assert_eq!(None, phone_util.pack(&it_number));
+    gb_number.set_country_code(0);
+    assert_eq!(None, phone_util.pack(&gb_number));
+    gb_number.set_country_code(1000);
+    assert_eq!(None, phone_util.pack(&gb_number));
+}
+
+#[test]
+fn is_possible_packed() {
+    let phone_util = get_phone_util();
+    let packed_of = |country_code: i32, national_number: u64| {
+        let mut number = PhoneNumber::new();
+        number.set_country_code(country_code);
+        number.set_national_number(national_number);
+        phone_util.pack(&number).unwrap()
+    };
+    for (country_code, national_number) in [(1, 6502530000), (1, 2530000), (1, 65025300000), (44, 2070313000), (2, 6502530000), (800, 12345678)] {
+        let packed = packed_of(country_code, national_number);
+        assert_eq!(
+            phone_util.is_possible_number(&phone_util.unpack(packed)),
+            phone_util.is_possible_packed(packed),
+            "+{country_code} {national_number}"
+        );
+    }
+    assert!(phone_util.is_possible_packed(packed_of(1, 6502530000)));
+    assert!(!phone_util.is_possible_packed(packed_of(1, 65025300000)));
+    // Значения, которые pack не может вернуть.
+    assert!(!phone_util.is_possible_packed(-1));
+    assert!(!phone_util.is_possible_packed(1 << 80));
 }
 
 #[test]
@@ -314,6 +565,113 @@ fn get_example_number() {
     assert!(phone_util.get_example_number(RegionCode::un001()).is_err());
 }
 
+#[test]
+fn enums_as_map_keys() {
+    use std::collections::{BTreeMap, HashMap};
+
+    let phone_util = get_phone_util();
+    // Группировка номеров по типу; порядок ключей совпадает с порядком объявления.
+    let mut by_type: BTreeMap<PhoneNumberType, Vec<PhoneNumber>> = BTreeMap::new();
+    let mut by_type_hashed: HashMap<PhoneNumberType, Vec<PhoneNumber>> = HashMap::new();
+    for (number_to_parse, region_code) in [
+        ("+44 7912 345 678", RegionCode::zz()),
+        ("+44 20 7031 3000", RegionCode::zz()),
+        ("+44 7912 345 679", RegionCode::zz()),
+    ] {
+        let number = phone_util.parse(number_to_parse, region_code).unwrap();
+        let number_type = phone_util.get_number_type(&number).unwrap();
+        by_type.entry(number_type).or_default().push(number.clone());
+        by_type_hashed.entry(number_type).or_default().push(number);
+    }
+    assert_eq!(
+        vec![PhoneNumberType::FixedLine, PhoneNumberType::Mobile],
+        by_type.keys().copied().collect::<Vec<_>>()
+    );
+    assert_eq!(2, by_type[&PhoneNumberType::Mobile].len());
+    assert_eq!(2, by_type_hashed[&PhoneNumberType::Mobile].len());
+
+    assert!(PhoneNumberType::FixedLine < PhoneNumberType::Unknown);
+    assert!(PhoneNumberFormat::E164 < PhoneNumberFormat::RFC3966);
+    assert!(NumberLengthType::IsPossible < NumberLengthType::IsPossibleLocalOnly);
+}
+
+#[test]
+fn example_numbers_for_region() {
+    let phone_util = get_phone_util();
+    let examples: Vec<_> = phone_util.example_numbers_for_region(RegionCode::de()).collect();
+    // Каждый пример совпадает с примером для соответствующего типа.
+    assert!(!examples.is_empty());
+    for (number_type, example_number) in &examples {
+        assert_eq!(
+            Some(example_number),
+            phone_util
+                .get_example_number_for_type_and_region_code(RegionCode::de(), *number_type)
+                .ok()
+                .as_ref()
+        );
+    }
+    // Типы перечисляются в порядке объявления, типы без примера пропускаются.
+    let types: Vec<_> = examples.iter().map(|(number_type, _)| *number_type).collect();
+    assert_eq!(
+        vec![
+            PhoneNumberType::FixedLine,
+            PhoneNumberType::Mobile,
+            PhoneNumberType::TollFree,
+            PhoneNumberType::PremiumRate,
+        ],
+        types
+    );
+
+    assert_eq!(0, phone_util.example_numbers_for_region(RegionCode::zz()).count());
+
+    let public_util = PhoneNumberUtil::from_metadata_bytes(&TEST_METADATA).unwrap();
+    let region_code = String::from("US");
+    assert!(public_util
+        .example_numbers_for_region(&region_code)
+        .eq(phone_util.example_numbers_for_region(RegionCode::us())));
+}
+
+#[test]
+fn get_second_example_number_for_type_and_region() {
+    let phone_util = get_phone_util();
+    for (region_code, number_type) in [
+        (RegionCode::de(), PhoneNumberType::FixedLine),
+        (RegionCode::us(), PhoneNumberType::Mobile),
+        (RegionCode::gb(), PhoneNumberType::FixedLine),
+    ] {
+        let first = phone_util
+            .get_example_number_for_type_and_region_code(region_code, number_type)
+            .unwrap();
+        let second = phone_util
+            .get_second_example_number_for_type_and_region(region_code, number_type)
+            .unwrap();
+        assert_ne!(first, second);
+        assert!(phone_util.is_valid_number_for_region(&second, region_code));
+        assert_eq!(
+            phone_util.get_number_type(&first).unwrap(),
+            phone_util.get_number_type(&second).unwrap()
+        );
+        assert_eq!(
+            phone_util.get_national_significant_number(&first).len(),
+            phone_util.get_national_significant_number(&second).len()
+        );
+    }
+
+    // Ошибки примера номера передаются как есть.
+    assert!(matches!(
+        phone_util
+            .get_second_example_number_for_type_and_region(RegionCode::us(), PhoneNumberType::VoiceMail)
+            .map_err(|err| err.into_public()),
+        Err(GetExampleNumberError::CouldNotGetNumber)
+    ));
+    assert!(matches!(
+        phone_util
+            .get_second_example_number_for_type_and_region(RegionCode::cs(), PhoneNumberType::Mobile)
+            .map_err(|err| err.into_public()),
+        Err(GetExampleNumberError::InvalidRegionCode)
+    ));
+}
+
 #[test]
 fn get_example_number_without_region() {
     let phone_util = get_phone_util();
@@ -359,6 +717,29 @@ fn get_example_number_for_non_geo_entity() {
     assert_eq!(universal_premium_rate, test_number);
 }
 
+#[test]
+fn get_example_numbers_for_non_geo_entity() {
+    let phone_util = get_phone_util();
+    assert!(phone_util.get_example_numbers_for_non_geo_entity(999).is_none());
+    // Код 1 принадлежит регионам, а не негеографическим объектам.
+    assert!(phone_util.get_example_numbers_for_non_geo_entity(1).is_none());
+
+    let examples: Vec<_> = phone_util
+        .get_example_numbers_for_non_geo_entity(979)
+        .expect("Code should exist")
+        .collect();
+    let mut universal_premium_rate = PhoneNumber::new();
+    universal_premium_rate.set_country_code(979);
+    universal_premium_rate.set_national_number(123456789);
+    assert_eq!(vec![(PhoneNumberType::PremiumRate, universal_premium_rate)], examples);
+
+    let public_util = PhoneNumberUtil::from_metadata_bytes(&TEST_METADATA).unwrap();
+    for (number_type, example_number) in public_util.get_example_numbers_for_non_geo_entity(800).unwrap() {
+        assert_eq!(800, example_number.country_code());
+        assert_eq!(number_type, public_util.get_number_type(&example_number));
+    }
+}
+
 #[test]
 fn format_us_number() {
     let phone_util = get_phone_util();
@@ -416,6 +797,273 @@ fn format_gb_number() {
     assert_eq!("+44 7912 345 678", phone_util.format(&test_number, PhoneNumberFormat::International).unwrap());
 }
 
+#[test]
+fn format_national_no_parens() {
+    let phone_util = get_phone_util();
+    let mut test_number = PhoneNumber::new();
+    test_number.set_country_code(44);
+    test_number.set_national_number(2087389353);
+    assert_eq!("020 8738 9353", phone_util.format_national_no_parens(&test_number).unwrap());
+
+    test_number.set_national_number(7912345678);
+    assert_eq!("07912 345 678", phone_util.format_national_no_parens(&test_number).unwrap());
+
+    // Номера без скобок в национальном формате не меняются.
+    test_number.set_country_code(1);
+    test_number.set_national_number(6502530000);
+    assert_eq!(
+        phone_util.format(&test_number, PhoneNumberFormat::National).unwrap(),
+        phone_util.format_national_no_parens(&test_number).unwrap()
+    );
+}
+
+#[test]
+fn format_e164_digits() {
+    let phone_util = get_phone_util();
+    let mut test_number = PhoneNumber::new();
+    test_number.set_country_code(1);
+    test_number.set_national_number(6502530000);
+    assert_eq!("16502530000", phone_util.format_e164_digits(&test_number));
+
+    // Ведущие нули итальянских номеров сохраняются, добавочный номер отбрасывается.
+    test_number.set_country_code(39);
+    test_number.set_national_number(236618300);
+    test_number.set_italian_leading_zero(true);
+    test_number.set_extension("1234".to_owned());
+    assert_eq!("390236618300", phone_util.format_e164_digits(&test_number));
+    assert_eq!(
+        phone_util.format(&test_number, PhoneNumberFormat::E164).unwrap()[1..],
+        phone_util.format_e164_digits(&test_number)
+    );
+}
+
+#[test]
+fn format_for_screen_reader() {
+    let phone_util = get_phone_util();
+    let mut test_number = PhoneNumber::new();
+    test_number.set_country_code(1);
+    test_number.set_national_number(6502530000);
+    assert_eq!("+1 6 5 0 2 5 3 0 0 0 0", phone_util.format_for_screen_reader(&test_number));
+
+    // Код страны произносится целиком, ведущий ноль сохраняется.
+    test_number.set_country_code(39);
+    test_number.set_national_number(236618300);
+    test_number.set_italian_leading_zero(true);
+    assert_eq!("+39 0 2 3 6 6 1 8 3 0 0", phone_util.format_for_screen_reader(&test_number));
+
+    test_number.set_extension("12".to_owned());
+    assert_eq!(
+        "+39 0 2 3 6 6 1 8 3 0 0 ext. 1 2",
+        phone_util.format_for_screen_reader(&test_number)
+    );
+}
+
+#[test]
+fn reload_metadata() {
+    let phone_util = PhoneNumberUtil::from_metadata_bytes(&TEST_METADATA).unwrap();
+    let supported_regions = phone_util.get_supported_regions().count();
+    let gb_region = phone_util.get_region_code_for_country_code(44);
+
+    // Некорректные данные не затрагивают загруженные метаданные.
+    assert!(matches!(
+        phone_util.reload_metadata(&[0xff, 0xff, 0xff]),
+        Err(MetadataError::InvalidMetadata(_))
+    ));
+    let mut metadata = PhoneMetadataCollection::parse_from_bytes(&TEST_METADATA).unwrap();
+    let us_metadata = metadata.metadata.iter_mut().find(|metadata| metadata.id() == RegionCode::us()).unwrap();
+    us_metadata.mobile.mut_or_insert_default().set_national_number_pattern("[".to_owned());
+    assert!(matches!(
+        phone_util.reload_metadata(&metadata.write_to_bytes().unwrap()),
+        Err(MetadataError::InvalidPattern(MetadataProblem::InvalidPattern { .. }))
+    ));
+    assert_eq!(supported_regions, phone_util.get_supported_regions().count());
+
+    let mut metadata = PhoneMetadataCollection::parse_from_bytes(&TEST_METADATA).unwrap();
+    metadata.metadata.retain(|metadata| metadata.id() != RegionCode::gb());
+    phone_util.reload_metadata(&metadata.write_to_bytes().unwrap()).unwrap();
+    assert_eq!(supported_regions - 1, phone_util.get_supported_regions().count());
+    assert!(!phone_util.get_supported_regions().any(|region| region == RegionCode::gb()));
+    assert_ne!(RegionCode::gb(), phone_util.get_region_code_for_country_code(44));
+    assert!(phone_util.parse("020 8738 9353", RegionCode::gb()).is_err());
+    assert!(phone_util.parse("(650) 253-0000", RegionCode::us()).is_ok());
+    // Строки из прежних метаданных остаются доступны.
+    assert_eq!(RegionCode::gb(), gb_region);
+
+    phone_util.reload_metadata(&TEST_METADATA).unwrap();
+    assert_eq!(supported_regions, phone_util.get_supported_regions().count());
+    assert_eq!(RegionCode::gb(), phone_util.get_region_code_for_country_code(44));
+}
+
+#[test]
+fn reload_metadata_compiles_patterns_once() {
+    let phone_util = PhoneNumberUtil::from_metadata_bytes(&TEST_METADATA).unwrap();
+    // Шаблоны, проверенные при загрузке, остаются в кэше.
+    let stats = phone_util.regex_cache_stats();
+    assert!(stats.size > 0);
+    phone_util.reload_metadata(&TEST_METADATA).unwrap();
+    assert_eq!(stats.misses, phone_util.regex_cache_stats().misses);
+    assert_eq!(stats.size, phone_util.regex_cache_stats().size);
+}
+
+#[test]
+fn metadata_with_invalid_region_code_is_skipped() {
+    let mut metadata = PhoneMetadataCollection::parse_from_bytes(&TEST_METADATA).unwrap();
+    metadata.metadata.retain(|metadata| metadata.id() == RegionCode::de());
+    let mut invalid_metadata = metadata.metadata[0].clone();
+    invalid_metadata.set_id("de".to_owned());
+    metadata.metadata.push(invalid_metadata);
+    let phone_util = PhoneNumberUtil::from_metadata_bytes(&metadata.write_to_bytes().unwrap()).unwrap();
+    assert_eq!(vec![RegionCode::de()], phone_util.get_supported_regions().collect::<Vec<_>>());
+    assert_eq!(Some(vec![RegionCode::de()]), phone_util.get_ordered_regions_for_country_code(49));
+    assert_eq!(
+        vec![MetadataProblem::InvalidRegionCode { region: "de".to_owned() }],
+        validate_metadata_collection(&metadata)
+    );
+}
+
+#[test]
+fn reload_metadata_while_in_use() {
+    let phone_util = PhoneNumberUtil::from_metadata_bytes(&TEST_METADATA).unwrap();
+    let mut metadata = PhoneMetadataCollection::parse_from_bytes(&TEST_METADATA).unwrap();
+    metadata.metadata.retain(|metadata| metadata.id() != RegionCode::gb());
+    let metadata_without_gb = metadata.write_to_bytes().unwrap();
+
+    // Каждый вызов видит либо старые, либо новые метаданные целиком.
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            for _ in 0..3 {
+                phone_util.reload_metadata(&metadata_without_gb).unwrap();
+                phone_util.reload_metadata(&TEST_METADATA).unwrap();
+            }
+        });
+        for _ in 0..50 {
+            let us_number = phone_util.parse("(650) 253-0000", RegionCode::us()).unwrap();
+            assert_eq!(RegionCode::us(), phone_util.get_region_code_for_number(&us_number));
+            let region = phone_util.get_region_code_for_country_code(44);
+            // Без GB основным регионом кода 44 становится GG.
+            assert!(region == RegionCode::gb() || region == RegionCode::gg(), "{}", region);
+        }
+    });
+}
+
+#[test]
+fn reload_metadata_keeps_metadata_of_helpers() {
+    let phone_util = PhoneNumberUtil::from_metadata_bytes(&TEST_METADATA).unwrap();
+    let mut formatter = phone_util.get_as_you_type_formatter(RegionCode::gb());
+    let mut metadata = PhoneMetadataCollection::parse_from_bytes(&TEST_METADATA).unwrap();
+    metadata.metadata.retain(|metadata| metadata.id() != RegionCode::gb());
+    phone_util.reload_metadata(&metadata.write_to_bytes().unwrap()).unwrap();
+
+    // Форматтер, созданный до перезагрузки, продолжает работать на прежних метаданных.
+    let formatted = "020 7031 3000".chars().filter(char::is_ascii_digit).map(|digit| formatter.input_digit(digit)).last();
+    assert_eq!(Some("020 7031 3000"), formatted.as_deref());
+    let mut formatter = phone_util.get_as_you_type_formatter(RegionCode::gb());
+    let formatted = "02070313000".chars().map(|digit| formatter.input_digit(digit)).last();
+    assert_eq!(Some("02070313000"), formatted.as_deref());
+}
+
+#[test]
+fn from_metadata_bytes() {
+    let phone_util = PhoneNumberUtil::from_metadata_bytes(&TEST_METADATA).unwrap();
+    assert!(phone_util.is_valid_number(&phone_util.parse("+1 650-253-0000", RegionCode::zz()).unwrap()));
+
+    // Метаданные только для одного региона.
+    let mut metadata = PhoneMetadataCollection::parse_from_bytes(&TEST_METADATA).unwrap();
+    metadata.metadata.retain(|metadata| metadata.id() == RegionCode::de());
+    let phone_util = PhoneNumberUtil::from_metadata_bytes(&metadata.write_to_bytes().unwrap()).unwrap();
+    assert_eq!(vec![RegionCode::de()], phone_util.get_supported_regions().collect::<Vec<_>>());
+    assert!(phone_util.parse("(650) 253-0000", RegionCode::us()).is_err());
+
+    // Ошибка декодирования отличается от ошибки компиляции шаблона.
+    assert!(matches!(
+        PhoneNumberUtil::from_metadata_bytes(&[0xff, 0xff, 0xff]),
+        Err(MetadataError::InvalidMetadata(_))
+    ));
+    let mut metadata = PhoneMetadataCollection::parse_from_bytes(&TEST_METADATA).unwrap();
+    let us_metadata = metadata.metadata.iter_mut().find(|metadata| metadata.id() == RegionCode::us()).unwrap();
+    us_metadata.mobile.mut_or_insert_default().set_national_number_pattern("[".to_owned());
+    let Err(MetadataError::InvalidPattern(MetadataProblem::InvalidPattern { region, field, pattern, .. })) =
+        PhoneNumberUtil::from_metadata_bytes(&metadata.write_to_bytes().unwrap())
+    else {
+        panic!("An invalid pattern should be reported");
+    };
+    assert_eq!((RegionCode::us(), "mobile", "["), (region.as_str(), field.as_str(), pattern.as_str()));
+}
+
+#[test]
+fn new_for_regions() {
+    // Используются настоящие метаданные.
+    let phone_util = PhoneNumberUtil::new_for_regions(&["US", "GB", "DE", "XX"]);
+    let supported_regions: Vec<_> = phone_util.get_supported_regions().collect();
+    for region_code in ["US", "CA", "BS", "GB", "GG", "JE", "DE"] {
+        assert!(supported_regions.contains(&region_code), "{}", region_code);
+    }
+    assert!(!supported_regions.contains(&"FR"));
+    assert!(!supported_regions.contains(&"RU"));
+    assert!(phone_util.get_example_numbers_for_non_geo_entity(800).is_none());
+
+    // Номера загруженных регионов обрабатываются как обычно.
+    let number = phone_util.parse("+1 587-530-2271", "ZZ").unwrap();
+    assert!(phone_util.is_valid_number(&number));
+    assert_eq!("CA", phone_util.get_region_code_for_number(&number));
+    assert!(phone_util.is_valid_number(&phone_util.parse("030 123456", "DE").unwrap()));
+
+    // Исключенные регионы неизвестны.
+    assert_eq!(Err(ParseError::InvalidCountryCode), phone_util.parse("+33 1 23 45 67 89", "ZZ"));
+    assert_eq!(Err(ParseError::InvalidCountryCode), phone_util.parse("01 23 45 67 89", "FR"));
+    assert_eq!(Err(GetExampleNumberError::InvalidRegionCode), phone_util.get_example_number("FR"));
+    assert_eq!(None, phone_util.get_country_code_for_region("FR"));
+
+    let phone_util = PhoneNumberUtil::new_for_regions(&["001"]);
+    assert_eq!(0, phone_util.get_supported_regions().count());
+    assert!(phone_util.get_example_numbers_for_non_geo_entity(800).is_some());
+}
+
+#[test]
+fn format_nbsp() {
+    let phone_util = get_phone_util();
+    let mut test_number = PhoneNumber::new();
+    test_number.set_country_code(44);
+    test_number.set_national_number(2087389353);
+    assert_eq!(
+        "+44\u{a0}20\u{a0}8738\u{a0}9353",
+        phone_util.format_nbsp(&test_number, PhoneNumberFormat::International).unwrap()
+    );
+    assert_eq!(
+        "(020)\u{a0}8738\u{a0}9353",
+        phone_util.format_nbsp(&test_number, PhoneNumberFormat::National).unwrap()
+    );
+    // Без пробелов строка не меняется.
+    assert_eq!(
+        "+442087389353",
+        phone_util.format_nbsp(&test_number, PhoneNumberFormat::E164).unwrap()
+    );
+
+    // Пробелы внутри префикса добавочного номера сохраняются.
+    test_number.set_extension("123".to_owned());
+    assert_eq!(
+        "+44\u{a0}20\u{a0}8738\u{a0}9353 ext. 123",
+        phone_util.format_nbsp(&test_number, PhoneNumberFormat::International).unwrap()
+    );
+}
+
+#[test]
+fn format_national_with_prepared_prefix_rules() {
+    let phone_util = get_phone_util();
+    let mut gb_number = PhoneNumber::new();
+    gb_number.set_country_code(44);
+    gb_number.set_national_number(2087389353);
+    let mut mx_number = PhoneNumber::new();
+    mx_number.set_country_code(52);
+    mx_number.set_national_number(3312345678);
+    // Повторное форматирование использует шаблоны, подготовленные при загрузке метаданных.
+    for _ in 0..3 {
+        assert_eq!("(020) 8738 9353", phone_util.format(&gb_number, PhoneNumberFormat::National).unwrap());
+        assert_eq!("+44 20 8738 9353", phone_util.format(&gb_number, PhoneNumberFormat::International).unwrap());
+        assert_eq!("01 33 1234 5678", phone_util.format(&mx_number, PhoneNumberFormat::National).unwrap());
+    }
+}
+
 #[test]
 fn format_de_number() {
     let phone_util = get_phone_util();
@@ -577,26 +1225,83 @@ fn format_out_of_country_calling_number() {
 }
 
 #[test]
-fn format_out_of_country_keeping_alpha_chars() {
+fn format_as_dialed_from() {
     let phone_util = get_phone_util();
-    let mut alpha_numeric_number = phone_util.parse_and_keep_raw_input("1800 six-flag", RegionCode::us()).unwrap();
-    
-    let formatted_number = phone_util.format_out_of_country_keeping_alpha_chars(&alpha_numeric_number, RegionCode::au()).unwrap();
-    assert_eq!("0011 1 800 SIX-FLAG", formatted_number);
-
-    // Formatting from within the NANPA region.
-    let formatted_number = phone_util.format_out_of_country_keeping_alpha_chars(&alpha_numeric_number, RegionCode::us()).unwrap();
-    assert_eq!("1 800 SIX-FLAG", formatted_number);
-
-    // Testing a number with extension.
-    let alpha_numeric_number_with_extn = phone_util.parse_and_keep_raw_input("800 SIX-flag ext. 1234", RegionCode::us()).unwrap();
-    let formatted_number = phone_util.format_out_of_country_keeping_alpha_chars(&alpha_numeric_number_with_extn, RegionCode::au()).unwrap();
-    assert_eq!("0011 1 800 SIX-FLAG extn. 1234", formatted_number);
+    let mut gb_number = PhoneNumber::new();
+    gb_number.set_country_code(44);
+    gb_number.set_national_number(2087389353);
+    gb_number.set_extension("1234".to_owned());
+    assert_eq!("011442087389353", phone_util.format_as_dialed_from(&gb_number, RegionCode::us()).unwrap());
+    assert_eq!("02087389353", phone_util.format_as_dialed_from(&gb_number, RegionCode::gb()).unwrap());
+    // Несколько международных префиксов: остаётся "+".
+    assert_eq!("+442087389353", phone_util.format_as_dialed_from(&gb_number, RegionCode::sg()).unwrap());
+
+    // Ожидание гудка "~" удаляется.
+    let mut it_number = PhoneNumber::new();
+    it_number.set_country_code(39);
+    it_number.set_national_number(236618300);
+    it_number.set_italian_leading_zero(true);
+    assert_eq!("8~10 39 02 3661 8300", phone_util.format_out_of_country_calling_number(&it_number, RegionCode::uz()).unwrap());
+    assert_eq!("810390236618300", phone_util.format_as_dialed_from(&it_number, RegionCode::uz()).unwrap());
 
-    // Testing that if the raw input doesn't exist, it is formatted using FormatOutOfCountryCallingNumber.
-    alpha_numeric_number.clear_raw_input();
-    let formatted_number = phone_util.format_out_of_country_keeping_alpha_chars(&alpha_numeric_number, RegionCode::de()).unwrap();
-    assert_eq!("00 1 800 749 3524", formatted_number);
+    let mut us_number = PhoneNumber::new();
+    us_number.set_country_code(1);
+    us_number.set_national_number(6502530000);
+    assert_eq!("16502530000", phone_util.format_as_dialed_from(&us_number, RegionCode::bs()).unwrap());
+}
+
+#[test]
+fn format_out_of_country_calling_number_reporting() {
+    let phone_util = get_phone_util();
+    let mut test_number = PhoneNumber::new();
+
+    test_number.set_country_code(49);
+    test_number.set_national_number(1234);
+    let (formatted, domestic) = phone_util
+        .format_out_of_country_calling_number_reporting(&test_number, RegionCode::de())
+        .unwrap();
+    assert_eq!(("1234", true), (formatted.as_ref(), domestic));
+    let (formatted, domestic) = phone_util
+        .format_out_of_country_calling_number_reporting(&test_number, RegionCode::gb())
+        .unwrap();
+    assert_eq!(("00 49 1234", false), (formatted.as_ref(), domestic));
+
+    // Звонки внутри NANPA тоже считаются внутренними.
+    test_number.set_country_code(1);
+    test_number.set_national_number(6502530000);
+    let (formatted, domestic) = phone_util
+        .format_out_of_country_calling_number_reporting(&test_number, RegionCode::bs())
+        .unwrap();
+    assert_eq!(("1 650 253 0000", true), (formatted.as_ref(), domestic));
+
+    // Неизвестный регион вызова: международный формат.
+    let (formatted, domestic) = phone_util
+        .format_out_of_country_calling_number_reporting(&test_number, RegionCode::get_unknown())
+        .unwrap();
+    assert_eq!(("+1 650 253 0000", false), (formatted.as_ref(), domestic));
+}
+
+#[test]
+fn format_out_of_country_keeping_alpha_chars() {
+    let phone_util = get_phone_util();
+    let mut alpha_numeric_number = phone_util.parse_and_keep_raw_input("1800 six-flag", RegionCode::us()).unwrap();
+    
+    let formatted_number = phone_util.format_out_of_country_keeping_alpha_chars(&alpha_numeric_number, RegionCode::au()).unwrap();
+    assert_eq!("0011 1 800 SIX-FLAG", formatted_number);
+
+    // Formatting from within the NANPA region.
+    let formatted_number = phone_util.format_out_of_country_keeping_alpha_chars(&alpha_numeric_number, RegionCode::us()).unwrap();
+    assert_eq!("1 800 SIX-FLAG", formatted_number);
+
+    // Testing a number with extension.
+    let alpha_numeric_number_with_extn = phone_util.parse_and_keep_raw_input("800 SIX-flag ext. 1234", RegionCode::us()).unwrap();
+    let formatted_number = phone_util.format_out_of_country_keeping_alpha_chars(&alpha_numeric_number_with_extn, RegionCode::au()).unwrap();
+    assert_eq!("0011 1 800 SIX-FLAG extn. 1234", formatted_number);
+
+    // Testing that if the raw input doesn't exist, it is formatted using FormatOutOfCountryCallingNumber.
+    alpha_numeric_number.clear_raw_input();
+    let formatted_number = phone_util.format_out_of_country_keeping_alpha_chars(&alpha_numeric_number, RegionCode::de()).unwrap();
+    assert_eq!("00 1 800 749 3524", formatted_number);
 }
 
 #[test]
@@ -637,6 +1342,71 @@ fn format_with_carrier_code() {
     assert_eq!("12345", formatted);
 }
 
+#[test]
+fn format_with_carrier_preselect() {
+    let phone_util = get_phone_util();
+
+    let mut ar_number = PhoneNumber::new();
+    ar_number.set_country_code(54);
+    ar_number.set_national_number(91234125678);
+    assert_eq!("01234 12-5678", phone_util.format(&ar_number, PhoneNumberFormat::National).unwrap());
+    // Код предвыбора оператора заменяет национальный префикс, а не добавляется после него.
+    assert_eq!("1051234 12-5678", phone_util.format_with_carrier_preselect(&ar_number, "105").unwrap());
+    assert_eq!("1234 12-5678", phone_util.format_with_carrier_preselect(&ar_number, "").unwrap());
+    // Код вставляется как есть, "$" не считается ссылкой на группу.
+    assert_eq!("$1$21234 12-5678", phone_util.format_with_carrier_preselect(&ar_number, "$1$2").unwrap());
+
+    // Префикс в скобках: "(0$1)" в Великобритании.
+    let mut gb_number = PhoneNumber::new();
+    gb_number.set_country_code(44);
+    gb_number.set_national_number(2070313000);
+    gb_number.set_extension("123".to_owned());
+    assert_eq!("(020) 7031 3000 ext. 123", phone_util.format(&gb_number, PhoneNumberFormat::National).unwrap());
+    assert_eq!("(1670 20) 7031 3000 ext. 123", phone_util.format_with_carrier_preselect(&gb_number, "1670 ").unwrap());
+
+    // Национальный префикс США "1" не используется в национальном формате.
+    let mut us_number = PhoneNumber::new();
+    us_number.set_country_code(1);
+    us_number.set_national_number(4241231234);
+    assert_eq!("424 123 1234", phone_util.format_with_carrier_preselect(&us_number, "15").unwrap());
+
+    // Номер без подходящего шаблона остаётся цифрами, но добавочный номер сохраняется.
+    let mut unformattable_number = PhoneNumber::new();
+    unformattable_number.set_country_code(1);
+    unformattable_number.set_national_number(12345);
+    unformattable_number.set_extension("678".to_owned());
+    assert_eq!("12345 extn. 678", phone_util.format(&unformattable_number, PhoneNumberFormat::National).unwrap());
+    assert_eq!("12345 extn. 678", phone_util.format_with_carrier_preselect(&unformattable_number, "15").unwrap());
+
+    let mut invalid_number = PhoneNumber::new();
+    invalid_number.set_country_code(0);
+    invalid_number.set_national_number(12345);
+    assert_eq!("12345", phone_util.format_with_carrier_preselect(&invalid_number, "89").unwrap());
+}
+
+#[test]
+fn format_with_carrier_preselect_without_leading_national_prefix() {
+    let mut metadata = PhoneMetadataCollection::parse_from_bytes(&TEST_METADATA).unwrap();
+    let nz_metadata = metadata.metadata.iter_mut().find(|metadata| metadata.id() == RegionCode::nz()).unwrap();
+    nz_metadata.number_format[0].set_national_prefix_formatting_rule("$1/0".to_owned());
+    let phone_util = PhoneNumberUtilInternal::new_for_metadata(metadata);
+
+    let mut nz_number = PhoneNumber::new();
+    nz_number.set_country_code(64);
+    nz_number.set_national_number(33316005);
+    assert_eq!("3/0-331 6005", phone_util.format(&nz_number, PhoneNumberFormat::National).unwrap());
+    // Национальный префикс стоит после первой группы, код предвыбора встаёт на его место.
+    assert_eq!("3/15-331 6005", phone_util.format_with_carrier_preselect(&nz_number, "15").unwrap());
+    assert_eq!("3/-331 6005", phone_util.format_with_carrier_preselect(&nz_number, "").unwrap());
+
+    // Без национального префикса в правиле номер форматируется как обычно.
+    let mut metadata = PhoneMetadataCollection::parse_from_bytes(&TEST_METADATA).unwrap();
+    let nz_metadata = metadata.metadata.iter_mut().find(|metadata| metadata.id() == RegionCode::nz()).unwrap();
+    nz_metadata.number_format[0].set_national_prefix_formatting_rule("($1)".to_owned());
+    let phone_util = PhoneNumberUtilInternal::new_for_metadata(metadata);
+    assert_eq!("(3)-331 6005", phone_util.format_with_carrier_preselect(&nz_number, "15").unwrap());
+}
+
 // Весь код, который написан - корректен и компилируется
 #[test]
 fn format_with_preferred_carrier_code() {
@@ -945,6 +1715,44 @@ fn format_by_pattern() {
     assert_eq!("+44 20 1234 5678", formatted_number);
 }
 
+#[test]
+fn format_by_pattern_with_country_code() {
+    let phone_util = get_phone_util();
+    let mut test_number = PhoneNumber::new();
+    test_number.set_country_code(1);
+    test_number.set_national_number(6502530000);
+
+    // $CC заменяется кодом страны и в формате, и в правиле национального префикса.
+    let mut number_format = NumberFormat::new();
+    number_format.set_pattern("(\\d{3})(\\d{3})(\\d{4})".to_string());
+    number_format.set_format("+$CC $1 $2-$3".to_string());
+    number_format.set_national_prefix_formatting_rule("($FG)".to_string());
+    let number_formats = vec![number_format.clone()];
+    let formatted_number = phone_util
+        .format_by_pattern(&test_number, PhoneNumberFormat::National, &number_formats)
+        .unwrap();
+    assert_eq!("+1 (650) 253-0000", formatted_number);
+
+    number_format.set_format("$1 $2-$3".to_string());
+    number_format.set_national_prefix_formatting_rule("+$CC ($FG)".to_string());
+    let number_formats = vec![number_format];
+    let formatted_number = phone_util
+        .format_by_pattern(&test_number, PhoneNumberFormat::National, &number_formats)
+        .unwrap();
+    assert_eq!("+1 (650) 253-0000", formatted_number);
+
+    test_number.set_country_code(44);
+    test_number.set_national_number(2012345678);
+    let mut number_format = NumberFormat::new();
+    number_format.set_pattern("(\\d{2})(\\d{4})(\\d{4})".to_string());
+    number_format.set_format("00$CC $1 $2 $3".to_string());
+    let number_formats = vec![number_format];
+    let formatted_number = phone_util
+        .format_by_pattern(&test_number, PhoneNumberFormat::National, &number_formats)
+        .unwrap();
+    assert_eq!("0044 20 1234 5678", formatted_number);
+}
+
 #[test]
 fn format_in_original_format() {
     let phone_util = get_phone_util();
@@ -1169,6 +1977,25 @@ fn convert_alpha_characters_in_number() {
     assert_eq!(expected_fullwidth_output, result);
 }
 
+#[test]
+fn synthesize_alpha_example() {
+    let phone_util = PhoneNumberUtil::from_metadata_bytes(&TEST_METADATA).unwrap();
+    let alpha_example = phone_util.synthesize_alpha_example(RegionCode::us()).unwrap();
+    assert_eq!("800 GJM PTW0", alpha_example);
+    // Обратное преобразование дает отформатированный пример номера.
+    let (_, example_number) = phone_util
+        .example_numbers_for_region(RegionCode::us())
+        .find(|(number_type, _)| *number_type == PhoneNumberType::TollFree)
+        .unwrap();
+    assert_eq!(
+        phone_util.format(&example_number, PhoneNumberFormat::National),
+        phone_util.convert_alpha_characters_in_number(&alpha_example)
+    );
+
+    // Нет бесплатного примера номера.
+    assert_eq!(None, phone_util.synthesize_alpha_example(RegionCode::zz()));
+}
+
 #[test]
 fn parse_and_keep_raw() {
     let phone_util = get_phone_util();
@@ -1177,12 +2004,15 @@ fn parse_and_keep_raw() {
     alpha_numeric_number.set_national_number(80074935247);
     alpha_numeric_number.set_raw_input("800 six-flags".to_string());
     alpha_numeric_number.set_country_code_source(CountryCodeSource::FROM_DEFAULT_COUNTRY);
+    // Вместе с исходной строкой сохраняются её группы цифр.
+    alpha_numeric_number.raw_input_groups = vec!["800".to_owned()];
 
     let test_number = phone_util.parse_and_keep_raw_input("800 six-flags", RegionCode::us()).unwrap();
     assert_eq!(alpha_numeric_number, test_number);
     
     alpha_numeric_number.set_national_number(8007493524);
     alpha_numeric_number.set_raw_input("1800 six-flag".to_string());
+    alpha_numeric_number.raw_input_groups = vec!["1800".to_owned()];
     alpha_numeric_number.set_country_code_source(CountryCodeSource::FROM_NUMBER_WITHOUT_PLUS_SIGN);
     let test_number = phone_util.parse_and_keep_raw_input("1800 six-flag", RegionCode::us()).unwrap();
     assert_eq!(alpha_numeric_number, test_number);
@@ -1193,6 +2023,7 @@ fn parse_and_keep_raw() {
     assert_eq!(alpha_numeric_number, test_number);
 
     alpha_numeric_number.set_raw_input("001800 six-flag".to_string());
+    alpha_numeric_number.raw_input_groups = vec!["001800".to_owned()];
     alpha_numeric_number.set_country_code_source(CountryCodeSource::FROM_NUMBER_WITH_IDD);
     let test_number = phone_util.parse_and_keep_raw_input("001800 six-flag", RegionCode::nz()).unwrap();
     assert_eq!(alpha_numeric_number, test_number);
@@ -1207,10 +2038,118 @@ fn parse_and_keep_raw() {
     korean_number.set_raw_input("08122123456".to_string());
     korean_number.set_country_code_source(CountryCodeSource::FROM_DEFAULT_COUNTRY);
     korean_number.set_preferred_domestic_carrier_code("81".to_string());
+    korean_number.raw_input_groups = vec!["08122123456".to_owned()];
     let test_number = phone_util.parse_and_keep_raw_input("08122123456", RegionCode::kr()).unwrap();
     assert_eq!(korean_number, test_number);
 }
 
+#[test]
+fn parse_into() {
+    let phone_util = get_phone_util();
+    let mut phone_number = PhoneNumber::new();
+    for (number_to_parse, region_code) in [
+        ("+44 20 8765 4321 ext. 1234", RegionCode::zz()),
+        ("(650) 253-0000", RegionCode::us()),
+        ("011", RegionCode::au()),
+        ("08122123456", RegionCode::kr()),
+    ] {
+        phone_util.parse_into(number_to_parse, region_code, &mut phone_number).unwrap();
+        assert_eq!(phone_util.parse(number_to_parse, region_code).unwrap(), phone_number);
+    }
+    // Прежнее содержимое, в том числе добавочный номер, не сохраняется.
+    phone_util.parse_into("+44 20 8765 4321 ext. 1234", RegionCode::zz(), &mut phone_number).unwrap();
+    phone_util.parse_into("+1 650 253 0000", RegionCode::zz(), &mut phone_number).unwrap();
+    assert!(phone_number.extension.is_none());
+    phone_number.set_raw_input("+1 650 253 0000".to_owned());
+    phone_number.set_preferred_domestic_carrier_code("15".to_owned());
+    phone_number.set_italian_leading_zero(true);
+    phone_util.parse_into("+1 650 253 0000", RegionCode::zz(), &mut phone_number).unwrap();
+    assert_eq!(phone_util.parse("+1 650 253 0000", RegionCode::zz()).unwrap(), phone_number);
+    // При ошибке номер остаётся пустым.
+    assert!(phone_util.parse_into("123 456 7890", RegionCode::cs(), &mut phone_number).is_err());
+    assert_eq!(PhoneNumber::new(), phone_number);
+}
+
+#[test]
+fn parse_rfc3966_with_isub() {
+    let phone_util = get_phone_util();
+    let mut us_number = PhoneNumber::new();
+    us_number.set_country_code(1);
+    us_number.set_national_number(6502530000);
+
+    let (number, isub) = phone_util.parse_rfc3966_with_isub("tel:+1-650-253-0000;isub=1234").unwrap();
+    assert_eq!(us_number, number);
+    assert_eq!(Some("1234"), isub.as_deref());
+
+    // За подадресом может следовать phone-context.
+    let (number, isub) = phone_util
+        .parse_rfc3966_with_isub("tel:253-0000;isub=12-34;phone-context=+1-650")
+        .unwrap();
+    assert_eq!(us_number, number);
+    assert_eq!(Some("12-34"), isub.as_deref());
+
+    let (number, isub) = phone_util.parse_rfc3966_with_isub("tel:+1-650-253-0000").unwrap();
+    assert_eq!(us_number, number);
+    assert_eq!(None, isub);
+
+    // Без региона по умолчанию локальный номер не разбирается.
+    assert!(phone_util.parse_rfc3966_with_isub("tel:253-0000;isub=1234").is_err());
+}
+
+#[test]
+fn parse_reporting_carrier() {
+    let phone_util = get_phone_util();
+    let mut korean_number = PhoneNumber::new();
+    korean_number.set_country_code(82);
+    korean_number.set_national_number(22123456);
+    let (test_number, carrier_code) = phone_util.parse_reporting_carrier("08122123456", RegionCode::kr()).unwrap();
+    assert_eq!(korean_number, test_number);
+    assert_eq!(Some("81"), carrier_code.as_deref());
+    // Номер совпадает с результатом обычного разбора.
+    assert_eq!(phone_util.parse("08122123456", RegionCode::kr()).unwrap(), test_number);
+
+    // Без кода оператора.
+    let (test_number, carrier_code) = phone_util.parse_reporting_carrier("+1 650 253 0000 ext 12", RegionCode::nz()).unwrap();
+    assert_eq!(phone_util.parse("+1 650 253 0000 ext 12", RegionCode::nz()).unwrap(), test_number);
+    assert_eq!(None, carrier_code);
+
+    assert!(phone_util.parse_reporting_carrier("123 456 7890", RegionCode::cs()).is_err());
+}
+
+#[test]
+fn regex_cache_stats() {
+    let cache = RegexCache::with_capacity(2);
+    assert_eq!(CacheStats::default(), cache.stats());
+    cache.get_regex("\\d").unwrap();
+    cache.get_regex("\\d").unwrap();
+    cache.get_regex("a").unwrap();
+    assert_eq!(CacheStats { hits: 1, misses: 2, size: 2 }, cache.stats());
+    // Кеш заполнен, поэтому одно из регулярных выражений вытесняется.
+    cache.get_regex("b").unwrap();
+    assert_eq!(CacheStats { hits: 1, misses: 3, size: 2 }, cache.stats());
+    assert!(cache.get_regex("(").is_err());
+    assert_eq!(2, cache.stats().size);
+
+    // С нулевой ёмкостью ничего не кешируется.
+    let cache = RegexCache::with_capacity(0);
+    cache.get_regex("\\d").unwrap();
+    cache.get_regex("\\d").unwrap();
+    assert_eq!(CacheStats { hits: 0, misses: 2, size: 0 }, cache.stats());
+
+    let phone_util = PhoneNumberUtil::new_with_options(
+        PhoneNumberUtilOptions::new().regex_cache_capacity(8),
+    );
+    for _ in 0..3 {
+        let number = phone_util.parse("+44 20 8765 4321", RegionCode::zz()).unwrap();
+        assert!(phone_util.is_valid_number(&number));
+    }
+    let stats = phone_util.regex_cache_stats();
+    assert!(stats.hits > 0);
+    assert!(stats.misses > 0);
+    // Ограничение действует на каждый из двух кешей.
+    assert!(stats.size <= 16);
+}
+
 #[test]
 fn parse_italian_leading_zeros() {
     let phone_util = get_phone_util();
@@ -1360,6 +2299,28 @@ fn format_out_of_country_with_preferred_intl_prefix() {
     assert_eq!("8~10 39 02 3661 8300", formatted_number);
 }
 
+#[test]
+fn expand_dialing_waits() {
+    let phone_util = get_phone_util();
+    let mut test_number = PhoneNumber::new();
+    test_number.set_country_code(39);
+    test_number.set_national_number(236618300);
+    test_number.set_italian_leading_zero(true);
+
+    let formatted_number = phone_util
+        .format_out_of_country_calling_number(&test_number, RegionCode::uz())
+        .unwrap();
+    // Ожидание "~" заменяется на паузу для номеронабирателя.
+    assert_eq!("8,10 39 02 3661 8300", phone_util.expand_dialing_waits(&formatted_number, ","));
+    // Пустая пауза просто убирает ожидание для отображения.
+    assert_eq!("810 39 02 3661 8300", phone_util.expand_dialing_waits(&formatted_number, ""));
+    // Многобайтовые токены паузы тоже поддерживаются.
+    assert_eq!("8\u{23F8}10 39 02 3661 8300", phone_util.expand_dialing_waits(&formatted_number, "\u{23F8}"));
+
+    // Номера без ожидания не меняются.
+    assert_eq!("+39 02 3661 8300", phone_util.expand_dialing_waits("+39 02 3661 8300", ","));
+}
+
 #[test]
 fn format_e164_number() {
     let phone_util = get_phone_util();
@@ -1395,6 +2356,179 @@ fn format_number_with_extension() {
     assert_eq!("650 253 0000 extn. 4567", phone_util.format(&us_number_with_extension, PhoneNumberFormat::National).unwrap());
 }
 
+#[test]
+fn format_with_extension_span() {
+    let phone_util = get_phone_util();
+    let mut nz_number = PhoneNumber::new();
+    nz_number.set_country_code(64);
+    nz_number.set_national_number(33316005);
+    nz_number.set_extension("1234".to_owned());
+
+    let (formatted, span) = phone_util.format_with_extension_span(&nz_number, PhoneNumberFormat::National).unwrap();
+    assert_eq!("03-331 6005 ext. 1234", formatted);
+    assert_eq!(" ext. 1234", &formatted[span.unwrap()]);
+    let (formatted, span) = phone_util.format_with_extension_span(&nz_number, PhoneNumberFormat::International).unwrap();
+    assert_eq!("+64 3-331 6005 ext. 1234", formatted);
+    assert_eq!(" ext. 1234", &formatted[span.unwrap()]);
+    let (formatted, span) = phone_util.format_with_extension_span(&nz_number, PhoneNumberFormat::RFC3966).unwrap();
+    assert_eq!("tel:+64-3-331-6005;ext=1234", formatted);
+    assert_eq!(";ext=1234", &formatted[span.unwrap()]);
+    // В E164 добавочный номер не выводится.
+    let (formatted, span) = phone_util.format_with_extension_span(&nz_number, PhoneNumberFormat::E164).unwrap();
+    assert_eq!("+6433316005", formatted);
+    assert_eq!(None, span);
+
+    // Предпочтительный префикс добавочного номера из метаданных.
+    let mut us_number = PhoneNumber::new();
+    us_number.set_country_code(1);
+    us_number.set_national_number(6502530000);
+    us_number.set_extension("4567".to_owned());
+    let (formatted, span) = phone_util.format_with_extension_span(&us_number, PhoneNumberFormat::National).unwrap();
+    assert_eq!("650 253 0000 extn. 4567", formatted);
+    assert_eq!(Some(12..23), span);
+
+    // Без добавочного номера диапазона нет.
+    us_number.clear_extension();
+    let (formatted, span) = phone_util.format_with_extension_span(&us_number, PhoneNumberFormat::National).unwrap();
+    assert_eq!("650 253 0000", formatted);
+    assert_eq!(None, span);
+}
+
+#[test]
+fn format_csv_safe() {
+    let phone_util = PhoneNumberUtil::from_metadata_bytes(&TEST_METADATA).unwrap();
+    let mut it_number = PhoneNumber::new();
+    it_number.set_country_code(39);
+    it_number.set_national_number(236618300);
+    it_number.set_italian_leading_zero(true);
+
+    assert_eq!(
+        "=\"02 3661 8300\"",
+        phone_util.format_csv_safe(&it_number, PhoneNumberFormat::National, CsvSafeStyle::FormulaQuote)
+    );
+    assert_eq!(
+        "\t02 3661 8300",
+        phone_util.format_csv_safe(&it_number, PhoneNumberFormat::National, CsvSafeStyle::LeadingTab)
+    );
+    // Знак "+" в E164 тоже сохраняется.
+    assert_eq!(
+        "=\"+390236618300\"",
+        phone_util.format_csv_safe(&it_number, PhoneNumberFormat::E164, CsvSafeStyle::FormulaQuote)
+    );
+    assert_eq!(
+        "\t+390236618300",
+        phone_util.format_csv_safe(&it_number, PhoneNumberFormat::E164, CsvSafeStyle::LeadingTab)
+    );
+}
+
+#[test]
+fn format_national_padded() {
+    let phone_util = get_phone_util();
+    let mut number = PhoneNumber::new();
+
+    // Номер Германии из 8 цифр при максимальной длине 11.
+    number.set_country_code(49);
+    number.set_national_number(30123456);
+    assert_eq!("030/123456...", phone_util.format_national_padded(&number, '.').unwrap());
+
+    // Номер максимальной длины не дополняется.
+    number.set_country_code(1);
+    number.set_national_number(6502530000);
+    assert_eq!("650 253 0000", phone_util.format_national_padded(&number, ' ').unwrap());
+    number.set_national_number(253000);
+    assert_eq!("253000****", phone_util.format_national_padded(&number, '*').unwrap());
+
+    // Дополнение вставляется перед добавочным номером.
+    number.set_national_number(253000);
+    number.set_extension("1234".to_owned());
+    assert_eq!("253000**** extn. 1234", phone_util.format_national_padded(&number, '*').unwrap());
+
+    // Неизвестный код страны не дополняется.
+    number.clear();
+    number.set_country_code(999);
+    number.set_national_number(1234);
+    assert_eq!("1234", phone_util.format_national_padded(&number, '*').unwrap());
+}
+
+#[test]
+fn format_split_extension() {
+    let phone_util = get_phone_util();
+    let mut nz_number = PhoneNumber::new();
+    nz_number.set_country_code(64);
+    nz_number.set_national_number(33316005);
+    nz_number.set_extension("1234".to_owned());
+
+    for (number_format, expected) in [
+        (PhoneNumberFormat::National, "03-331 6005"),
+        (PhoneNumberFormat::International, "+64 3-331 6005"),
+        (PhoneNumberFormat::RFC3966, "tel:+64-3-331-6005"),
+        (PhoneNumberFormat::E164, "+6433316005"),
+    ] {
+        let (formatted, extension) = phone_util.format_split_extension(&nz_number, number_format).unwrap();
+        assert_eq!(expected, formatted);
+        assert_eq!(Some("1234"), extension.as_deref());
+    }
+
+    // Без добавочного номера результат совпадает с `format`.
+    nz_number.clear_extension();
+    let (formatted, extension) = phone_util.format_split_extension(&nz_number, PhoneNumberFormat::National).unwrap();
+    assert_eq!(phone_util.format(&nz_number, PhoneNumberFormat::National).unwrap(), formatted);
+    assert_eq!(None, extension);
+}
+
+#[test]
+fn format_with_format_info() {
+    let phone_util = get_phone_util();
+    let mut nz_number = PhoneNumber::new();
+    nz_number.set_country_code(64);
+    nz_number.set_national_number(33316005);
+
+    let (formatted, number_format) = phone_util.format_with_format_info(&nz_number, PhoneNumberFormat::National).unwrap();
+    assert_eq!("03-331 6005", formatted);
+    let number_format = number_format.unwrap();
+    assert_eq!("(\\d)(\\d{3})(\\d{4})", number_format.pattern());
+    assert_eq!("$1-$2 $3", number_format.format());
+    let (formatted, number_format) = phone_util.format_with_format_info(&nz_number, PhoneNumberFormat::International).unwrap();
+    assert_eq!("+64 3-331 6005", formatted);
+    assert_eq!("$1-$2 $3", number_format.unwrap().format());
+    // В E164 цифры не группируются.
+    let (formatted, number_format) = phone_util.format_with_format_info(&nz_number, PhoneNumberFormat::E164).unwrap();
+    assert_eq!("+6433316005", formatted);
+    assert!(number_format.is_none());
+
+    // Ни одно правило не подошло — номер выводится как есть.
+    nz_number.set_national_number(12);
+    let (formatted, number_format) = phone_util.format_with_format_info(&nz_number, PhoneNumberFormat::National).unwrap();
+    assert_eq!("12", formatted);
+    assert!(number_format.is_none());
+}
+
+#[test]
+fn format_with_format_info_outlives_reload() {
+    let phone_util = PhoneNumberUtil::from_metadata_bytes(&TEST_METADATA).unwrap();
+    let mut nz_number = PhoneNumber::new();
+    nz_number.set_country_code(64);
+    nz_number.set_national_number(33316005);
+
+    let (formatted, number_format) = phone_util.format_with_format_info(&nz_number, PhoneNumberFormat::National);
+    assert_eq!("03-331 6005", formatted);
+    let number_format = number_format.unwrap();
+    // Правило не копируется, а берётся из метаданных.
+    let (_, again) = phone_util.format_with_format_info(&nz_number, PhoneNumberFormat::National);
+    assert!(std::ptr::eq(number_format, again.unwrap()));
+
+    // После перезагрузки без NZ правило остаётся доступным.
+    let mut metadata = PhoneMetadataCollection::parse_from_bytes(&TEST_METADATA).unwrap();
+    metadata.metadata.retain(|metadata| metadata.id() != RegionCode::nz());
+    phone_util.reload_metadata(&metadata.write_to_bytes().unwrap()).unwrap();
+    assert_eq!("(\\d)(\\d{3})(\\d{4})", number_format.pattern());
+    assert_eq!("$1-$2 $3", number_format.format());
+    // Новые метаданные тоже закрепляются, а старое правило остаётся на месте.
+    let (formatted, _) = phone_util.format_with_format_info(&nz_number, PhoneNumberFormat::National);
+    assert_eq!("33316005", formatted);
+    assert_eq!("$1-$2 $3", number_format.format());
+}
+
 #[test]
 fn get_length_of_geographical_area_code() {
     let phone_util = get_phone_util();
@@ -1469,6 +2603,26 @@ fn get_length_of_geographical_area_code() {
     assert_eq!(0, phone_util.get_length_of_geographical_area_code(&cn_mobile).unwrap());
 }
 
+#[test]
+fn get_length_of_geographical_area_code_for_string() {
+    let phone_util = PhoneNumberUtil::from_metadata_bytes(&TEST_METADATA).unwrap();
+    // Google MTV, с кодом города "650".
+    assert_eq!(Ok(3), phone_util.get_length_of_geographical_area_code_for_string("650 253 0000", RegionCode::us()));
+    // Google London, с кодом города "20".
+    assert_eq!(Ok(2), phone_util.get_length_of_geographical_area_code_for_string("+44 20 7031 3000", RegionCode::zz()));
+    // Мобильный номер в Великобритании не имеет кода города.
+    assert_eq!(Ok(0), phone_util.get_length_of_geographical_area_code_for_string("07912 345678", RegionCode::gb()));
+    // Нераспознаваемая строка возвращает ошибку разбора.
+    assert!(matches!(
+        phone_util.get_length_of_geographical_area_code_for_string("not a number", RegionCode::us()),
+        Err(ParseError::NotANumber(_))
+    ));
+    assert_eq!(
+        Err(ParseError::InvalidCountryCode),
+        phone_util.get_length_of_geographical_area_code_for_string("650 253 0000", RegionCode::zz())
+    );
+}
+
 #[test]
 fn get_length_of_national_destination_code() {
     let phone_util = get_phone_util();
@@ -1548,6 +2702,150 @@ fn get_length_of_national_destination_code() {
     assert_eq!(3, phone_util.get_length_of_national_destination_code(&cn_mobile).unwrap());
 }
 
+#[test]
+fn get_national_destination_code_for_non_geo_number() {
+    let phone_util = get_phone_util();
+
+    // Международный бесплатный номер (+800) не привязан к региону,
+    // но NDC "1234" должен определяться так же, как и для географических номеров.
+    let mut number = PhoneNumber::new();
+    number.set_country_code(800);
+    number.set_national_number(12345678);
+    assert_eq!(RegionCode::un001(), phone_util.get_region_code_for_number(&number).unwrap());
+    assert_eq!(4, phone_util.get_length_of_national_destination_code(&number).unwrap());
+    assert_eq!("1234", phone_util.get_national_destination_code(&number).unwrap());
+
+    // Добавочный номер не влияет на NDC.
+    number.set_extension("321".to_string());
+    assert_eq!("1234", phone_util.get_national_destination_code(&number).unwrap());
+
+    // Для сравнения: географический номер Google London с NDC "20".
+    let mut gb_number = PhoneNumber::new();
+    gb_number.set_country_code(44);
+    gb_number.set_national_number(2070313000);
+    assert_eq!("20", phone_util.get_national_destination_code(&gb_number).unwrap());
+
+    // Номер без NDC возвращает пустую строку.
+    let mut ad_number = PhoneNumber::new();
+    ad_number.set_country_code(376);
+    ad_number.set_national_number(12345);
+    assert_eq!("", phone_util.get_national_destination_code(&ad_number).unwrap());
+}
+
+#[test]
+fn format_common() {
+    let phone_util = get_phone_util();
+
+    let mut number = PhoneNumber::new();
+    number.set_country_code(44);
+    number.set_national_number(2087389353);
+    number.set_extension("123".to_owned());
+    assert_eq!(
+        CommonFormats {
+            e164: "+442087389353".to_owned(),
+            international: "+44 20 8738 9353 ext. 123".to_owned(),
+            national: "(020) 8738 9353 ext. 123".to_owned(),
+            rfc3966: "tel:+44-20-8738-9353;ext=123".to_owned(),
+        },
+        phone_util.format_common(&number).unwrap()
+    );
+
+    // Результат совпадает с отдельными вызовами format, в том числе для регионов
+    // с отдельными международными форматами (AR) и без метаданных.
+    let mut numbers = Vec::new();
+    for (country_code, national_number) in [
+        (1, 6502530000),
+        (54, 91187654321),
+        (54, 1187654321),
+        (39, 236618300),
+        (800, 12345678),
+        (999, 12345678),
+    ] {
+        let mut number = PhoneNumber::new();
+        number.set_country_code(country_code);
+        number.set_national_number(national_number);
+        numbers.push(number);
+    }
+    let mut number_with_leading_zero = PhoneNumber::new();
+    number_with_leading_zero.set_country_code(39);
+    number_with_leading_zero.set_national_number(236618300);
+    number_with_leading_zero.set_italian_leading_zero(true);
+    numbers.push(number_with_leading_zero);
+    let mut unparseable_number = PhoneNumber::new();
+    unparseable_number.set_raw_input("not a number".to_owned());
+    numbers.push(unparseable_number);
+
+    for number in numbers {
+        let formats = phone_util.format_common(&number).unwrap();
+        assert_eq!(phone_util.format(&number, PhoneNumberFormat::E164).unwrap(), formats.e164);
+        assert_eq!(
+            phone_util.format(&number, PhoneNumberFormat::International).unwrap(),
+            formats.international
+        );
+        assert_eq!(phone_util.format(&number, PhoneNumberFormat::National).unwrap(), formats.national);
+        assert_eq!(phone_util.format(&number, PhoneNumberFormat::RFC3966).unwrap(), formats.rfc3966);
+    }
+}
+
+#[test]
+fn format_parts() {
+    let phone_util = get_phone_util();
+
+    // Google London: код города совпадает с NDC.
+    let mut number = PhoneNumber::new();
+    number.set_country_code(44);
+    number.set_national_number(2070313000);
+    number.set_extension("123".to_owned());
+    assert_eq!(
+        NumberParts {
+            country_code: "44".to_owned(),
+            area_code: Some("20".to_owned()),
+            ndc: Some("20".to_owned()),
+            subscriber: "70313000".to_owned(),
+            extension: Some("123".to_owned()),
+        },
+        phone_util.format_parts(&number).unwrap()
+    );
+
+    // Мобильный номер Великобритании имеет NDC, но не код города.
+    let mut number = PhoneNumber::new();
+    number.set_country_code(44);
+    number.set_national_number(7912345678);
+    assert_eq!(
+        NumberParts {
+            country_code: "44".to_owned(),
+            area_code: None,
+            ndc: Some("7912".to_owned()),
+            subscriber: "345678".to_owned(),
+            extension: None,
+        },
+        phone_util.format_parts(&number).unwrap()
+    );
+
+    // Негеографический номер.
+    let mut number = PhoneNumber::new();
+    number.set_country_code(800);
+    number.set_national_number(12345678);
+    assert_eq!(
+        NumberParts {
+            country_code: "800".to_owned(),
+            area_code: None,
+            ndc: Some("1234".to_owned()),
+            subscriber: "5678".to_owned(),
+            extension: None,
+        },
+        phone_util.format_parts(&number).unwrap()
+    );
+
+    // Номер без NDC целиком считается абонентским.
+    let mut number = PhoneNumber::new();
+    number.set_country_code(376);
+    number.set_national_number(12345);
+    let parts = phone_util.format_parts(&number).unwrap();
+    assert_eq!(None, parts.ndc);
+    assert_eq!("12345", parts.subscriber);
+}
+
 #[test]
 fn extract_possible_number() {
     let phone_util = get_phone_util();
@@ -1672,27 +2970,184 @@ fn is_valid_number_for_region() {
     assert!(phone_util.is_valid_number_for_region(&re_number, RegionCode::yt()));
     assert!(!phone_util.is_valid_number_for_region(&re_number, RegionCode::re()));
 
-    // This number is valid in both.
-    re_number.set_national_number(800123456);
-    assert!(phone_util.is_valid_number_for_region(&re_number, RegionCode::yt()));
-    assert!(phone_util.is_valid_number_for_region(&re_number, RegionCode::re()));
+    // This number is valid in both.
+    re_number.set_national_number(800123456);
+    assert!(phone_util.is_valid_number_for_region(&re_number, RegionCode::yt()));
+    assert!(phone_util.is_valid_number_for_region(&re_number, RegionCode::re()));
+
+    let mut intl_toll_free = PhoneNumber::new();
+    intl_toll_free.set_country_code(800);
+    intl_toll_free.set_national_number(12345678);
+    assert!(phone_util.is_valid_number_for_region(&intl_toll_free, RegionCode::un001()));
+    assert!(!phone_util.is_valid_number_for_region(&intl_toll_free, RegionCode::us()));
+    assert!(!phone_util.is_valid_number_for_region(&intl_toll_free, RegionCode::zz()));
+
+    let mut invalid_number = PhoneNumber::new();
+    invalid_number.set_country_code(3923);
+    invalid_number.set_national_number(2366);
+    assert!(!phone_util.is_valid_number_for_region(&invalid_number, RegionCode::zz()));
+    assert!(!phone_util.is_valid_number_for_region(&invalid_number, RegionCode::un001()));
+    
+    invalid_number.set_country_code(0);
+    assert!(!phone_util.is_valid_number_for_region(&invalid_number, RegionCode::un001()));
+    assert!(!phone_util.is_valid_number_for_region(&invalid_number, RegionCode::zz()));
+}
+
+#[test]
+fn is_valid_non_geo_number() {
+    let phone_util = get_phone_util();
+    let mut intl_toll_free = PhoneNumber::new();
+    intl_toll_free.set_country_code(800);
+    intl_toll_free.set_national_number(12345678);
+    assert!(phone_util.is_valid_non_geo_number(&intl_toll_free));
+
+    // Слишком короткий номер недействителен.
+    intl_toll_free.set_national_number(1234567);
+    assert!(!phone_util.is_valid_non_geo_number(&intl_toll_free));
+
+    // Номер географического региона не относится к негеографическим.
+    let mut us_number = PhoneNumber::new();
+    us_number.set_country_code(1);
+    us_number.set_national_number(6502530000);
+    assert!(!phone_util.is_valid_non_geo_number(&us_number));
+
+    let mut invalid_number = PhoneNumber::new();
+    invalid_number.set_country_code(3923);
+    invalid_number.set_national_number(2366);
+    assert!(!phone_util.is_valid_non_geo_number(&invalid_number));
+    invalid_number.set_country_code(0);
+    assert!(!phone_util.is_valid_non_geo_number(&invalid_number));
+}
+
+#[test]
+fn validation_with_partial_metadata() {
+    // Метаданные только для части регионов: номера других кодов стран
+    // должны быть невалидными, а не вызывать панику.
+    let mut metadata = PhoneMetadataCollection::parse_from_bytes(&TEST_METADATA).unwrap();
+    metadata.metadata.retain(|m| matches!(m.id(), "US" | "GB"));
+    let phone_util = PhoneNumberUtilInternal::new_for_metadata(metadata);
+
+    let mut us_number = PhoneNumber::new();
+    us_number.set_country_code(1);
+    us_number.set_national_number(6502530000);
+    assert!(phone_util.is_valid_number(&us_number).unwrap());
+    assert_eq!(PhoneNumberType::FixedLineOrMobile, phone_util.get_number_type(&us_number).unwrap());
+
+    // Номер Багамских островов разделяет код страны с US, но метаданных BS нет.
+    let mut bs_number = PhoneNumber::new();
+    bs_number.set_country_code(1);
+    bs_number.set_national_number(2423651234);
+    assert!(!phone_util.is_valid_number(&bs_number).unwrap());
+    assert_eq!(PhoneNumberType::Unknown, phone_util.get_number_type(&bs_number).unwrap());
+
+    let mut de_number = PhoneNumber::new();
+    de_number.set_country_code(49);
+    de_number.set_national_number(30123456);
+    assert!(!phone_util.is_valid_number(&de_number).unwrap());
+    assert_eq!(PhoneNumberType::Unknown, phone_util.get_number_type(&de_number).unwrap());
+    assert_eq!(
+        (PhoneNumberType::Unknown, None),
+        phone_util.get_number_type_with_descriptor(&de_number).unwrap()
+    );
+    assert!(!phone_util.is_valid_number_for_type(&de_number, PhoneNumberType::FixedLine).unwrap());
+    assert_eq!(RegionCode::zz(), phone_util.get_region_code_for_number(&de_number).unwrap());
+    assert!(!phone_util.is_possible_number(&de_number));
+
+    let mut international_toll_free = PhoneNumber::new();
+    international_toll_free.set_country_code(800);
+    international_toll_free.set_national_number(12345678);
+    assert!(!phone_util.is_valid_number(&international_toll_free).unwrap());
+}
+
+#[test]
+fn is_valid_number_for_type() {
+    let phone_util = get_phone_util();
+    let mut number = PhoneNumber::new();
+
+    // В США шаблоны фиксированных и мобильных совпадают: номер подходит для обоих типов.
+    number.set_country_code(1);
+    number.set_national_number(6502531111);
+    assert!(phone_util.is_valid_number_for_type(&number, PhoneNumberType::FixedLineOrMobile).unwrap());
+    assert!(phone_util.is_valid_number_for_type(&number, PhoneNumberType::FixedLine).unwrap());
+    assert!(phone_util.is_valid_number_for_type(&number, PhoneNumberType::Mobile).unwrap());
+    assert!(!phone_util.is_valid_number_for_type(&number, PhoneNumberType::TollFree).unwrap());
+
+    // В Великобритании мобильный номер не считается фиксированным.
+    number.set_country_code(44);
+    number.set_national_number(7912345678);
+    assert!(phone_util.is_valid_number_for_type(&number, PhoneNumberType::Mobile).unwrap());
+    assert!(phone_util.is_valid_number_for_type(&number, PhoneNumberType::FixedLineOrMobile).unwrap());
+    assert!(!phone_util.is_valid_number_for_type(&number, PhoneNumberType::FixedLine).unwrap());
+
+    number.set_national_number(8012345678);
+    assert!(phone_util.is_valid_number_for_type(&number, PhoneNumberType::TollFree).unwrap());
+    assert!(!phone_util.is_valid_number_for_type(&number, PhoneNumberType::FixedLineOrMobile).unwrap());
+
+    // Невалидный номер не подходит ни для одного типа.
+    number.set_country_code(1);
+    number.set_national_number(65025311111);
+    assert!(!phone_util.is_valid_number_for_type(&number, PhoneNumberType::FixedLineOrMobile).unwrap());
+    assert!(!phone_util.is_valid_number_for_type(&number, PhoneNumberType::Unknown).unwrap());
+}
+
+#[test]
+fn is_valid_and_type() {
+    let phone_util = get_phone_util();
+    let mut number = PhoneNumber::new();
+
+    // Номер США подходит и для мобильного, и для фиксированного типа.
+    number.set_country_code(1);
+    number.set_national_number(6502531111);
+    assert_eq!(Ok(()), phone_util.is_valid_and_type(&number, PhoneNumberType::Mobile).unwrap());
+    assert_eq!(Ok(()), phone_util.is_valid_and_type(&number, PhoneNumberType::FixedLine).unwrap());
+    assert_eq!(
+        Err(TypeValidationError::WrongType { actual: PhoneNumberType::FixedLineOrMobile }),
+        phone_util.is_valid_and_type(&number, PhoneNumberType::TollFree).unwrap()
+    );
+
+    // Фиксированный номер Великобритании не подходит для SMS.
+    number.set_country_code(44);
+    number.set_national_number(2070313000);
+    assert_eq!(Ok(()), phone_util.is_valid_and_type(&number, PhoneNumberType::FixedLineOrMobile).unwrap());
+    assert_eq!(
+        Err(TypeValidationError::WrongType { actual: PhoneNumberType::FixedLine }),
+        phone_util.is_valid_and_type(&number, PhoneNumberType::Mobile).unwrap()
+    );
+
+    // Невалидный номер.
+    number.set_country_code(1);
+    number.set_national_number(65025311111);
+    assert_eq!(
+        Err(TypeValidationError::Invalid),
+        phone_util.is_valid_and_type(&number, PhoneNumberType::Mobile).unwrap()
+    );
+}
+
+#[test]
+fn is_valid_number_with_reason() {
+    let phone_util = get_phone_util();
+    let mut number = PhoneNumber::new();
 
-    let mut intl_toll_free = PhoneNumber::new();
-    intl_toll_free.set_country_code(800);
-    intl_toll_free.set_national_number(12345678);
-    assert!(phone_util.is_valid_number_for_region(&intl_toll_free, RegionCode::un001()));
-    assert!(!phone_util.is_valid_number_for_region(&intl_toll_free, RegionCode::us()));
-    assert!(!phone_util.is_valid_number_for_region(&intl_toll_free, RegionCode::zz()));
+    number.set_country_code(1);
+    number.set_national_number(6502530000);
+    assert_eq!(Ok(()), phone_util.is_valid_number_with_reason(&number).unwrap());
 
-    let mut invalid_number = PhoneNumber::new();
-    invalid_number.set_country_code(3923);
-    invalid_number.set_national_number(2366);
-    assert!(!phone_util.is_valid_number_for_region(&invalid_number, RegionCode::zz()));
-    assert!(!phone_util.is_valid_number_for_region(&invalid_number, RegionCode::un001()));
-    
-    invalid_number.set_country_code(0);
-    assert!(!phone_util.is_valid_number_for_region(&invalid_number, RegionCode::un001()));
-    assert!(!phone_util.is_valid_number_for_region(&invalid_number, RegionCode::zz()));
+    // Номер только для местного набора невалиден, хотя его длина допустима.
+    number.set_national_number(2530000);
+    assert_eq!(Err(ValidationError::PatternMismatch), phone_util.is_valid_number_with_reason(&number).unwrap());
+    // Длина допустима, но номер не соответствует шаблону.
+    number.set_country_code(44);
+    number.set_national_number(791234567);
+    assert_eq!(Err(ValidationError::PatternMismatch), phone_util.is_valid_number_with_reason(&number).unwrap());
+    number.set_country_code(1);
+
+    // Ошибки длины совпадают с is_possible_number_with_reason.
+    number.set_national_number(253000);
+    assert_eq!(Err(ValidationError::TooShort { length: 6 }), phone_util.is_valid_number_with_reason(&number).unwrap());
+    number.set_national_number(65025300000);
+    assert_eq!(Err(ValidationError::TooLong { length: 11 }), phone_util.is_valid_number_with_reason(&number).unwrap());
+    number.set_country_code(999);
+    assert_eq!(Err(ValidationError::InvalidCountryCode { country_code: 999 }), phone_util.is_valid_number_with_reason(&number).unwrap());
 }
 
 #[test]
@@ -1762,8 +3217,134 @@ fn get_region_code_for_number() {
     number.set_country_code(979);
     number.set_national_number(123456789);
     assert_eq!(RegionCode::un001(), phone_util.get_region_code_for_number(&number).unwrap());
+
+    number.set_country_code(39);
+    number.set_national_number(236618300);
+    number.set_italian_leading_zero(true);
+    assert_eq!(RegionCode::it(), phone_util.get_region_code_for_number(&number).unwrap());
+}
+
+#[test]
+fn get_region_code_for_number_with_italian_leading_zero() {
+    // В настоящих метаданных код 39 общий для Италии и Ватикана, и ведущий ноль
+    // входит в начальные цифры Ватикана "06698".
+    let phone_util = &*PHONE_NUMBER_UTIL;
+    let mut number = PhoneNumber::new();
+    number.set_country_code(39);
+    number.set_italian_leading_zero(true);
+
+    number.set_national_number(236618300);
+    assert_eq!("IT", phone_util.get_region_code_for_number(&number));
+    assert_eq!(number, phone_util.parse("02 3661 8300", "IT").unwrap());
+
+    number.set_national_number(669812345);
+    assert_eq!("VA", phone_util.get_region_code_for_number(&number));
+    assert_eq!(number, phone_util.parse("+39 06 698 12345", "ZZ").unwrap());
+
+    // Без ведущего нуля номер не подходит под начальные цифры Ватикана.
+    number.clear_italian_leading_zero();
+    assert_ne!("VA", phone_util.get_region_code_for_number(&number));
+}
+
+
+#[test]
+fn classify() {
+    let phone_util = get_phone_util();
+    // Результат должен совпадать с раздельными вызовами.
+    for (country_code, national_number) in [
+        (1, 2423232345),
+        (1, 2423570000),
+        (1, 4241231234),
+        (1, 6502530000),
+        (44, 7912345678),
+        (44, 2070313000),
+        (800, 12345678),
+        (979, 123456789),
+        (39, 236618300),
+        (64, 21387835),
+        (1, 1234567890),
+    ] {
+        let mut number = PhoneNumber::new();
+        number.set_country_code(country_code);
+        number.set_national_number(national_number);
+        let region_code = phone_util.get_region_code_for_number(&number).unwrap();
+        let number_type = phone_util.get_number_type(&number).unwrap();
+        assert_eq!((region_code, number_type), phone_util.classify(&number).unwrap(), "{number:?}");
+    }
+
+    let mut number = PhoneNumber::new();
+    number.set_country_code(1);
+    number.set_national_number(2423570000);
+    assert_eq!((RegionCode::bs(), PhoneNumberType::Mobile), phone_util.classify(&number).unwrap());
+    // Общий код страны, но ни один регион не подходит.
+    number.set_national_number(123);
+    assert_eq!(RegionCode::get_unknown(), phone_util.get_region_code_for_number(&number).unwrap());
+    assert_eq!(
+        (RegionCode::get_unknown(), PhoneNumberType::Unknown),
+        phone_util.classify(&number).unwrap()
+    );
+    // Неизвестный код страны.
+    number.set_country_code(0);
+    assert_eq!(
+        (RegionCode::get_unknown(), PhoneNumberType::Unknown),
+        phone_util.classify(&number).unwrap()
+    );
+}
+
+#[test]
+fn validity_score() {
+    let phone_util = get_phone_util();
+    let score = |country_code: i32, national_number: u64| {
+        let mut number = PhoneNumber::new();
+        number.set_country_code(country_code);
+        number.set_national_number(national_number);
+        phone_util.validity_score(&number).unwrap()
+    };
+    // Валидный мобильный номер.
+    assert_eq!(1.0, score(44, 7912345678));
+    // Валидный номер США: стационарный или мобильный.
+    assert_eq!(0.9, score(1, 6502530000));
+    // Возможная длина, но номер не подходит под шаблоны.
+    assert_eq!(0.5, score(1, 7123456789));
+    // Номер, который можно набрать только локально.
+    assert_eq!(0.25, score(1, 2530000));
+    // Невозможная длина и неизвестный код страны.
+    assert_eq!(0.0, score(1, 65025300001));
+    assert_eq!(0.0, score(0, 6502530000));
+}
+
+#[test]
+fn get_types_with_local_only_lengths_for_region() {
+    let phone_util = get_phone_util();
+    assert_eq!(
+        vec![PhoneNumberType::FixedLine, PhoneNumberType::Mobile],
+        phone_util.get_types_with_local_only_lengths_for_region(RegionCode::us())
+    );
+    // В Германии только стационарные номера можно набирать без кода города.
+    assert_eq!(
+        vec![PhoneNumberType::FixedLine],
+        phone_util.get_types_with_local_only_lengths_for_region(RegionCode::de())
+    );
+    assert!(phone_util.get_types_with_local_only_lengths_for_region(RegionCode::it()).is_empty());
+    assert!(phone_util.get_types_with_local_only_lengths_for_region(RegionCode::zz()).is_empty());
 }
 
+#[test]
+fn is_possible_number_without_country_code() {
+    let phone_util = get_phone_util();
+    let mut number = PhoneNumber::new();
+    number.set_country_code(0);
+    number.set_national_number(6502530000);
+    assert!(!phone_util.is_possible_number(&number));
+    assert_eq!(
+        Err(ValidationError::InvalidCountryCode { country_code: 0 }),
+        phone_util.is_possible_number_with_reason(&number)
+    );
+    // Поле кода страны не задано вовсе.
+    let mut number = PhoneNumber::new();
+    number.set_national_number(6502530000);
+    assert!(!phone_util.is_possible_number(&number));
+}
 
 #[test]
 fn is_possible_number() {
@@ -1939,14 +3520,14 @@ fn is_possible_number_with_reason() {
     assert_eq!(Ok(NumberLengthType::IsPossibleLocalOnly), phone_util.is_possible_number_with_reason(&number));
     
     number.set_country_code(0);
-    assert_eq!(Err(ValidationError::InvalidCountryCode), phone_util.is_possible_number_with_reason(&number));
+    assert_eq!(Err(ValidationError::InvalidCountryCode { country_code: 0 }), phone_util.is_possible_number_with_reason(&number));
 
     number.set_country_code(1);
     number.set_national_number(253000);
-    assert_eq!(Err(ValidationError::TooShort), phone_util.is_possible_number_with_reason(&number));
+    assert_eq!(Err(ValidationError::TooShort { length: 6 }), phone_util.is_possible_number_with_reason(&number));
 
     number.set_national_number(65025300000);
-    assert_eq!(Err(ValidationError::TooLong), phone_util.is_possible_number_with_reason(&number));
+    assert_eq!(Err(ValidationError::TooLong { length: 11 }), phone_util.is_possible_number_with_reason(&number));
 
     number.set_country_code(44);
     number.set_national_number(2070310000);
@@ -1962,7 +3543,7 @@ fn is_possible_number_with_reason() {
 
     number.set_country_code(800);
     number.set_national_number(123456789);
-    assert_eq!(Err(ValidationError::TooLong), phone_util.is_possible_number_with_reason(&number));
+    assert_eq!(Err(ValidationError::TooLong { length: 9 }), phone_util.is_possible_number_with_reason(&number));
 }
 
 #[test]
@@ -1972,32 +3553,32 @@ fn is_possible_number_for_type_with_reason() {
     ar_number.set_country_code(54);
 
     ar_number.set_national_number(12345);
-    assert_eq!(Err(ValidationError::TooShort), phone_util.is_possible_number_for_type_with_reason(&ar_number, PhoneNumberType::Unknown));
-    assert_eq!(Err(ValidationError::TooShort), phone_util.is_possible_number_for_type_with_reason(&ar_number, PhoneNumberType::FixedLine));
+    assert_eq!(Err(ValidationError::TooShort { length: 5 }), phone_util.is_possible_number_for_type_with_reason(&ar_number, PhoneNumberType::Unknown));
+    assert_eq!(Err(ValidationError::TooShort { length: 5 }), phone_util.is_possible_number_for_type_with_reason(&ar_number, PhoneNumberType::FixedLine));
 
     ar_number.set_national_number(123456);
     assert_eq!(Ok(NumberLengthType::IsPossible), phone_util.is_possible_number_for_type_with_reason(&ar_number, PhoneNumberType::Unknown));
     assert_eq!(Ok(NumberLengthType::IsPossible), phone_util.is_possible_number_for_type_with_reason(&ar_number, PhoneNumberType::FixedLine));
-    assert_eq!(Err(ValidationError::TooShort), phone_util.is_possible_number_for_type_with_reason(&ar_number, PhoneNumberType::Mobile));
-    assert_eq!(Err(ValidationError::TooShort), phone_util.is_possible_number_for_type_with_reason(&ar_number, PhoneNumberType::TollFree));
+    assert_eq!(Err(ValidationError::TooShort { length: 6 }), phone_util.is_possible_number_for_type_with_reason(&ar_number, PhoneNumberType::Mobile));
+    assert_eq!(Err(ValidationError::TooShort { length: 6 }), phone_util.is_possible_number_for_type_with_reason(&ar_number, PhoneNumberType::TollFree));
 
     ar_number.set_national_number(12345678901);
     assert_eq!(Ok(NumberLengthType::IsPossible), phone_util.is_possible_number_for_type_with_reason(&ar_number, PhoneNumberType::Unknown));
-    assert_eq!(Err(ValidationError::TooLong), phone_util.is_possible_number_for_type_with_reason(&ar_number, PhoneNumberType::FixedLine));
+    assert_eq!(Err(ValidationError::TooLong { length: 11 }), phone_util.is_possible_number_for_type_with_reason(&ar_number, PhoneNumberType::FixedLine));
     assert_eq!(Ok(NumberLengthType::IsPossible), phone_util.is_possible_number_for_type_with_reason(&ar_number, PhoneNumberType::Mobile));
-    assert_eq!(Err(ValidationError::TooLong), phone_util.is_possible_number_for_type_with_reason(&ar_number, PhoneNumberType::TollFree));
+    assert_eq!(Err(ValidationError::TooLong { length: 11 }), phone_util.is_possible_number_for_type_with_reason(&ar_number, PhoneNumberType::TollFree));
     
     let mut de_number = PhoneNumber::new();
     de_number.set_country_code(49);
     de_number.set_national_number(12);
     assert_eq!(Ok(NumberLengthType::IsPossibleLocalOnly), phone_util.is_possible_number_for_type_with_reason(&de_number, PhoneNumberType::Unknown));
     assert_eq!(Ok(NumberLengthType::IsPossibleLocalOnly), phone_util.is_possible_number_for_type_with_reason(&de_number, PhoneNumberType::FixedLine));
-    assert_eq!(Err(ValidationError::TooShort), phone_util.is_possible_number_for_type_with_reason(&de_number, PhoneNumberType::Mobile));
+    assert_eq!(Err(ValidationError::TooShort { length: 2 }), phone_util.is_possible_number_for_type_with_reason(&de_number, PhoneNumberType::Mobile));
 
     let mut br_number = PhoneNumber::new();
     br_number.set_country_code(55);
     br_number.set_national_number(12345678);
-    assert_eq!(Err(ValidationError::InvalidLength), phone_util.is_possible_number_for_type_with_reason(&br_number, PhoneNumberType::Mobile));
+    assert_eq!(Err(ValidationError::InvalidLength { length: 8 }), phone_util.is_possible_number_for_type_with_reason(&br_number, PhoneNumberType::Mobile));
     assert_eq!(Ok(NumberLengthType::IsPossibleLocalOnly), phone_util.is_possible_number_for_type_with_reason(&br_number, PhoneNumberType::FixedLineOrMobile));
 }
 
@@ -2009,24 +3590,24 @@ fn is_possible_number_for_type_with_reason_different_type_lengths() {
     number.set_country_code(54);
     number.set_national_number(12345);
 
-    assert_eq!(Err(ValidationError::TooShort), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Unknown));
-    assert_eq!(Err(ValidationError::TooShort), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLine));
+    assert_eq!(Err(ValidationError::TooShort { length: 5 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Unknown));
+    assert_eq!(Err(ValidationError::TooShort { length: 5 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLine));
 
     // 6-значные номера подходят для стационарных телефонов.
     number.set_national_number(123456);
     assert_eq!(Ok(NumberLengthType::IsPossible), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Unknown));
     assert_eq!(Ok(NumberLengthType::IsPossible), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLine));
     // Но слишком коротки для мобильных.
-    assert_eq!(Err(ValidationError::TooShort), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Mobile));
+    assert_eq!(Err(ValidationError::TooShort { length: 6 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Mobile));
     // И слишком коротки для бесплатных номеров.
-    assert_eq!(Err(ValidationError::TooShort), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::TollFree));
+    assert_eq!(Err(ValidationError::TooShort { length: 6 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::TollFree));
 
     // То же самое касается 9-значных номеров.
     number.set_national_number(123456789);
     assert_eq!(Ok(NumberLengthType::IsPossible), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Unknown));
     assert_eq!(Ok(NumberLengthType::IsPossible), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLine));
-    assert_eq!(Err(ValidationError::TooShort), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Mobile));
-    assert_eq!(Err(ValidationError::TooShort), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::TollFree));
+    assert_eq!(Err(ValidationError::TooShort { length: 9 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Mobile));
+    assert_eq!(Err(ValidationError::TooShort { length: 9 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::TollFree));
 
     // 10-значные номера возможны для всех типов.
     number.set_national_number(1234567890);
@@ -2039,9 +3620,9 @@ fn is_possible_number_for_type_with_reason_different_type_lengths() {
     // с которой начинаются все мобильные номера и которая была бы необходима для действительного мобильного номера.
     number.set_national_number(12345678901);
     assert_eq!(Ok(NumberLengthType::IsPossible), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Unknown));
-    assert_eq!(Err(ValidationError::TooLong), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLine));
+    assert_eq!(Err(ValidationError::TooLong { length: 11 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLine));
     assert_eq!(Ok(NumberLengthType::IsPossible), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Mobile));
-    assert_eq!(Err(ValidationError::TooLong), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::TollFree));
+    assert_eq!(Err(ValidationError::TooLong { length: 11 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::TollFree));
 }
 
 #[test]
@@ -2054,7 +3635,7 @@ fn is_possible_number_for_type_with_reason_local_only() {
     assert_eq!(Ok(NumberLengthType::IsPossibleLocalOnly), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Unknown));
     assert_eq!(Ok(NumberLengthType::IsPossibleLocalOnly), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLine));
     // Мобильные номера должны состоять из 10 или 11 цифр, и для них нет только местных длин.
-    assert_eq!(Err(ValidationError::TooShort), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Mobile));
+    assert_eq!(Err(ValidationError::TooShort { length: 2 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Mobile));
 }
 
 #[test]
@@ -2081,28 +3662,28 @@ fn is_possible_number_for_type_with_reason_number_type_not_supported_for_region(
     // В этом регионе вообще *нет* мобильных номеров, поэтому мы возвращаем INVALID_LENGTH.
     number.set_country_code(55);
     number.set_national_number(12345678);
-    assert_eq!(Err(ValidationError::InvalidLength), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Mobile));
+    assert_eq!(Err(ValidationError::InvalidLength { length: 8 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Mobile));
     // Однако это соответствует длине стационарного номера.
     assert_eq!(Ok(NumberLengthType::IsPossibleLocalOnly), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLineOrMobile));
     // Этот номер слишком короткий для стационарного, а мобильных номеров не существует.
     number.set_national_number(1234567);
-    assert_eq!(Err(ValidationError::InvalidLength), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Mobile));
-    assert_eq!(Err(ValidationError::TooShort), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLineOrMobile));
-    assert_eq!(Err(ValidationError::TooShort), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLine));
+    assert_eq!(Err(ValidationError::InvalidLength { length: 7 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Mobile));
+    assert_eq!(Err(ValidationError::TooShort { length: 7 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLineOrMobile));
+    assert_eq!(Err(ValidationError::TooShort { length: 7 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLine));
     // Этот номер слишком короткий для мобильного, а стационарных номеров не существует.
     number.set_country_code(882);
     number.set_national_number(1234567);
-    assert_eq!(Err(ValidationError::TooShort), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Mobile));
-    assert_eq!(Err(ValidationError::TooShort), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLineOrMobile));
-    assert_eq!(Err(ValidationError::InvalidLength), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLine));
+    assert_eq!(Err(ValidationError::TooShort { length: 7 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Mobile));
+    assert_eq!(Err(ValidationError::TooShort { length: 7 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLineOrMobile));
+    assert_eq!(Err(ValidationError::InvalidLength { length: 7 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLine));
 
     // Для этого кода страны вообще *нет* ни стационарных, ни мобильных номеров,
     // поэтому мы возвращаем INVALID_LENGTH.
     number.set_country_code(979);
     number.set_national_number(123456789);
-    assert_eq!(Err(ValidationError::InvalidLength), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Mobile));
-    assert_eq!(Err(ValidationError::InvalidLength), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLine));
-    assert_eq!(Err(ValidationError::InvalidLength), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLineOrMobile));
+    assert_eq!(Err(ValidationError::InvalidLength { length: 9 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Mobile));
+    assert_eq!(Err(ValidationError::InvalidLength { length: 9 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLine));
+    assert_eq!(Err(ValidationError::InvalidLength { length: 9 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLineOrMobile));
     assert_eq!(Ok(NumberLengthType::IsPossible), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::PremiumRate));
 }
 
@@ -2114,28 +3695,28 @@ fn is_possible_number_for_type_with_reason_fixed_line_or_mobile() {
     // возможным длинам для мобильных *или* стационарных номеров.
     number.set_country_code(290);
     number.set_national_number(1234);
-    assert_eq!(Err(ValidationError::TooShort), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLine));
+    assert_eq!(Err(ValidationError::TooShort { length: 4 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLine));
     assert_eq!(Ok(NumberLengthType::IsPossible), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Mobile));
     assert_eq!(Ok(NumberLengthType::IsPossible), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLineOrMobile));
 
     number.set_national_number(12345);
-    assert_eq!(Err(ValidationError::TooShort), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLine));
-    assert_eq!(Err(ValidationError::TooLong), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Mobile));
-    assert_eq!(Err(ValidationError::InvalidLength), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLineOrMobile));
+    assert_eq!(Err(ValidationError::TooShort { length: 5 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLine));
+    assert_eq!(Err(ValidationError::TooLong { length: 5 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Mobile));
+    assert_eq!(Err(ValidationError::InvalidLength { length: 5 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLineOrMobile));
 
     number.set_national_number(123456);
     assert_eq!(Ok(NumberLengthType::IsPossible), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLine));
-    assert_eq!(Err(ValidationError::TooLong), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Mobile));
+    assert_eq!(Err(ValidationError::TooLong { length: 6 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Mobile));
     assert_eq!(Ok(NumberLengthType::IsPossible), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLineOrMobile));
 
     number.set_national_number(1234567);
-    assert_eq!(Err(ValidationError::TooLong), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLine));
-    assert_eq!(Err(ValidationError::TooLong), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Mobile));
-    assert_eq!(Err(ValidationError::TooLong), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLineOrMobile));
+    assert_eq!(Err(ValidationError::TooLong { length: 7 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLine));
+    assert_eq!(Err(ValidationError::TooLong { length: 7 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::Mobile));
+    assert_eq!(Err(ValidationError::TooLong { length: 7 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLineOrMobile));
 
     number.set_national_number(12345678);
     assert_eq!(Ok(NumberLengthType::IsPossible), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::TollFree));
-    assert_eq!(Err(ValidationError::TooLong), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLineOrMobile));
+    assert_eq!(Err(ValidationError::TooLong { length: 8 }), phone_util.is_possible_number_for_type_with_reason(&number, PhoneNumberType::FixedLineOrMobile));
 }
 
 #[test]
@@ -2157,6 +3738,27 @@ fn truncate_too_long_number() {
     assert_eq!(too_short_number_copy, too_short_number);
 }
 
+#[test]
+fn truncate_too_long_number_returning() {
+    let phone_util = PhoneNumberUtil::from_metadata_bytes(&TEST_METADATA).unwrap();
+    let valid_number = phone_util.parse("+16502530000", RegionCode::us()).unwrap();
+
+    // Возвращаются отброшенные цифры.
+    let mut too_long_number = phone_util.parse("+1650253000012", RegionCode::us()).unwrap();
+    assert_eq!(Some("12".to_owned()), phone_util.truncate_too_long_number_returning(&mut too_long_number));
+    assert_eq!(valid_number, too_long_number);
+
+    // Корректный номер не обрезается.
+    let mut valid_number_copy = valid_number.clone();
+    assert_eq!(None, phone_util.truncate_too_long_number_returning(&mut valid_number_copy));
+    assert_eq!(valid_number, valid_number_copy);
+
+    let mut too_short_number = phone_util.parse("+11234", RegionCode::us()).unwrap();
+    let too_short_number_copy = too_short_number.clone();
+    assert_eq!(None, phone_util.truncate_too_long_number_returning(&mut too_short_number));
+    assert_eq!(too_short_number_copy, too_short_number);
+}
+
 #[test]
 fn normalise_remove_punctuation() {
     let phone_util = get_phone_util();
@@ -2185,6 +3787,33 @@ fn normalise_other_digits() {
     // Eastern-Arabic 5 and 0
     let input = "\u{06f5}2\u{06f0}"; // "۵2۰"
     assert_eq!("520", phone_util.normalize(&input));
+
+    // Все цифры деванагари от 0 до 9.
+    let input: String = ('\u{0966}'..='\u{096F}').collect(); // "०१२३४५६७८९"
+    assert_eq!("0123456789", phone_util.normalize(&input));
+    // Бенгальские цифры 0 и 9.
+    let input = "\u{09e6}1\u{09ef}"; // "০1৯"
+    assert_eq!("019", phone_util.normalize(input));
+    // Любые другие цифры категории Nd, например тайские и монгольские.
+    let input = "\u{0e51}\u{0e52}\u{1813}"; // "๑๒᠓"
+    assert_eq!("123", phone_util.normalize(input));
+    // Цифры других письменностей сохраняются и в номерах с буквами.
+    assert_eq!("18003569377", phone_util.normalize("१-800-FLOWERS"));
+}
+
+#[test]
+fn parse_other_digits() {
+    let phone_util = get_phone_util();
+    let mut in_number = PhoneNumber::new();
+    in_number.set_country_code(1);
+    in_number.set_national_number(6502530000);
+    // Номер, полностью записанный цифрами деванагари.
+    assert_eq!(in_number, phone_util.parse("+१ ६५०-२५३-००००", RegionCode::zz()).unwrap());
+    assert_eq!(in_number, phone_util.parse("(६५०) २५३-००००", RegionCode::us()).unwrap());
+    // Бенгальские цифры.
+    assert_eq!(in_number, phone_util.parse("+১ ৬৫০ ২৫৩ ০০০০", RegionCode::zz()).unwrap());
+    // Смешанная запись.
+    assert_eq!(in_number, phone_util.parse("+1 ६५० 253 ০০০০", RegionCode::zz()).unwrap());
 }
 
 #[test]
@@ -2206,6 +3835,12 @@ fn normalise_strip_non_diallable_characters() {
         phone_util.normalize_diallable_chars_only(&input_number), 
         "Conversion did not correctly remove non-diallable characters"
     );
+
+    // Звёздочка и решётка сохраняются в сервисных номерах.
+    let public_util = PhoneNumberUtil::from_metadata_bytes(&TEST_METADATA).unwrap();
+    assert_eq!(expected_output, public_util.normalize_diallable_chars_only(input_number));
+    assert_eq!("*#06#", public_util.normalize_diallable_chars_only("*#06#"));
+    assert_eq!("*100#", public_util.normalize_diallable_chars_only(" * 100 # "));
 }
 
 #[test]
@@ -2311,6 +3946,34 @@ fn maybe_strip_extension() {
     assert_eq!(expected_extension, extension.unwrap());
 }
 
+#[test]
+fn maybe_strip_extension_with_span() {
+    let phone_util = PhoneNumberUtil::from_metadata_bytes(&TEST_METADATA).unwrap();
+    assert_eq!(
+        ("650 253 0000", Some(("123".to_owned(), 13))),
+        phone_util.maybe_strip_extension_with_span("650 253 0000 ext. 123")
+    );
+    assert_eq!(
+        ("650 253 0000", Some(("123".to_owned(), 12))),
+        phone_util.maybe_strip_extension_with_span("650 253 0000x123")
+    );
+    // Позиция считается в байтах, а не в символах.
+    let number = "６５０ 253 0000 ext. 89";
+    let (stripped, extension) = phone_util.maybe_strip_extension_with_span(number);
+    let (extension, start) = extension.unwrap();
+    assert_eq!("89", extension);
+    assert_eq!("６５０ 253 0000", stripped);
+    assert_eq!(19, start);
+    assert!(number[start..].starts_with("ext."));
+    // Метка в стиле RFC3966 начинается с ";ext=".
+    assert_eq!(
+        ("+1-650-253-0000", Some(("12".to_owned(), 15))),
+        phone_util.maybe_strip_extension_with_span("+1-650-253-0000;ext=12")
+    );
+    // Без добавочного номера строка не меняется.
+    assert_eq!(("650 253 0000", None), phone_util.maybe_strip_extension_with_span("650 253 0000"));
+}
+
 #[test]
 fn get_number_type() {
     let phone_util = get_phone_util();
@@ -2360,11 +4023,112 @@ fn get_number_type() {
     number.clear(); number.set_country_code(44); number.set_national_number(7031231234);
     assert_eq!(PhoneNumberType::PersonalNumber, phone_util.get_number_type(&number).unwrap());
     
+    // PAGER
+    number.clear(); number.set_country_code(44); number.set_national_number(7623456789);
+    assert_eq!(PhoneNumberType::Pager, phone_util.get_number_type(&number).unwrap());
+
+    // UAN
+    number.clear(); number.set_country_code(971); number.set_national_number(600123456);
+    assert_eq!(PhoneNumberType::UAN, phone_util.get_number_type(&number).unwrap());
+
     // UNKNOWN
     number.clear(); number.set_country_code(1); number.set_national_number(65025311111);
     assert_eq!(PhoneNumberType::Unknown, phone_util.get_number_type(&number).unwrap());
 }
 
+#[test]
+fn get_number_type_with_real_metadata() {
+    // В тестовых метаданных нет голосовой почты, поэтому используются настоящие.
+    let phone_util = &*PHONE_NUMBER_UTIL;
+    let classify = |number: &str| phone_util.get_number_type(&phone_util.parse(number, "ZZ").unwrap());
+
+    // VOICEMAIL
+    assert_eq!(PhoneNumberType::VoiceMail, classify("+41 860 123 456 789"));
+    // UAN: номера 03 и 055 в Великобритании.
+    assert_eq!(PhoneNumberType::UAN, classify("+44 3012 345 678"));
+    assert_eq!(PhoneNumberType::UAN, classify("+44 55 1234 5678"));
+    // Номера 0845 в метаданных относятся к платным, а не к UAN.
+    assert_eq!(PhoneNumberType::PremiumRate, classify("+44 845 123 4567"));
+    // PAGER
+    assert_eq!(PhoneNumberType::Pager, classify("+44 7640 123456"));
+
+    // Пример каждого из этих типов распознается как этот тип.
+    for (region_code, number_type) in [
+        ("CH", PhoneNumberType::VoiceMail),
+        ("GB", PhoneNumberType::UAN),
+        ("GB", PhoneNumberType::Pager),
+    ] {
+        let (_, example_number) = phone_util
+            .example_numbers_for_region(region_code)
+            .find(|(example_type, _)| *example_type == number_type)
+            .unwrap();
+        assert_eq!(number_type, phone_util.get_number_type(&example_number));
+    }
+}
+
+#[test]
+fn get_number_type_for_region() {
+    let phone_util = get_phone_util();
+    let mut number = PhoneNumber::new();
+
+    number.set_country_code(44);
+    number.set_national_number(7912345678);
+    assert_eq!(Ok(PhoneNumberType::Mobile), phone_util.get_number_type_for_region(&number, RegionCode::gb()));
+    number.set_national_number(2070313000);
+    assert_eq!(Ok(PhoneNumberType::FixedLine), phone_util.get_number_type_for_region(&number, RegionCode::gb()));
+
+    number.set_country_code(800);
+    number.set_national_number(12345678);
+    assert_eq!(Ok(PhoneNumberType::TollFree), phone_util.get_number_type_for_region(&number, RegionCode::un001()));
+
+    // Результат совпадает с get_number_type для региона номера.
+    number.set_country_code(1);
+    number.set_national_number(2423570000);
+    let region_code = phone_util.get_region_code_for_number(&number).unwrap();
+    assert_eq!(
+        Ok(phone_util.get_number_type(&number).unwrap()),
+        phone_util.get_number_type_for_region(&number, region_code)
+    );
+    // Номер другого региона с тем же кодом страны не распознается.
+    assert_eq!(Ok(PhoneNumberType::Unknown), phone_util.get_number_type_for_region(&number, RegionCode::us()));
+
+    // Регион с другим кодом страны или неизвестный регион.
+    assert_eq!(Err(ValidationError::InvalidCountryCode { country_code: 1 }), phone_util.get_number_type_for_region(&number, RegionCode::gb()));
+    assert_eq!(Err(ValidationError::InvalidCountryCode { country_code: 1 }), phone_util.get_number_type_for_region(&number, RegionCode::zz()));
+}
+
+#[test]
+fn get_number_type_with_descriptor() {
+    let phone_util = get_phone_util();
+    let gb_metadata = phone_util.get_metadata_for_region(RegionCode::gb()).unwrap();
+    let mut number = PhoneNumber::new();
+
+    // Мобильный номер определяется по шаблону дескриптора mobile.
+    number.set_country_code(44); number.set_national_number(7912345678);
+    assert_eq!(
+        (PhoneNumberType::Mobile, Some(gb_metadata.mobile.national_number_pattern().to_owned())),
+        phone_util.get_number_type_with_descriptor(&number).unwrap()
+    );
+
+    number.set_national_number(8431231234);
+    assert_eq!(
+        (PhoneNumberType::SharedCost, Some(gb_metadata.shared_cost.national_number_pattern().to_owned())),
+        phone_util.get_number_type_with_descriptor(&number).unwrap()
+    );
+
+    // Для FIXED_LINE_OR_MOBILE совпадает шаблон fixed_line.
+    let us_metadata = phone_util.get_metadata_for_region(RegionCode::us()).unwrap();
+    number.set_country_code(1); number.set_national_number(6502531111);
+    assert_eq!(
+        (PhoneNumberType::FixedLineOrMobile, Some(us_metadata.fixed_line.national_number_pattern().to_owned())),
+        phone_util.get_number_type_with_descriptor(&number).unwrap()
+    );
+
+    // Неизвестный тип не имеет дескриптора.
+    number.set_national_number(65025311111);
+    assert_eq!((PhoneNumberType::Unknown, None), phone_util.get_number_type_with_descriptor(&number).unwrap());
+}
+
 #[test]
 fn parse_national_number() {
     let phone_util = get_phone_util();
@@ -2409,6 +4173,21 @@ fn parse_national_number() {
     let test_number = phone_util.parse("03-331-6005;phone-context=+64", RegionCode::nz()).unwrap();
     assert_eq!(nz_number, test_number);
 
+    // Схема "tel:" без phone-context (скопированная ссылка) разбирается
+    // с регионом по умолчанию.
+    let mut us_number = PhoneNumber::new();
+    us_number.set_country_code(1);
+    us_number.set_national_number(6502530000);
+    let test_number = phone_util.parse("tel:6502530000", RegionCode::us()).unwrap();
+    assert_eq!(us_number, test_number);
+    let test_number = phone_util.parse("TEL:650-253-0000", RegionCode::us()).unwrap();
+    assert_eq!(us_number, test_number);
+    let test_number = phone_util.parse(" tel:+1-650-253-0000", RegionCode::zz()).unwrap();
+    assert_eq!(us_number, test_number);
+    us_number.set_extension("123".to_owned());
+    let test_number = phone_util.parse("tel:6502530000;ext=123", RegionCode::us()).unwrap();
+    assert_eq!(us_number, test_number);
+
     // Тестирование международных префиксов.
     // Код страны должен быть удалён.
     let test_number = phone_util.parse("0064 3 d331 6005", RegionCode::nz()).unwrap();
@@ -2446,42 +4225,297 @@ fn parse_national_number() {
     let test_number = phone_util.parse("+64(0)64123456", RegionCode::us()).unwrap();
     assert_eq!(nz_number_issue, test_number);
 
-    // Проверка, что "/" в номере телефона обрабатывается корректно.
-    let mut de_number = PhoneNumber::new();
-    de_number.set_country_code(49);
-    de_number.set_national_number(12345678);
-    let test_number = phone_util.parse("123/45678", RegionCode::de()).unwrap();
-    assert_eq!(de_number, test_number);
+    // Проверка, что "/" в номере телефона обрабатывается корректно.
+    let mut de_number = PhoneNumber::new();
+    de_number.set_country_code(49);
+    de_number.set_national_number(12345678);
+    let test_number = phone_util.parse("123/45678", RegionCode::de()).unwrap();
+    assert_eq!(de_number, test_number);
+
+    let mut us_number = PhoneNumber::new();
+    us_number.set_country_code(1);
+    // Проверка, что '1' не используется как код страны при парсинге, если номер уже валиден.
+    us_number.set_national_number(1234567890);
+    let test_number = phone_util.parse("123-456-7890", RegionCode::us()).unwrap();
+    assert_eq!(us_number, test_number);
+
+    // Тестирование номеров со звездочкой.
+    let mut star_number = PhoneNumber::new();
+    star_number.set_country_code(81);
+    star_number.set_national_number(2345);
+    let test_number = phone_util.parse("+81 *2345", RegionCode::jp()).unwrap();
+    assert_eq!(star_number, test_number);
+
+    let mut short_number = PhoneNumber::new();
+    short_number.set_country_code(64);
+    short_number.set_national_number(12);
+    let test_number = phone_util.parse("12", RegionCode::nz()).unwrap();
+    assert_eq!(short_number, test_number);
+
+    // Тест для короткого номера с ведущим нулём для страны, где 0 - национальный префикс.
+    // Убедиться, что он не интерпретируется как национальный префикс, если
+    // оставшаяся длина номера соответствует только местному номеру.
+    let mut short_number = PhoneNumber::new();
+    short_number.set_country_code(44);
+    short_number.set_national_number(123456);
+    short_number.set_italian_leading_zero(true);
+    let test_number = phone_util.parse("0123456", RegionCode::gb()).unwrap();
+    assert_eq!(short_number, test_number);
+}
+
+#[test]
+fn parse_keep_national_prefix() {
+    let phone_util = get_phone_util();
+
+    // Национальный префикс "0" сохраняется как ведущий ноль.
+    let mut gb_number = PhoneNumber::new();
+    gb_number.set_country_code(44);
+    gb_number.set_national_number(2070313000);
+    gb_number.set_italian_leading_zero(true);
+    assert_eq!(gb_number, phone_util.parse_keep_national_prefix("020 7031 3000", RegionCode::gb()).unwrap());
+    gb_number.clear_italian_leading_zero();
+    assert_eq!(gb_number, phone_util.parse("020 7031 3000", RegionCode::gb()).unwrap());
+
+    // Номера в международном формате разбираются как обычно.
+    assert_eq!(
+        phone_util.parse("+44 20 7031 3000", RegionCode::zz()).unwrap(),
+        phone_util.parse_keep_national_prefix("+44 20 7031 3000", RegionCode::zz()).unwrap()
+    );
+}
+
+#[test]
+fn parse_borrowed() {
+    let phone_util = get_phone_util();
+
+    // Чистый национальный номер не копируется.
+    let number = phone_util.parse_borrowed("6502530000", RegionCode::us()).unwrap();
+    assert!(number.is_borrowed());
+    assert_eq!(1, number.country_code());
+    assert_eq!("6502530000", number.national_number());
+    assert_eq!(phone_util.parse("6502530000", RegionCode::us()).unwrap(), number.into_owned());
+
+    // Всё, что требует преобразования, разбирается как обычно.
+    for (input, region) in [
+        ("650 253 0000", RegionCode::us()),
+        ("16502530000", RegionCode::us()),
+        ("02070313000", RegionCode::gb()),
+        ("0236618300", RegionCode::it()),
+        ("+16502530000", RegionCode::zz()),
+    ] {
+        let number = phone_util.parse_borrowed(input, region).unwrap();
+        assert!(!number.is_borrowed(), "{}", input);
+        assert_eq!(phone_util.parse(input, region).unwrap(), number.into_owned());
+    }
+
+    assert_eq!(
+        phone_util.parse("123", RegionCode::zz()).unwrap_err(),
+        phone_util.parse_borrowed("123", RegionCode::zz()).unwrap_err()
+    );
+}
+
+#[test]
+fn parse_with_region_sanity() {
+    let phone_util = PhoneNumberUtil::from_metadata_bytes(&TEST_METADATA).unwrap();
+    let mut us_number = PhoneNumber::new();
+    us_number.set_country_code(1);
+    us_number.set_national_number(6502530000);
+    assert_eq!(Ok(us_number.clone()), phone_util.parse_with_region_sanity("650 253 0000", RegionCode::us()));
+    assert_eq!(Ok(us_number), phone_util.parse_with_region_sanity("1 650 253 0000", RegionCode::us()));
+
+    // Номер Багамских островов, введенный без "+", при выбранном регионе США.
+    let mismatch = ParseError::RegionMismatchSuspected {
+        default_region: "US".to_owned(),
+        actual_region: "BS".to_owned(),
+    };
+    assert_eq!(6, mismatch.code());
+    let mismatch = Err(mismatch);
+    assert_eq!(mismatch, phone_util.parse_with_region_sanity("242 365 1234", RegionCode::us()));
+    assert_eq!(mismatch, phone_util.parse_with_region_sanity("1 242 365 1234", RegionCode::us()));
+
+    // С "+" или префиксом международного вызова регион указан явно.
+    let mut bs_number = PhoneNumber::new();
+    bs_number.set_country_code(1);
+    bs_number.set_national_number(2423651234);
+    assert_eq!(Ok(bs_number.clone()), phone_util.parse_with_region_sanity("+1 242 365 1234", RegionCode::us()));
+    assert_eq!(Ok(bs_number.clone()), phone_util.parse_with_region_sanity("011 1 242 365 1234", RegionCode::us()));
+    assert_eq!(Ok(bs_number), phone_util.parse_with_region_sanity("242 365 1234", RegionCode::bs()));
+
+    // Невалидные номера не относятся ни к какому региону и возвращаются как есть.
+    assert_eq!(
+        phone_util.parse("123 456 7890", RegionCode::us()),
+        phone_util.parse_with_region_sanity("123 456 7890", RegionCode::us())
+    );
+    // Ошибки разбора передаются без изменений.
+    assert_eq!(Err(ParseError::InvalidCountryCode), phone_util.parse_with_region_sanity("650 253 0000", RegionCode::zz()));
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parse_many() {
+    let phone_util = PhoneNumberUtil::from_metadata_bytes(&TEST_METADATA).unwrap();
+    let inputs: Vec<(&str, &str)> = [
+        ("650 253 0000", RegionCode::us()),
+        ("+44 20 7031 3000", RegionCode::zz()),
+        ("This is not a phone number", RegionCode::us()),
+        ("02 3661 8300", RegionCode::it()),
+    ]
+    .into_iter()
+    .cycle()
+    .take(1000)
+    .collect();
+
+    let results = phone_util.parse_many(&inputs);
+    // Порядок результатов совпадает с порядком входных данных.
+    let expected: Vec<_> = inputs.iter().map(|(number, region)| phone_util.parse(number, region)).collect();
+    assert_eq!(expected, results);
+    assert!(phone_util.parse_many(&[]).is_empty());
+}
+
+#[test]
+fn parse_with_strip_leading_symbols() {
+    let phone_util = get_phone_util();
+    let mut us_number = PhoneNumber::new();
+    us_number.set_country_code(1);
+    us_number.set_national_number(6502530000);
+    let options = ParseOptions::new().strip_leading_symbols(true);
+
+    assert_eq!(us_number, phone_util.parse_with_options("☎ +1 650 253 0000", RegionCode::us(), &options).unwrap());
+    assert_eq!(us_number, phone_util.parse_with_options("📞 650 253 0000", RegionCode::us(), &options).unwrap());
+    assert_eq!(us_number, phone_util.parse_with_options("• +1 650 253 0000", RegionCode::us(), &options).unwrap());
+    // Символы между плюсом и цифрами тоже удаляются.
+    assert_eq!(us_number, phone_util.parse_with_options("+☎️ 1 650 253 0000", RegionCode::us(), &options).unwrap());
+    // RFC3966 не затрагивается.
+    assert_eq!(
+        us_number,
+        phone_util.parse_with_options("tel:+1-650-253-0000", RegionCode::zz(), &options).unwrap()
+    );
+
+    // По умолчанию поведение строгое и совпадает с parse.
+    let default_options = ParseOptions::default();
+    assert_eq!(us_number, phone_util.parse_with_options("☎ +1 650 253 0000", RegionCode::us(), &default_options).unwrap());
+    assert!(matches!(
+        phone_util.parse_with_options("+☎️ 1 650 253 0000", RegionCode::us(), &default_options).unwrap_err().into_public(),
+        ParseError::NotANumber(_)
+    ));
+}
+
+#[test]
+fn parse_with_keep_raw_input_only() {
+    let phone_util = get_phone_util();
+    let options = ParseOptions::new().keep_raw_input_only(true);
+
+    let number = phone_util.parse_with_options("+1 650-253-0000", RegionCode::us(), &options).unwrap();
+    assert_eq!("+1 650-253-0000", number.raw_input());
+    // Источник кода страны не заполняется, в отличие от parse_and_keep_raw_input.
+    assert!(!number.has_country_code_source());
+
+    let national = phone_util.parse_with_options("(650) 253-0000", RegionCode::us(), &options).unwrap();
+    assert_eq!("(650) 253-0000", national.raw_input());
+    assert!(!national.has_country_code_source());
+    // Номера отличаются только исходной строкой.
+    let mut national_without_raw_input = national.clone();
+    national_without_raw_input.clear_raw_input();
+    let mut number_without_raw_input = number.clone();
+    number_without_raw_input.clear_raw_input();
+    assert_eq!(number_without_raw_input, national_without_raw_input);
+
+    // Сохраняется исходная строка, до удаления символов.
+    let options = options.strip_leading_symbols(true);
+    let number = phone_util.parse_with_options("☎ 650 253 0000", RegionCode::us(), &options).unwrap();
+    assert_eq!("☎ 650 253 0000", number.raw_input());
+
+    // По умолчанию исходная строка не сохраняется.
+    let number = phone_util.parse_with_options("+1 650-253-0000", RegionCode::us(), &ParseOptions::new()).unwrap();
+    assert!(!number.has_raw_input());
+}
+
+#[test]
+fn parse_with_extensions_not_allowed() {
+    let phone_util = get_phone_util();
+    let options = ParseOptions::new().allow_extensions(false);
+
+    assert_eq!(
+        ParseError::ExtensionNotAllowed { extension: "5".to_owned() },
+        phone_util.parse_with_options("+1 650 253 0000 ext 5", RegionCode::us(), &options).unwrap_err().into_public()
+    );
+    // Номер без добавочного разбирается как обычно.
+    assert_eq!(
+        phone_util.parse("+1 650 253 0000", RegionCode::us()).unwrap(),
+        phone_util.parse_with_options("+1 650 253 0000", RegionCode::us(), &options).unwrap()
+    );
+    // По умолчанию добавочные номера разрешены.
+    let number = phone_util.parse_with_options("+1 650 253 0000 ext 5", RegionCode::us(), &ParseOptions::new()).unwrap();
+    assert_eq!("5", number.extension());
+}
+
+#[test]
+fn parse_e164() {
+    let phone_util = get_phone_util();
+    let inputs = [
+        "+16502530000",
+        "+442087654321",
+        "+390236618300",
+        "+80012345678",
+        "+5491187654321",
+        // Национальный префикс после кода страны удаляется так же, как в parse.
+        "+4402087654321",
+        "+64033316005",
+        "+376123",
+        // Ошибки тоже должны совпадать.
+        "+9991234567",
+        "+0123456",
+        "+12",
+        "+1234567890123456",
+        // Не-E.164 ввод обрабатывается полным парсером.
+        "+1 650 253 0000",
+        "tel:+1-650-253-0000",
+        "6502530000",
+    ];
+    for input in inputs {
+        assert_eq!(
+            phone_util.parse(input, RegionCode::get_unknown()),
+            phone_util.parse_e164(input),
+            "input: {input}"
+        );
+    }
+}
+
+#[test]
+fn parse_local() {
+    let phone_util = get_phone_util();
 
-    let mut us_number = PhoneNumber::new();
-    us_number.set_country_code(1);
-    // Проверка, что '1' не используется как код страны при парсинге, если номер уже валиден.
-    us_number.set_national_number(1234567890);
-    let test_number = phone_util.parse("123-456-7890", RegionCode::us()).unwrap();
-    assert_eq!(us_number, test_number);
+    // Локальный номер США из 7 цифр сохраняется как есть и считается
+    // возможным только для локального набора.
+    let mut us_local = PhoneNumber::new();
+    us_local.set_country_code(1);
+    us_local.set_national_number(2530000);
+    let test_number = phone_util.parse_local("253-0000", RegionCode::us()).unwrap();
+    assert_eq!(us_local, test_number);
+    assert_eq!(Ok(NumberLengthType::IsPossibleLocalOnly), phone_util.is_possible_number_with_reason(&test_number));
 
-    // Тестирование номеров со звездочкой.
-    let mut star_number = PhoneNumber::new();
-    star_number.set_country_code(81);
-    star_number.set_national_number(2345);
-    let test_number = phone_util.parse("+81 *2345", RegionCode::jp()).unwrap();
-    assert_eq!(star_number, test_number);
+    // Добавочный номер по-прежнему распознаётся.
+    us_local.set_extension("12".to_owned());
+    assert_eq!(us_local, phone_util.parse_local("253-0000 ext. 12", RegionCode::us()).unwrap());
 
-    let mut short_number = PhoneNumber::new();
-    short_number.set_country_code(64);
-    short_number.set_national_number(12);
-    let test_number = phone_util.parse("12", RegionCode::nz()).unwrap();
-    assert_eq!(short_number, test_number);
+    // Национальный префикс не удаляется, в отличие от обычного парсинга.
+    let mut nz_number = PhoneNumber::new();
+    nz_number.set_country_code(64);
+    nz_number.set_national_number(33316005);
+    assert_eq!(nz_number, phone_util.parse("033316005", RegionCode::nz()).unwrap());
+    let test_number = phone_util.parse_local("033316005", RegionCode::nz()).unwrap();
+    assert_eq!(64, test_number.country_code());
+    assert!(test_number.italian_leading_zero());
+    assert_eq!("033316005", phone_util.get_national_significant_number(&test_number));
 
-    // Тест для короткого номера с ведущим нулём для страны, где 0 - национальный префикс.
-    // Убедиться, что он не интерпретируется как национальный префикс, если
-    // оставшаяся длина номера соответствует только местному номеру.
-    let mut short_number = PhoneNumber::new();
-    short_number.set_country_code(44);
-    short_number.set_national_number(123456);
-    short_number.set_italian_leading_zero(true);
-    let test_number = phone_util.parse("0123456", RegionCode::gb()).unwrap();
-    assert_eq!(short_number, test_number);
+    // Без поддерживаемого региона локальный номер разобрать нельзя.
+    assert!(matches!(
+        phone_util.parse_local("253-0000", RegionCode::zz()).unwrap_err().into_public(),
+        ParseError::InvalidCountryCode
+    ));
+    assert!(matches!(
+        phone_util.parse_local("2", RegionCode::us()).unwrap_err().into_public(),
+        ParseError::NotANumber(_)
+    ));
 }
 
 #[test]
@@ -2782,7 +4816,7 @@ fn failed_parse_on_invalid_numbers() {
     ));
     assert_eq!(
         phone_util.parse("01495 72553301873 810104", RegionCode::gb()).unwrap_err().into_public(),
-        ParseError::TooLongNsn
+        ParseError::TooLongNsn { length: 21 }
     );
     assert!(matches!(
         phone_util.parse("+---", RegionCode::de()).unwrap_err().into_public(),
@@ -2798,7 +4832,7 @@ fn failed_parse_on_invalid_numbers() {
     ));
     assert_eq!(
         phone_util.parse("+49 0", RegionCode::de()).unwrap_err().into_public(),
-        ParseError::TooShortNsn
+        ParseError::TooShortNsn { length: 1 }
     );
     assert_eq!(
         phone_util.parse("+210 3456 56789", RegionCode::nz()).unwrap_err().into_public(),
@@ -2819,19 +4853,19 @@ fn failed_parse_on_invalid_numbers() {
     );
     assert_eq!(
         phone_util.parse("0044-----", RegionCode::gb()).unwrap_err().into_public(),
-        ParseError::TooShortAfterIdd
+        ParseError::TooShortAfterIdd { idd: "00".to_owned(), remaining: "44".to_owned() }
     );
     assert_eq!(
         phone_util.parse("0044", RegionCode::gb()).unwrap_err().into_public(),
-        ParseError::TooShortAfterIdd
+        ParseError::TooShortAfterIdd { idd: "00".to_owned(), remaining: "44".to_owned() }
     );
     assert_eq!(
         phone_util.parse("011", RegionCode::us()).unwrap_err().into_public(),
-        ParseError::TooShortAfterIdd
+        ParseError::TooShortAfterIdd { idd: "011".to_owned(), remaining: "".to_owned() }
     );
     assert_eq!(
         phone_util.parse("0119", RegionCode::us()).unwrap_err().into_public(),
-        ParseError::TooShortAfterIdd
+        ParseError::TooShortAfterIdd { idd: "011".to_owned(), remaining: "9".to_owned() }
     );
     // RFC3966 phone-context является веб-сайтом.
     assert_eq!(
@@ -2892,6 +4926,7 @@ fn parse_numbers_with_plus_with_no_region() {
 
     nz_number.set_raw_input("+64 3 331 6005".to_string());
     nz_number.set_country_code_source(CountryCodeSource::FROM_NUMBER_WITH_PLUS_SIGN);
+    nz_number.raw_input_groups = ["64", "3", "331", "6005"].map(str::to_owned).to_vec();
     result_proto = phone_util.parse_and_keep_raw_input("+64 3 331 6005", RegionCode::zz()).unwrap();
     assert_eq!(nz_number, result_proto);
 }
@@ -3077,6 +5112,162 @@ fn parse_extensions() {
     assert_eq!(us_with_extension, test_number);
 }
 
+#[test]
+fn parse_with_extra_extension_separators() {
+    let metadata = PhoneMetadataCollection::parse_from_bytes(&TEST_METADATA).unwrap();
+    let phone_util = PhoneNumberUtilInternal::new_for_metadata_and_reg_exps(
+        metadata,
+        PhoneNumberRegExpsAndMappings::with_extra_extension_separators(&['/']),
+    );
+    let mut nz_number = PhoneNumber::new();
+    nz_number.set_country_code(64);
+    nz_number.set_national_number(33316005);
+    nz_number.set_extension("456".to_owned());
+    assert_eq!(nz_number, phone_util.parse("03 331 6005 / 456", RegionCode::nz()).unwrap());
+    assert_eq!(nz_number, phone_util.parse("+64 3 331 6005/456", RegionCode::nz()).unwrap());
+    let (_, label) = phone_util
+        .parse_reporting_extension_label("03 331 6005 / 456", RegionCode::nz())
+        .unwrap();
+    assert_eq!(Some(ExtensionLabelKind::Ambiguous), label);
+
+    // Стандартные метки продолжают работать.
+    assert_eq!(nz_number, phone_util.parse("03 331 6005 ext 456", RegionCode::nz()).unwrap());
+
+    // Без дополнительных разделителей "/" не считается меткой добавочного номера.
+    let default_util = get_phone_util();
+    assert!(!default_util.parse("03 331 6005 / 456", RegionCode::nz()).unwrap().has_extension());
+}
+
+#[test]
+fn parse_reporting_all_extensions() {
+    let phone_util = get_phone_util();
+    let mut us_with_extension = PhoneNumber::new();
+    us_with_extension.set_country_code(1);
+    us_with_extension.set_national_number(2121231234);
+    us_with_extension.set_extension("508".to_owned());
+
+    // Второй добавочный номер игнорируется при разборе, но попадает в кандидаты.
+    for input in ["(212)123-1234 x508/x1234", "(212)123-1234 x508/ x1234", "(212)123-1234 x508\\x1234"] {
+        let (number, candidates) = phone_util
+            .parse_reporting_all_extensions(input, RegionCode::us())
+            .unwrap();
+        assert_eq!(us_with_extension, number, "{input}");
+        assert_eq!(vec!["508", "1234"], candidates, "{input}");
+    }
+
+    let (number, candidates) = phone_util
+        .parse_reporting_all_extensions("(212)123-1234 ext. 508", RegionCode::us())
+        .unwrap();
+    assert_eq!(us_with_extension, number);
+    assert_eq!(vec!["508"], candidates);
+
+    let (number, candidates) = phone_util
+        .parse_reporting_all_extensions("(212)123-1234", RegionCode::us())
+        .unwrap();
+    assert!(!number.has_extension());
+    assert!(candidates.is_empty());
+}
+
+#[test]
+fn raw_input_groups() {
+    let phone_util = PhoneNumberUtil::from_metadata_bytes(&TEST_METADATA).unwrap();
+    let number = phone_util
+        .parse_and_keep_raw_input("+1 650-253-0000 ext. 12", RegionCode::us())
+        .unwrap();
+    assert_eq!(vec!["1", "650", "253", "0000"], phone_util.raw_input_groups(&number));
+
+    let number = phone_util
+        .parse_and_keep_raw_input("(020) 7031 3000", RegionCode::gb())
+        .unwrap();
+    assert_eq!(vec!["020", "7031", "3000"], phone_util.raw_input_groups(&number));
+
+    // Параметры RFC3966 не попадают в группы.
+    let number = phone_util
+        .parse_and_keep_raw_input("tel:+1-650-253-0000;isub=12345", RegionCode::us())
+        .unwrap();
+    assert_eq!(vec!["1", "650", "253", "0000"], phone_util.raw_input_groups(&number));
+
+    // Цифры других систем письма приводятся к ASCII.
+    let number = phone_util
+        .parse_and_keep_raw_input("+1 \u{0666}\u{0665}\u{0660} 253 0000", RegionCode::us())
+        .unwrap();
+    assert_eq!(vec!["1", "650", "253", "0000"], phone_util.raw_input_groups(&number));
+
+    // Без сохранённого ввода групп нет, в том числе при разборе в уже заполненный номер.
+    let mut number = phone_util.parse("+1 650-253-0000", RegionCode::us()).unwrap();
+    assert!(phone_util.raw_input_groups(&number).is_empty());
+    number = phone_util.parse_and_keep_raw_input("650 253 0000", RegionCode::us()).unwrap();
+    phone_util.parse_into("650 253 0000", RegionCode::us(), &mut number).unwrap();
+    assert!(phone_util.raw_input_groups(&number).is_empty());
+}
+
+#[test]
+fn parse_best_region() {
+    let phone_util = get_phone_util();
+    let mut gb_number = PhoneNumber::new();
+    gb_number.set_country_code(44);
+    gb_number.set_national_number(2070313000);
+
+    // Номер невалиден для предпочтительного региона, используется резервный.
+    assert!(!phone_util.is_valid_number(&phone_util.parse("020 7031 3000", RegionCode::fr()).unwrap()).unwrap());
+    assert_eq!(
+        gb_number,
+        phone_util.parse_best_region("020 7031 3000", RegionCode::fr(), &[RegionCode::us(), RegionCode::gb()]).unwrap()
+    );
+    assert_eq!(
+        gb_number,
+        phone_util.parse_best_region("020 7031 3000", RegionCode::gb(), &[RegionCode::fr()]).unwrap()
+    );
+    // Международный формат не зависит от региона.
+    assert_eq!(
+        gb_number,
+        phone_util.parse_best_region("+44 20 7031 3000", RegionCode::fr(), &[] as &[&str]).unwrap()
+    );
+    // Если ни один регион не подошёл, возвращается результат для предпочтительного.
+    assert_eq!(
+        phone_util.parse("020 7031 3000", RegionCode::fr()).unwrap(),
+        phone_util.parse_best_region("020 7031 3000", RegionCode::fr(), &[RegionCode::us()]).unwrap()
+    );
+    assert!(phone_util
+        .parse_best_region("020 7031 3000", RegionCode::zz(), &[RegionCode::us()])
+        .is_err_and(|err| matches!(err.into_public(), ParseError::InvalidCountryCode)));
+    assert_eq!(
+        gb_number,
+        phone_util.parse_best_region("020 7031 3000", RegionCode::zz(), &[RegionCode::gb()]).unwrap()
+    );
+}
+
+#[test]
+fn parse_reporting_extension_label() {
+    let phone_util = get_phone_util();
+    let cases = [
+        ("tel:+1-650-253-0000;ext=123", ExtensionLabelKind::Rfc3966),
+        ("650 253 0000 ext. 123", ExtensionLabelKind::Explicit),
+        ("650 253 0000 extension 123", ExtensionLabelKind::Explicit),
+        ("650 253 0000 x123", ExtensionLabelKind::Ambiguous),
+        ("650 253 0000 #123", ExtensionLabelKind::Ambiguous),
+        ("650-253-0000 - 123#", ExtensionLabelKind::HashSuffix),
+        ("650 253 0000,,123", ExtensionLabelKind::AutoDialling),
+        ("650 253 0000;123", ExtensionLabelKind::AutoDialling),
+        ("650 253 0000,123", ExtensionLabelKind::AutoDialling),
+    ];
+    for (input, expected_label) in cases {
+        let (number, label) = phone_util
+            .parse_reporting_extension_label(input, RegionCode::us())
+            .unwrap();
+        assert_eq!(Some(expected_label), label, "{input}");
+        assert_eq!("123", number.extension(), "{input}");
+        assert_eq!(phone_util.parse(input, RegionCode::us()).unwrap(), number);
+    }
+
+    // Без добавочного номера метки нет.
+    let (number, label) = phone_util
+        .parse_reporting_extension_label("650 253 0000", RegionCode::us())
+        .unwrap();
+    assert!(!number.has_extension());
+    assert_eq!(None, label);
+}
+
 #[test]
 fn test_parse_handles_long_extensions_with_explicit_labels() {
     let phone_util = get_phone_util();
@@ -3232,4 +5423,480 @@ fn is_alpha_number() {
     assert!(phone_util.is_alpha_number("+800 six-flags"));
     assert!(!phone_util.is_alpha_number("1800 123-1234"));
     assert!(!phone_util.is_alpha_number("1 six-flags"));
-}
\ No newline at end of file
+}
+
+#[test]
+fn is_alpha_number_excluding_extension() {
+    let phone_util = get_phone_util();
+    assert!(phone_util.is_alpha_number_excluding_extension("1800 six-flags"));
+    assert!(phone_util.is_alpha_number_excluding_extension("1800 six-flags ext. 1234"));
+    assert!(phone_util.is_alpha_number_excluding_extension("+800 six-flags"));
+    assert!(!phone_util.is_alpha_number_excluding_extension("1800 123-1234"));
+    assert!(!phone_util.is_alpha_number_excluding_extension("1800 123-1234 ext. 1234"));
+    assert!(!phone_util.is_alpha_number_excluding_extension("1 six-flags"));
+    // Метка добавочного номера без цифр не делает номер буквенным.
+    assert!(phone_util.is_alpha_number("1800 123-1234 extension"));
+    assert!(!phone_util.is_alpha_number_excluding_extension("1800 123-1234 extension"));
+    assert!(!phone_util.is_alpha_number_excluding_extension("1800 123-1234 ext."));
+    assert!(!phone_util.is_alpha_number_excluding_extension("1800 123-1234 Ext: "));
+    assert!(phone_util.is_alpha_number_excluding_extension("1800 six-flags ext."));
+}
+
+#[test]
+fn is_number_match_lenient() {
+    let phone_util = get_phone_util();
+    let match_of = |first: NumberOrString, second: NumberOrString| {
+        phone_util.is_number_match_lenient(first, second).unwrap()
+    };
+    let mut nz_number = PhoneNumber::new();
+    nz_number.set_country_code(64);
+    nz_number.set_national_number(33316005);
+
+    assert_eq!(MatchType::ExactMatch, match_of((&nz_number).into(), (&nz_number).into()));
+    assert_eq!(MatchType::ExactMatch, match_of((&nz_number).into(), "+64 3 331 6005".into()));
+    assert_eq!(MatchType::ExactMatch, match_of("+64 3 331 6005".into(), (&nz_number).into()));
+    assert_eq!(MatchType::ExactMatch, match_of("+64 3 331 6005".into(), "+64 03 331 6005".into()));
+    // Строка без кода страны разбирается в регионе другого номера.
+    assert_eq!(MatchType::NsnMatch, match_of("03 331 6005".into(), (&nz_number).into()));
+    assert_eq!(MatchType::NsnMatch, match_of("+64 3 331 6005".into(), "03 331 6005".into()));
+    assert_eq!(MatchType::NoMatch, match_of((&nz_number).into(), "+64 3 331 6006".into()));
+    assert_eq!(
+        phone_util.is_number_match_with_two_strings("+64 3 331-6005", "3 331 6005").unwrap(),
+        match_of("+64 3 331-6005".into(), "3 331 6005".into())
+    );
+    // Строки, не являющиеся номерами, дают ошибку.
+    assert!(phone_util
+        .is_number_match_lenient("asdkjhf".into(), (&nz_number).into())
+        .is_err());
+
+    // Публичный API возвращает NotANumber, как и перегрузки со строками.
+    let public_util = PhoneNumberUtil::from_metadata_bytes(&TEST_METADATA).unwrap();
+    assert_eq!(MatchType::NotANumber, public_util.is_number_match_lenient("asdkjhf", &nz_number));
+    assert_eq!(MatchType::NotANumber, public_util.is_number_match_lenient("+64 3 331 6005", "asdkjhf"));
+    assert_eq!(
+        public_util.is_number_match_with_strings("+64 3 331 6005", "3 331 6005"),
+        public_util.is_number_match_lenient("+64 3 331 6005", "3 331 6005")
+    );
+}
+
+#[test]
+fn is_number_match_ignores_context_fields() {
+    let phone_util = get_phone_util();
+    let mut first_number = PhoneNumber::new();
+    first_number.set_country_code(82);
+    first_number.set_national_number(22123456);
+    first_number.set_preferred_domestic_carrier_code("81".to_owned());
+    let mut second_number = first_number.clone();
+    second_number.set_preferred_domestic_carrier_code("82".to_owned());
+    // Код оператора, исходный ввод и источник кода страны не влияют на сравнение.
+    assert_eq!(MatchType::ExactMatch, phone_util.is_number_match(&first_number, &second_number));
+    second_number.clear_preferred_domestic_carrier_code();
+    second_number.set_raw_input("02 2123 456".to_owned());
+    second_number.set_country_code_source(CountryCodeSource::FROM_DEFAULT_COUNTRY);
+    assert_eq!(MatchType::ExactMatch, phone_util.is_number_match(&first_number, &second_number));
+    assert_eq!(MatchType::ExactMatch, phone_util.is_number_match(&second_number, &first_number));
+
+    // Номера из разных источников с разными пометками оператора.
+    let parsed_number = phone_util.parse_and_keep_raw_input("08122123456", RegionCode::kr()).unwrap();
+    assert_eq!("81", parsed_number.preferred_domestic_carrier_code());
+    let other_parsed_number = phone_util.parse_and_keep_raw_input("+82 2 2123 456", RegionCode::zz()).unwrap();
+    assert_eq!(MatchType::ExactMatch, phone_util.is_number_match(&parsed_number, &other_parsed_number));
+}
+
+#[test]
+fn best_match() {
+    let phone_util = PhoneNumberUtil::from_metadata_bytes(&TEST_METADATA).unwrap();
+    let mut target = PhoneNumber::new();
+    target.set_country_code(64);
+    target.set_national_number(33316005);
+
+    let mut other = PhoneNumber::new();
+    other.set_country_code(64);
+    other.set_national_number(33316006);
+    // Без кода страны совпадает только NSN.
+    let mut nsn_match = target.clone();
+    nsn_match.set_country_code(0);
+    // Добавочный номер есть только у одного из номеров.
+    let mut short_nsn_match = target.clone();
+    short_nsn_match.set_extension("1234".to_owned());
+    let exact_match = target.clone();
+
+    assert_eq!(
+        Some((&short_nsn_match, MatchType::ShortNsnMatch)),
+        phone_util.best_match(&target, [&other, &short_nsn_match])
+    );
+    assert_eq!(
+        Some((&nsn_match, MatchType::NsnMatch)),
+        phone_util.best_match(&target, [&other, &short_nsn_match, &nsn_match])
+    );
+    assert_eq!(
+        Some((&exact_match, MatchType::ExactMatch)),
+        phone_util.best_match(&target, [&other, &nsn_match, &exact_match])
+    );
+    // При равных совпадениях возвращается первый кандидат.
+    let candidates = vec![nsn_match.clone(), nsn_match.clone()];
+    let (best, match_type) = phone_util.best_match(&target, &candidates).unwrap();
+    assert!(std::ptr::eq(&candidates[0], best));
+    assert_eq!(MatchType::NsnMatch, match_type);
+
+    // Без совпадений.
+    assert_eq!(None, phone_util.best_match(&target, [&other]));
+    assert_eq!(None, phone_util.best_match(&target, []));
+
+    assert!(MatchType::ExactMatch > MatchType::NsnMatch);
+    assert!(MatchType::ShortNsnMatch > MatchType::NoMatch);
+    // NotANumber слабее любого совпадения, хотя и объявлен последним.
+    assert!(MatchType::NotANumber < MatchType::NoMatch);
+    assert_eq!(Some(MatchType::NoMatch), [MatchType::NotANumber, MatchType::NoMatch].into_iter().max());
+    // Дискриминанты совпадают со стабильными кодами.
+    assert_eq!(MatchType::NotANumber as i32, MatchType::NotANumber.code());
+    assert_eq!(MatchType::ExactMatch as i32, MatchType::ExactMatch.code());
+}
+
+#[test]
+fn is_number_match_with_strings() {
+    let phone_util = PhoneNumberUtil::from_metadata_bytes(&TEST_METADATA).unwrap();
+    assert_eq!(MatchType::ExactMatch, phone_util.is_number_match_with_strings("+64 3 331-6005", "+64 03 331 6005"));
+    assert_eq!(MatchType::ExactMatch, phone_util.is_number_match_with_strings("+64 3 331-6005 ext. 1234", "+6433316005;1234"));
+    // Номер без кода страны разбирается в регионе другого номера.
+    assert_eq!(MatchType::NsnMatch, phone_util.is_number_match_with_strings("+64 3 331-6005", "03 331 6005"));
+    assert_eq!(MatchType::NsnMatch, phone_util.is_number_match_with_strings("3 331 6005", "+64 3 331-6005"));
+    assert_eq!(MatchType::ShortNsnMatch, phone_util.is_number_match_with_strings("+64 3 331-6005", "331 6005"));
+    assert_eq!(MatchType::NoMatch, phone_util.is_number_match_with_strings("+64 3 331-6005", "+64 3 331-6006"));
+    assert_eq!(MatchType::NoMatch, phone_util.is_number_match_with_strings("+64 3 331-6005", "+1 3 331 6005"));
+
+    // Строки, которые не разбираются как номера.
+    assert_eq!(MatchType::NotANumber, phone_util.is_number_match_with_strings("4", "3 331 6043"));
+    assert_eq!(MatchType::NotANumber, phone_util.is_number_match_with_strings("+43", "+64 3 331 6005"));
+    assert_eq!(MatchType::NotANumber, phone_util.is_number_match_with_strings("+64 3 331 6005", "asdkjhf"));
+}
+
+#[test]
+fn is_number_match_string_and_number() {
+    let phone_util = PhoneNumberUtil::from_metadata_bytes(&TEST_METADATA).unwrap();
+    let mut nz_number = PhoneNumber::new();
+    nz_number.set_country_code(64);
+    nz_number.set_national_number(33316005);
+    assert_eq!(MatchType::ExactMatch, phone_util.is_number_match_string_and_number("+64 3 331 6005", &nz_number));
+    assert_eq!(MatchType::NsnMatch, phone_util.is_number_match_string_and_number("03 331 6005", &nz_number));
+    assert_eq!(MatchType::ShortNsnMatch, phone_util.is_number_match_string_and_number("331 6005", &nz_number));
+    assert_eq!(MatchType::NoMatch, phone_util.is_number_match_string_and_number("+64 3 331 6006", &nz_number));
+    assert_eq!(MatchType::NotANumber, phone_util.is_number_match_string_and_number("asdkjhf", &nz_number));
+    assert_eq!(MatchType::NotANumber, phone_util.is_number_match_string_and_number("", &nz_number));
+}
+
+#[test]
+fn parse_without_extension_leaves_extension_unset() {
+    let phone_util = get_phone_util();
+    // Для номера без добавочного строка под него не создаётся.
+    for (number_to_parse, region_code) in [
+        ("(650) 253-0000", RegionCode::us()),
+        ("+44 20 8765 4321", RegionCode::zz()),
+        ("tel:+1-650-253-0000", RegionCode::zz()),
+    ] {
+        let number = phone_util.parse(number_to_parse, region_code).unwrap();
+        assert!(number.extension.is_none());
+        let number = phone_util.parse_local(number_to_parse, RegionCode::us()).unwrap();
+        assert!(number.extension.is_none());
+    }
+    let number = phone_util.parse("+44 20 8765 4321 ext. 1234", RegionCode::zz()).unwrap();
+    assert_eq!(Some("1234"), number.extension.as_deref());
+}
+
+#[test]
+fn parse_chars() {
+    let phone_util = get_phone_util();
+    for (number_to_parse, region_code) in [
+        ("(650) 253-0000", RegionCode::us()),
+        ("+44 20 8765 4321 ext. 123", RegionCode::zz()),
+        ("tel:253-0000;phone-context=+1-650", RegionCode::us()),
+        ("\u{FF10}\u{FF12}\u{FF10} 8765 4321", RegionCode::gb()),
+    ] {
+        assert_eq!(
+            phone_util.parse(number_to_parse, region_code).unwrap(),
+            phone_util.parse_chars(number_to_parse.chars(), region_code).unwrap()
+        );
+    }
+    // Ошибки совпадают с ошибками `parse`, а буфер не сохраняет прошлый ввод.
+    assert_eq!(
+        phone_util.parse("123", RegionCode::zz()).unwrap_err(),
+        phone_util.parse_chars("123".chars(), RegionCode::zz()).unwrap_err()
+    );
+    assert!(phone_util.parse_chars("".chars(), RegionCode::us()).is_err());
+}
+
+#[test]
+fn is_number_match_with_distance() {
+    let phone_util = get_phone_util();
+    let number = |country_code: i32, national_number: u64| {
+        let mut number = PhoneNumber::new();
+        number.set_country_code(country_code);
+        number.set_national_number(national_number);
+        number
+    };
+    let us_number = number(1, 6502530000);
+    assert_eq!((MatchType::ExactMatch, Some(0)), phone_util.is_number_match_with_distance(&us_number, &us_number));
+    assert_eq!(
+        (MatchType::NoMatch, Some(1)),
+        phone_util.is_number_match_with_distance(&us_number, &number(1, 6502530001))
+    );
+    // Перестановка соседних цифр - две замены.
+    assert_eq!(
+        (MatchType::NoMatch, Some(2)),
+        phone_util.is_number_match_with_distance(&us_number, &number(1, 6505230000))
+    );
+    // Сдвиг цифр дешевле, чем их замена.
+    assert_eq!(
+        (MatchType::NoMatch, Some(2)),
+        phone_util.is_number_match_with_distance(&number(1, 1234567890), &number(1, 2345678901))
+    );
+    // Разные коды стран или длины не сравниваются.
+    assert_eq!(
+        (MatchType::NoMatch, None),
+        phone_util.is_number_match_with_distance(&us_number, &number(44, 6502530000))
+    );
+    assert_eq!(
+        (MatchType::ShortNsnMatch, None),
+        phone_util.is_number_match_with_distance(&us_number, &number(1, 2530000))
+    );
+}
+
+#[test]
+fn is_number_match_italian_leading_zero() {
+    let phone_util = get_phone_util();
+    let mut it_number = PhoneNumber::new();
+    it_number.set_country_code(39);
+    it_number.set_national_number(236618300);
+    it_number.set_italian_leading_zero(true);
+    let mut it_number_without_zero = it_number.clone();
+    it_number_without_zero.clear_italian_leading_zero();
+
+    // Разница только в ведущем нуле даёт SHORT_NSN_MATCH в обе стороны.
+    assert_eq!(MatchType::ShortNsnMatch, phone_util.is_number_match(&it_number, &it_number_without_zero));
+    assert_eq!(MatchType::ShortNsnMatch, phone_util.is_number_match(&it_number_without_zero, &it_number));
+    assert_eq!(
+        (MatchType::ShortNsnMatch, true),
+        phone_util.is_number_match_with_leading_zero_info(&it_number, &it_number_without_zero)
+    );
+    // Без кода страны у одного из номеров результат тот же.
+    let mut it_number_without_country_code = it_number_without_zero.clone();
+    it_number_without_country_code.clear_country_code();
+    assert_eq!(
+        (MatchType::ShortNsnMatch, true),
+        phone_util.is_number_match_with_leading_zero_info(&it_number, &it_number_without_country_code)
+    );
+
+    // Разное количество ведущих нулей.
+    let mut it_number_with_two_zeros = it_number.clone();
+    it_number_with_two_zeros.set_number_of_leading_zeros(2);
+    assert_eq!(
+        (MatchType::ShortNsnMatch, true),
+        phone_util.is_number_match_with_leading_zero_info(&it_number, &it_number_with_two_zeros)
+    );
+    // Без italian_leading_zero количество нулей не учитывается.
+    let mut it_number_ignored_zeros = it_number_without_zero.clone();
+    it_number_ignored_zeros.set_number_of_leading_zeros(2);
+    assert_eq!(
+        (MatchType::ExactMatch, false),
+        phone_util.is_number_match_with_leading_zero_info(&it_number_without_zero, &it_number_ignored_zeros)
+    );
+
+    // Одинаковые номера и номера, отличающиеся не только нулём.
+    assert_eq!(
+        (MatchType::ExactMatch, false),
+        phone_util.is_number_match_with_leading_zero_info(&it_number, &it_number.clone())
+    );
+    let mut it_short_number = it_number.clone();
+    it_short_number.set_national_number(36618300);
+    assert_eq!(
+        (MatchType::ShortNsnMatch, false),
+        phone_util.is_number_match_with_leading_zero_info(&it_number, &it_short_number)
+    );
+    // Разница в добавочном номере не связана с ведущим нулём.
+    let mut it_number_with_extension = it_number.clone();
+    it_number_with_extension.set_extension("1234".to_owned());
+    assert_eq!(
+        (MatchType::ShortNsnMatch, false),
+        phone_util.is_number_match_with_leading_zero_info(&it_number, &it_number_with_extension)
+    );
+}
+
+#[test]
+fn phone_number_from_str_and_display() {
+    // FromStr и Display используют глобальный PHONE_NUMBER_UTIL с настоящими метаданными.
+    let number: PhoneNumber = "+1 650-253-0000".parse().unwrap();
+    let mut expected = PhoneNumber::new();
+    expected.set_country_code(1);
+    expected.set_national_number(6502530000);
+    assert_eq!(expected, number);
+    assert_eq!("+16502530000", number.to_string());
+
+    // Добавочный номер разбирается, но не выводится в формате E164.
+    let number: PhoneNumber = "+44 20 7031 3000 ext. 123".parse().unwrap();
+    assert_eq!("123", number.extension());
+    assert_eq!("+442070313000", number.to_string());
+
+    // Без "+" регион неизвестен.
+    assert_eq!(Err(ParseError::InvalidCountryCode), "650-253-0000".parse::<PhoneNumber>());
+    assert!(matches!("not a number".parse::<PhoneNumber>(), Err(ParseError::NotANumber(_))));
+}
+
+#[test]
+fn phone_number_builder() {
+    let number = PhoneNumberBuilder::new()
+        .country_code(39)
+        .national_number(236618300)
+        .extension("123")
+        .italian_leading_zero(true)
+        .build();
+    let mut expected = PhoneNumber::new();
+    expected.set_country_code(39);
+    expected.set_national_number(236618300);
+    expected.set_extension("123".to_owned());
+    expected.set_italian_leading_zero(true);
+    assert_eq!(expected, number);
+
+    // Незаданные поля остаются пустыми, и ничего не проверяется.
+    let number = PhoneNumberBuilder::new().country_code(999).build();
+    assert!(number.has_country_code());
+    assert!(!number.has_national_number());
+    assert!(!number.has_extension());
+    assert!(!number.has_italian_leading_zero());
+    assert_eq!(PhoneNumber::new(), PhoneNumberBuilder::new().build());
+}
+
+#[test]
+fn stable_result_codes() {
+    // Коды сохраняются во внешних хранилищах и не должны меняться.
+    assert_eq!(0, MatchType::NoMatch.code());
+    assert_eq!(1, MatchType::ShortNsnMatch.code());
+    assert_eq!(2, MatchType::NsnMatch.code());
+    assert_eq!(3, MatchType::ExactMatch.code());
+    assert_eq!(4, MatchType::NotANumber.code());
+
+    assert_eq!(0, ValidationError::InvalidCountryCode { country_code: 999 }.code());
+    assert_eq!(1, ValidationError::TooShort { length: 1 }.code());
+    assert_eq!(2, ValidationError::InvalidLength { length: 1 }.code());
+    assert_eq!(3, ValidationError::TooLong { length: 1 }.code());
+    assert_eq!(4, ValidationError::PatternMismatch.code());
+
+    let phone_util = get_phone_util();
+    let code_of = |number: &str, region: &str| {
+        phone_util.parse(number, region).unwrap_err().into_public().code()
+    };
+    assert_eq!(0, code_of("+999 123 456", RegionCode::us()));
+    assert_eq!(1, code_of("This is not a phone number", RegionCode::us()));
+    assert_eq!(2, code_of("0044", RegionCode::gb()));
+    assert_eq!(3, code_of("+49 0", RegionCode::de()));
+    assert_eq!(4, code_of("01495 72553301873 810104", RegionCode::gb()));
+    assert_eq!(5, code_of("+44\u{0096}2087654321", RegionCode::gb()));
+    assert_eq!(7, ParseError::ExtensionNotAllowed { extension: "5".to_owned() }.code());
+    assert_eq!(7, ParseError::ExtensionNotAllowed { extension: "5".to_owned() }.code());
+}
+
+#[test]
+fn error_messages() {
+    let phone_util = get_phone_util();
+    let message_of = |number: &str, region: &str| {
+        phone_util.parse(number, region).unwrap_err().into_public().to_string()
+    };
+    // Вложенная причина NotANumber попадает в сообщение.
+    assert_eq!(
+        "Not a number: No valid start character found",
+        message_of("This is not a phone number", RegionCode::us())
+    );
+    assert_eq!(
+        "Too short after the international prefix \"00\": only \"44\" remained",
+        message_of("0044", RegionCode::gb())
+    );
+    // Сообщения о длине называют фактическое число цифр.
+    assert_eq!(
+        "The national significant number is too short: its length is 1, but it must have at least 2 digits",
+        message_of("+49 0", RegionCode::de())
+    );
+    assert_eq!(
+        "The national significant number is too long: its length is 21, but it must have at most 17 digits",
+        message_of("01495 72553301873 810104", RegionCode::gb())
+    );
+    assert!(message_of("+999 123 456", RegionCode::us()).starts_with("Invalid country calling code"));
+
+    let mut number = PhoneNumber::new();
+    number.set_country_code(1);
+    number.set_national_number(253000);
+    assert_eq!(
+        "The number is shorter than all valid numbers for its region: its national significant number has length 6",
+        phone_util.is_possible_number_with_reason(&number).unwrap_err().to_string()
+    );
+    number.set_country_code(999);
+    assert_eq!(
+        "The country calling code 999 does not belong to any supported region",
+        phone_util.is_possible_number_with_reason(&number).unwrap_err().to_string()
+    );
+    // Перенос строки в сообщении не теряет пробел.
+    assert!(ValidationError::InvalidLength { length: 8 }.to_string().contains("region, but"));
+    // Краткое описание не зависит от длины.
+    assert_eq!(
+        "The number is shorter than all valid numbers for its region",
+        ValidationError::TooShort { length: 6 }.description()
+    );
+    assert_eq!(
+        ValidationError::TooLong { length: 12 }.description(),
+        ValidationError::TooLong { length: 20 }.description()
+    );
+    // Ошибки можно передавать дальше как `Box<dyn Error>`.
+    let boxed: Box<dyn std::error::Error> = Box::new(ValidationError::TooLong { length: 12 });
+    assert_eq!(
+        "The number is longer than all valid numbers for its region: its national significant number has length 12",
+        boxed.to_string()
+    );
+}
+
+#[test]
+fn validate_metadata() {
+    // Тестовые метаданные не должны содержать проблем.
+    let metadata = PhoneMetadataCollection::parse_from_bytes(&TEST_METADATA).unwrap();
+    assert_eq!(Vec::<MetadataProblem>::new(), validate_metadata_collection(&metadata));
+
+    let mut broken = PhoneMetadata::new();
+    broken.set_id("XX".to_owned());
+    let mut general_desc = PhoneNumberDesc::new();
+    general_desc.set_national_number_pattern("[1-9\\d{5}".to_owned());
+    broken.general_desc = MessageField::some(general_desc);
+    let mut mobile_desc = PhoneNumberDesc::new();
+    mobile_desc.possible_length = vec![7, 8];
+    mobile_desc.possible_length_local_only = vec![6, 7];
+    broken.mobile = MessageField::some(mobile_desc);
+    let mut number_format = NumberFormat::new();
+    number_format.set_pattern("(\\d{3})(\\d{3})".to_owned());
+    number_format.leading_digits_pattern.push("(1".to_owned());
+    broken.number_format.push(number_format);
+    let mut collection = PhoneMetadataCollection::new();
+    collection.metadata.push(broken);
+
+    let problems = validate_metadata_collection(&collection);
+    assert_eq!(4, problems.len(), "{problems:?}");
+    assert!(matches!(
+        &problems[0],
+        MetadataProblem::InvalidPattern { region, field, .. } if region == "XX" && field == "general_desc"
+    ));
+    assert_eq!(
+        MetadataProblem::MissingPossibleLengths { region: "XX".to_owned(), field: "general_desc".to_owned() },
+        problems[1]
+    );
+    assert_eq!(
+        MetadataProblem::LocalOnlyLengthOverlap { region: "XX".to_owned(), field: "mobile".to_owned(), length: 7 },
+        problems[2]
+    );
+    assert!(matches!(
+        &problems[3],
+        MetadataProblem::InvalidPattern { field, .. } if field == "number_format[0].leading_digits_pattern[0]"
+    ));
+}
+
+// Компилирует все шаблоны встроенных метаданных, это занимает несколько секунд.
+#[test]
+#[ignore = "slow, run with `cargo test -- --ignored`"]
+fn validate_production_metadata() {
+    let metadata = PhoneMetadataCollection::parse_from_bytes(&crate::generated::metadata::METADATA).unwrap();
+    assert_eq!(Vec::<MetadataProblem>::new(), validate_metadata_collection(&metadata));
+}
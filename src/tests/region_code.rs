@@ -86,6 +86,10 @@ impl RegionCode {
         "GB"
     }
 
+    pub fn gg() -> &'static str {
+        "GG"
+    }
+
     pub fn hu() -> &'static str {
         "HU"
     }
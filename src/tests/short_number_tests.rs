@@ -0,0 +1,210 @@
+use std::sync::Arc;
+
+use crate::{
+    phonenumberutil::{
+        enums::ShortNumberCost, phonenumberutil_internal::PhoneNumberUtilInternal,
+        short_number::ShortNumberInfo,
+    },
+    PhoneNumber,
+};
+
+use super::{get_phone_util, number, region_code::RegionCode};
+
+fn parse(phone_util: &PhoneNumberUtilInternal, number: &str, region_code: &str) -> PhoneNumber {
+    phone_util.parse(number, region_code).unwrap()
+}
+
+#[test]
+fn is_possible_short_number() {
+    let phone_util = get_phone_util();
+    let short_info = ShortNumberInfo::new(Arc::clone(&phone_util));
+    let possible_number = number(33, 123456);
+    assert!(short_info.is_possible_short_number(&possible_number));
+    assert!(short_info.is_possible_short_number_for_region(
+        &parse(&phone_util, "123456", RegionCode::fr()),
+        RegionCode::fr()
+    ));
+
+    let impossible_number = number(33, 9);
+    assert!(!short_info.is_possible_short_number(&impossible_number));
+
+    // Код страны +44 используется несколькими регионами, и номер возможен хотя бы в одном из них.
+    assert!(short_info.is_possible_short_number(&number(44, 11001)));
+}
+
+#[test]
+fn is_valid_short_number() {
+    let phone_util = get_phone_util();
+    let short_info = ShortNumberInfo::new(Arc::clone(&phone_util));
+    assert!(short_info.is_valid_short_number(&number(33, 1010)));
+    assert!(short_info.is_valid_short_number_for_region(
+        &parse(&phone_util, "1010", RegionCode::fr()),
+        RegionCode::fr()
+    ));
+    assert!(!short_info.is_valid_short_number(&number(33, 123456)));
+    assert!(!short_info.is_valid_short_number_for_region(
+        &parse(&phone_util, "123456", RegionCode::fr()),
+        RegionCode::fr()
+    ));
+
+    // Код страны +44 используется несколькими регионами.
+    assert!(short_info.is_valid_short_number(&number(44, 18001)));
+    // Номер не может быть коротким номером другого региона.
+    assert!(!short_info.is_valid_short_number_for_region(&number(33, 1010), RegionCode::us()));
+}
+
+#[test]
+fn is_carrier_specific() {
+    let phone_util = get_phone_util();
+    let short_info = ShortNumberInfo::new(Arc::clone(&phone_util));
+    let carrier_specific_number = number(1, 33669);
+    assert!(short_info.is_carrier_specific(&carrier_specific_number));
+    assert!(short_info.is_carrier_specific_for_region(
+        &parse(&phone_util, "33669", RegionCode::us()),
+        RegionCode::us()
+    ));
+
+    let not_carrier_specific_number = number(1, 911);
+    assert!(!short_info.is_carrier_specific(&not_carrier_specific_number));
+    assert!(!short_info.is_carrier_specific_for_region(
+        &parse(&phone_util, "911", RegionCode::us()),
+        RegionCode::us()
+    ));
+
+    let carrier_specific_number_for_some_region = number(1, 211);
+    assert!(short_info.is_carrier_specific(&carrier_specific_number_for_some_region));
+    assert!(short_info.is_carrier_specific_for_region(
+        &carrier_specific_number_for_some_region,
+        RegionCode::us()
+    ));
+    assert!(!short_info.is_carrier_specific_for_region(
+        &carrier_specific_number_for_some_region,
+        RegionCode::bb()
+    ));
+}
+
+#[test]
+fn is_sms_service() {
+    let phone_util = get_phone_util();
+    let short_info = ShortNumberInfo::new(Arc::clone(&phone_util));
+    let sms_service_number_for_some_region = number(1, 21234);
+    assert!(short_info.is_sms_service_for_region(
+        &sms_service_number_for_some_region,
+        RegionCode::us()
+    ));
+    assert!(!short_info.is_sms_service_for_region(
+        &sms_service_number_for_some_region,
+        RegionCode::bb()
+    ));
+}
+
+#[test]
+fn get_expected_cost() {
+    let phone_util = get_phone_util();
+    let short_info = ShortNumberInfo::new(Arc::clone(&phone_util));
+    for cost in [
+        ShortNumberCost::PremiumRate,
+        ShortNumberCost::StandardRate,
+        ShortNumberCost::TollFree,
+    ] {
+        let example = short_info.get_example_short_number_for_cost(RegionCode::fr(), cost);
+        assert!(!example.is_empty(), "{cost:?}");
+        let number = parse(&phone_util, &example, RegionCode::fr());
+        assert_eq!(cost, short_info.get_expected_cost_for_region(&number, RegionCode::fr()));
+        assert_eq!(cost, short_info.get_expected_cost(&number));
+    }
+
+    let unknown_cost_number = parse(&phone_util, "12345", RegionCode::fr());
+    assert_eq!(
+        ShortNumberCost::UnknownCost,
+        short_info.get_expected_cost_for_region(&unknown_cost_number, RegionCode::fr())
+    );
+    assert_eq!(ShortNumberCost::UnknownCost, short_info.get_expected_cost(&unknown_cost_number));
+    assert_eq!(
+        String::new(),
+        short_info.get_example_short_number_for_cost(RegionCode::fr(), ShortNumberCost::UnknownCost)
+    );
+
+    // Номер экстренной службы всегда бесплатный.
+    let emergency_number = parse(&phone_util, "911", RegionCode::us());
+    assert_eq!(
+        ShortNumberCost::TollFree,
+        short_info.get_expected_cost_for_region(&emergency_number, RegionCode::us())
+    );
+
+    // Номер другого региона имеет неизвестную стоимость.
+    assert_eq!(
+        ShortNumberCost::UnknownCost,
+        short_info.get_expected_cost_for_region(&emergency_number, RegionCode::fr())
+    );
+}
+
+#[test]
+fn get_expected_cost_for_shared_country_calling_code() {
+    let phone_util = get_phone_util();
+    let short_info = ShortNumberInfo::new(Arc::clone(&phone_util));
+    // Стоимость "211" неизвестна хотя бы в одном из регионов NANPA, поэтому общая
+    // стоимость тоже неизвестна.
+    let ambiguous_standard_rate_number = number(1, 211);
+    assert_eq!(
+        ShortNumberCost::UnknownCost,
+        short_info.get_expected_cost(&ambiguous_standard_rate_number)
+    );
+    // "911" бесплатный в США, но не является коротким номером в некоторых других регионах NANPA.
+    let toll_free_number = number(1, 911);
+    assert_eq!(
+        ShortNumberCost::TollFree,
+        short_info.get_expected_cost_for_region(&toll_free_number, RegionCode::us())
+    );
+    assert_eq!(ShortNumberCost::UnknownCost, short_info.get_expected_cost(&toll_free_number));
+}
+
+#[test]
+fn get_example_short_number() {
+    let phone_util = get_phone_util();
+    let short_info = ShortNumberInfo::new(Arc::clone(&phone_util));
+    assert_eq!("15", short_info.get_example_short_number(RegionCode::fr()));
+    assert_eq!("112", short_info.get_example_short_number(RegionCode::us()));
+    assert_eq!("", short_info.get_example_short_number(RegionCode::zz()));
+    assert_eq!(
+        "1000",
+        short_info.get_example_short_number_for_cost(RegionCode::fr(), ShortNumberCost::PremiumRate)
+    );
+}
+
+#[test]
+fn is_emergency_number() {
+    let phone_util = get_phone_util();
+    let short_info = ShortNumberInfo::new(Arc::clone(&phone_util));
+    assert!(short_info.is_emergency_number("911", RegionCode::us()));
+    assert!(short_info.is_emergency_number("112", RegionCode::us()));
+    assert!(short_info.is_emergency_number("1-1-2", RegionCode::us()));
+    assert!(!short_info.is_emergency_number("999", RegionCode::us()));
+    assert!(short_info.is_emergency_number("999", RegionCode::gb()));
+    assert!(short_info.is_emergency_number("110", RegionCode::de()));
+    // Номер с кодом страны не является номером экстренной службы.
+    assert!(!short_info.is_emergency_number("+1 911", RegionCode::us()));
+    // Лишние цифры не допускаются.
+    assert!(!short_info.is_emergency_number("9116666666", RegionCode::us()));
+    assert!(!short_info.is_emergency_number("112", RegionCode::zz()));
+    assert!(!short_info.is_emergency_number("", RegionCode::us()));
+}
+
+#[test]
+fn connects_to_emergency_number() {
+    let phone_util = get_phone_util();
+    let short_info = ShortNumberInfo::new(Arc::clone(&phone_util));
+    assert!(short_info.connects_to_emergency_number("911", RegionCode::us()));
+    assert!(!short_info.connects_to_emergency_number("999", RegionCode::us()));
+    // Лишние цифры после номера экстренной службы допускаются.
+    assert!(short_info.connects_to_emergency_number("9116666666", RegionCode::us()));
+    assert!(short_info.connects_to_emergency_number("1126666666", RegionCode::us()));
+    assert!(!short_info.connects_to_emergency_number("+1 911", RegionCode::us()));
+
+    // В Бразилии номер экстренной службы должен набираться без лишних цифр.
+    assert!(short_info.connects_to_emergency_number("911", RegionCode::br()));
+    assert!(short_info.connects_to_emergency_number("190", RegionCode::br()));
+    assert!(!short_info.connects_to_emergency_number("999", RegionCode::br()));
+    assert!(!short_info.connects_to_emergency_number("9111", RegionCode::br()));
+    assert!(!short_info.connects_to_emergency_number("1900", RegionCode::br()));
+}
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use crate::phonenumberutil::timezones::{PhoneNumberToTimeZonesMapper, UNKNOWN_TIMEZONE};
+
+use super::{get_phone_util, number};
+
+#[test]
+fn get_time_zones_for_geographical_number() {
+    let phone_util = get_phone_util();
+    let mapper = PhoneNumberToTimeZonesMapper::new(Arc::clone(&phone_util));
+    assert_eq!(vec!["America/Los_Angeles"], mapper.get_time_zones_for_number(&number(1, 6502530000)));
+    assert_eq!(vec!["America/New_York"], mapper.get_time_zones_for_number(&number(1, 2015550123)));
+    assert_eq!(vec!["Europe/Berlin"], mapper.get_time_zones_for_number(&number(49, 30123456)));
+    // Берётся самый длинный подходящий префикс, а не список всей страны.
+    assert_eq!(vec!["Australia/Sydney"], mapper.get_time_zones_for_number(&number(61, 236661800)));
+}
+
+#[test]
+fn get_time_zones_for_non_geographical_number() {
+    let phone_util = get_phone_util();
+    let mapper = PhoneNumberToTimeZonesMapper::new(Arc::clone(&phone_util));
+    // Мобильные номера Великобритании получают часовые пояса всей страны.
+    assert_eq!(
+        vec!["Europe/Guernsey", "Europe/Isle_of_Man", "Europe/Jersey", "Europe/London"],
+        mapper.get_time_zones_for_number(&number(44, 7912345678))
+    );
+    assert_eq!(vec!["Europe/Berlin"], mapper.get_time_zones_for_number(&number(49, 15123456789)));
+    // Для негеографических номеров данных нет.
+    assert_eq!(vec![UNKNOWN_TIMEZONE], mapper.get_time_zones_for_number(&number(800, 12345678)));
+}
+
+#[test]
+fn get_time_zones_for_unknown_number() {
+    let phone_util = get_phone_util();
+    let mapper = PhoneNumberToTimeZonesMapper::new(Arc::clone(&phone_util));
+    assert_eq!(vec![UNKNOWN_TIMEZONE], mapper.get_time_zones_for_number(&number(1, 123456789)));
+    assert_eq!(vec![UNKNOWN_TIMEZONE], mapper.get_time_zones_for_number(&number(999, 123456789)));
+    // Без проверки номера поиск идёт только по префиксу.
+    assert_eq!(
+        vec!["Europe/Berlin"],
+        mapper.get_time_zones_for_geographical_number(&number(49, 1))
+    );
+    assert_eq!(
+        vec![UNKNOWN_TIMEZONE],
+        mapper.get_time_zones_for_geographical_number(&number(999, 123456789))
+    );
+}
@@ -0,0 +1,141 @@
+// Copyright (C) 2025 Kashin Vladislav
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Thin `wasm-bindgen` wrapper over the shared `PHONE_NUMBER_UTIL` instance.
+//!
+//! Enums cross the boundary as upper snake case strings (the names used by the
+//! Java/JS libphonenumber), and errors are thrown as JS `Error`s whose message is
+//! one of the stable names returned by `parse_error_name` / `validation_error_name`.
+
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    NumberLengthType, PHONE_NUMBER_UTIL, ParseError, PhoneNumber, PhoneNumberFormat,
+    PhoneNumberType, ValidationError,
+};
+
+/// A parsed phone number handed out to JS.
+#[wasm_bindgen(js_name = PhoneNumber)]
+pub struct WasmPhoneNumber {
+    number: PhoneNumber,
+}
+
+#[wasm_bindgen(js_class = PhoneNumber)]
+impl WasmPhoneNumber {
+    #[wasm_bindgen(getter, js_name = countryCode)]
+    pub fn country_code(&self) -> i32 {
+        self.number.country_code()
+    }
+
+    /// The national number as a digit string, since a `u64` doesn't fit a JS number.
+    #[wasm_bindgen(getter, js_name = nationalNumber)]
+    pub fn national_number(&self) -> String {
+        self.number.national_number().to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn extension(&self) -> Option<String> {
+        self.number.has_extension().then(|| self.number.extension().to_owned())
+    }
+}
+
+/// Parses `number_to_parse`, throwing one of the `parse_error_name` messages on failure.
+#[wasm_bindgen]
+pub fn parse(number_to_parse: &str, default_region: &str) -> Result<WasmPhoneNumber, JsError> {
+    PHONE_NUMBER_UTIL
+        .parse(number_to_parse, default_region)
+        .map(|number| WasmPhoneNumber { number })
+        .map_err(|err| JsError::new(parse_error_name(&err)))
+}
+
+/// Formats the number; `number_format` is one of "E164", "INTERNATIONAL",
+/// "NATIONAL" or "RFC3966".
+#[wasm_bindgen]
+pub fn format(number: &WasmPhoneNumber, number_format: &str) -> Result<String, JsError> {
+    let number_format = match number_format {
+        "E164" => PhoneNumberFormat::E164,
+        "INTERNATIONAL" => PhoneNumberFormat::International,
+        "NATIONAL" => PhoneNumberFormat::National,
+        "RFC3966" => PhoneNumberFormat::RFC3966,
+        _ => return Err(JsError::new("INVALID_FORMAT")),
+    };
+    Ok(PHONE_NUMBER_UTIL.format(&number.number, number_format).into_owned())
+}
+
+#[wasm_bindgen(js_name = isValidNumber)]
+pub fn is_valid_number(number: &WasmPhoneNumber) -> bool {
+    PHONE_NUMBER_UTIL.is_valid_number(&number.number)
+}
+
+/// Returns the type name of the number, e.g. "FIXED_LINE" or "UNKNOWN".
+#[wasm_bindgen(js_name = getNumberType)]
+pub fn get_number_type(number: &WasmPhoneNumber) -> String {
+    number_type_name(PHONE_NUMBER_UTIL.get_number_type(&number.number)).to_owned()
+}
+
+/// Returns "IS_POSSIBLE" or "IS_POSSIBLE_LOCAL_ONLY", throwing one of the
+/// `validation_error_name` messages if the number isn't possible.
+#[wasm_bindgen(js_name = isPossibleNumberWithReason)]
+pub fn is_possible_number_with_reason(number: &WasmPhoneNumber) -> Result<String, JsError> {
+    PHONE_NUMBER_UTIL
+        .is_possible_number_with_reason(&number.number)
+        .map(|length_type| match length_type {
+            NumberLengthType::IsPossible => "IS_POSSIBLE",
+            NumberLengthType::IsPossibleLocalOnly => "IS_POSSIBLE_LOCAL_ONLY",
+        }.to_owned())
+        .map_err(|err| JsError::new(validation_error_name(&err)))
+}
+
+fn number_type_name(number_type: PhoneNumberType) -> &'static str {
+    match number_type {
+        PhoneNumberType::FixedLine => "FIXED_LINE",
+        PhoneNumberType::Mobile => "MOBILE",
+        PhoneNumberType::FixedLineOrMobile => "FIXED_LINE_OR_MOBILE",
+        PhoneNumberType::TollFree => "TOLL_FREE",
+        PhoneNumberType::PremiumRate => "PREMIUM_RATE",
+        PhoneNumberType::SharedCost => "SHARED_COST",
+        PhoneNumberType::VoIP => "VOIP",
+        PhoneNumberType::PersonalNumber => "PERSONAL_NUMBER",
+        PhoneNumberType::Pager => "PAGER",
+        PhoneNumberType::UAN => "UAN",
+        PhoneNumberType::VoiceMail => "VOICEMAIL",
+        PhoneNumberType::Unknown => "UNKNOWN",
+    }
+}
+
+/// Stable exception message for each `ParseError` kind. Like `ParseError::code`,
+/// the mapping must not change once assigned.
+fn parse_error_name(err: &ParseError) -> &'static str {
+    match err {
+        ParseError::InvalidCountryCode => "INVALID_COUNTRY_CODE",
+        ParseError::NotANumber(_) => "NOT_A_NUMBER",
+        ParseError::TooShortAfterIdd { .. } => "TOO_SHORT_AFTER_IDD",
+        ParseError::TooShortNsn { .. } => "TOO_SHORT_NSN",
+        ParseError::TooLongNsn { .. } => "TOO_LONG",
+        ParseError::InvalidCharacter { .. } => "INVALID_CHARACTER",
+        ParseError::RegionMismatchSuspected { .. } => "REGION_MISMATCH_SUSPECTED",
+        ParseError::ExtensionNotAllowed { .. } => "EXTENSION_NOT_ALLOWED",
+    }
+}
+
+/// Stable exception message for each `ValidationError` kind.
+fn validation_error_name(err: &ValidationError) -> &'static str {
+    match err {
+        ValidationError::InvalidCountryCode { .. } => "INVALID_COUNTRY_CODE",
+        ValidationError::TooShort { .. } => "TOO_SHORT",
+        ValidationError::InvalidLength { .. } => "INVALID_LENGTH",
+        ValidationError::TooLong { .. } => "TOO_LONG",
+        ValidationError::PatternMismatch => "PATTERN_MISMATCH",
+    }
+}
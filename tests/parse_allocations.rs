@@ -0,0 +1,107 @@
+//! Counts the heap allocations of `parse`. The counting allocator is global to
+//! this test binary, so it lives apart from the other tests and benchmarks.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    hint::black_box,
+};
+
+use rlibphonenumber::{PHONE_NUMBER_UTIL, PhoneNumber};
+
+/// Allocator counting the allocations of the current thread, so that tests
+/// running in parallel don't disturb each other's counts.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Returns the number of allocations made by one call of `parse`.
+fn count_parse_allocations(number_to_parse: &str, region_code: &str) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    let number = PHONE_NUMBER_UTIL.parse(black_box(number_to_parse), black_box(region_code)).unwrap();
+    let allocations = ALLOCATIONS.with(Cell::get) - before;
+    drop(black_box(number));
+    allocations
+}
+
+/// Parses the number once so that the compiled regexes are cached, then counts
+/// the allocations of parsing it again.
+fn count_warm_parse_allocations(number_to_parse: &str, region_code: &str) -> usize {
+    PHONE_NUMBER_UTIL.parse(number_to_parse, region_code).unwrap();
+    count_parse_allocations(number_to_parse, region_code)
+}
+
+/// Numbers without an extension and the allocations recorded for parsing them.
+/// Lower a baseline when parsing gets cheaper; raising one needs a reason.
+const BASELINES_WITHOUT_EXTENSION: [(&str, &str, usize); 2] = [
+    ("+44 20 8765 4321", "GB", 10),
+    ("(650) 253-0000", "US", 11),
+];
+
+#[test]
+fn parse_without_extension_stays_within_baseline() {
+    for (number_to_parse, region_code, baseline) in BASELINES_WITHOUT_EXTENSION {
+        let allocations = count_warm_parse_allocations(number_to_parse, region_code);
+        assert!(
+            allocations <= baseline,
+            "parsing {:?} allocated {} times, the baseline is {}",
+            number_to_parse, allocations, baseline,
+        );
+    }
+}
+
+#[test]
+fn parse_allocates_extension_only_when_present() {
+    for (without_extension, with_extension, region_code) in [
+        ("+44 20 8765 4321", "+44 20 8765 4321 ext. 1234", "GB"),
+        ("(650) 253-0000", "(650) 253-0000 x 12", "US"),
+    ] {
+        // The only additional allocation is the extension string itself.
+        assert_eq!(
+            count_warm_parse_allocations(without_extension, region_code) + 1,
+            count_warm_parse_allocations(with_extension, region_code),
+            "{:?}",
+            with_extension,
+        );
+    }
+}
+
+#[test]
+fn parse_into_reuses_extension_buffer() {
+    let mut number = PhoneNumber::new();
+    for (number_to_parse, region_code) in [
+        ("+44 20 8765 4321 ext. 1234", "GB"),
+        ("(650) 253-0000 x 12", "US"),
+    ] {
+        PHONE_NUMBER_UTIL.parse_into(number_to_parse, region_code, &mut number).unwrap();
+        let before = ALLOCATIONS.with(Cell::get);
+        PHONE_NUMBER_UTIL
+            .parse_into(black_box(number_to_parse), black_box(region_code), &mut number)
+            .unwrap();
+        let allocations = ALLOCATIONS.with(Cell::get) - before;
+        // The extension is written into the buffer of the previous one.
+        assert_eq!(
+            count_warm_parse_allocations(number_to_parse, region_code) - 1,
+            allocations,
+            "{:?}",
+            number_to_parse,
+        );
+    }
+    assert_eq!("12", number.extension());
+}
@@ -59,17 +59,22 @@ generate "PhoneNumberMetadata.xml" "metadata" "metadata" "METADATA"
 # generate test metadata
 generate "PhoneNumberMetadataForTesting.xml" "test_metadata" "metadata" "TEST_METADATA"
 
+# generate short number metadata
+generate "ShortNumberMetadata.xml" "short_metadata" "short_numbers" "SHORT_METADATA"
+
 # remove unnecessary nesting with pub use
 echo "\
 $copyright_header
 
 mod metadata;
+mod short_metadata;
 
 // use only in test case 
 #[cfg(test)]
 mod test_metadata;
 
 pub use metadata::METADATA;
+pub use short_metadata::SHORT_METADATA;
 #[cfg(test)]
 pub use test_metadata::TEST_METADATA;
 " > "$generated_dir/mod.rs"
\ No newline at end of file
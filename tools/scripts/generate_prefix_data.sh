@@ -0,0 +1,68 @@
+#!/bin/bash
+
+# Generates the tables of bundled prefix data files (geocoding, carrier names,
+# time zones) from resources/. The files are embedded with include_str! and parsed lazily.
+
+filedir="./$(dirname "$0")"
+project_home="$filedir/../.."
+generated_dir="$project_home/src/generated/prefix_data"
+resources_dir="$project_home/resources"
+
+copyright_header="\
+// Copyright (C) 2025 Kashin Vladislav (Rust adaptation author)
+//
+// Licensed under the Apache License, Version 2.0 (the \"License\");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an \"AS IS\" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+"
+
+mkdir -p "$generated_dir"
+
+# generate <resources subdirectory> <const name>
+function generate {
+    output="$generated_dir/$1.rs"
+    {
+        echo "$copyright_header"
+        echo "/// Files from resources/$1 as (language, country calling code, contents)."
+        echo "pub static $2: &[(&str, i32, &str)] = &["
+        for file in $(cd "$resources_dir/$1" && ls -d */*.txt | sort); do
+            language="${file%%/*}"
+            country_code="$(basename "$file" .txt)"
+            echo "    (\"$language\", $country_code, include_str!(\"../../../resources/$1/$file\")),"
+        done
+        echo "];"
+    } > "$output"
+}
+
+generate "geocoding" "GEOCODING_DATA"
+generate "carrier" "CARRIER_DATA"
+
+{
+    echo "$copyright_header"
+    echo "/// The prefix to time zones map from resources/timezones."
+    echo "pub static TIMEZONES_DATA: &str = include_str!(\"../../../resources/timezones/map_data.txt\");"
+} > "$generated_dir/timezones.rs"
+
+echo "\
+$copyright_header
+#[cfg(feature = \"carrier\")]
+mod carrier;
+#[cfg(feature = \"geocoder\")]
+mod geocoding;
+#[cfg(feature = \"timezones\")]
+mod timezones;
+
+#[cfg(feature = \"carrier\")]
+pub use carrier::CARRIER_DATA;
+#[cfg(feature = \"geocoder\")]
+pub use geocoding::GEOCODING_DATA;
+#[cfg(feature = \"timezones\")]
+pub use timezones::TIMEZONES_DATA;" > "$generated_dir/mod.rs"